@@ -3,7 +3,7 @@ pragma solidity ^0.8.22;
 
 import { Ownable } from "@openzeppelin/contracts/access/Ownable.sol";
 
-import { Origin } from "@layerzerolabs/oapp-evm/contracts/oapp/OApp.sol";
+import { Origin, MessagingFee } from "@layerzerolabs/oapp-evm/contracts/oapp/OApp.sol";
 import { OFTMsgCodec } from "@layerzerolabs/oft-evm/contracts/libs/OFTMsgCodec.sol";
 
 import { OFTAdapter } from "@layerzerolabs/oft-evm/contracts/OFTAdapter.sol";
@@ -53,6 +53,62 @@ contract GMX_LockboxAdapter is OFTAdapter, OverridableInboundRateLimiter {
         _outflowOverridable(_from, amountSentLD, _dstEid);
     }
 
+    /**
+     * @notice Echoes the control challenge received from the peer on `_eid` back to it
+     * @dev Anyone may pay for the echo, it only forwards the nonce this contract received from that peer.
+     * @param _eid The endpoint ID of the challenging peer.
+     * @param _options Executor options, combined with the enforced SEND options.
+     */
+    function echoPeerChallenge(uint32 _eid, bytes calldata _options) external payable {
+        _lzSend(
+            _eid,
+            _takePeerChallengeEcho(_eid),
+            combineOptions(_eid, SEND, _options),
+            MessagingFee(msg.value, 0),
+            payable(msg.sender)
+        );
+    }
+
+    /**
+     * @notice Acknowledges the delivery of `_guid` to the peer it came from
+     * @dev Anyone may pay for the ack, it only reports a delivery this contract recorded.
+     * @param _guid The GUID of the delivered transfer.
+     * @param _options Executor options, combined with the enforced SEND options.
+     */
+    function sendDeliveryAck(bytes32 _guid, bytes calldata _options) external payable {
+        (uint32 dstEid, bytes memory message) = _takeDeliveryAck(_guid);
+        _lzSend(
+            dstEid,
+            message,
+            combineOptions(dstEid, SEND, _options),
+            MessagingFee(msg.value, 0),
+            payable(msg.sender)
+        );
+    }
+
+    /**
+     * @notice Sends guid overrides to the Solana deployment on `_dstEid`, which must have this contract as governance peer
+     * @dev Only the owner can send them, as they let the given deliveries bypass the Solana inbound rate limit.
+     * @param _dstEid The endpoint ID of the Solana deployment.
+     * @param _guids The GUIDs whose deliveries may override the rate limit.
+     * @param _srcEids The source endpoint ID of each GUID, 0 for any source.
+     * @param _options Executor options, combined with the enforced SEND options.
+     */
+    function sendOverrideGuids(
+        uint32 _dstEid,
+        bytes32[] calldata _guids,
+        uint32[] calldata _srcEids,
+        bytes calldata _options
+    ) external payable onlyOwner {
+        _lzSend(
+            _dstEid,
+            _encodeOverrideGuids(_dstEid, _guids, _srcEids),
+            combineOptions(_dstEid, SEND, _options),
+            MessagingFee(msg.value, 0),
+            payable(msg.sender)
+        );
+    }
+
     /**
      * @notice Override the base _lzReceive() function to apply rate limiting before super._lzReceive()
      * @dev This function is called when a message is received from another chain.
@@ -69,10 +125,14 @@ contract GMX_LockboxAdapter is OFTAdapter, OverridableInboundRateLimiter {
         address _executor, // @dev unused in the default implementation.
         bytes calldata _extraData // @dev unused in the default implementation.
     ) internal virtual override {
+        /// @dev Control messages from a Solana deployment carry no tokens and are not transfers
+        if (_applyControlMessage(_message, peers[_origin.srcEid], _origin.srcEid)) return;
+
         address toAddress = _message.sendTo().bytes32ToAddress();
 
-        /// @dev We can assume that every layerzero message is an OFT transfer and that there are no non-token messages
+        /// @dev Every other layerzero message is an OFT transfer
         _inflowOverridable(_guid, toAddress, _toLD(_message.amountSD()), _origin.srcEid);
+        _recordDeliveryAck(_origin.srcEid, _guid, _message.amountSD());
 
         super._lzReceive(_origin, _guid, _message, _executor, _extraData);
     }
@@ -3,8 +3,13 @@ pragma solidity ^0.8.22;
 
 import { Ownable } from "@openzeppelin/contracts/access/Ownable.sol";
 import { RateLimiter } from "@layerzerolabs/oapp-evm/contracts/oapp/utils/RateLimiter.sol";
+import { OFTMsgCodec } from "@layerzerolabs/oft-evm/contracts/libs/OFTMsgCodec.sol";
 
-import { IOverridableInboundRateLimiter, RateLimitExemptAddress } from "./interfaces/IOverridableInboundRateLimiter.sol";
+import {
+    IOverridableInboundRateLimiter,
+    PendingDeliveryAck,
+    RateLimitExemptAddress
+} from "./interfaces/IOverridableInboundRateLimiter.sol";
 
 /**
  * @title OverridableRateLimiter
@@ -14,6 +19,45 @@ import { IOverridableInboundRateLimiter, RateLimitExemptAddress } from "./interf
  * @dev to add rate limiting with exemption and override capabilities.
  */
 abstract contract OverridableInboundRateLimiter is RateLimiter, Ownable, IOverridableInboundRateLimiter {
+    using OFTMsgCodec for bytes;
+
+    /// @dev sendTo of every control message exchanged with a Solana deployment: a zero amount transfer composed by
+    ///      the sending OFT itself, whose compose message is controlType (1) | payload
+    bytes32 public constant CONTROL_SEND_TO = bytes32("gmx-oft:control-message");
+
+    /// @dev Offset of the control type: sendTo (32) | amountSD (8) | composeFrom (32)
+    uint256 private constant CONTROL_TYPE_OFFSET = 72;
+
+    /// @dev Offset of the control payload, after the control type
+    uint256 private constant CONTROL_PAYLOAD_OFFSET = 73;
+
+    /// @dev The rate limit override list a Solana deployment mirrors to its peers
+    uint8 public constant CONTROL_OVERRIDE_LIST = 1;
+
+    /// @dev A control challenge a Solana deployment sends to a new peer, and its echo
+    uint8 public constant CONTROL_PEER_CHALLENGE = 2;
+
+    /// @dev Guid overrides this contract, as governance peer, adds on a Solana deployment
+    uint8 public constant CONTROL_OVERRIDE_GUIDS = 3;
+
+    /// @dev A notice a Solana deployment sends its peers while it is paused
+    uint8 public constant CONTROL_PAUSE_NOTICE = 4;
+
+    /// @dev The acknowledgement of a delivery sent back to a Solana deployment: guid (32) | amountSD (8)
+    uint8 public constant CONTROL_DELIVERY_ACK = 5;
+
+    /// @dev Each mirrored override list entry is packed as address (20) | isExempt (1)
+    uint256 private constant OVERRIDE_LIST_ENTRY_LENGTH = 21;
+
+    /// @dev Control challenge nonces received per source endpoint, awaiting their echo
+    mapping(uint32 => bytes32) public peerChallenges;
+
+    /// @dev Source endpoints whose transfers are acknowledged with sendDeliveryAck()
+    mapping(uint32 => bool) public deliveryAcksEnabled;
+
+    /// @dev Deliveries awaiting their ack, per guid
+    mapping(bytes32 => PendingDeliveryAck) public pendingDeliveryAcks;
+
     /// @dev Mapping to track addresses exempt from rate limiting
     mapping(address => bool) public exemptAddresses;
 
@@ -59,6 +103,193 @@ abstract contract OverridableInboundRateLimiter is RateLimiter, Ownable, IOverri
         emit RateLimitOverrider_ModifiedGUID(_guids, _canOverride);
     }
 
+    /**
+     * @notice Enables or disables delivery acks for the transfers received from `_srcEid`
+     * @dev Only for Solana peers tracking their transfers in flight, which apply the acks. Deliveries recorded while
+     *      enabled can still be acknowledged after it is disabled.
+     * @param _srcEid The endpoint ID of the sending peer.
+     * @param _enabled Whether its transfers are recorded to be acknowledged.
+     */
+    function setDeliveryAcks(uint32 _srcEid, bool _enabled) external onlyOwner {
+        deliveryAcksEnabled[_srcEid] = _enabled;
+        emit DeliveryAcksUpdated(_srcEid, _enabled);
+    }
+
+    /**
+     * @notice Applies a control message from the Solana deployment on `_srcEid`, if `_message` is one
+     * @dev Control messages are zero amount transfers to CONTROL_SEND_TO composed by the peer itself, which no user
+     *      can send as. Reverts with InvalidControlMessage if the type is unknown or the payload malformed.
+     * @param _message The message data.
+     * @param _peer The peer the message was received from.
+     * @param _srcEid The endpoint ID the message was received from.
+     * @return applied Whether the message was a control message, in which case it carries no tokens to credit.
+     */
+    function _applyControlMessage(
+        bytes calldata _message,
+        bytes32 _peer,
+        uint32 _srcEid
+    ) internal virtual returns (bool applied) {
+        if (
+            _message.sendTo() != CONTROL_SEND_TO ||
+            _message.amountSD() != 0 ||
+            !_message.isComposed() ||
+            _message.composeFrom() != _peer
+        ) return false;
+        if (_message.length < CONTROL_PAYLOAD_OFFSET) revert InvalidControlMessage(0, _message.length);
+
+        uint8 controlType = uint8(_message[CONTROL_TYPE_OFFSET]);
+        bytes calldata payload = _message[CONTROL_PAYLOAD_OFFSET:];
+        if (controlType == CONTROL_OVERRIDE_LIST) {
+            _applyOverrideList(payload);
+        } else if (controlType == CONTROL_PEER_CHALLENGE) {
+            _recordPeerChallenge(payload, _srcEid);
+        } else if (controlType == CONTROL_PAUSE_NOTICE) {
+            _reportPauseNotice(payload, _srcEid);
+        } else {
+            revert InvalidControlMessage(controlType, payload.length);
+        }
+        return true;
+    }
+
+    /**
+     * @notice Applies a rate limit override list mirrored from a Solana deployment
+     * @dev Sent by the Solana admin through the program's OFT store, which is the peer itself.
+     * @param _payload The mirrored entries.
+     */
+    function _applyOverrideList(bytes calldata _payload) internal virtual {
+        if (_payload.length % OVERRIDE_LIST_ENTRY_LENGTH != 0) {
+            revert InvalidControlMessage(CONTROL_OVERRIDE_LIST, _payload.length);
+        }
+
+        RateLimitExemptAddress[] memory modified = new RateLimitExemptAddress[](
+            _payload.length / OVERRIDE_LIST_ENTRY_LENGTH
+        );
+        for (uint256 i; i < modified.length; ++i) {
+            bytes calldata entry = _payload[i * OVERRIDE_LIST_ENTRY_LENGTH:(i + 1) * OVERRIDE_LIST_ENTRY_LENGTH];
+            modified[i] = RateLimitExemptAddress({ addr: address(bytes20(entry[:20])), isExempt: entry[20] != 0 });
+            exemptAddresses[modified[i].addr] = modified[i].isExempt;
+        }
+
+        emit RateLimitOverrider_ModifiedAddress(modified);
+    }
+
+    /**
+     * @notice Records a control challenge sent by the Solana deployment on `_srcEid`
+     * @dev The Solana program refuses transfers to a new peer until the peer echoes the challenge back, proving
+     *      the configured address is this contract and that it is wired back to the program.
+     * @param _payload The challenge nonce.
+     * @param _srcEid The endpoint ID the challenge was received from.
+     */
+    function _recordPeerChallenge(bytes calldata _payload, uint32 _srcEid) internal virtual {
+        if (_payload.length != 32) revert InvalidControlMessage(CONTROL_PEER_CHALLENGE, _payload.length);
+
+        bytes32 nonce = bytes32(_payload);
+        peerChallenges[_srcEid] = nonce;
+
+        emit PeerChallengeReceived(_srcEid, nonce);
+    }
+
+    /**
+     * @notice Reports a pause notice from the peer on `_srcEid`, a Solana deployment that has paused
+     * @dev Advisory only: this contract has no pause, so the owner decides how to respond.
+     * @param _payload The time the notice was sent at.
+     * @param _srcEid The endpoint ID the notice was received from.
+     */
+    function _reportPauseNotice(bytes calldata _payload, uint32 _srcEid) internal virtual {
+        if (_payload.length != 8) revert InvalidControlMessage(CONTROL_PAUSE_NOTICE, _payload.length);
+
+        emit PauseNoticeReceived(_srcEid, uint64(bytes8(_payload)));
+    }
+
+    /**
+     * @notice Encodes a control message of `_controlType` composed by this contract
+     * @param _controlType The control type.
+     * @param _payload The control payload.
+     * @return message The control message.
+     */
+    function _encodeControlMessage(
+        uint8 _controlType,
+        bytes memory _payload
+    ) internal view virtual returns (bytes memory message) {
+        return
+            abi.encodePacked(
+                CONTROL_SEND_TO,
+                uint64(0),
+                bytes32(uint256(uint160(address(this)))),
+                _controlType,
+                _payload
+            );
+    }
+
+    /**
+     * @notice Takes the pending control challenge from `_eid` and encodes its echo
+     * @dev The echo is composed by this contract, which the Solana program checks against its peer address.
+     * @param _eid The endpoint ID of the challenging peer.
+     * @return message The echo, to be sent back to the peer.
+     */
+    function _takePeerChallengeEcho(uint32 _eid) internal virtual returns (bytes memory message) {
+        bytes32 nonce = peerChallenges[_eid];
+        if (nonce == bytes32(0)) revert NoPeerChallenge(_eid);
+        delete peerChallenges[_eid];
+
+        emit PeerChallengeEchoed(_eid, nonce);
+        return _encodeControlMessage(CONTROL_PEER_CHALLENGE, abi.encodePacked(nonce));
+    }
+
+    /**
+     * @notice Encodes guid overrides for a Solana deployment that has this contract as its governance peer
+     * @dev Composed by this contract, which the Solana program checks against its governance peer address.
+     *      Each entry is packed as guid (32) | srcEid (4), a srcEid of 0 matching a delivery from any source.
+     * @param _dstEid The endpoint ID of the Solana deployment.
+     * @param _guids The GUIDs whose deliveries may override the rate limit.
+     * @param _srcEids The source endpoint ID of each GUID.
+     * @return message The override message, to be sent to the Solana deployment.
+     */
+    function _encodeOverrideGuids(
+        uint32 _dstEid,
+        bytes32[] calldata _guids,
+        uint32[] calldata _srcEids
+    ) internal virtual returns (bytes memory message) {
+        if (_guids.length != _srcEids.length) revert InputLengthMismatch(_guids.length, _srcEids.length);
+        if (_guids.length == 0) revert NoOverrideGuids();
+
+        bytes memory payload;
+        for (uint256 i; i < _guids.length; ++i) {
+            payload = abi.encodePacked(payload, _guids[i], _srcEids[i]);
+        }
+        message = _encodeControlMessage(CONTROL_OVERRIDE_GUIDS, payload);
+
+        emit OverrideGuidsSent(_dstEid, _guids, _srcEids);
+    }
+
+    /**
+     * @notice Records a delivered transfer to be acknowledged, if acks are enabled for `_srcEid`
+     * @param _srcEid The endpoint ID the transfer was received from.
+     * @param _guid The GUID of the transfer.
+     * @param _amountSD The amount of the transfer in shared decimals, as sent.
+     */
+    function _recordDeliveryAck(uint32 _srcEid, bytes32 _guid, uint64 _amountSD) internal virtual {
+        if (!deliveryAcksEnabled[_srcEid]) return;
+        pendingDeliveryAcks[_guid] = PendingDeliveryAck({ srcEid: _srcEid, amountSD: _amountSD });
+    }
+
+    /**
+     * @notice Takes the pending ack of the delivery of `_guid` and encodes it
+     * @dev Composed by this contract, which the Solana program checks against its peer address. Each delivery is
+     *      acknowledged once.
+     * @param _guid The GUID of the delivered transfer.
+     * @return dstEid The endpoint ID of the peer the transfer came from.
+     * @return message The ack, to be sent back to that peer.
+     */
+    function _takeDeliveryAck(bytes32 _guid) internal virtual returns (uint32 dstEid, bytes memory message) {
+        PendingDeliveryAck memory ack = pendingDeliveryAcks[_guid];
+        if (ack.srcEid == 0) revert NoPendingDeliveryAck(_guid);
+        delete pendingDeliveryAcks[_guid];
+
+        emit DeliveryAckSent(ack.srcEid, _guid, ack.amountSD);
+        return (ack.srcEid, _encodeControlMessage(CONTROL_DELIVERY_ACK, abi.encodePacked(_guid, ack.amountSD)));
+    }
+
     /**
      * @notice Apply rate limiting for outbound transfers (inverted to act as inbound rate limit)
      * @dev Uses LayerZero's outbound rate limiter in reverse - calling _inflow() to consume capacity
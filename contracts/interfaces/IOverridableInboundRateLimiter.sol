@@ -7,18 +7,36 @@ struct RateLimitExemptAddress {
     bool isExempt;
 }
 
+struct PendingDeliveryAck {
+    uint32 srcEid;
+    uint64 amountSD;
+}
+
 interface IOverridableInboundRateLimiter {
     error InputLengthMismatch(uint256 addressOrGUIDLength, uint256 overridableLength); // 0x6b7f6f0e
+    error InvalidControlMessage(uint8 controlType, uint256 payloadLength);
+    error NoPeerChallenge(uint32 eid);
+    error NoOverrideGuids();
+    error NoPendingDeliveryAck(bytes32 guid);
 
     event RateLimitUpdated(RateLimiter.RateLimitConfig[] newConfigs);
     event RateLimitOverrider_ModifiedAddress(RateLimitExemptAddress[] indexed addresses);
     event RateLimitOverrider_ModifiedGUID(bytes32[] indexed guid, bool canOverride);
+    event PeerChallengeReceived(uint32 indexed srcEid, bytes32 nonce);
+    event PeerChallengeEchoed(uint32 indexed dstEid, bytes32 nonce);
+    event OverrideGuidsSent(uint32 indexed dstEid, bytes32[] guids, uint32[] srcEids);
+    event PauseNoticeReceived(uint32 indexed srcEid, uint64 sentAt);
+    event DeliveryAcksUpdated(uint32 indexed srcEid, bool enabled);
+    event DeliveryAckSent(uint32 indexed dstEid, bytes32 guid, uint64 amountSD);
 
     /// ------------------------------------------------------------------------------
     /// Storage Variables
     /// ------------------------------------------------------------------------------
     function exemptAddresses(address addr) external view returns (bool isExempt);
     function guidOverrides(bytes32 guid) external view returns (bool canOverride);
+    function peerChallenges(uint32 eid) external view returns (bytes32 nonce);
+    function deliveryAcksEnabled(uint32 eid) external view returns (bool enabled);
+    function pendingDeliveryAcks(bytes32 guid) external view returns (uint32 srcEid, uint64 amountSD);
 
     /*
      * @notice Sets the rate limits for the contract.
@@ -43,4 +61,11 @@ interface IOverridableInboundRateLimiter {
      * @dev canOverride is applied to all GUIDs in the array.
      */
     function modifyOverridableGUIDs(bytes32[] calldata guids, bool canOverride) external;
+
+    /*
+     * @notice Enables or disables delivery acks for the transfers received from `srcEid`.
+     * @param srcEid The endpoint ID of the sending peer, a Solana deployment tracking its transfers in flight.
+     * @param enabled Whether its transfers are recorded to be acknowledged with sendDeliveryAck().
+     */
+    function setDeliveryAcks(uint32 srcEid, bool enabled) external;
 }
@@ -0,0 +1,27 @@
+use crate::*;
+
+use cpi_helper::CpiContext;
+
+#[derive(CpiContext, Accounts)]
+#[instruction(params: ClearParams)]
+pub struct Clear<'info> {
+    /// The PDA of the OApp
+    pub signer: Signer<'info>,
+}
+
+impl Clear<'_> {
+    pub fn apply(ctx: &mut Context<Clear>, params: &ClearParams) -> Result<[u8; 32]> {
+        require_keys_eq!(ctx.accounts.signer.key(), params.receiver);
+        Ok(params.guid)
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct ClearParams {
+    pub receiver: Pubkey,
+    pub src_eid: u32,
+    pub sender: [u8; 32],
+    pub nonce: u64,
+    pub guid: [u8; 32],
+    pub message: Vec<u8>,
+}
@@ -1,3 +1,13 @@
+pub mod clear;
+pub mod quote;
 pub mod register_oapp;
+pub mod send;
+pub mod send_compose;
+pub mod set_delegate;
 
+pub use clear::*;
+pub use quote::*;
 pub use register_oapp::*;
+pub use send::*;
+pub use send_compose::*;
+pub use set_delegate::*;
@@ -0,0 +1,26 @@
+use crate::*;
+
+use cpi_helper::CpiContext;
+
+#[derive(CpiContext, Accounts)]
+#[instruction(params: QuoteParams)]
+pub struct Quote<'info> {
+    /// CHECK: not used by the mock
+    pub send_library_program: UncheckedAccount<'info>,
+}
+
+impl Quote<'_> {
+    pub fn apply(_ctx: &Context<Quote>, _params: &QuoteParams) -> Result<MessagingFee> {
+        Ok(MessagingFee { native_fee: 0, lz_token_fee: 0 })
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct QuoteParams {
+    pub sender: Pubkey,
+    pub dst_eid: u32,
+    pub receiver: [u8; 32],
+    pub message: Vec<u8>,
+    pub options: Vec<u8>,
+    pub pay_in_lz_token: bool,
+}
@@ -0,0 +1,38 @@
+use crate::*;
+use anchor_lang::solana_program::hash::hashv;
+
+use cpi_helper::CpiContext;
+
+#[derive(CpiContext, Accounts)]
+#[instruction(params: SendParams)]
+pub struct Send<'info> {
+    /// The PDA of the OApp
+    pub sender: Signer<'info>,
+}
+
+impl Send<'_> {
+    pub fn apply(ctx: &mut Context<Send>, params: &SendParams) -> Result<MessagingReceipt> {
+        let guid = hashv(&[
+            ctx.accounts.sender.key.as_ref(),
+            &params.dst_eid.to_be_bytes(),
+            &params.receiver,
+            &params.message,
+        ])
+        .to_bytes();
+        Ok(MessagingReceipt {
+            guid,
+            nonce: 1,
+            fee: MessagingFee { native_fee: params.native_fee, lz_token_fee: params.lz_token_fee },
+        })
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SendParams {
+    pub dst_eid: u32,
+    pub receiver: [u8; 32],
+    pub message: Vec<u8>,
+    pub options: Vec<u8>,
+    pub native_fee: u64,
+    pub lz_token_fee: u64,
+}
@@ -0,0 +1,24 @@
+use crate::*;
+
+use cpi_helper::CpiContext;
+
+#[derive(CpiContext, Accounts)]
+#[instruction(params: SendComposeParams)]
+pub struct SendCompose<'info> {
+    /// The PDA of the OApp
+    pub from: Signer<'info>,
+}
+
+impl SendCompose<'_> {
+    pub fn apply(_ctx: &mut Context<SendCompose>, _params: &SendComposeParams) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SendComposeParams {
+    pub to: Pubkey,
+    pub guid: [u8; 32],
+    pub index: u16,
+    pub message: Vec<u8>,
+}
@@ -16,4 +16,37 @@ pub mod endpoint {
     pub fn register_oapp(mut ctx: Context<RegisterOApp>, params: RegisterOAppParams) -> Result<()> {
         RegisterOApp::apply(&mut ctx, &params)
     }
+
+    pub fn set_delegate(mut ctx: Context<SetDelegate>, params: SetDelegateParams) -> Result<()> {
+        SetDelegate::apply(&mut ctx, &params)
+    }
+
+    pub fn quote(ctx: Context<Quote>, params: QuoteParams) -> Result<MessagingFee> {
+        Quote::apply(&ctx, &params)
+    }
+
+    pub fn send(mut ctx: Context<Send>, params: SendParams) -> Result<MessagingReceipt> {
+        Send::apply(&mut ctx, &params)
+    }
+
+    pub fn clear(mut ctx: Context<Clear>, params: ClearParams) -> Result<[u8; 32]> {
+        Clear::apply(&mut ctx, &params)
+    }
+
+    pub fn send_compose(mut ctx: Context<SendCompose>, params: SendComposeParams) -> Result<()> {
+        SendCompose::apply(&mut ctx, &params)
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct MessagingFee {
+    pub native_fee: u64,
+    pub lz_token_fee: u64,
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct MessagingReceipt {
+    pub guid: [u8; 32],
+    pub nonce: u64,
+    pub fee: MessagingFee,
 }
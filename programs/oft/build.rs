@@ -1,3 +1,12 @@
+// The program deployed on mainnet-beta, see deployments/solana-mainnet/OFT.json
+const MAINNET_OFT_ID: &str = "HYVSSRw542kNTAa2x8Ub3tb2ygK6bJEW7pHtP6GwguqP";
+
 fn main() {
     println!("cargo:rerun-if-env-changed=OFT_ID");
+    // test mode mints freely and lifts the rate limits, and only the feature keeps it off
+    if std::env::var_os("CARGO_FEATURE_TEST_MODE").is_some()
+        && std::env::var("OFT_ID").map_or(false, |oft_id| oft_id == MAINNET_OFT_ID)
+    {
+        panic!("the test-mode feature must not be built for the mainnet-beta program");
+    }
 }
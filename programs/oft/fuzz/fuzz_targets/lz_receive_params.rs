@@ -0,0 +1,26 @@
+#![no_main]
+
+use anchor_lang::AnchorDeserialize;
+use libfuzzer_sys::fuzz_target;
+use oapp::LzReceiveParams;
+use oft::{msg_codec, state::OFTStore};
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(params) = LzReceiveParams::try_from_slice(data) else {
+        return;
+    };
+    if msg_codec::validate(&params.message).is_err() {
+        return;
+    }
+
+    // amounts must either convert exactly or be rejected, never wrap
+    let amount_sd = msg_codec::amount_sd(&params.message);
+    for decimals_diff in 0..=18 {
+        let oft_store = OFTStore { ld2sd_rate: 10u64.pow(decimals_diff), ..Default::default() };
+        match oft_store.sd2ld(amount_sd) {
+            Ok(amount_ld) => assert_eq!(oft_store.ld2sd(amount_ld).unwrap(), amount_sd),
+            Err(_) => assert!(amount_sd.checked_mul(oft_store.ld2sd_rate).is_none()),
+        }
+    }
+    let _ = msg_codec::compose_msg(&params.message);
+});
@@ -0,0 +1,24 @@
+#![no_main]
+
+use anchor_lang::prelude::Pubkey;
+use libfuzzer_sys::fuzz_target;
+use oft::msg_codec;
+
+fuzz_target!(|message: &[u8]| {
+    if msg_codec::validate(message).is_err() {
+        return;
+    }
+
+    // a valid message must decode without panicking and re-encode to the same bytes
+    let send_to = msg_codec::send_to(message);
+    let amount_sd = msg_codec::amount_sd(message);
+    let compose_msg = msg_codec::compose_msg(message);
+    let encoded = match &compose_msg {
+        Some(msg) => {
+            let sender = Pubkey::from(msg_codec::compose_from(message).unwrap());
+            msg_codec::encode(send_to, amount_sd, sender, &Some(msg[32..].to_vec()))
+        },
+        None => msg_codec::encode(send_to, amount_sd, Pubkey::default(), &None),
+    };
+    assert_eq!(encoded, message);
+});
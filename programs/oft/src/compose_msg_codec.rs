@@ -14,7 +14,7 @@ pub fn encode(
     encoded.extend_from_slice(&nonce.to_be_bytes());
     encoded.extend_from_slice(&src_eid.to_be_bytes());
     encoded.extend_from_slice(&amount_ld.to_be_bytes());
-    encoded.extend_from_slice(&compose_msg);
+    encoded.extend_from_slice(compose_msg);
     encoded
 }
 
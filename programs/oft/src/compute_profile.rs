@@ -0,0 +1,20 @@
+//! Compute unit accounting for the phases of send and lz_receive, behind the `compute-profile`
+//! feature.
+//!
+//! Each checkpoint logs the phase that just ended, followed by the runtime's
+//! "Program consumption: N units remaining" line, so the units a phase used are the difference
+//! to the previous checkpoint in the transaction logs. The logging costs about 200 units per
+//! checkpoint itself, so profile builds are for measuring only and never for mainnet. Without
+//! the feature checkpoints compile to nothing.
+use crate::*;
+
+#[inline(always)]
+pub fn checkpoint(phase: &str) {
+    #[cfg(feature = "compute-profile")]
+    {
+        msg!("compute-profile: {}", phase);
+        anchor_lang::solana_program::log::sol_log_compute_units();
+    }
+    #[cfg(not(feature = "compute-profile"))]
+    let _ = phase;
+}
@@ -15,4 +15,90 @@ pub enum OFTError {
     NotInOverrideList,
     RateLimitOverrideListFull,
     ManageRateLimitOverrideParamsLengthMismatch,
+    EndpointMigrationPending,
+    NoPendingEndpointMigration,
+    EndpointMigrationTimelocked,
+    PeerNotVerified,
+    AlreadyMigrated,
+    InvalidMigration,
+    InvalidPendingCredit,
+    AmountOverflow,
+    AmountUnderflow,
+    InvalidMessage,
+    TvlOverflow,
+    TvlUnderflow,
+    InvalidTimestamp,
+    InvalidRateLimitOverrideProposal,
+    InvalidOverrideListSize,
+    NonceGapDetected,
+    InsufficientFeeVaultBalance,
+    FeeVaultSendersListFull,
+    InvalidCrankTask,
+    CrankTaskNotDue,
+    InvalidOptions,
+    InsufficientDstGas,
+    UnauthorizedFlowTag,
+    DustToFeesDisabled,
+    InvalidProbationPolicy,
+    ProbationLimitExceeded,
+    SourceSenderAllowlistFull,
+    SourceSenderNotAllowed,
+    InvalidRecipient,
+    WithdrawDestinationNotAllowed,
+    WithdrawDestinationsListFull,
+    NoPendingWithdrawDestinations,
+    WithdrawDestinationsTimelocked,
+    NoPendingSupplyAdjustment,
+    SupplyAdjustmentTimelocked,
+    InvalidPriorityTiers,
+    UnknownPriorityTier,
+    NoExpectedRemoteConfig,
+    OverrideCosignersListFull,
+    OverrideCosignerRequired,
+    InvalidInboundSplit,
+    NothingVested,
+    TestModeUnavailable,
+    TestModeDisabled,
+    InvalidTvlAlert,
+    DuplicateSend,
+    InvalidSendRecord,
+    InvalidLamportsTarget,
+    InsufficientSurplusLamports,
+    AllowedComposersListFull,
+    ExecutorAllowlistFull,
+    InvalidTransferReceiptPolicy,
+    InvalidTransferReceipt,
+    TransferReceiptRetained,
+    DstEidNotAllowed,
+    AllowedDstEidsListFull,
+    ZeroAmountMessage,
+    InvalidFeeStream,
+    FeeStreamActive,
+    InvalidSilenceMonitor,
+    EvmAddressMapFull,
+    EmptyOverrideListMirror,
+    InvalidSenderRateLimit,
+    InvalidSenderRateLimitAccount,
+    InvalidRateLimitMode,
+    PeerControlNotProven,
+    InvalidLaunchSchedule,
+    LaunchLimitExceeded,
+    InvalidOverrideMultiplier,
+    RateLimitBatchLengthMismatch,
+    InvalidPeerAccount,
+    InvalidRateLimitBoost,
+    InvalidOverrideScope,
+    OverrideApproverRequired,
+    InvalidMsgType,
+    PeerPaused,
+    InvalidBreachMonitor,
+    FeeExemptListFull,
+    AlreadyFeeExempt,
+    NotFeeExempt,
+    TreasuryNotSet,
+    InsufficientAccruedFees,
+    ControlMessagePaused,
+    NotPaused,
+    TransferAccountMissing,
+    InFlightNotTracked,
 }
@@ -7,6 +7,11 @@ pub struct OFTSent {
     pub from: Pubkey,
     pub amount_sent_ld: u64,
     pub amount_received_ld: u64,
+    // amount_received_ld in shared decimals, as carried in the message
+    pub amount_sd: u64,
+    pub message_hash: [u8; 32],
+    pub flow_tag: u8,
+    pub outflow: OutflowStats,
 }
 
 #[event]
@@ -15,6 +20,22 @@ pub struct OFTReceived {
     pub src_eid: u32,
     pub to: Pubkey,
     pub amount_received_ld: u64,
+    // the amount carried in the message, before any token transfer fee on the credit
+    pub amount_sd: u64,
+    pub message_hash: [u8; 32],
+}
+
+#[event]
+pub struct HeldCreditRefunded {
+    pub guid: [u8; 32],
+    pub to: Pubkey,
+    pub lamports: u64,
+}
+
+#[event]
+pub struct DustSwept {
+    pub owner: Pubkey,
+    pub dust_ld: u64,
 }
 
 #[event]
@@ -23,6 +44,12 @@ pub struct RateLimitOverrideUpdated {
     pub action: RateLimitOverrideAction,
 }
 
+#[event]
+pub struct FeeExemptionUpdated {
+    pub address: Pubkey,
+    pub action: RateLimitOverrideAction,
+}
+
 #[event]
 pub struct RateLimitOverrideGuidUpdated {
     pub guid: [u8; 32],
@@ -31,13 +58,445 @@ pub struct RateLimitOverrideGuidUpdated {
 
 #[event]
 pub struct RateLimitOverrideTriggered {
-    pub address: Pubkey,
+    pub address: Pubkey, // the sender, or the receiving token account
     pub amount_ld: u64,
+    pub remote_eid: u32, // the destination of a send, the source of a credit
+    pub direction: RateLimitDirection,
+    pub available_ld: Option<u64>, // left in the skipped rate limiter, None if there is none
 }
 
 #[event]
 pub struct RateLimitOverrideGuidTriggered {
     pub guid: [u8; 32],
     pub amount_ld: u64,
+    pub consumed: bool, // removed from the store's guid overrides by this delivery
+    pub src_eid: u32,
+    pub available_ld: Option<u64>, // left in the skipped inbound rate limiter, None if there is none
+}
+
+#[event]
+pub struct EndpointMigrationProposed {
+    pub new_endpoint_program: Pubkey,
+    pub executable_at: i64,
+}
+
+#[event]
+pub struct EndpointMigrationCancelled {
+    pub new_endpoint_program: Pubkey,
+}
+
+#[event]
+pub struct SupplyAdjustmentProposed {
+    pub amount_ld: u64,
+    pub executable_at: i64,
+}
+
+#[event]
+pub struct SupplyAdjustmentCancelled {
+    pub amount_ld: u64,
+}
+
+#[event]
+pub struct SupplyAdjustmentExecuted {
+    pub amount_ld: u64,
+    pub tvl_ld: u64, // after the burn
+}
+
+#[event]
+pub struct WithdrawDestinationsProposed {
+    pub destinations: Vec<Pubkey>,
+    pub executable_at: i64,
+}
+
+#[event]
+pub struct WithdrawDestinationsCancelled {
+    pub destinations: Vec<Pubkey>,
+}
+
+#[event]
+pub struct WithdrawDestinationsExecuted {
+    pub destinations: Vec<Pubkey>,
+}
+
+#[event]
+pub struct EndpointMigrationExecuted {
+    pub old_endpoint_program: Pubkey,
+    pub new_endpoint_program: Pubkey,
+    pub endpoint_version: u8,
+}
+
+#[event]
+pub struct OFTStoreMigrated {
+    pub from_version: u8,
+    pub version: u8,
+}
+
+#[event]
+pub struct DeprecatedInstructionUsed {
+    pub instruction: DeprecatedInstruction,
+}
+
+#[event]
+pub struct OverrideListsResized {
+    pub max_rate_limit_overrides: u8,
+    pub max_rate_limit_override_guid_count: u8,
+}
+
+#[event]
+pub struct PeerConfigMigrated {
+    pub remote_eid: u32,
+    pub from_version: u8,
+    pub version: u8,
+}
+
+#[event]
+pub struct CreditHeld {
+    pub guid: [u8; 32],
+    pub src_eid: u32,
+    pub to: Pubkey,
+    pub amount_ld: u64,
+    pub awaiting_verification: bool,
+    pub awaiting_claim: bool,
+}
+
+#[event]
+pub struct CreditQueued {
+    pub guid: [u8; 32],
+    pub src_eid: u32,
+    pub to: Pubkey,
+    pub amount_ld: u64,
+}
+
+#[event]
+pub struct CreditReleased {
+    pub guid: [u8; 32],
+    pub src_eid: u32,
+    pub to: Pubkey,
+    pub amount_ld: u64,
+}
+
+#[event]
+pub struct CreditVesting {
+    pub guid: [u8; 32],
+    pub src_eid: u32,
+    pub to: Pubkey,
+    pub immediate_ld: u64, // credited right away and reported in OFTReceived
+    pub vesting_ld: u64,
+    pub vesting: Vesting,
+}
+
+#[event]
+pub struct VestedCreditClaimed {
+    pub guid: [u8; 32],
+    pub to: Pubkey,
+    pub amount_received_ld: u64,
+    pub remaining_ld: u64,
+}
+
+#[event]
+pub struct PendingCreditResolved {
+    pub guid: [u8; 32],
+    pub approved: bool,
+    pub resolver: Pubkey,
+}
+
+#[event]
+pub struct InboundOptOutUpdated {
+    pub owner: Pubkey,
+    pub opted_out: bool,
+}
+
+#[event]
+pub struct NotificationHookUpdated {
+    pub owner: Pubkey,
+    pub registered: bool,
+}
+
+#[event]
+pub struct RateLimitOverrideProposed {
+    pub target: RateLimitOverrideTarget,
+    pub notional_ld: u64,
+    pub proposer: Pubkey,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct RateLimitOverrideApproved {
+    pub target: RateLimitOverrideTarget,
+    pub notional_ld: u64,
+    pub approver: Pubkey,
+}
+
+#[event]
+pub struct RateLimitOverrideExpired {
+    pub target: RateLimitOverrideTarget,
+}
+
+#[event]
+pub struct NonceGapDetected {
+    pub src_eid: u32,
+    pub nonce: u64,
+    pub highest_inbound_nonce: u64,
+}
+
+#[event]
+pub struct PeerProbationUpdated {
+    pub remote_eid: u32,
+    pub ends_at: i64,
+}
+
+#[event]
+pub struct SourceSenderAllowlistUpdated {
+    pub remote_eid: u32,
+    pub allowlist: Option<SourceSenderAllowlist>,
+}
+
+#[event]
+pub struct ConfigDivergence {
+    pub remote_eid: u32,
+    pub expected_hash: [u8; 32],
+    pub submitted_hash: [u8; 32],
+    pub config: RemoteConfig,
+}
+
+#[event]
+pub struct MinDstGasUpdated {
+    pub remote_eid: u32,
+    pub min_dst_gas: Option<MinDstGas>,
+}
+
+#[event]
+pub struct CrankExecuted {
+    pub task: CrankTask,
+    pub keeper: Pubkey,
+    pub bounty_ld: u64,
+}
+
+#[event]
+pub struct ControlMessagePauseUpdated {
+    pub message: ControlMessage,
+    pub paused: bool,
+}
+
+#[event]
+pub struct PeerAutoPaused {
+    pub remote_eid: u32,
+    pub breaches: u16,
+    pub window_seconds: i64,
+}
+
+#[event]
+pub struct PeerPauseUpdated {
+    pub remote_eid: u32,
+    pub paused: bool,
+}
+
+#[event]
+pub struct PeerFeeUpdated {
+    pub remote_eid: u32,
+    pub fee_bps: Option<u16>, // None charges the store's default_fee_bps
+}
+
+#[event]
+pub struct PeerSilent {
+    pub remote_eid: u32,
+    pub last_inbound_at: i64, // 0 if nothing was ever received from the peer
+    pub max_silence_seconds: i64,
+}
+
+#[event]
+pub struct FeeCollectorUpdated {
+    pub fee_collector: Option<Pubkey>,
+}
+
+#[event]
+pub struct TreasuryUpdated {
+    pub treasury: Option<Pubkey>, // the only token account withdraw_fees pays out to
+}
+
+#[event]
+pub struct FeesWithdrawn {
+    pub authority: Pubkey, // the admin or the fee collector
+    pub treasury: Pubkey,
+    pub amount_ld: u64,
+    pub accrued_fees_ld: u64, // left after the withdrawal
+}
+
+#[event]
+pub struct FeesStreamed {
+    pub recipient: Pubkey,
+    pub amount_ld: u64,
+}
+
+#[event]
+pub struct TestMinted {
+    pub to: Pubkey,
+    pub amount_ld: u64,
+}
+
+#[event]
+pub struct TvlDropAlert {
+    pub window_start: i64,
+    pub high_ld: u64,
+    pub tvl_ld: u64,
+}
+
+#[event]
+pub struct LamportsToppedUp {
+    pub account: Pubkey,
+    pub contributor: Pubkey,
+    pub lamports: u64,
+    pub contributed_lamports: u64,
+}
+
+#[event]
+pub struct SurplusLamportsWithdrawn {
+    pub account: Pubkey,
+    pub to: Pubkey,
+    pub lamports: u64,
+}
+
+#[event]
+pub struct ExecutorAllowlistUpdated {
+    pub remote_eid: u32,
+    pub executors: Vec<Pubkey>,
+}
+
+#[event]
+pub struct ExecutorRejected {
+    pub src_eid: u32,
+    pub guid: [u8; 32],
+    pub executor: Pubkey,
+}
+
+#[event]
+pub struct ZeroAmountIgnored {
+    pub src_eid: u32,
+    pub guid: [u8; 32],
+    pub to: Pubkey,
+    pub composed: bool, // the compose message was dropped with it
 }
 
+#[event]
+pub struct InboundRateLimitUpdated {
+    pub src_eid: u32,
+    pub rate_limiter: Option<RateLimiter>,
+}
+
+#[event]
+pub struct MsgTypeRateLimitUpdated {
+    pub remote_eid: u32,
+    pub msg_type: u8,
+    pub direction: RateLimitDirection,
+    pub rate_limiter: Option<RateLimiter>,
+}
+
+#[event]
+pub struct OverrideApproverUpdated {
+    pub override_approver: Option<Pubkey>,
+}
+
+#[event]
+pub struct GovernanceEidUpdated {
+    pub governance_eid: Option<u32>,
+}
+
+#[event]
+pub struct ReservedCapacityUpdated {
+    pub remote_eid: u32,
+    pub reserved_capacity_ld: u64,
+}
+
+#[event]
+pub struct RateLimitEnabledUpdated {
+    pub remote_eid: u32,
+    pub enabled: bool,
+}
+
+#[event]
+pub struct RateLimitBoosted {
+    pub remote_eid: u32,
+    pub direction: RateLimitDirection,
+    pub capacity: u64,
+    pub refill_per_second: u64,
+    pub expires_at: i64, // the rate limiter's own limits are restored from then on
+}
+
+#[event]
+pub struct RateLimitConsumptionReset {
+    pub remote_eid: u32,
+    pub direction: RateLimitDirection,
+    pub flushed_ld: u64, // what had been consumed of the peer's rate limiter, 0 without one
+}
+
+#[event]
+pub struct OutboundRateLimitUpdated {
+    pub dst_eid: u32,
+    pub rate_limiter: Option<RateLimiter>,
+}
+
+#[event]
+pub struct EvmAddressMappingSet {
+    pub address: Pubkey,
+    pub evm_address: Option<[u8; 20]>,
+}
+
+#[event]
+pub struct OverrideListMirrored {
+    pub dst_eid: u32,
+    pub guid: [u8; 32],
+    pub exempt: Vec<[u8; 20]>,
+    pub not_exempt: Vec<[u8; 20]>,
+}
+
+#[event]
+pub struct PauseNoticeSent {
+    pub remote_eid: u32,
+    pub guid: [u8; 32],
+    pub sent_at: i64,
+}
+
+#[event]
+pub struct PauseNoticeReceived {
+    pub src_eid: u32,
+    pub sent_at: i64,
+    pub honored: bool, // whether it paused this store, see OFTStore::honor_pause_notices
+}
+
+#[event]
+pub struct HonorPauseNoticesUpdated {
+    pub honor_pause_notices: bool,
+}
+
+#[event]
+pub struct PeerControlChallenged {
+    pub remote_eid: u32,
+    pub guid: [u8; 32],
+    pub nonce: [u8; 32],
+}
+
+#[event]
+pub struct PeerControlProven {
+    pub remote_eid: u32,
+    pub peer_address: [u8; 32],
+}
+
+// Emitted when lz_receive queues a credit the peer's inbound rate limiters cannot cover yet.
+// Transfers failing with OFTError::RateLimitExceeded only return the error, since indexers skip
+// the logs of failed transactions.
+#[event]
+pub struct RateLimitExceeded {
+    pub remote_eid: u32,
+    pub direction: RateLimitDirection,
+    pub requested_ld: u64, // what the transfer was charged, scaled down for multiplier overrides
+    pub available_ld: u64,
+    pub actor: Pubkey, // the sender, or the receiving token account
+}
+
+// Emitted when a peer acknowledges the delivery of a transfer sent to it.
+#[event]
+pub struct DeliveryAcknowledged {
+    pub remote_eid: u32,
+    pub guid: [u8; 32], // the guid of the transfer sent to the peer
+    pub amount_ld: u64,
+}
@@ -0,0 +1,112 @@
+use crate::*;
+
+pub const MAX_RATE_LIMIT_BOOST_SECONDS: i64 = 7 * 24 * 60 * 60;
+
+/// Raises the capacity and refill rate of one of a peer's rate limiters for duration_seconds,
+/// adding the extra capacity to what is available. Once the boost expires, the next transfer
+/// through the peer puts its own limits back. Boosting again before then extends the boost and
+/// still reverts to the limits from before the first.
+#[derive(Accounts)]
+#[instruction(params: BoostRateLimitParams)]
+pub struct BoostRateLimit<'info> {
+    /// admin, or the store's rate limiter
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump,
+        constraint = oft_store.is_rate_limiter(admin.key) @OFTError::Unauthorized
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        mut,
+        seeds = [PEER_SEED, oft_store.key().as_ref(), &params.remote_eid.to_be_bytes()],
+        bump = peer.bump
+    )]
+    pub peer: Account<'info, PeerConfig>,
+    /// CHECK: the peer's RateLimitState, updated only if it has been created
+    #[account(
+        mut,
+        seeds = [
+            RATE_LIMIT_STATE_SEED,
+            oft_store.key().as_ref(),
+            &params.remote_eid.to_be_bytes()
+        ],
+        bump
+    )]
+    pub rate_limit_state: UncheckedAccount<'info>,
+}
+
+impl BoostRateLimit<'_> {
+    pub fn apply(ctx: &mut Context<BoostRateLimit>, params: &BoostRateLimitParams) -> Result<()> {
+        require!(
+            params.duration_seconds > 0 && params.duration_seconds <= MAX_RATE_LIMIT_BOOST_SECONDS,
+            OFTError::InvalidRateLimitBoost
+        );
+        let now = Clock::get()?.unix_timestamp;
+        let peer: &mut PeerConfig = &mut ctx.accounts.peer;
+        peer.expire_rate_limit_boosts(now)?;
+
+        let (boost, rate_limiter, mode) = match params.direction {
+            RateLimitDirection::Outbound => (
+                &mut peer.outbound_rate_limit_boost,
+                &mut peer.outbound_rate_limiter,
+                peer.outbound_rate_limit_mode,
+            ),
+            RateLimitDirection::Inbound => (
+                &mut peer.inbound_rate_limit_boost,
+                &mut peer.inbound_rate_limiter,
+                peer.inbound_rate_limit_mode,
+            ),
+        };
+        let rate_limiter = rate_limiter.as_mut().ok_or(error!(OFTError::InvalidRateLimitBoost))?;
+        require!(
+            params.capacity >= rate_limiter.capacity
+                && params.refill_per_second >= rate_limiter.refill_per_second,
+            OFTError::InvalidRateLimitBoost
+        );
+
+        // keep the limits from before an earlier boost, unless they have been set since
+        let (capacity, refill_per_second) = match boost.as_ref() {
+            Some(boost)
+                if rate_limiter.capacity == boost.boosted_capacity
+                    && rate_limiter.refill_per_second == boost.boosted_refill_per_second =>
+            {
+                (boost.capacity, boost.refill_per_second)
+            },
+            _ => (rate_limiter.capacity, rate_limiter.refill_per_second),
+        };
+        rate_limiter.set_rate(mode, params.refill_per_second)?;
+        let extra_tokens = params.capacity - rate_limiter.capacity;
+        rate_limiter.capacity = params.capacity;
+        rate_limiter.refill(mode, extra_tokens)?;
+
+        let expires_at =
+            now.checked_add(params.duration_seconds).ok_or(error!(OFTError::InvalidTimestamp))?;
+        *boost = Some(RateLimitBoost {
+            expires_at,
+            capacity,
+            refill_per_second,
+            boosted_capacity: params.capacity,
+            boosted_refill_per_second: params.refill_per_second,
+        });
+
+        RateLimitState::sync(&ctx.accounts.rate_limit_state, &ctx.accounts.peer)?;
+        emit!(RateLimitBoosted {
+            remote_eid: params.remote_eid,
+            direction: params.direction,
+            capacity: params.capacity,
+            refill_per_second: params.refill_per_second,
+            expires_at,
+        });
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct BoostRateLimitParams {
+    pub remote_eid: u32,
+    pub direction: RateLimitDirection,
+    pub capacity: u64,          // at least the rate limiter's current capacity
+    pub refill_per_second: u64, // at least the rate limiter's current refill rate
+    pub duration_seconds: i64,  // up to MAX_RATE_LIMIT_BOOST_SECONDS
+}
@@ -0,0 +1,110 @@
+use crate::*;
+use anchor_spl::token_interface::{self, Burn, Mint, TokenAccount, TokenInterface};
+
+/// Executes a DAO-approved supply adjustment through the bridge accounting. For an Adapter the
+/// burn comes out of the TVL, matching a burn of the bridged supply on the other chains; for a
+/// Native OFT it burns the fees held by the escrow, taking them off accrued_fees_ld. Burns only
+/// execute SUPPLY_ADJUSTMENT_DELAY_SECONDS after they are proposed.
+#[derive(Accounts)]
+pub struct BurnFromEscrow<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump,
+        has_one = admin @OFTError::Unauthorized
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        mut,
+        address = oft_store.token_mint,
+        mint::token_program = token_program
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        address = oft_store.token_escrow,
+        token::authority = oft_store,
+        token::mint = token_mint,
+        token::token_program = token_program
+    )]
+    pub token_escrow: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl BurnFromEscrow<'_> {
+    pub fn apply(ctx: &mut Context<BurnFromEscrow>, params: &BurnFromEscrowParams) -> Result<()> {
+        match params.clone() {
+            BurnFromEscrowParams::Propose(amount_ld) => {
+                let executable_at = Clock::get()?
+                    .unix_timestamp
+                    .checked_add(SUPPLY_ADJUSTMENT_DELAY_SECONDS)
+                    .ok_or_else(|| error!(OFTError::InvalidTimestamp))?;
+                // a new proposal replaces the pending one and restarts the timelock
+                ctx.accounts.oft_store.pending_supply_adjustment =
+                    Some(SupplyAdjustment { amount_ld, executable_at });
+
+                emit!(SupplyAdjustmentProposed { amount_ld, executable_at });
+            },
+            BurnFromEscrowParams::Cancel => {
+                let adjustment = ctx
+                    .accounts
+                    .oft_store
+                    .pending_supply_adjustment
+                    .take()
+                    .ok_or(OFTError::NoPendingSupplyAdjustment)?;
+
+                emit!(SupplyAdjustmentCancelled { amount_ld: adjustment.amount_ld });
+            },
+            BurnFromEscrowParams::Execute => {
+                let adjustment = ctx
+                    .accounts
+                    .oft_store
+                    .pending_supply_adjustment
+                    .take()
+                    .ok_or(OFTError::NoPendingSupplyAdjustment)?;
+                require!(
+                    Clock::get()?.unix_timestamp >= adjustment.executable_at,
+                    OFTError::SupplyAdjustmentTimelocked
+                );
+
+                if ctx.accounts.oft_store.oft_type == OFTType::Adapter {
+                    ctx.accounts.oft_store.decrease_tvl(adjustment.amount_ld)?;
+                } else {
+                    // a native escrow holds nothing but fees
+                    ctx.accounts.oft_store.pay_out_fees(adjustment.amount_ld);
+                }
+                let seeds: &[&[u8]] = &[
+                    OFT_SEED,
+                    &ctx.accounts.token_escrow.key().to_bytes(),
+                    &[ctx.accounts.oft_store.bump],
+                ];
+                token_interface::burn(
+                    CpiContext::new(
+                        ctx.accounts.token_program.to_account_info(),
+                        Burn {
+                            mint: ctx.accounts.token_mint.to_account_info(),
+                            from: ctx.accounts.token_escrow.to_account_info(),
+                            authority: ctx.accounts.oft_store.to_account_info(),
+                        },
+                    )
+                    .with_signer(&[&seeds]),
+                    adjustment.amount_ld,
+                )?;
+
+                emit!(SupplyAdjustmentExecuted {
+                    amount_ld: adjustment.amount_ld,
+                    tvl_ld: ctx.accounts.oft_store.tvl_ld,
+                });
+            },
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub enum BurnFromEscrowParams {
+    Propose(u64), // amount_ld, executable after SUPPLY_ADJUSTMENT_DELAY_SECONDS
+    Cancel,
+    Execute,
+}
@@ -0,0 +1,76 @@
+use crate::*;
+use anchor_lang::solana_program::keccak;
+use oapp::endpoint::instructions::SendParams as EndpointSendParams;
+
+/// Sends a control challenge to the peer. Sends to the peer are rejected until it echoes the
+/// nonce back from its peer address, so a mistyped peer address, which nothing can send from,
+/// never receives user funds. A new challenge replaces any pending one. The nonce only has to be
+/// fresh, not secret: lz_receive only accepts the echo from the peer address itself.
+#[derive(Accounts)]
+#[instruction(params: ChallengePeerParams)]
+pub struct ChallengePeer<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump,
+        has_one = admin @OFTError::Unauthorized
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        mut,
+        seeds = [
+            PEER_SEED,
+            oft_store.key().as_ref(),
+            &params.dst_eid.to_be_bytes()
+        ],
+        bump = peer.bump
+    )]
+    pub peer: Account<'info, PeerConfig>,
+}
+
+impl ChallengePeer<'_> {
+    pub fn apply(ctx: &mut Context<ChallengePeer>, params: &ChallengePeerParams) -> Result<()> {
+        let oft_store = &ctx.accounts.oft_store;
+        require!(oft_store.key() == ctx.remaining_accounts[1].key(), OFTError::InvalidSender);
+
+        let clock = Clock::get()?;
+        let nonce = keccak::hashv(&[
+            oft_store.key().as_ref(),
+            &params.dst_eid.to_be_bytes(),
+            &ctx.accounts.peer.peer_address,
+            &clock.slot.to_be_bytes(),
+            &clock.unix_timestamp.to_be_bytes(),
+        ])
+        .to_bytes();
+        let msg_receipt = oapp::endpoint_cpi::send(
+            oft_store.endpoint_program,
+            oft_store.key(),
+            ctx.remaining_accounts,
+            &[OFT_SEED, oft_store.token_escrow.as_ref(), &[oft_store.bump]],
+            EndpointSendParams {
+                dst_eid: params.dst_eid,
+                receiver: ctx.accounts.peer.peer_address,
+                message: msg_codec::encode_peer_challenge(oft_store.key().to_bytes(), nonce),
+                options: ctx
+                    .accounts
+                    .peer
+                    .enforced_options
+                    .combine_options(&None, &params.options)?,
+                native_fee: params.native_fee,
+                lz_token_fee: params.lz_token_fee,
+            },
+        )?;
+
+        ctx.accounts.peer.control_challenge = Some(nonce);
+        emit!(PeerControlChallenged { remote_eid: params.dst_eid, guid: msg_receipt.guid, nonce });
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct ChallengePeerParams {
+    pub dst_eid: u32,
+    pub options: Vec<u8>,
+    pub native_fee: u64,
+    pub lz_token_fee: u64,
+}
@@ -0,0 +1,75 @@
+use crate::*;
+
+/// Compares the parameters a keeper read from the EVM deployment of a peer against the hash the
+/// admin configured for it, emitting ConfigDivergence when they differ. Permissionless: a
+/// dishonest keeper can only raise a false alarm, which the submitted values make easy to check.
+#[derive(Accounts)]
+#[instruction(params: CheckRemoteConfigParams)]
+pub struct CheckRemoteConfig<'info> {
+    #[account(
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        seeds = [
+            PEER_SEED,
+            oft_store.key().as_ref(),
+            &params.remote_eid.to_be_bytes()
+        ],
+        bump = peer.bump
+    )]
+    pub peer: Account<'info, PeerConfig>,
+}
+
+impl CheckRemoteConfig<'_> {
+    /// Returns whether the submitted config matches.
+    pub fn apply(
+        ctx: &Context<CheckRemoteConfig>,
+        params: &CheckRemoteConfigParams,
+    ) -> Result<bool> {
+        let expected_hash = match ctx.accounts.peer.expected_remote_config_hash {
+            Some(hash) => hash,
+            None => return err!(OFTError::NoExpectedRemoteConfig),
+        };
+        let submitted_hash = params.config.hash();
+        if submitted_hash == expected_hash {
+            return Ok(true);
+        }
+
+        emit!(ConfigDivergence {
+            remote_eid: params.remote_eid,
+            expected_hash,
+            submitted_hash,
+            config: params.config.clone(),
+        });
+        Ok(false)
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct CheckRemoteConfigParams {
+    pub remote_eid: u32,
+    pub config: RemoteConfig,
+}
+
+/// The EVM-side parameters that must stay in line with this deployment.
+#[derive(Clone, AnchorSerialize, AnchorDeserialize, PartialEq, Eq, Debug)]
+pub struct RemoteConfig {
+    pub fee_bps: u16,
+    pub rate_limit_capacity: u64,
+    pub rate_limit_refill_per_second: u64,
+}
+
+impl RemoteConfig {
+    /// keccak256(abi.encodePacked(feeBps, rateLimitCapacity, rateLimitRefillPerSecond)), so the
+    /// expected hash can be computed with the same tooling on both sides.
+    pub fn hash(&self) -> [u8; 32] {
+        anchor_lang::solana_program::keccak::hashv(&[
+            &self.fee_bps.to_be_bytes(),
+            &self.rate_limit_capacity.to_be_bytes(),
+            &self.rate_limit_refill_per_second.to_be_bytes(),
+        ])
+        .to_bytes()
+    }
+}
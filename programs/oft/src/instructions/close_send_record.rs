@@ -0,0 +1,37 @@
+use crate::*;
+
+/// Returns the rent of an expired SendRecord to its sender. Permissionless, so keepers can clean
+/// up after backends.
+#[derive(Accounts)]
+pub struct CloseSendRecord<'info> {
+    #[account(
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        mut,
+        close = sender,
+        seeds = [
+            SEND_RECORD_SEED,
+            oft_store.key().as_ref(),
+            send_record.sender.as_ref(),
+            &send_record.idempotency_key
+        ],
+        bump
+    )]
+    pub send_record: Account<'info, SendRecord>,
+    /// CHECK: the sender that paid for the record
+    #[account(mut, address = send_record.sender)]
+    pub sender: AccountInfo<'info>,
+}
+
+impl CloseSendRecord<'_> {
+    pub fn apply(ctx: &mut Context<CloseSendRecord>) -> Result<()> {
+        require!(
+            Clock::get()?.unix_timestamp >= ctx.accounts.send_record.expires_at,
+            OFTError::DuplicateSend
+        );
+        Ok(())
+    }
+}
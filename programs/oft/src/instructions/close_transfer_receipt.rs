@@ -0,0 +1,32 @@
+use crate::*;
+
+/// Returns the rent of a TransferReceipt past its retention period to whoever paid for it.
+/// Permissionless, like close_send_record.
+#[derive(Accounts)]
+pub struct CloseTransferReceipt<'info> {
+    #[account(
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        mut,
+        close = payer,
+        seeds = [TRANSFER_RECEIPT_SEED, oft_store.key().as_ref(), &transfer_receipt.guid],
+        bump
+    )]
+    pub transfer_receipt: Account<'info, TransferReceipt>,
+    /// CHECK: the account that paid for the receipt
+    #[account(mut, address = transfer_receipt.payer)]
+    pub payer: AccountInfo<'info>,
+}
+
+impl CloseTransferReceipt<'_> {
+    pub fn apply(ctx: &mut Context<CloseTransferReceipt>) -> Result<()> {
+        require!(
+            Clock::get()?.unix_timestamp >= ctx.accounts.transfer_receipt.closable_at,
+            OFTError::TransferReceiptRetained
+        );
+        Ok(())
+    }
+}
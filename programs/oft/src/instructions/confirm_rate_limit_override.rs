@@ -0,0 +1,50 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct ConfirmRateLimitOverride<'info> {
+    /// admin, or the override manager to clean up an expired proposal
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump,
+        constraint = oft_store.admin == signer.key()
+            || oft_store.override_manager == Some(signer.key()) @OFTError::Unauthorized
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        mut,
+        close = proposer,
+        seeds = [
+            RATE_LIMIT_OVERRIDE_PROPOSAL_SEED,
+            oft_store.key().as_ref(),
+            &proposal.target.seed()
+        ],
+        bump
+    )]
+    pub proposal: Account<'info, RateLimitOverrideProposal>,
+    /// CHECK: refunded the proposal rent
+    #[account(mut, address = proposal.proposer)]
+    pub proposer: AccountInfo<'info>,
+}
+
+impl ConfirmRateLimitOverride<'_> {
+    pub fn apply(ctx: &mut Context<ConfirmRateLimitOverride>) -> Result<()> {
+        let target = ctx.accounts.proposal.target.clone();
+        if Clock::get()?.unix_timestamp > ctx.accounts.proposal.expires_at {
+            // the proposal is closed without taking effect
+            emit!(RateLimitOverrideExpired { target });
+            return Ok(());
+        }
+
+        require!(ctx.accounts.signer.key() == ctx.accounts.oft_store.admin, OFTError::Unauthorized);
+        target.add_to(&mut ctx.accounts.oft_store)?;
+
+        emit!(RateLimitOverrideApproved {
+            target,
+            notional_ld: ctx.accounts.proposal.notional_ld,
+            approver: ctx.accounts.signer.key(),
+        });
+        Ok(())
+    }
+}
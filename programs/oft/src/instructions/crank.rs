@@ -0,0 +1,233 @@
+use crate::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+/// Permissionless maintenance. The keeper runs one due task per call and is paid the store's
+/// crank_bounty_ld out of the fees accrued in the escrow, as far as they cover it. A StreamFees
+/// bounty comes out of the fees it vests, so it never reaches the unvested part of the stream.
+#[derive(Accounts)]
+pub struct Crank<'info> {
+    pub keeper: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        address = oft_store.token_mint,
+        mint::token_program = token_program
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        address = oft_store.token_escrow,
+        token::authority = oft_store,
+        token::mint = token_mint,
+        token::token_program = token_program
+    )]
+    pub token_escrow: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::token_program = token_program
+    )]
+    pub keeper_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
+    // Only provided for CrankTask::ExpireRateLimitOverrideProposal
+    #[account(
+        mut,
+        seeds = [
+            RATE_LIMIT_OVERRIDE_PROPOSAL_SEED,
+            oft_store.key().as_ref(),
+            &proposal.target.seed()
+        ],
+        bump
+    )]
+    pub proposal: Option<Account<'info, RateLimitOverrideProposal>>,
+    /// CHECK: refunded the proposal rent, checked against the proposal in apply
+    #[account(mut)]
+    pub proposer: Option<AccountInfo<'info>>,
+    // Only provided for CrankTask::StreamFees, checked against the fee stream in apply
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::token_program = token_program
+    )]
+    pub fee_stream_recipient: Option<InterfaceAccount<'info, TokenAccount>>,
+    // Only provided for CrankTask::ReportPeerSilence, checked against its remote_eid in apply
+    #[account(mut)]
+    pub peer: Option<Account<'info, PeerConfig>>,
+}
+
+impl Crank<'_> {
+    pub fn apply(ctx: &mut Context<Crank>, params: &CrankParams) -> Result<()> {
+        let max_bounty_ld = match params.task {
+            CrankTask::ExpireRateLimitOverrideProposal => {
+                Self::expire_proposal(ctx)?;
+                u64::MAX
+            },
+            CrankTask::StreamFees => Self::stream_fees(ctx)?,
+            CrankTask::ReportPeerSilence { remote_eid } => {
+                Self::report_peer_silence(ctx, remote_eid)?;
+                u64::MAX
+            },
+        };
+
+        let bounty_ld = Self::pay_bounty(ctx, max_bounty_ld)?;
+        emit!(CrankExecuted {
+            task: params.task.clone(),
+            keeper: ctx.accounts.keeper.key(),
+            bounty_ld,
+        });
+        Ok(())
+    }
+
+    fn expire_proposal(ctx: &mut Context<Crank>) -> Result<()> {
+        let (proposal, proposer) =
+            match (ctx.accounts.proposal.as_ref(), ctx.accounts.proposer.as_ref()) {
+                (Some(proposal), Some(proposer)) => (proposal, proposer),
+                _ => return err!(OFTError::InvalidCrankTask),
+            };
+        require!(proposer.key() == proposal.proposer, OFTError::InvalidCrankTask);
+        require!(
+            Clock::get()?.unix_timestamp > proposal.expires_at,
+            OFTError::CrankTaskNotDue
+        );
+
+        emit!(RateLimitOverrideExpired { target: proposal.target.clone() });
+        proposal.close(proposer.to_account_info())
+    }
+
+    /// Pays the fee stream's recipient the fees vested since the last payout, less the bounty,
+    /// and returns the bounty left to pay out of them. Due at most every min_interval_seconds.
+    fn stream_fees(ctx: &mut Context<Crank>) -> Result<u64> {
+        let (fee_stream, recipient) = match (
+            ctx.accounts.oft_store.fee_stream.as_ref(),
+            ctx.accounts.fee_stream_recipient.as_ref(),
+        ) {
+            (Some(fee_stream), Some(recipient)) => (fee_stream, recipient),
+            _ => return err!(OFTError::InvalidCrankTask),
+        };
+        require!(recipient.key() == fee_stream.recipient, OFTError::InvalidCrankTask);
+        require!(
+            ctx.accounts.oft_store.is_withdraw_destination(&recipient.owner),
+            OFTError::WithdrawDestinationNotAllowed
+        );
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            now >= fee_stream.last_paid_at.saturating_add(fee_stream.min_interval_seconds),
+            OFTError::CrankTaskNotDue
+        );
+        let vested_ld = fee_stream.vested_ld(Self::available_fee_ld(ctx)?, now);
+        require!(vested_ld > 0, OFTError::CrankTaskNotDue);
+
+        let bounty_ld = ctx.accounts.oft_store.crank_bounty_ld.unwrap_or(0).min(vested_ld);
+        let amount_ld = vested_ld - bounty_ld;
+        let recipient = recipient.to_account_info();
+        if amount_ld > 0 {
+            Self::transfer_fee(ctx, recipient.clone(), amount_ld)?;
+            ctx.accounts.token_escrow.reload()?;
+            ctx.accounts.oft_store.pay_out_fees(amount_ld);
+        }
+        if let Some(fee_stream) = ctx.accounts.oft_store.fee_stream.as_mut() {
+            fee_stream.last_paid_at = now;
+        }
+        emit!(FeesStreamed { recipient: recipient.key(), amount_ld });
+        Ok(bounty_ld)
+    }
+
+    fn report_peer_silence(ctx: &mut Context<Crank>, remote_eid: u32) -> Result<()> {
+        let oft_store = ctx.accounts.oft_store.key();
+        let peer = match ctx.accounts.peer.as_mut() {
+            Some(peer) => peer,
+            None => return err!(OFTError::InvalidCrankTask),
+        };
+        let expected = Pubkey::create_program_address(
+            &[PEER_SEED, oft_store.as_ref(), &remote_eid.to_be_bytes(), &[peer.bump]],
+            &ID,
+        )
+        .map_err(|_| error!(OFTError::InvalidCrankTask))?;
+        require!(peer.key() == expected, OFTError::InvalidCrankTask);
+        require!(peer.is_silent(Clock::get()?.unix_timestamp), OFTError::CrankTaskNotDue);
+        let last_inbound_at = peer.last_inbound_at;
+        let monitor = match peer.silence_monitor.as_mut() {
+            Some(monitor) if !monitor.reported => monitor,
+            _ => return err!(OFTError::CrankTaskNotDue),
+        };
+
+        monitor.reported = true;
+        emit!(PeerSilent {
+            remote_eid,
+            last_inbound_at,
+            max_silence_seconds: monitor.max_silence_seconds,
+        });
+        Ok(())
+    }
+
+    /// Pays the bounty, capped at `max_bounty_ld` and the fees available in the escrow, and
+    /// returns the amount paid.
+    fn pay_bounty(ctx: &mut Context<Crank>, max_bounty_ld: u64) -> Result<u64> {
+        let bounty_ld = match ctx.accounts.oft_store.crank_bounty_ld {
+            Some(bounty_ld) => bounty_ld,
+            None => return Ok(0),
+        };
+        let bounty_ld = bounty_ld.min(max_bounty_ld).min(Self::available_fee_ld(ctx)?);
+        if bounty_ld == 0 {
+            return Ok(0);
+        }
+        let keeper_token_account = ctx.accounts.keeper_token_account.to_account_info();
+        Self::transfer_fee(ctx, keeper_token_account, bounty_ld)?;
+        ctx.accounts.oft_store.pay_out_fees(bounty_ld);
+        Ok(bounty_ld)
+    }
+
+    fn available_fee_ld(ctx: &Context<Crank>) -> Result<u64> {
+        ctx.accounts
+            .token_escrow
+            .amount
+            .checked_sub(ctx.accounts.oft_store.tvl_ld)
+            .ok_or_else(|| error!(OFTError::TvlUnderflow))
+    }
+
+    fn transfer_fee<'info>(
+        ctx: &Context<Crank<'info>>,
+        to: AccountInfo<'info>,
+        amount_ld: u64,
+    ) -> Result<()> {
+        let seeds: &[&[u8]] = &[
+            OFT_SEED,
+            &ctx.accounts.token_escrow.key().to_bytes(),
+            &[ctx.accounts.oft_store.bump],
+        ];
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.token_escrow.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to,
+                    authority: ctx.accounts.oft_store.to_account_info(),
+                },
+            )
+            .with_signer(&[&seeds]),
+            amount_ld,
+            ctx.accounts.token_mint.decimals,
+        )
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct CrankParams {
+    pub task: CrankTask,
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize, PartialEq, Eq)]
+pub enum CrankTask {
+    // Closes a RateLimitOverrideProposal past its expiry and refunds the proposer.
+    ExpireRateLimitOverrideProposal,
+    // Pays the store's fee_stream recipient the fees vested since its last payout, at most every
+    // min_interval_seconds.
+    StreamFees,
+    // Emits PeerSilent for a peer silent for longer than its silence monitor allows.
+    ReportPeerSilence { remote_eid: u32 },
+}
@@ -0,0 +1,32 @@
+use crate::*;
+
+// Entrypoints kept for instructions that were renamed or superseded. Each forwards to its
+// replacement and emits DeprecatedInstructionUsed, so integrators can migrate on their own
+// schedule; a shim is removed once the event no longer shows up on-chain.
+
+#[derive(Clone, Copy, AnchorSerialize, AnchorDeserialize, PartialEq, Eq, Debug)]
+pub enum DeprecatedInstruction {
+    SetRateLimit, // superseded by set_outbound_rate_limit
+}
+
+/// Params of the former set_rate_limit, which only ever set the outbound limit. Laid out like
+/// SetOutboundRateLimitParams, which its accounts are validated against.
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SetRateLimitParams {
+    pub remote_eid: u32,
+    pub rate_limit: Option<RateLimitParams>, // None removes the limit
+}
+
+impl SetOutboundRateLimit<'_> {
+    pub fn apply_deprecated(
+        ctx: &mut Context<SetOutboundRateLimit>,
+        params: &SetRateLimitParams,
+    ) -> Result<()> {
+        emit!(DeprecatedInstructionUsed { instruction: DeprecatedInstruction::SetRateLimit });
+        let params = SetOutboundRateLimitParams {
+            remote_eid: params.remote_eid,
+            rate_limit: params.rate_limit.clone(),
+        };
+        Self::apply(ctx, &params)
+    }
+}
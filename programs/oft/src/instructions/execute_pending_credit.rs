@@ -0,0 +1,67 @@
+use crate::*;
+
+/// Charges a credit queued because the peer's inbound rate limit could not cover it to that rate
+/// limit, and releases it unless it is also held for verification or claim. Anyone can call it;
+/// it fails with RateLimitExceeded until the rate limit has refilled enough.
+#[derive(Accounts)]
+pub struct ExecutePendingCredit<'info> {
+    /// anyone, see ResolvePendingCredit for the accounts
+    pub release: ResolvePendingCredit<'info>,
+    #[account(
+        mut,
+        seeds = [
+            PEER_SEED,
+            release.oft_store.key().as_ref(),
+            &release.pending_credit.src_eid.to_be_bytes()
+        ],
+        bump = peer.bump
+    )]
+    pub peer: Account<'info, PeerConfig>,
+    /// CHECK: the peer's RateLimitState, updated only if it has been created
+    #[account(
+        mut,
+        seeds = [
+            RATE_LIMIT_STATE_SEED,
+            release.oft_store.key().as_ref(),
+            &release.pending_credit.src_eid.to_be_bytes()
+        ],
+        bump
+    )]
+    pub rate_limit_state: UncheckedAccount<'info>,
+}
+
+impl ExecutePendingCredit<'_> {
+    pub fn apply(ctx: &mut Context<ExecutePendingCredit>) -> Result<()> {
+        let accounts = &mut ctx.accounts;
+        require!(accounts.release.pending_credit.awaiting_capacity, OFTError::InvalidPendingCredit);
+        let pending_credit = &accounts.release.pending_credit;
+        let (guid, src_eid, to, amount_ld) = (
+            pending_credit.guid,
+            pending_credit.src_eid,
+            pending_credit.to_address,
+            pending_credit.amount_ld,
+        );
+        let msg_type = msg_codec::msg_type(pending_credit.compose_msg.is_some());
+        policy::consume_rate_limit(
+            &mut accounts.release.oft_store,
+            &mut accounts.peer,
+            &accounts.release.token_dest.key(),
+            &to,
+            &guid,
+            src_eid,
+            msg_type,
+            amount_ld,
+            false,
+        )?;
+        RateLimitState::sync(&accounts.rate_limit_state, &accounts.peer)?;
+        emit!(CreditReleased { guid, src_eid, to, amount_ld });
+
+        accounts.release.pending_credit.awaiting_capacity = false;
+        if accounts.release.pending_credit.awaiting_verification
+            || accounts.release.pending_credit.awaiting_claim
+        {
+            return Ok(());
+        }
+        accounts.release.release(ctx.remaining_accounts)
+    }
+}
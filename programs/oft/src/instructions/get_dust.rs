@@ -0,0 +1,29 @@
+use crate::*;
+use anchor_spl::token_interface::TokenAccount;
+
+/// Reports the part of a token account balance below the shared decimals, which sends leave
+/// behind since it cannot be bridged.
+#[derive(Accounts)]
+pub struct GetDust<'info> {
+    #[account(
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(token::mint = oft_store.token_mint)]
+    pub token_source: InterfaceAccount<'info, TokenAccount>,
+}
+
+impl GetDust<'_> {
+    pub fn apply(ctx: &Context<GetDust>) -> Result<Dust> {
+        let balance_ld = ctx.accounts.token_source.amount;
+        let dust_ld = balance_ld - ctx.accounts.oft_store.remove_dust(balance_ld)?;
+        Ok(Dust { balance_ld, dust_ld })
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct Dust {
+    pub balance_ld: u64,
+    pub dust_ld: u64,
+}
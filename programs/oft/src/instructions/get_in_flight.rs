@@ -0,0 +1,45 @@
+use crate::*;
+
+/// Reports the value sent to a peer that it has not acknowledged yet, for risk dashboards to read
+/// the unsettled exposure per chain. Fails with InFlightNotTracked unless set_peer_config
+/// InFlightTracking is enabled for the peer.
+#[derive(Accounts)]
+#[instruction(params: GetInFlightParams)]
+pub struct GetInFlight<'info> {
+    #[account(
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        seeds = [PEER_SEED, oft_store.key().as_ref(), &params.remote_eid.to_be_bytes()],
+        bump = peer.bump
+    )]
+    pub peer: Account<'info, PeerConfig>,
+}
+
+impl GetInFlight<'_> {
+    pub fn apply(
+        ctx: &Context<GetInFlight>,
+        _params: &GetInFlightParams,
+    ) -> Result<InFlightReport> {
+        let in_flight = ctx.accounts.peer.in_flight.clone().ok_or(OFTError::InFlightNotTracked)?;
+        Ok(InFlightReport {
+            in_flight_count: in_flight.sent_count.saturating_sub(in_flight.acknowledged_count),
+            in_flight_ld: in_flight.sent_ld.saturating_sub(in_flight.acknowledged_ld),
+            counters: in_flight,
+        })
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct GetInFlightParams {
+    pub remote_eid: u32,
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct InFlightReport {
+    pub counters: InFlight,
+    pub in_flight_count: u64, // sent and not acknowledged, at least 0
+    pub in_flight_ld: u64,
+}
@@ -0,0 +1,44 @@
+use crate::*;
+
+/// Bumped whenever the layout of `OFTMetadataView` changes.
+pub const OFT_METADATA_VIEW_VERSION: u8 = 1;
+
+/// Lets wallets and explorers identify the canonical GMX deployment and its parameters.
+#[derive(Accounts)]
+pub struct GetOFTMetadata<'info> {
+    #[account(
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+}
+
+impl GetOFTMetadata<'_> {
+    pub fn apply(ctx: &Context<GetOFTMetadata>) -> Result<OFTMetadataView> {
+        let oft_store = &ctx.accounts.oft_store;
+        Ok(OFTMetadataView {
+            version: OFT_METADATA_VIEW_VERSION,
+            metadata: oft_store.metadata.clone(),
+            oft_type: oft_store.oft_type.clone(),
+            token_mint: oft_store.token_mint,
+            ld2sd_rate: oft_store.ld2sd_rate,
+            endpoint_program: oft_store.endpoint_program,
+            default_fee_bps: oft_store.default_fee_bps,
+            paused: oft_store.paused,
+        })
+    }
+}
+
+/// Returned by `get_oft_metadata`. New fields are only ever appended, together with a version
+/// bump, so existing decoders keep working.
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct OFTMetadataView {
+    pub version: u8,
+    pub metadata: Option<OFTMetadata>,
+    pub oft_type: OFTType,
+    pub token_mint: Pubkey,
+    pub ld2sd_rate: u64,
+    pub endpoint_program: Pubkey,
+    pub default_fee_bps: u16,
+    pub paused: bool,
+}
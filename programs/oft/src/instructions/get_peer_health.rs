@@ -0,0 +1,49 @@
+use crate::*;
+
+/// Reports whether the pathway from a peer looks healthy, for UIs to read straight from chain:
+/// when a message from it was last executed and whether it has been silent for longer than its
+/// silence monitor allows, and whether transfers with it are paused.
+#[derive(Accounts)]
+#[instruction(params: GetPeerHealthParams)]
+pub struct GetPeerHealth<'info> {
+    #[account(
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        seeds = [PEER_SEED, oft_store.key().as_ref(), &params.remote_eid.to_be_bytes()],
+        bump = peer.bump
+    )]
+    pub peer: Account<'info, PeerConfig>,
+}
+
+impl GetPeerHealth<'_> {
+    pub fn apply(
+        ctx: &Context<GetPeerHealth>,
+        _params: &GetPeerHealthParams,
+    ) -> Result<PeerHealth> {
+        let peer = &ctx.accounts.peer;
+        Ok(PeerHealth {
+            last_inbound_at: peer.last_inbound_at,
+            highest_inbound_nonce: peer.highest_inbound_nonce,
+            max_silence_seconds: peer.silence_monitor.as_ref().map(|m| m.max_silence_seconds),
+            silent: peer.is_silent(Clock::get()?.unix_timestamp),
+            paused: peer.paused,
+        })
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct GetPeerHealthParams {
+    pub remote_eid: u32,
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct PeerHealth {
+    pub last_inbound_at: i64, // 0 if nothing was ever received from the peer
+    pub highest_inbound_nonce: u64,
+    pub max_silence_seconds: Option<i64>, // None without a silence monitor
+    pub silent: bool,
+    pub paused: bool, // by set_peer_config or the peer's breach monitor
+}
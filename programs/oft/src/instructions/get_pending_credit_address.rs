@@ -0,0 +1,27 @@
+use crate::*;
+
+/// Computes where a credit to `to_address` for `guid` is held, so exchanges can watch the
+/// address for expected deposits. The account only exists while the credit is held.
+#[derive(Accounts)]
+pub struct GetPendingCreditAddress<'info> {
+    #[account(
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+}
+
+impl GetPendingCreditAddress<'_> {
+    pub fn apply(
+        ctx: &Context<GetPendingCreditAddress>,
+        params: &GetPendingCreditAddressParams,
+    ) -> Result<Pubkey> {
+        Ok(PendingCredit::address(&ctx.accounts.oft_store.key(), &params.to_address, &params.guid))
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct GetPendingCreditAddressParams {
+    pub to_address: Pubkey,
+    pub guid: [u8; 32],
+}
@@ -0,0 +1,78 @@
+use crate::*;
+
+/// Reports the rate limiters of a peer as they stand now, so front-ends can warn before a send or
+/// an inbound transfer would fail with RateLimitExceeded. Address and GUID overrides are not
+/// taken into account, see simulate_send and simulate_receive for a specific transfer.
+#[derive(Accounts)]
+#[instruction(params: GetRateLimitCapacityParams)]
+pub struct GetRateLimitCapacity<'info> {
+    #[account(
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        seeds = [PEER_SEED, oft_store.key().as_ref(), &params.remote_eid.to_be_bytes()],
+        bump = peer.bump
+    )]
+    pub peer: Account<'info, PeerConfig>,
+}
+
+impl GetRateLimitCapacity<'_> {
+    pub fn apply(
+        ctx: &Context<GetRateLimitCapacity>,
+        _params: &GetRateLimitCapacityParams,
+    ) -> Result<RateLimitCapacities> {
+        // as set by the launch stage in effect, if any
+        let mut peer = PeerConfig::clone(&ctx.accounts.peer);
+        if !peer.rate_limit_enabled {
+            return Ok(RateLimitCapacities { outbound: None, inbound: None });
+        }
+        policy::refresh_rate_limits(&ctx.accounts.oft_store, &mut peer)?;
+        let clock = Clock::get()?;
+        let (outbound_mode, inbound_mode) =
+            (peer.outbound_rate_limit_mode, peer.inbound_rate_limit_mode);
+        Ok(RateLimitCapacities {
+            outbound: peer
+                .outbound_rate_limiter
+                .as_ref()
+                .map(|r| RateLimitCapacity::at(r, outbound_mode, outbound_mode.now(&clock))),
+            inbound: peer
+                .inbound_rate_limiter
+                .as_ref()
+                .map(|r| RateLimitCapacity::at(r, inbound_mode, inbound_mode.now(&clock))),
+        })
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct GetRateLimitCapacityParams {
+    pub remote_eid: u32,
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct RateLimitCapacities {
+    pub outbound: Option<RateLimitCapacity>, // None without a rate limit in that direction
+    pub inbound: Option<RateLimitCapacity>,
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize, PartialEq, Eq, Debug)]
+pub struct RateLimitCapacity {
+    pub tokens: u64,
+    pub capacity: u64,
+    pub refill_per_second: u64, // tokens per slot under RateLimitMode::SlotBucket
+    pub seconds_until_full: Option<u64>, // None if it never refills
+    pub mode: RateLimitMode,
+}
+
+impl RateLimitCapacity {
+    pub fn at(rate_limiter: &RateLimiter, mode: RateLimitMode, now: u64) -> Self {
+        Self {
+            tokens: simulation::available_ld(rate_limiter, mode, now),
+            capacity: rate_limiter.capacity,
+            refill_per_second: rate_limiter.refill_per_second,
+            seconds_until_full: simulation::seconds_until_full(rate_limiter, mode, now),
+            mode,
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use crate::*;
+
+/// Bumped whenever the layout of `RateLimitOverrides` changes.
+pub const RATE_LIMIT_OVERRIDES_VIEW_VERSION: u8 = 8;
+
+#[derive(Accounts)]
+pub struct GetRateLimitOverrides<'info> {
+    #[account(
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+}
+
+impl GetRateLimitOverrides<'_> {
+    pub fn apply(ctx: &Context<GetRateLimitOverrides>) -> Result<RateLimitOverrides> {
+        let oft_store = &ctx.accounts.oft_store;
+        let overrides = &oft_store.rate_limit_override;
+        Ok(RateLimitOverrides {
+            version: RATE_LIMIT_OVERRIDES_VIEW_VERSION,
+            addresses: overrides.iter().map(|x| x.address).collect(),
+            max_addresses: oft_store.max_rate_limit_overrides,
+            guids: oft_store.rate_limit_override_guids.clone(),
+            max_guids: oft_store.max_rate_limit_override_guid_count,
+            override_manager: oft_store.override_manager,
+            override_approval_threshold_ld: oft_store.override_approval_threshold_ld,
+            address_expires_at: overrides.iter().map(|x| x.expires_at).collect(),
+            address_directions: overrides.iter().map(|x| x.direction).collect(),
+            address_multipliers: overrides.iter().map(|x| x.multiplier).collect(),
+            total_overridden_volume_ld: oft_store.total_overridden_volume_ld,
+            override_trigger_count: oft_store.override_trigger_count,
+            address_scopes: overrides.iter().map(|x| x.scope).collect(),
+            override_approver: oft_store.override_approver,
+        })
+    }
+}
+
+/// Returned by `get_rate_limit_overrides`. New fields are only ever appended, together with a
+/// version bump, so existing decoders keep working.
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct RateLimitOverrides {
+    pub version: u8,
+    pub addresses: Vec<Pubkey>,
+    pub max_addresses: u8,
+    pub guids: Vec<RateLimitOverrideGuid>,
+    pub max_guids: u8,
+    pub override_manager: Option<Pubkey>,
+    pub override_approval_threshold_ld: Option<u64>,
+    // One entry per address; expired overrides are listed until they are pruned.
+    pub address_expires_at: Vec<Option<i64>>,
+    // One entry per address; None for overrides of both directions.
+    pub address_directions: Vec<Option<RateLimitDirection>>,
+    // One entry per address; None for overrides that skip the rate limiters.
+    pub address_multipliers: Vec<Option<u16>>,
+    // Since the store was created, see OFTStore::record_override_trigger.
+    pub total_overridden_volume_ld: u64,
+    pub override_trigger_count: u64,
+    pub address_scopes: Vec<RateLimitOverrideScope>, // one entry per address
+    pub override_approver: Option<Pubkey>,
+}
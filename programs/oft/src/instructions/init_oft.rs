@@ -3,13 +3,17 @@ use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use oapp::endpoint::{instructions::RegisterOAppParams, ID as ENDPOINT_ID};
 
 #[derive(Accounts)]
+#[instruction(params: InitOFTParams)]
 pub struct InitOFT<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
     #[account(
         init,
         payer = payer,
-        space = 8 + OFTStore::INIT_SPACE,
+        space = OFTStore::space(
+            params.max_rate_limit_overrides,
+            params.max_rate_limit_override_guid_count
+        ),
         seeds = [OFT_SEED, token_escrow.key().as_ref()],
         bump
     )]
@@ -44,8 +48,9 @@ impl InitOFT<'_> {
             ctx.accounts.token_mint.decimals >= params.shared_decimals,
             OFTError::InvalidDecimals
         );
-        ctx.accounts.oft_store.ld2sd_rate =
-            10u64.pow((ctx.accounts.token_mint.decimals - params.shared_decimals) as u32);
+        ctx.accounts.oft_store.ld2sd_rate = 10u64
+            .checked_pow((ctx.accounts.token_mint.decimals - params.shared_decimals) as u32)
+            .ok_or_else(|| error!(OFTError::InvalidDecimals))?;
         ctx.accounts.oft_store.token_mint = ctx.accounts.token_mint.key();
         ctx.accounts.oft_store.token_escrow = ctx.accounts.token_escrow.key();
         ctx.accounts.oft_store.endpoint_program =
@@ -61,10 +66,60 @@ impl InitOFT<'_> {
         ctx.accounts.oft_store.paused = false;
         ctx.accounts.oft_store.pauser = None;
         ctx.accounts.oft_store.unpauser = None;
+        ctx.accounts.oft_store.version = OFT_STORE_VERSION;
 
         ctx.accounts.oft_store.rate_limit_override = Vec::new();
-        ctx.accounts.oft_store.max_rate_limit_overrides = MAX_RATE_LIMIT_OVERRIDE_COUNT;
-        ctx.accounts.oft_store.max_rate_limit_override_guid_count = MAX_RATE_LIMIT_OVERRIDE_GUID_COUNT;
+        require!(
+            params.max_rate_limit_overrides <= MAX_RATE_LIMIT_OVERRIDE_COUNT
+                && params.max_rate_limit_override_guid_count <= MAX_RATE_LIMIT_OVERRIDE_GUID_COUNT,
+            OFTError::InvalidOverrideListSize
+        );
+        ctx.accounts.oft_store.max_rate_limit_overrides = params.max_rate_limit_overrides;
+        ctx.accounts.oft_store.max_rate_limit_override_guid_count =
+            params.max_rate_limit_override_guid_count;
+        ctx.accounts.oft_store.endpoint_version = 0;
+        ctx.accounts.oft_store.pending_endpoint_migration = None;
+        ctx.accounts.oft_store.large_credit_threshold_ld = None;
+        ctx.accounts.oft_store.credit_verifier = None;
+        ctx.accounts.oft_store.override_manager = None;
+        ctx.accounts.oft_store.override_approval_threshold_ld = None;
+        ctx.accounts.oft_store.fee_vault_senders = Vec::new();
+        ctx.accounts.oft_store.crank_bounty_ld = None;
+        ctx.accounts.oft_store.dust_to_fees = false;
+        ctx.accounts.oft_store.fee_rounding_mode = RoundingMode::Floor;
+        ctx.accounts.oft_store.probation_policy = None;
+        ctx.accounts.oft_store.held_credit_refund = HeldCreditRefund::Payer;
+        ctx.accounts.oft_store.metadata = None;
+        ctx.accounts.oft_store.withdraw_destinations = Vec::new();
+        ctx.accounts.oft_store.pending_withdraw_destinations = None;
+        ctx.accounts.oft_store.pending_supply_adjustment = None;
+        ctx.accounts.oft_store.override_cosigners = Vec::new();
+        ctx.accounts.oft_store.override_cosign_threshold_ld = None;
+        ctx.accounts.oft_store.test_mode = false;
+        ctx.accounts.oft_store.tvl_alert = None;
+        ctx.accounts.oft_store.tvl_watermarks = TvlWatermarks::default();
+        ctx.accounts.oft_store.contributed_lamports = 0;
+        ctx.accounts.oft_store.allowed_composers = Vec::new();
+        ctx.accounts.oft_store.transfer_receipts = None;
+        ctx.accounts.oft_store.allowed_dst_eids = Vec::new();
+        ctx.accounts.oft_store.zero_amount_policy = ZeroAmountPolicy::MessageOnly;
+        ctx.accounts.oft_store.fee_stream = None;
+        ctx.accounts.oft_store.rate_limiter = None;
+        ctx.accounts.oft_store.sender_rate_limit = None;
+        LaunchStage::validate_schedule(&params.launch_schedule)?;
+        ctx.accounts.oft_store.launch_schedule = params.launch_schedule.clone();
+        ctx.accounts.oft_store.overrides_consume_capacity = false;
+        ctx.accounts.oft_store.queue_over_limit_credits = false;
+        ctx.accounts.oft_store.total_overridden_volume_ld = 0;
+        ctx.accounts.oft_store.override_trigger_count = 0;
+        ctx.accounts.oft_store.override_approver = None;
+        ctx.accounts.oft_store.governance_eid = None;
+        ctx.accounts.oft_store.fee_exempt = Vec::new();
+        ctx.accounts.oft_store.accrued_fees_ld = 0;
+        ctx.accounts.oft_store.fee_collector = None;
+        ctx.accounts.oft_store.treasury = None;
+        ctx.accounts.oft_store.paused_control_messages = Vec::new();
+        ctx.accounts.oft_store.honor_pause_notices = false;
 
         // Initialize the lz_receive_types_accounts
         ctx.accounts.lz_receive_types_accounts.oft_store = ctx.accounts.oft_store.key();
@@ -87,4 +142,7 @@ pub struct InitOFTParams {
     pub admin: Pubkey,
     pub shared_decimals: u8,
     pub endpoint_program: Option<Pubkey>,
+    pub max_rate_limit_overrides: u8,
+    pub max_rate_limit_override_guid_count: u8,
+    pub launch_schedule: Vec<LaunchStage>, // empty for no launch limits
 }
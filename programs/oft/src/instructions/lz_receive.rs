@@ -1,4 +1,4 @@
-use crate::*;
+use crate::{simulation::CreditOutcome, *};
 use anchor_lang::solana_program;
 use anchor_spl::{
     associated_token::AssociatedToken,
@@ -25,7 +25,9 @@ pub struct LzReceive<'info> {
             &params.src_eid.to_be_bytes()
         ],
         bump = peer.bump,
-        constraint = peer.peer_address == params.sender @OFTError::InvalidSender
+        constraint = peer.peer_address == params.sender @OFTError::InvalidSender,
+        // checked before to_address decodes the message
+        constraint = msg_codec::validate(&params.message).is_ok() @OFTError::InvalidMessage
     )]
     pub peer: Account<'info, PeerConfig>,
     #[account(
@@ -42,9 +44,10 @@ pub struct LzReceive<'info> {
         token::token_program = token_program
     )]
     pub token_escrow: InterfaceAccount<'info, TokenAccount>,
-    /// CHECK: the wallet address to receive the token
+    /// CHECK: the wallet address to receive the token. This and the other accounts of the
+    /// recipient are omitted for control messages, which credit no one.
     #[account(address = Pubkey::from(msg_codec::send_to(&params.message)) @OFTError::InvalidTokenDest)]
-    pub to_address: AccountInfo<'info>,
+    pub to_address: Option<AccountInfo<'info>>,
     #[account(
         init_if_needed,
         payer = payer,
@@ -52,7 +55,7 @@ pub struct LzReceive<'info> {
         associated_token::authority = to_address,
         associated_token::token_program = token_program
     )]
-    pub token_dest: InterfaceAccount<'info, TokenAccount>,
+    pub token_dest: Option<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         mut,
         address = oft_store.token_mint,
@@ -67,11 +70,78 @@ pub struct LzReceive<'info> {
     pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
+    /// CHECK: created by PendingCredit::init only when the credit is held
+    #[account(
+        mut,
+        seeds = [
+            PENDING_CREDIT_SEED,
+            oft_store.key().as_ref(),
+            &msg_codec::send_to(&params.message),
+            &params.guid
+        ],
+        bump
+    )]
+    pub pending_credit: Option<UncheckedAccount<'info>>,
+    /// CHECK: the recipient's InboundOptOut, which only has to exist
+    #[account(
+        seeds = [
+            INBOUND_OPT_OUT_SEED,
+            oft_store.key().as_ref(),
+            &msg_codec::send_to(&params.message)
+        ],
+        bump
+    )]
+    pub inbound_opt_out: Option<UncheckedAccount<'info>>,
+    /// CHECK: the peer's RateLimitState, updated only if it has been created
+    #[account(
+        mut,
+        seeds = [
+            RATE_LIMIT_STATE_SEED,
+            oft_store.key().as_ref(),
+            &params.src_eid.to_be_bytes()
+        ],
+        bump
+    )]
+    pub rate_limit_state: UncheckedAccount<'info>,
+    /// CHECK: created by TransferReceipt::init only for transfers of at least the store's
+    /// transfer_receipts.min_amount_ld
+    #[account(
+        mut,
+        seeds = [TRANSFER_RECEIPT_SEED, oft_store.key().as_ref(), &params.guid],
+        bump
+    )]
+    pub transfer_receipt: Option<UncheckedAccount<'info>>,
+    /// CHECK: the recipient's NotificationHook, written only if the recipient has registered it
+    #[account(
+        mut,
+        seeds = [
+            NOTIFICATION_HOOK_SEED,
+            oft_store.key().as_ref(),
+            &msg_codec::send_to(&params.message)
+        ],
+        bump
+    )]
+    pub notification_hook: Option<UncheckedAccount<'info>>,
 }
 
 impl LzReceive<'_> {
     pub fn apply(ctx: &mut Context<LzReceive>, params: &LzReceiveParams) -> Result<()> {
-        require!(!ctx.accounts.oft_store.paused, OFTError::Paused);
+        compute_profile::checkpoint("lz_receive:start");
+        // the message is left uncleared for an allowed executor
+        if !policy::check_executor(&ctx.accounts.peer, &ctx.accounts.payer.key()) {
+            emit!(ExecutorRejected {
+                src_eid: params.src_eid,
+                guid: params.guid,
+                executor: ctx.accounts.payer.key(),
+            });
+            return Ok(());
+        }
+        policy::check_receive(
+            &ctx.accounts.oft_store,
+            &mut ctx.accounts.peer,
+            params.src_eid,
+            params.nonce,
+        )?;
 
         let oft_store_seed = ctx.accounts.token_escrow.key();
         let seeds: &[&[u8]] = &[OFT_SEED, oft_store_seed.as_ref(), &[ctx.accounts.oft_store.bump]];
@@ -92,79 +162,237 @@ impl LzReceive<'_> {
                 message: params.message.clone(),
             },
         )?;
+        compute_profile::checkpoint("lz_receive:endpoint");
+
+        // control messages carry no tokens
+        if let Some(control) = msg_codec::decode_control(&params.message, &params.sender)? {
+            return Self::apply_control(ctx, params, control);
+        }
+
+        let to_address = transfer_account(&ctx.accounts.to_address)?.key();
+        let token_dest = transfer_account(&ctx.accounts.token_dest)?.key();
+        // the executor may not leave out the accounts that hold or notify the credit
+        let awaiting_claim =
+            InboundOptOut::exists(transfer_account(&ctx.accounts.inbound_opt_out)?);
+        transfer_account(&ctx.accounts.notification_hook)?;
 
         // Convert the amount from sd to ld
         let amount_sd = msg_codec::amount_sd(&params.message);
-        let mut amount_received_ld = ctx.accounts.oft_store.sd2ld(amount_sd);
-
-        let receiver_address = ctx.accounts.token_dest.key();
+        let mut amount_received_ld = ctx.accounts.oft_store.sd2ld(amount_sd)?;
 
-        // If token receiver address is in the rate limit override list, emit event and skip rate limiting
-        if ctx.accounts.oft_store.is_rate_limit_override(&receiver_address) {
-            emit!(RateLimitOverrideTriggered {
-                address: receiver_address,
-                amount_ld: amount_received_ld,
-            });
-        } else if let Some(index) = ctx.accounts.oft_store.rate_limit_override_guids.iter().position(|x| x == &params.guid) {
-            emit!(RateLimitOverrideGuidTriggered {
+        let source_sender_hold = policy::check_source_sender(&ctx.accounts.peer, &params.message)?;
+        if !policy::check_zero_amount(&ctx.accounts.oft_store, amount_received_ld)? {
+            emit!(ZeroAmountIgnored {
+                src_eid: params.src_eid,
                 guid: params.guid,
-                amount_ld: amount_received_ld,
+                to: to_address,
+                composed: msg_codec::compose_msg(&params.message).is_some(),
             });
-
-            ctx.accounts.oft_store.rate_limit_override_guids.swap_remove(index);
-        } else {
-            // Consume the inbound rate limiter
-            if let Some(rate_limiter) = ctx.accounts.peer.inbound_rate_limiter.as_mut() {
-                rate_limiter.try_consume(amount_received_ld)?;
-            }
+            return Ok(());
+        }
+        if amount_received_ld == 0 {
+            // message-only: nothing to limit, hold or credit
+            return Self::deliver(ctx, params, amount_sd, 0, 0);
+        }
+        let probation_hold = policy::check_probation(
+            &ctx.accounts.oft_store,
+            &ctx.accounts.peer,
+            amount_received_ld,
+        )?;
+        let queue_over_limit =
+            policy::queues_over_limit(&ctx.accounts.oft_store, &ctx.accounts.peer)
+                && ctx.accounts.pending_credit.is_some();
+        let awaiting_capacity = !policy::consume_rate_limit(
+            &mut ctx.accounts.oft_store,
+            &mut ctx.accounts.peer,
+            &token_dest,
+            &to_address,
+            &params.guid,
+            params.src_eid,
+            msg_codec::msg_type(msg_codec::compose_msg(&params.message).is_some()),
+            amount_received_ld,
+            queue_over_limit,
+        )?;
+        RateLimitState::sync(&ctx.accounts.rate_limit_state, &ctx.accounts.peer)?;
+        if awaiting_capacity {
+            policy::record_rate_limit_breach(&mut ctx.accounts.peer, params.src_eid)?;
         }
 
-        if ctx.accounts.oft_store.oft_type == OFTType::Adapter {
-            // unlock from escrow
-            ctx.accounts.oft_store.tvl_ld -= amount_received_ld;
-            token_interface::transfer_checked(
-                CpiContext::new(
-                    ctx.accounts.token_program.to_account_info(),
-                    TransferChecked {
-                        from: ctx.accounts.token_escrow.to_account_info(),
-                        mint: ctx.accounts.token_mint.to_account_info(),
-                        to: ctx.accounts.token_dest.to_account_info(),
-                        authority: ctx.accounts.oft_store.to_account_info(),
-                    },
-                )
-                .with_signer(&[&seeds]),
+        // Held credits are released later by resolve_pending_credit, claim_pending_credit or
+        // execute_pending_credit
+        let composer_hold =
+            policy::check_composer(&ctx.accounts.oft_store, &to_address, &params.message);
+        let awaiting_verification = source_sender_hold
+            || probation_hold
+            || composer_hold
+            || ctx.accounts.oft_store.requires_credit_verification(amount_received_ld);
+        // Otherwise large credits from a peer with an inbound split vest
+        let outcome = simulation::credit_outcome(
+            amount_received_ld,
+            awaiting_verification || awaiting_claim || awaiting_capacity,
+            msg_codec::compose_msg(&params.message).is_some(),
+            ctx.accounts.peer.inbound_split.as_ref(),
+        );
+        policy::check_credit_hold(
+            outcome != CreditOutcome::Immediate,
+            ctx.accounts.pending_credit.is_some(),
+        )?;
+        // recorded for held credits too, with the amount received before any split
+        compute_profile::checkpoint("lz_receive:policy");
+        if ctx.accounts.oft_store.requires_transfer_receipt(amount_received_ld) {
+            Self::init_transfer_receipt(ctx, params, amount_received_ld)?;
+        }
+        if outcome == CreditOutcome::Held {
+            Self::init_pending_credit(
+                ctx,
+                params,
                 amount_received_ld,
-                ctx.accounts.token_mint.decimals,
+                awaiting_verification,
+                awaiting_claim,
+                awaiting_capacity,
+                None,
             )?;
+            if awaiting_capacity {
+                emit!(CreditQueued {
+                    guid: params.guid,
+                    src_eid: params.src_eid,
+                    to: to_address,
+                    amount_ld: amount_received_ld,
+                });
+            }
+            emit!(CreditHeld {
+                guid: params.guid,
+                src_eid: params.src_eid,
+                to: to_address,
+                amount_ld: amount_received_ld,
+                awaiting_verification,
+                awaiting_claim,
+            });
+            Self::notify(ctx, params, amount_received_ld, 0)?;
+            compute_profile::checkpoint("lz_receive:hold");
+            return Ok(());
+        }
 
-            // update the amount_received_ld with the post transfer fee amount
-            amount_received_ld =
-                get_post_fee_amount_ld(&ctx.accounts.token_mint, amount_received_ld)?
-        } else if let Some(mint_authority) = &ctx.accounts.mint_authority {
-            // Native type
-            // mint
-            let ix = spl_token_2022::instruction::mint_to(
-                ctx.accounts.token_program.key,
-                &ctx.accounts.token_mint.key(),
-                &ctx.accounts.token_dest.key(),
-                mint_authority.key,
-                &[&ctx.accounts.oft_store.key()],
-                amount_received_ld,
+        let amount_ld = amount_received_ld;
+        let mut credit_ld = amount_received_ld;
+        if let (CreditOutcome::Split { immediate_ld, vesting_ld }, Some(split)) =
+            (outcome, ctx.accounts.peer.inbound_split.clone())
+        {
+            credit_ld = immediate_ld;
+            let vesting = Vesting {
+                start: Clock::get()?.unix_timestamp,
+                epoch_seconds: split.epoch_seconds,
+                epochs: split.epochs,
+                claimed_ld: 0,
+            };
+            let vesting_credit = Some(vesting.clone());
+            Self::init_pending_credit(
+                ctx,
+                params,
+                vesting_ld,
+                false,
+                false,
+                false,
+                vesting_credit,
             )?;
-            solana_program::program::invoke_signed(
-                &ix,
-                &[
-                    ctx.accounts.token_dest.to_account_info(),
-                    ctx.accounts.token_mint.to_account_info(),
-                    mint_authority.to_account_info(),
-                    ctx.accounts.oft_store.to_account_info(),
-                ],
-                &[&seeds],
-            )?;
-        } else {
-            return Err(OFTError::InvalidMintAuthority.into());
+            emit!(CreditVesting {
+                guid: params.guid,
+                src_eid: params.src_eid,
+                to: to_address,
+                immediate_ld: credit_ld,
+                vesting_ld,
+                vesting,
+            });
+        }
+
+        amount_received_ld = credit(
+            &mut ctx.accounts.oft_store,
+            &ctx.accounts.token_escrow,
+            transfer_account(&ctx.accounts.token_dest)?,
+            &ctx.accounts.token_mint,
+            &ctx.accounts.mint_authority,
+            &ctx.accounts.token_program,
+            credit_ld,
+        )?;
+        compute_profile::checkpoint("lz_receive:token");
+        Self::deliver(ctx, params, amount_sd, amount_ld, amount_received_ld)
+    }
+
+    /// Applies a control message from the peer. Stale challenge echoes are just cleared, and pause
+    /// notices only pause this store if it honors them.
+    fn apply_control(
+        ctx: &mut Context<LzReceive>,
+        params: &LzReceiveParams,
+        control: msg_codec::Control,
+    ) -> Result<()> {
+        policy::check_control_message_paused(&ctx.accounts.oft_store, control.message())?;
+        match control {
+            msg_codec::Control::PeerChallenge(nonce) => {
+                if ctx.accounts.peer.prove_control(&nonce) {
+                    emit!(PeerControlProven {
+                        remote_eid: params.src_eid,
+                        peer_address: params.sender
+                    });
+                }
+                Ok(())
+            },
+            msg_codec::Control::PauseNotice(sent_at) => {
+                let honored = ctx.accounts.oft_store.honor_pause_notices;
+                if honored {
+                    ctx.accounts.oft_store.paused = true;
+                }
+                emit!(PauseNoticeReceived { src_eid: params.src_eid, sent_at, honored });
+                Ok(())
+            },
+            msg_codec::Control::OverrideGuids(entries) => {
+                require!(
+                    ctx.accounts.oft_store.governance_eid == Some(params.src_eid),
+                    OFTError::InvalidSender
+                );
+                Self::add_override_guids(ctx, entries)
+            },
+            msg_codec::Control::DeliveryAck { guid, amount_sd } => {
+                let amount_ld = ctx.accounts.oft_store.sd2ld(amount_sd)?;
+                if let Some(in_flight) = ctx.accounts.peer.in_flight.as_mut() {
+                    in_flight.record_acknowledged(amount_ld);
+                }
+                emit!(DeliveryAcknowledged { remote_eid: params.src_eid, guid, amount_ld });
+                Ok(())
+            },
         }
+    }
+
+    /// Adds the guid overrides sent by the governance peer, skipping guids already overridden.
+    /// Fails with RateLimitOverrideListFull, leaving the message to be retried, if they do not all
+    /// fit in the list.
+    fn add_override_guids(
+        ctx: &mut Context<LzReceive>,
+        entries: Vec<([u8; 32], Option<u32>)>,
+    ) -> Result<()> {
+        for (guid, src_eid) in entries {
+            if ctx.accounts.oft_store.is_rate_limit_override_guid(&guid) {
+                continue;
+            }
+            ctx.accounts.oft_store.add_rate_limit_override_guid(RateLimitOverrideGuid {
+                guid,
+                expected_amount_ld: None,
+                src_eid,
+            })?;
+            emit!(RateLimitOverrideGuidUpdated { guid, action: RateLimitOverrideAction::Add });
+        }
+        Ok(())
+    }
 
+    /// Sends the compose message, if any, and records the credit of `amount_received_ld`.
+    fn deliver(
+        ctx: &Context<LzReceive>,
+        params: &LzReceiveParams,
+        amount_sd: u64,
+        amount_ld: u64,
+        amount_received_ld: u64,
+    ) -> Result<()> {
+        let oft_store_seed = ctx.accounts.token_escrow.key();
+        let seeds: &[&[u8]] = &[OFT_SEED, oft_store_seed.as_ref(), &[ctx.accounts.oft_store.bump]];
         if let Some(message) = msg_codec::compose_msg(&params.message) {
             oapp::endpoint_cpi::send_compose(
                 ctx.accounts.oft_store.endpoint_program,
@@ -172,7 +400,7 @@ impl LzReceive<'_> {
                 &ctx.remaining_accounts[Clear::MIN_ACCOUNTS_LEN..],
                 seeds,
                 SendComposeParams {
-                    to: ctx.accounts.to_address.key(),
+                    to: transfer_account(&ctx.accounts.to_address)?.key(),
                     guid: params.guid,
                     index: 0, // only 1 compose msg per lzReceive
                     message: compose_msg_codec::encode(
@@ -188,9 +416,162 @@ impl LzReceive<'_> {
         emit_cpi!(OFTReceived {
             guid: params.guid,
             src_eid: params.src_eid,
-            to: ctx.accounts.to_address.key(),
+            to: transfer_account(&ctx.accounts.to_address)?.key(),
             amount_received_ld,
+            amount_sd,
+            message_hash: msg_codec::hash(&params.message),
         });
+        Self::notify(ctx, params, amount_ld, amount_received_ld)?;
+        compute_profile::checkpoint("lz_receive:compose");
         Ok(())
     }
+
+    fn notify(
+        ctx: &Context<LzReceive>,
+        params: &LzReceiveParams,
+        amount_ld: u64,
+        credited_ld: u64,
+    ) -> Result<()> {
+        NotificationHook::notify(
+            transfer_account(&ctx.accounts.notification_hook)?,
+            CreditNotification {
+                guid: params.guid,
+                src_eid: params.src_eid,
+                amount_ld,
+                credited_ld,
+                slot: Clock::get()?.slot,
+            },
+        )
+    }
+
+    fn init_pending_credit(
+        ctx: &Context<LzReceive>,
+        params: &LzReceiveParams,
+        amount_ld: u64,
+        awaiting_verification: bool,
+        awaiting_claim: bool,
+        awaiting_capacity: bool,
+        vesting: Option<Vesting>,
+    ) -> Result<()> {
+        let pending_credit = PendingCredit {
+            oft_store: ctx.accounts.oft_store.key(),
+            guid: params.guid,
+            src_eid: params.src_eid,
+            nonce: params.nonce,
+            to_address: transfer_account(&ctx.accounts.to_address)?.key(),
+            amount_ld,
+            payer: ctx.accounts.payer.key(),
+            rejected: false,
+            message_hash: msg_codec::hash(&params.message),
+            awaiting_verification,
+            awaiting_claim,
+            awaiting_capacity,
+            vesting,
+            compose_msg: msg_codec::compose_msg(&params.message),
+        };
+        let pending_credit_info =
+            ctx.accounts.pending_credit.as_ref().ok_or(OFTError::InvalidPendingCredit)?;
+        pending_credit.init(
+            pending_credit_info,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            ctx.bumps.pending_credit,
+        )
+    }
+
+    fn init_transfer_receipt(
+        ctx: &Context<LzReceive>,
+        params: &LzReceiveParams,
+        amount_ld: u64,
+    ) -> Result<()> {
+        let transfer_receipt_info =
+            ctx.accounts.transfer_receipt.as_ref().ok_or(OFTError::InvalidTransferReceipt)?;
+        let retention_seconds =
+            ctx.accounts.oft_store.transfer_receipts.as_ref().map_or(0, |p| p.retention_seconds);
+        let clock = Clock::get()?;
+        TransferReceipt {
+            oft_store: ctx.accounts.oft_store.key(),
+            guid: params.guid,
+            remote_eid: params.src_eid,
+            outbound: false,
+            recipient: transfer_account(&ctx.accounts.to_address)?.key().to_bytes(),
+            amount_ld,
+            slot: clock.slot,
+            payer: ctx.accounts.payer.key(),
+            closable_at: clock.unix_timestamp.saturating_add(retention_seconds),
+        }
+        .init(
+            transfer_receipt_info,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            ctx.bumps.transfer_receipt,
+        )
+    }
+}
+
+/// An account of the recipient, which only control messages may omit.
+fn transfer_account<T>(account: &Option<T>) -> Result<&T> {
+    Ok(account.as_ref().ok_or(OFTError::TransferAccountMissing)?)
+}
+
+/// Unlocks `amount_ld` from the escrow (Adapter) or mints it (Native) into `token_dest`.
+/// Returns the amount received after any token2022 transfer fee.
+pub fn credit<'info>(
+    oft_store: &mut Account<'info, OFTStore>,
+    token_escrow: &InterfaceAccount<'info, TokenAccount>,
+    token_dest: &InterfaceAccount<'info, TokenAccount>,
+    token_mint: &InterfaceAccount<'info, Mint>,
+    mint_authority: &Option<AccountInfo<'info>>,
+    token_program: &Interface<'info, TokenInterface>,
+    amount_ld: u64,
+) -> Result<u64> {
+    let oft_store_seed = token_escrow.key();
+    let bump = oft_store.bump;
+    let seeds: &[&[u8]] = &[OFT_SEED, oft_store_seed.as_ref(), &[bump]];
+
+    if oft_store.oft_type == OFTType::Adapter {
+        // unlock from escrow
+        oft_store.decrease_tvl(amount_ld)?;
+        token_interface::transfer_checked(
+            CpiContext::new(
+                token_program.to_account_info(),
+                TransferChecked {
+                    from: token_escrow.to_account_info(),
+                    mint: token_mint.to_account_info(),
+                    to: token_dest.to_account_info(),
+                    authority: oft_store.to_account_info(),
+                },
+            )
+            .with_signer(&[&seeds]),
+            amount_ld,
+            token_mint.decimals,
+        )?;
+
+        // return the post transfer fee amount
+        get_post_fee_amount_ld(token_mint, amount_ld)
+    } else if let Some(mint_authority) = mint_authority {
+        // Native type
+        // mint
+        let ix = spl_token_2022::instruction::mint_to(
+            token_program.key,
+            &token_mint.key(),
+            &token_dest.key(),
+            mint_authority.key,
+            &[&oft_store.key()],
+            amount_ld,
+        )?;
+        solana_program::program::invoke_signed(
+            &ix,
+            &[
+                token_dest.to_account_info(),
+                token_mint.to_account_info(),
+                mint_authority.to_account_info(),
+                oft_store.to_account_info(),
+            ],
+            &[&seeds],
+        )?;
+        Ok(amount_ld)
+    } else {
+        Err(OFTError::InvalidMintAuthority.into())
+    }
 }
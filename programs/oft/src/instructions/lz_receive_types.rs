@@ -23,23 +23,33 @@ pub struct LzReceiveTypes<'info> {
 // account 1 - peer
 // account 2 - oft store
 // account 3 - token escrow
-// account 4 - to address / wallet address
-// account 5 - token dest
+// account 4 - to address / wallet address, omitted for control messages
+// account 5 - token dest, omitted for control messages
 // account 6 - token mint
-// account 7 - mint authority (optional)
+// account 7 - mint authority (optional), omitted for control messages
 // account 8 - token program
 // account 9 - associated token program
 // account 10 - system program
-// account 11 - event authority
-// account 12 - this program
+// account 11 - pending credit, only created when the credit is held or split, omitted for control
+//              messages
+// account 12 - inbound opt out of the to address, omitted for control messages
+// account 13 - rate limit state of the peer
+// account 14 - transfer receipt, only created for transfers of at least the receipt minimum,
+//              omitted for control messages
+// account 15 - notification hook of the to address, omitted for control messages
+// account 16 - event authority
+// account 17 - this program
 // account remaining accounts
 //      0..9 - accounts for clear
-//      9..16 - accounts for compose
+//      9..16 - accounts for compose, not listed for control messages
+// Omitted accounts are passed as this program's id.
 impl LzReceiveTypes<'_> {
     pub fn apply(
         ctx: &Context<LzReceiveTypes>,
         params: &LzReceiveParams,
     ) -> Result<Vec<LzAccount>> {
+        msg_codec::validate(&params.message)?;
+
         let (peer, _) = Pubkey::find_program_address(
             &[PEER_SEED, ctx.accounts.oft_store.key().as_ref(), &params.src_eid.to_be_bytes()],
             ctx.program_id,
@@ -57,6 +67,10 @@ impl LzReceiveTypes<'_> {
             }, // 3
         ];
 
+        if msg_codec::is_control(&params.message, &params.sender) {
+            return Self::control_accounts(ctx, params, accounts);
+        }
+
         // account 4..9
         let to_address = Pubkey::from(msg_codec::send_to(&params.message));
         let token_program = ctx.accounts.token_mint.to_account_info().owner;
@@ -84,17 +98,44 @@ impl LzReceiveTypes<'_> {
             LzAccount { pubkey: ASSOCIATED_TOKEN_ID, is_signer: false, is_writable: false }, // 9
         ]);
 
-        // account 10..12
+        // account 10..17
+        // Whether the credit is held also depends on the recipient's InboundOptOut, which is
+        // not readable here, so the pending credit is always listed.
+        let pending_credit =
+            PendingCredit::address(&ctx.accounts.oft_store.key(), &to_address, &params.guid);
+        let (inbound_opt_out, _) = Pubkey::find_program_address(
+            &[INBOUND_OPT_OUT_SEED, ctx.accounts.oft_store.key().as_ref(), to_address.as_ref()],
+            ctx.program_id,
+        );
+        let (rate_limit_state, _) = Pubkey::find_program_address(
+            &[
+                RATE_LIMIT_STATE_SEED,
+                ctx.accounts.oft_store.key().as_ref(),
+                &params.src_eid.to_be_bytes(),
+            ],
+            ctx.program_id,
+        );
+        let (transfer_receipt, _) =
+            TransferReceipt::address(&ctx.accounts.oft_store.key(), &params.guid);
+        let (notification_hook, _) = Pubkey::find_program_address(
+            &[NOTIFICATION_HOOK_SEED, ctx.accounts.oft_store.key().as_ref(), to_address.as_ref()],
+            ctx.program_id,
+        );
         let (event_authority_account, _) =
-            Pubkey::find_program_address(&[oapp::endpoint_cpi::EVENT_SEED], &ctx.program_id);
+            Pubkey::find_program_address(&[oapp::endpoint_cpi::EVENT_SEED], ctx.program_id);
         accounts.extend_from_slice(&[
             LzAccount {
                 pubkey: solana_program::system_program::ID,
                 is_signer: false,
                 is_writable: false,
             }, // 10
-            LzAccount { pubkey: event_authority_account, is_signer: false, is_writable: false }, // 11
-            LzAccount { pubkey: ctx.program_id.key(), is_signer: false, is_writable: false }, // 12
+            LzAccount { pubkey: pending_credit, is_signer: false, is_writable: true }, // 11
+            LzAccount { pubkey: inbound_opt_out, is_signer: false, is_writable: false }, // 12
+            LzAccount { pubkey: rate_limit_state, is_signer: false, is_writable: true }, // 13
+            LzAccount { pubkey: transfer_receipt, is_signer: false, is_writable: true }, // 14
+            LzAccount { pubkey: notification_hook, is_signer: false, is_writable: true }, // 15
+            LzAccount { pubkey: event_authority_account, is_signer: false, is_writable: false }, // 16
+            LzAccount { pubkey: ctx.program_id.key(), is_signer: false, is_writable: false }, // 17
         ]);
 
         let endpoint_program = ctx.accounts.oft_store.endpoint_program;
@@ -111,7 +152,7 @@ impl LzReceiveTypes<'_> {
         // remaining accounts 9..16
         if let Some(message) = msg_codec::compose_msg(&params.message) {
             let amount_sd = msg_codec::amount_sd(&params.message);
-            let amount_ld = ctx.accounts.oft_store.sd2ld(amount_sd);
+            let amount_ld = ctx.accounts.oft_store.sd2ld(amount_sd)?;
             let amount_received_ld = if ctx.accounts.oft_store.oft_type == OFTType::Native {
                 amount_ld
             } else {
@@ -136,4 +177,60 @@ impl LzReceiveTypes<'_> {
 
         Ok(accounts)
     }
+
+    /// The accounts of a control message, which credit no one: the accounts of the recipient are
+    /// omitted and there is nothing to compose.
+    fn control_accounts(
+        ctx: &Context<LzReceiveTypes>,
+        params: &LzReceiveParams,
+        mut accounts: Vec<LzAccount>,
+    ) -> Result<Vec<LzAccount>> {
+        let omitted =
+            LzAccount { pubkey: ctx.program_id.key(), is_signer: false, is_writable: false };
+        let token_program = ctx.accounts.token_mint.to_account_info().owner;
+        let (rate_limit_state, _) = Pubkey::find_program_address(
+            &[
+                RATE_LIMIT_STATE_SEED,
+                ctx.accounts.oft_store.key().as_ref(),
+                &params.src_eid.to_be_bytes(),
+            ],
+            ctx.program_id,
+        );
+        let (event_authority_account, _) =
+            Pubkey::find_program_address(&[oapp::endpoint_cpi::EVENT_SEED], ctx.program_id);
+        accounts.extend_from_slice(&[
+            omitted.clone(), // 4
+            omitted.clone(), // 5
+            LzAccount {
+                pubkey: ctx.accounts.token_mint.key(),
+                is_signer: false,
+                is_writable: true,
+            }, // 6
+            omitted.clone(), // 7
+            LzAccount { pubkey: *token_program, is_signer: false, is_writable: false }, // 8
+            LzAccount { pubkey: ASSOCIATED_TOKEN_ID, is_signer: false, is_writable: false }, // 9
+            LzAccount {
+                pubkey: solana_program::system_program::ID,
+                is_signer: false,
+                is_writable: false,
+            }, // 10
+            omitted.clone(), // 11
+            omitted.clone(), // 12
+            LzAccount { pubkey: rate_limit_state, is_signer: false, is_writable: true }, // 13
+            omitted.clone(), // 14
+            omitted.clone(), // 15
+            LzAccount { pubkey: event_authority_account, is_signer: false, is_writable: false }, // 16
+            omitted, // 17
+        ]);
+
+        // remaining accounts 0..9
+        accounts.extend(oapp::endpoint_cpi::get_accounts_for_clear(
+            ctx.accounts.oft_store.endpoint_program,
+            &ctx.accounts.oft_store.key(),
+            params.src_eid,
+            &params.sender,
+            params.nonce,
+        ));
+        Ok(accounts)
+    }
 }
@@ -0,0 +1,37 @@
+use crate::*;
+
+/// Adds addresses to or removes them from the store's fee exempt list. Sends whose debit
+/// authority is on it pay no OFT fee, so the treasury, market makers and protocol owned accounts
+/// can bridge without one.
+#[derive(Accounts)]
+pub struct ManageFeeExempt<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump,
+        has_one = admin @OFTError::Unauthorized
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+}
+
+impl ManageFeeExempt<'_> {
+    pub fn apply(ctx: &mut Context<ManageFeeExempt>, params: &ManageFeeExemptParams) -> Result<()> {
+        for address in params.addresses.iter() {
+            match params.action {
+                RateLimitOverrideAction::Add => ctx.accounts.oft_store.add_fee_exempt(*address)?,
+                RateLimitOverrideAction::Remove => {
+                    ctx.accounts.oft_store.remove_fee_exempt(address)?
+                },
+            }
+            emit!(FeeExemptionUpdated { address: *address, action: params.action.clone() });
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct ManageFeeExemptParams {
+    pub addresses: Vec<Pubkey>,
+    pub action: RateLimitOverrideAction, // applied to every address
+}
@@ -0,0 +1,59 @@
+use crate::*;
+use anchor_lang::system_program::{self, Transfer};
+
+#[derive(Accounts)]
+pub struct ManageFeeVault<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump,
+        has_one = admin @OFTError::Unauthorized
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + FeeVault::INIT_SPACE,
+        seeds = [FEE_VAULT_SEED, oft_store.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+    pub system_program: Program<'info, System>,
+}
+
+impl ManageFeeVault<'_> {
+    pub fn apply(ctx: &mut Context<ManageFeeVault>, params: &ManageFeeVaultParams) -> Result<()> {
+        ctx.accounts.fee_vault.oft_store = ctx.accounts.oft_store.key();
+        ctx.accounts.fee_vault.bump = ctx.bumps.fee_vault;
+        match params {
+            ManageFeeVaultParams::Fund(lamports) => system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.admin.to_account_info(),
+                        to: ctx.accounts.fee_vault.to_account_info(),
+                    },
+                ),
+                *lamports,
+            ),
+            ManageFeeVaultParams::Withdraw(lamports) => {
+                require!(
+                    ctx.accounts.oft_store.is_withdraw_destination(&ctx.accounts.admin.key()),
+                    OFTError::WithdrawDestinationNotAllowed
+                );
+                FeeVault::transfer_lamports(
+                    &ctx.accounts.fee_vault.to_account_info(),
+                    &ctx.accounts.admin.to_account_info(),
+                    *lamports,
+                )
+            },
+        }
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub enum ManageFeeVaultParams {
+    Fund(u64),
+    Withdraw(u64),
+}
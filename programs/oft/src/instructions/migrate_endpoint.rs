@@ -0,0 +1,91 @@
+use crate::*;
+use oapp::endpoint::instructions::RegisterOAppParams;
+
+#[derive(Accounts)]
+pub struct MigrateEndpoint<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump,
+        has_one = admin @OFTError::Unauthorized
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+}
+
+impl MigrateEndpoint<'_> {
+    pub fn apply(ctx: &mut Context<MigrateEndpoint>, params: &MigrateEndpointParams) -> Result<()> {
+        match params.clone() {
+            MigrateEndpointParams::Propose(new_endpoint_program) => {
+                require!(
+                    !ctx.accounts.oft_store.is_endpoint_migration_pending(),
+                    OFTError::EndpointMigrationPending
+                );
+                let executable_at = Clock::get()?
+                    .unix_timestamp
+                    .checked_add(ENDPOINT_MIGRATION_DELAY_SECONDS)
+                    .ok_or_else(|| error!(OFTError::InvalidTimestamp))?;
+                ctx.accounts.oft_store.pending_endpoint_migration =
+                    Some(EndpointMigration { new_endpoint_program, executable_at });
+
+                emit!(EndpointMigrationProposed { new_endpoint_program, executable_at });
+            },
+            MigrateEndpointParams::Cancel => {
+                let migration = ctx
+                    .accounts
+                    .oft_store
+                    .pending_endpoint_migration
+                    .take()
+                    .ok_or(OFTError::NoPendingEndpointMigration)?;
+
+                emit!(EndpointMigrationCancelled {
+                    new_endpoint_program: migration.new_endpoint_program,
+                });
+            },
+            MigrateEndpointParams::Execute => {
+                let migration = ctx
+                    .accounts
+                    .oft_store
+                    .pending_endpoint_migration
+                    .clone()
+                    .ok_or(OFTError::NoPendingEndpointMigration)?;
+                require!(
+                    Clock::get()?.unix_timestamp >= migration.executable_at,
+                    OFTError::EndpointMigrationTimelocked
+                );
+
+                // Register the oapp with the new endpoint, keeping the admin as the delegate
+                let oft_store_seed = ctx.accounts.oft_store.token_escrow.key();
+                let seeds: &[&[u8]] =
+                    &[OFT_SEED, &oft_store_seed.to_bytes(), &[ctx.accounts.oft_store.bump]];
+                oapp::endpoint_cpi::register_oapp(
+                    migration.new_endpoint_program,
+                    ctx.accounts.oft_store.key(),
+                    ctx.remaining_accounts,
+                    seeds,
+                    RegisterOAppParams { delegate: ctx.accounts.oft_store.admin },
+                )?;
+
+                let oft_store = &mut ctx.accounts.oft_store;
+                let old_endpoint_program = oft_store.endpoint_program;
+                oft_store.endpoint_program = migration.new_endpoint_program;
+                oft_store.endpoint_version = oft_store.endpoint_version.wrapping_add(1);
+                oft_store.pending_endpoint_migration = None;
+
+                emit!(EndpointMigrationExecuted {
+                    old_endpoint_program,
+                    new_endpoint_program: migration.new_endpoint_program,
+                    endpoint_version: oft_store.endpoint_version,
+                });
+            },
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub enum MigrateEndpointParams {
+    Propose(Pubkey), // new endpoint program, executable after ENDPOINT_MIGRATION_DELAY_SECONDS
+    Cancel,
+    Execute,
+}
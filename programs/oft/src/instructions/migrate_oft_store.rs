@@ -0,0 +1,52 @@
+use crate::*;
+use anchor_lang::Discriminator;
+
+/// Rewrites a store written before stores had a version in the current layout, resizing it
+/// at the payer's expense. Peers are migrated separately, see migrate_peer_config.
+#[derive(Accounts)]
+pub struct MigrateOFTStore<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub admin: Signer<'info>,
+    /// CHECK: a store in an earlier layout, which Account<OFTStore> cannot load; checked in apply
+    #[account(mut, owner = crate::ID)]
+    pub oft_store: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl MigrateOFTStore<'_> {
+    pub fn apply(ctx: &mut Context<MigrateOFTStore>) -> Result<()> {
+        let info = ctx.accounts.oft_store.to_account_info();
+        let (from_version, mut oft_store) = {
+            let data = info.try_borrow_data()?;
+            require!(
+                data.len() > 8 && data[..8] == OFTStore::DISCRIMINATOR,
+                OFTError::InvalidMigration
+            );
+            OFTStore::deserialize_versioned(&mut &data[8..])?
+        };
+        require!(from_version < OFT_STORE_VERSION, OFTError::AlreadyMigrated);
+        require!(oft_store.admin == ctx.accounts.admin.key(), OFTError::Unauthorized);
+        let expected = Pubkey::create_program_address(
+            &[OFT_SEED, oft_store.token_escrow.as_ref(), &[oft_store.bump]],
+            &ID,
+        )
+        .map_err(|_| error!(OFTError::InvalidMigration))?;
+        require!(info.key() == expected, OFTError::InvalidMigration);
+
+        oft_store.version = OFT_STORE_VERSION;
+        realloc_zeroed(
+            &info,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            OFTStore::space(
+                oft_store.max_rate_limit_overrides,
+                oft_store.max_rate_limit_override_guid_count,
+            ),
+        )?;
+        oft_store.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+        emit!(OFTStoreMigrated { from_version, version: OFT_STORE_VERSION });
+        Ok(())
+    }
+}
@@ -0,0 +1,66 @@
+use crate::*;
+use anchor_lang::Discriminator;
+
+/// Rewrites a peer written before peers had a version in the current layout, resizing it
+/// at the payer's expense.
+#[derive(Accounts)]
+#[instruction(params: MigratePeerConfigParams)]
+pub struct MigratePeerConfig<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump,
+        has_one = admin @OFTError::Unauthorized
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    /// CHECK: a peer in an earlier layout, which Account<PeerConfig> cannot load; checked in apply
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [PEER_SEED, oft_store.key().as_ref(), &params.remote_eid.to_be_bytes()],
+        bump
+    )]
+    pub peer: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl MigratePeerConfig<'_> {
+    pub fn apply(
+        ctx: &mut Context<MigratePeerConfig>,
+        params: &MigratePeerConfigParams,
+    ) -> Result<()> {
+        let info = ctx.accounts.peer.to_account_info();
+        let (from_version, mut peer) = {
+            let data = info.try_borrow_data()?;
+            require!(
+                data.len() > 8 && data[..8] == PeerConfig::DISCRIMINATOR,
+                OFTError::InvalidMigration
+            );
+            PeerConfig::deserialize_versioned(&mut &data[8..], data.len())?
+        };
+        require!(from_version < PEER_CONFIG_VERSION, OFTError::AlreadyMigrated);
+
+        peer.version = PEER_CONFIG_VERSION;
+        realloc_zeroed(
+            &info,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            8 + PeerConfig::INIT_SPACE,
+        )?;
+        peer.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+        emit!(PeerConfigMigrated {
+            remote_eid: params.remote_eid,
+            from_version,
+            version: PEER_CONFIG_VERSION,
+        });
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct MigratePeerConfigParams {
+    pub remote_eid: u32,
+}
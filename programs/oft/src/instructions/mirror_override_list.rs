@@ -0,0 +1,102 @@
+use crate::*;
+use oapp::endpoint::instructions::SendParams as EndpointSendParams;
+
+/// Sends the rate limit override list to the EVM deployment of a peer, so its exempt addresses
+/// follow this store's list. Every mapped address is sent, exempt if it has an unexpired override
+/// for both directions and not exempt otherwise, so removals and expiries are mirrored too; the
+/// EVM exemption applies both ways and in full, so direction scoped overrides and overrides with a
+/// multiplier are mirrored as not exempt.
+#[derive(Accounts)]
+#[instruction(params: MirrorOverrideListParams)]
+pub struct MirrorOverrideList<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump,
+        has_one = admin @OFTError::Unauthorized
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        seeds = [
+            PEER_SEED,
+            oft_store.key().as_ref(),
+            &params.dst_eid.to_be_bytes()
+        ],
+        bump = peer.bump
+    )]
+    pub peer: Account<'info, PeerConfig>,
+    #[account(
+        seeds = [EVM_ADDRESS_MAP_SEED, oft_store.key().as_ref()],
+        bump = evm_address_map.bump
+    )]
+    pub evm_address_map: Account<'info, EvmAddressMap>,
+}
+
+impl MirrorOverrideList<'_> {
+    pub fn apply(
+        ctx: &mut Context<MirrorOverrideList>,
+        params: &MirrorOverrideListParams,
+    ) -> Result<()> {
+        let oft_store = &ctx.accounts.oft_store;
+        require!(
+            !ctx.accounts.evm_address_map.mappings.is_empty(),
+            OFTError::EmptyOverrideListMirror
+        );
+        require!(oft_store.key() == ctx.remaining_accounts[1].key(), OFTError::InvalidSender);
+
+        let now = Clock::get()?.unix_timestamp;
+        let entries: Vec<([u8; 20], bool)> = ctx
+            .accounts
+            .evm_address_map
+            .mappings
+            .iter()
+            .map(|mapping| {
+                let is_exempt = [RateLimitDirection::Inbound, RateLimitDirection::Outbound]
+                    .into_iter()
+                    .all(|direction| {
+                        oft_store.is_rate_limit_override_at(&mapping.address, direction, now)
+                    });
+                (mapping.evm_address, is_exempt)
+            })
+            .collect();
+        let message = msg_codec::encode_override_list(oft_store.key(), &entries);
+        let msg_receipt = oapp::endpoint_cpi::send(
+            oft_store.endpoint_program,
+            oft_store.key(),
+            ctx.remaining_accounts,
+            &[OFT_SEED, oft_store.token_escrow.as_ref(), &[oft_store.bump]],
+            EndpointSendParams {
+                dst_eid: params.dst_eid,
+                receiver: ctx.accounts.peer.peer_address,
+                message,
+                options: ctx
+                    .accounts
+                    .peer
+                    .enforced_options
+                    .combine_options(&None, &params.options)?,
+                native_fee: params.native_fee,
+                lz_token_fee: params.lz_token_fee,
+            },
+        )?;
+
+        emit!(OverrideListMirrored {
+            dst_eid: params.dst_eid,
+            guid: msg_receipt.guid,
+            exempt: entries.iter().filter(|(_, is_exempt)| *is_exempt).map(|(a, _)| *a).collect(),
+            not_exempt: entries
+                .iter()
+                .filter(|(_, is_exempt)| !is_exempt)
+                .map(|(a, _)| *a)
+                .collect(),
+        });
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct MirrorOverrideListParams {
+    pub dst_eid: u32,
+    pub options: Vec<u8>,
+    pub native_fee: u64,
+    pub lz_token_fee: u64,
+}
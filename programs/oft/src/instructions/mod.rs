@@ -1,23 +1,119 @@
+pub mod boost_rate_limit;
+pub mod burn_from_escrow;
+pub mod challenge_peer;
+pub mod check_remote_config;
+pub mod close_send_record;
+pub mod close_transfer_receipt;
+pub mod confirm_rate_limit_override;
+pub mod crank;
+pub mod deprecated;
+pub mod execute_pending_credit;
+pub mod get_dust;
+pub mod get_in_flight;
+pub mod get_oft_metadata;
+pub mod get_peer_health;
+pub mod get_pending_credit_address;
+pub mod get_rate_limit_capacity;
+pub mod get_rate_limit_overrides;
 pub mod init_oft;
 pub mod lz_receive;
 pub mod lz_receive_types;
+pub mod manage_fee_exempt;
+pub mod manage_fee_vault;
+pub mod migrate_endpoint;
+pub mod migrate_oft_store;
+pub mod migrate_peer_config;
+pub mod mirror_override_list;
+pub mod propose_rate_limit_override;
 pub mod quote_oft;
 pub mod quote_send;
+pub mod register_inbound_opt_out;
+pub mod register_notification_hook;
+pub mod remove_inbound_opt_out;
+pub mod remove_notification_hook;
+pub mod reset_rate_limit_consumption;
+pub mod resize_override_lists;
+pub mod resolve_pending_credit;
 pub mod send;
+pub mod send_pause_notice;
+pub mod send_with_fee_vault;
+pub mod set_control_message_pause;
+pub mod set_evm_address_mapping;
+pub mod set_inbound_rate_limit;
 pub mod set_oft_config;
+pub mod set_outbound_rate_limit;
 pub mod set_pause;
 pub mod set_peer_config;
+pub mod set_rate_limit_enabled;
+pub mod set_rate_limits_batch;
 pub mod set_ratelimit_override;
+pub mod set_withdraw_destinations;
+pub mod simulate_receive;
+pub mod simulate_send;
+pub mod sweep_dust;
+pub mod sync_rate_limit_state;
+pub mod test_mint;
+pub mod top_up_lamports;
 pub mod withdraw_fee;
+pub mod withdraw_fees;
+pub mod withdraw_surplus_lamports;
 
+pub use boost_rate_limit::*;
+pub use burn_from_escrow::*;
+pub use challenge_peer::*;
+pub use check_remote_config::*;
+pub use close_send_record::*;
+pub use close_transfer_receipt::*;
+pub use confirm_rate_limit_override::*;
+pub use crank::*;
+pub use deprecated::*;
+pub use execute_pending_credit::*;
+pub use get_dust::*;
+pub use get_in_flight::*;
+pub use get_oft_metadata::*;
+pub use get_peer_health::*;
+pub use get_pending_credit_address::*;
+pub use get_rate_limit_capacity::*;
+pub use get_rate_limit_overrides::*;
 pub use init_oft::*;
 pub use lz_receive::*;
 pub use lz_receive_types::*;
+pub use manage_fee_exempt::*;
+pub use manage_fee_vault::*;
+pub use migrate_endpoint::*;
+pub use migrate_oft_store::*;
+pub use migrate_peer_config::*;
+pub use mirror_override_list::*;
+pub use propose_rate_limit_override::*;
 pub use quote_oft::*;
 pub use quote_send::*;
+pub use register_inbound_opt_out::*;
+pub use register_notification_hook::*;
+pub use remove_inbound_opt_out::*;
+pub use remove_notification_hook::*;
+pub use reset_rate_limit_consumption::*;
+pub use resize_override_lists::*;
+pub use resolve_pending_credit::*;
 pub use send::*;
+pub use send_pause_notice::*;
+pub use send_with_fee_vault::*;
+pub use set_control_message_pause::*;
+pub use set_evm_address_mapping::*;
+pub use set_inbound_rate_limit::*;
 pub use set_oft_config::*;
+pub use set_outbound_rate_limit::*;
 pub use set_pause::*;
 pub use set_peer_config::*;
+pub use set_rate_limit_enabled::*;
+pub use set_rate_limits_batch::*;
 pub use set_ratelimit_override::*;
-pub use withdraw_fee::*;
\ No newline at end of file
+pub use set_withdraw_destinations::*;
+pub use simulate_receive::*;
+pub use simulate_send::*;
+pub use sweep_dust::*;
+pub use sync_rate_limit_state::*;
+pub use test_mint::*;
+pub use top_up_lamports::*;
+pub use withdraw_fee::*;
+pub use withdraw_fees::*;
+pub use withdraw_surplus_lamports::*;
\ No newline at end of file
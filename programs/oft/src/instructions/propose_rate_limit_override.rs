@@ -0,0 +1,73 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(params: ProposeRateLimitOverrideParams)]
+pub struct ProposeRateLimitOverride<'info> {
+    /// override manager
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump,
+        constraint = oft_store.override_manager == Some(signer.key()) @OFTError::Unauthorized
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    // Only provided when the notional is above the store's override_approval_threshold_ld
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + RateLimitOverrideProposal::INIT_SPACE,
+        seeds = [
+            RATE_LIMIT_OVERRIDE_PROPOSAL_SEED,
+            oft_store.key().as_ref(),
+            &params.target.seed()
+        ],
+        bump
+    )]
+    pub proposal: Option<Account<'info, RateLimitOverrideProposal>>,
+    pub system_program: Program<'info, System>,
+}
+
+impl ProposeRateLimitOverride<'_> {
+    pub fn apply(
+        ctx: &mut Context<ProposeRateLimitOverride>,
+        params: &ProposeRateLimitOverrideParams,
+    ) -> Result<()> {
+        let requires_approval =
+            ctx.accounts.oft_store.requires_override_approval(params.notional_ld);
+        require!(
+            ctx.accounts.proposal.is_some() == requires_approval,
+            OFTError::InvalidRateLimitOverrideProposal
+        );
+
+        if let Some(proposal) = ctx.accounts.proposal.as_mut() {
+            let expires_at = Clock::get()?
+                .unix_timestamp
+                .checked_add(RATE_LIMIT_OVERRIDE_PROPOSAL_WINDOW_SECONDS)
+                .ok_or_else(|| error!(OFTError::InvalidTimestamp))?;
+            proposal.oft_store = ctx.accounts.oft_store.key();
+            proposal.target = params.target.clone();
+            proposal.notional_ld = params.notional_ld;
+            proposal.proposer = ctx.accounts.signer.key();
+            proposal.expires_at = expires_at;
+
+            emit!(RateLimitOverrideProposed {
+                target: params.target.clone(),
+                notional_ld: params.notional_ld,
+                proposer: ctx.accounts.signer.key(),
+                expires_at,
+            });
+        } else {
+            // small exemptions take effect immediately
+            params.target.add_to(&mut ctx.accounts.oft_store)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct ProposeRateLimitOverrideParams {
+    pub target: RateLimitOverrideTarget,
+    pub notional_ld: u64, // the expected volume moving through the override
+}
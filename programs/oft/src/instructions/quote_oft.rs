@@ -24,20 +24,32 @@ pub struct QuoteOFT<'info> {
 
 impl QuoteOFT<'_> {
     pub fn apply(ctx: &Context<QuoteOFT>, params: &QuoteOFTParams) -> Result<QuoteOFTResult> {
-        require!(!ctx.accounts.oft_store.paused, OFTError::Paused);
-
         let (amount_sent_ld, amount_received_ld, oft_fee_ld) = compute_fee_and_adjust_amount(
             params.amount_ld,
             &ctx.accounts.oft_store,
             &ctx.accounts.token_mint,
             ctx.accounts.peer.fee_bps,
         )?;
-        require!(amount_received_ld >= params.min_amount_ld, OFTError::SlippageExceeded);
+        let blockers = policy::send_blockers(
+            &ctx.accounts.oft_store,
+            &ctx.accounts.peer,
+            params.dst_eid,
+            &params.to,
+            amount_received_ld,
+            params.min_amount_ld,
+        )?;
 
-        let oft_limits = OFTLimits { min_amount_ld: 0, max_amount_ld: 0xffffffffffffffff };
-        let mut oft_fee_details = if amount_received_ld + oft_fee_ld < amount_sent_ld {
+        let oft_limits = Self::oft_limits(
+            &ctx.accounts.oft_store,
+            &ctx.accounts.peer,
+            params.compose_msg.is_some(),
+        )?;
+        let amount_after_oft_fee_ld = amount_received_ld
+            .checked_add(oft_fee_ld)
+            .ok_or_else(|| error!(OFTError::AmountOverflow))?;
+        let mut oft_fee_details = if amount_after_oft_fee_ld < amount_sent_ld {
             vec![OFTFeeDetail {
-                fee_amount_ld: amount_sent_ld - oft_fee_ld - amount_received_ld,
+                fee_amount_ld: amount_sent_ld - amount_after_oft_fee_ld,
                 description: "Token2022 Transfer Fee".to_string(),
             }]
         } else {
@@ -50,8 +62,69 @@ impl QuoteOFT<'_> {
                 description: "Cross Chain Fee".to_string(),
             });
         }
+        // deducted by the peer on delivery, in shared decimals like the local fee
+        let dst_fee_ld = match ctx.accounts.peer.remote_fee_bps {
+            Some(fee_bps) => ctx
+                .accounts
+                .oft_store
+                .remove_dust(calculate_fee(amount_received_ld, 0, Some(fee_bps))?)?,
+            None => 0,
+        };
+        if dst_fee_ld > 0 {
+            oft_fee_details.push(OFTFeeDetail {
+                fee_amount_ld: dst_fee_ld,
+                description: "Destination Fee (estimated)".to_string(),
+            });
+        }
         let oft_receipt = OFTReceipt { amount_sent_ld, amount_received_ld };
-        Ok(QuoteOFTResult { oft_limits, oft_fee_details, oft_receipt })
+        let delivery_sla_seconds =
+            ctx.accounts.peer.priority_tier(params.priority)?.map(|tier| tier.sla_seconds);
+        Ok(QuoteOFTResult {
+            oft_limits,
+            oft_fee_details,
+            oft_receipt,
+            delivery_sla_seconds,
+            estimated_dst_amount_ld: amount_received_ld - dst_fee_ld,
+            blockers,
+        })
+    }
+
+    /// The smallest amount that is not all dust, and the largest amount received that the launch
+    /// cap, probation and the peer's outbound rate limits let through now, dust removed, or
+    /// u64::MAX if nothing caps it. As for the blockers, the sender is not known, so rate limit
+    /// overrides are not taken into account and the peer's reserved capacity is not available.
+    fn oft_limits(oft_store: &OFTStore, peer: &PeerConfig, composed: bool) -> Result<OFTLimits> {
+        let now = Clock::get()?.unix_timestamp;
+        let mut caps = vec![];
+        if let Some(stage) = oft_store.launch_stage(now) {
+            caps.push(stage.max_amount_ld);
+        }
+        if let Some(policy) = oft_store.probation_policy.as_ref() {
+            if peer.is_on_probation(now) {
+                caps.push(policy.max_amount_ld);
+            }
+        }
+        if peer.rate_limit_enabled {
+            let mut peer = peer.clone();
+            policy::refresh_rate_limits(oft_store, &mut peer)?;
+            let mode = peer.outbound_rate_limit_mode;
+            let now = mode.now(&Clock::get()?);
+            if let Some(rate_limiter) = &peer.outbound_rate_limiter {
+                let reserved_ld = peer.reserved_capacity_ld;
+                caps.push(simulation::unreserved_ld(rate_limiter, mode, now, reserved_ld));
+            }
+            let msg_type = msg_codec::msg_type(composed);
+            if let Some(rate_limiter) =
+                peer.msg_type_rate_limiter(msg_type, RateLimitDirection::Outbound)
+            {
+                caps.push(simulation::available_ld(rate_limiter, mode, now));
+            }
+        }
+        let max_amount_ld = match caps.into_iter().min() {
+            Some(cap) => oft_store.remove_dust(cap)?,
+            None => u64::MAX,
+        };
+        Ok(OFTLimits { min_amount_ld: oft_store.ld2sd_rate, max_amount_ld })
     }
 }
 
@@ -64,6 +137,7 @@ pub struct QuoteOFTParams {
     pub options: Vec<u8>,
     pub compose_msg: Option<Vec<u8>>,
     pub pay_in_lz_token: bool,
+    pub priority: u8,
 }
 
 #[derive(Clone, AnchorSerialize, AnchorDeserialize)]
@@ -71,6 +145,11 @@ pub struct QuoteOFTResult {
     pub oft_limits: OFTLimits,
     pub oft_fee_details: Vec<OFTFeeDetail>,
     pub oft_receipt: OFTReceipt,
+    pub delivery_sla_seconds: Option<u32>, // None when the priority has no configured tier
+    // What the recipient ends up with after the peer's remote_fee_bps, if configured
+    pub estimated_dst_amount_ld: u64,
+    // The policies that would reject the send now; empty if it would go through
+    pub blockers: Vec<policy::SendBlocker>,
 }
 
 #[derive(Clone, AnchorSerialize, AnchorDeserialize)]
@@ -85,8 +164,9 @@ pub struct OFTReceipt {
     pub amount_received_ld: u64,
 }
 
+/// Mirrors the OFTLimits of quoteOFT on EVM chains.
 #[derive(Clone, AnchorSerialize, AnchorDeserialize)]
 pub struct OFTLimits {
     pub min_amount_ld: u64,
-    pub max_amount_ld: u64,
+    pub max_amount_ld: u64, // of the amount received, which the rate limits are charged
 }
@@ -35,7 +35,7 @@ pub struct QuoteSend<'info> {
 
 impl QuoteSend<'_> {
     pub fn apply(ctx: &Context<QuoteSend>, params: &QuoteSendParams) -> Result<MessagingFee> {
-        require!(!ctx.accounts.oft_store.paused, OFTError::Paused);
+        policy::check_paused(&ctx.accounts.oft_store)?;
 
         let (_, amount_received_ld, _) = compute_fee_and_adjust_amount(
             params.amount_ld,
@@ -43,7 +43,7 @@ impl QuoteSend<'_> {
             &ctx.accounts.token_mint,
             ctx.accounts.peer.fee_bps,
         )?;
-        require!(amount_received_ld >= params.min_amount_ld, OFTError::SlippageExceeded);
+        policy::check_slippage(amount_received_ld, params.min_amount_ld)?;
 
         // calling endpoint cpi
         oapp::endpoint_cpi::quote(
@@ -60,11 +60,11 @@ impl QuoteSend<'_> {
                     &params.compose_msg,
                 ),
                 pay_in_lz_token: params.pay_in_lz_token,
-                options: ctx
-                    .accounts
-                    .peer
-                    .enforced_options
-                    .combine_options(&params.compose_msg, &params.options)?,
+                options: ctx.accounts.peer.combine_options(
+                    &params.compose_msg,
+                    &params.options,
+                    params.priority,
+                )?,
             },
         )
     }
@@ -78,40 +78,57 @@ pub fn compute_fee_and_adjust_amount(
 ) -> Result<(u64, u64, u64)> {
     let (amount_sent_ld, amount_received_ld, oft_fee_ld) = if OFTType::Adapter == oft_store.oft_type
     {
-        let mut amount_received_ld =
-            oft_store.remove_dust(get_post_fee_amount_ld(token_mint, amount_ld)?);
+        let amount_received_ld =
+            oft_store.remove_dust(get_post_fee_amount_ld(token_mint, amount_ld)?)?;
         let amount_sent_ld = get_pre_fee_amount_ld(token_mint, amount_received_ld)?;
 
         // remove the oft fee from the amount_received_ld
-        let oft_fee_ld = oft_store.remove_dust(calculate_fee(
-            amount_received_ld,
-            oft_store.default_fee_bps,
-            fee_bps,
-        ));
-        amount_received_ld -= oft_fee_ld;
+        let oft_fee_ld = calculate_fee_ld(oft_store, amount_received_ld, fee_bps)?;
+        let amount_received_ld = amount_received_ld
+            .checked_sub(oft_fee_ld)
+            .ok_or_else(|| error!(OFTError::AmountUnderflow))?;
         (amount_sent_ld, amount_received_ld, oft_fee_ld)
     } else {
         // if it is Native OFT, there is no transfer fee
-        let amount_sent_ld = oft_store.remove_dust(amount_ld);
-        let oft_fee_ld = oft_store.remove_dust(calculate_fee(
-            amount_sent_ld,
-            oft_store.default_fee_bps,
-            fee_bps,
-        ));
-        let amount_received_ld = amount_sent_ld - oft_fee_ld;
+        let amount_sent_ld = oft_store.remove_dust(amount_ld)?;
+        let oft_fee_ld = calculate_fee_ld(oft_store, amount_sent_ld, fee_bps)?;
+        let amount_received_ld = amount_sent_ld
+            .checked_sub(oft_fee_ld)
+            .ok_or_else(|| error!(OFTError::AmountUnderflow))?;
         (amount_sent_ld, amount_received_ld, oft_fee_ld)
     };
     Ok((amount_sent_ld, amount_received_ld, oft_fee_ld))
 }
 
-fn calculate_fee(pre_fee_amount: u64, default_fee_bps: u16, fee_bps: Option<u16>) -> u64 {
+/// The OFT fee in local decimals, rounded to shared decimals per the store's fee_rounding_mode.
+/// `pre_fee_amount` must be dust free.
+pub fn calculate_fee_ld(
+    oft_store: &OFTStore,
+    pre_fee_amount: u64,
+    fee_bps: Option<u16>,
+) -> Result<u64> {
+    simulation::oft_fee_ld(
+        pre_fee_amount,
+        fee_bps.unwrap_or(oft_store.default_fee_bps),
+        oft_store.ld2sd_rate,
+        oft_store.fee_rounding_mode,
+    )
+}
+
+pub fn calculate_fee(
+    pre_fee_amount: u64,
+    default_fee_bps: u16,
+    fee_bps: Option<u16>,
+) -> Result<u64> {
     let final_fee_bps = if let Some(bps) = fee_bps { bps as u128 } else { default_fee_bps as u128 };
     if final_fee_bps == 0 || pre_fee_amount == 0 {
-        0
+        Ok(0)
     } else {
         // pre_fee_amount * final_fee_bps / ONE_IN_BASIS_POINTS
-        let fee = (pre_fee_amount as u128) * final_fee_bps;
-        (fee / ONE_IN_BASIS_POINTS) as u64
+        let fee = (pre_fee_amount as u128)
+            .checked_mul(final_fee_bps)
+            .ok_or_else(|| error!(OFTError::AmountOverflow))?;
+        u64::try_from(fee / ONE_IN_BASIS_POINTS).map_err(|_| error!(OFTError::AmountOverflow))
     }
 }
 
@@ -151,7 +168,7 @@ fn get_pre_fee_amount_ld(token_mint: &InterfaceAccount<Mint>, amount_ld: u64) ->
 // bug reported on token2022: https://github.com/solana-labs/solana-program-library/pull/6704/files
 // copy code over as fix has not been published
 pub const MAX_FEE_BASIS_POINTS: u16 = 10_000;
-const ONE_IN_BASIS_POINTS: u128 = MAX_FEE_BASIS_POINTS as u128;
+pub(crate) const ONE_IN_BASIS_POINTS: u128 = MAX_FEE_BASIS_POINTS as u128;
 fn calculate_pre_fee_amount(fee: &TransferFee, post_fee_amount: u64) -> Option<u64> {
     let maximum_fee = u64::from(fee.maximum_fee);
     let transfer_fee_basis_points = u16::from(fee.transfer_fee_basis_points) as u128;
@@ -190,4 +207,5 @@ pub struct QuoteSendParams {
     pub options: Vec<u8>,
     pub compose_msg: Option<Vec<u8>>,
     pub pay_in_lz_token: bool,
+    pub priority: u8,
 }
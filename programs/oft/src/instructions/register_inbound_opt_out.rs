@@ -0,0 +1,31 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct RegisterInboundOptOut<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + InboundOptOut::INIT_SPACE,
+        seeds = [INBOUND_OPT_OUT_SEED, oft_store.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub inbound_opt_out: Account<'info, InboundOptOut>,
+    pub system_program: Program<'info, System>,
+}
+
+impl RegisterInboundOptOut<'_> {
+    pub fn apply(ctx: &mut Context<RegisterInboundOptOut>) -> Result<()> {
+        ctx.accounts.inbound_opt_out.oft_store = ctx.accounts.oft_store.key();
+        ctx.accounts.inbound_opt_out.owner = ctx.accounts.owner.key();
+        ctx.accounts.inbound_opt_out.bump = ctx.bumps.inbound_opt_out;
+        emit!(InboundOptOutUpdated { owner: ctx.accounts.owner.key(), opted_out: true });
+        Ok(())
+    }
+}
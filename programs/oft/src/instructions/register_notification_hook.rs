@@ -0,0 +1,33 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct RegisterNotificationHook<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + NotificationHook::INIT_SPACE,
+        seeds = [NOTIFICATION_HOOK_SEED, oft_store.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub notification_hook: Account<'info, NotificationHook>,
+    pub system_program: Program<'info, System>,
+}
+
+impl RegisterNotificationHook<'_> {
+    pub fn apply(ctx: &mut Context<RegisterNotificationHook>) -> Result<()> {
+        ctx.accounts.notification_hook.oft_store = ctx.accounts.oft_store.key();
+        ctx.accounts.notification_hook.owner = ctx.accounts.owner.key();
+        ctx.accounts.notification_hook.bump = ctx.bumps.notification_hook;
+        ctx.accounts.notification_hook.count = 0;
+        ctx.accounts.notification_hook.last = None;
+        emit!(NotificationHookUpdated { owner: ctx.accounts.owner.key(), registered: true });
+        Ok(())
+    }
+}
@@ -0,0 +1,27 @@
+use crate::*;
+
+/// Credits already held for the owner stay claimable; only new credits go to its token account.
+#[derive(Accounts)]
+pub struct RemoveInboundOptOut<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        mut,
+        close = owner,
+        seeds = [INBOUND_OPT_OUT_SEED, oft_store.key().as_ref(), owner.key().as_ref()],
+        bump = inbound_opt_out.bump
+    )]
+    pub inbound_opt_out: Account<'info, InboundOptOut>,
+}
+
+impl RemoveInboundOptOut<'_> {
+    pub fn apply(ctx: &mut Context<RemoveInboundOptOut>) -> Result<()> {
+        emit!(InboundOptOutUpdated { owner: ctx.accounts.owner.key(), opted_out: false });
+        Ok(())
+    }
+}
@@ -0,0 +1,26 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct RemoveNotificationHook<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        mut,
+        close = owner,
+        seeds = [NOTIFICATION_HOOK_SEED, oft_store.key().as_ref(), owner.key().as_ref()],
+        bump = notification_hook.bump
+    )]
+    pub notification_hook: Account<'info, NotificationHook>,
+}
+
+impl RemoveNotificationHook<'_> {
+    pub fn apply(ctx: &mut Context<RemoveNotificationHook>) -> Result<()> {
+        emit!(NotificationHookUpdated { owner: ctx.accounts.owner.key(), registered: false });
+        Ok(())
+    }
+}
@@ -0,0 +1,84 @@
+use crate::*;
+
+/// Refills one of a peer's rate limiters to its capacity, along with the message type rate
+/// limiters of that direction, without changing their capacity or refill rate. Meant for after
+/// an incident, once the consumed capacity should not hold transfers back any longer.
+#[derive(Accounts)]
+#[instruction(params: ResetRateLimitConsumptionParams)]
+pub struct ResetRateLimitConsumption<'info> {
+    /// admin, or the store's rate limiter
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump,
+        constraint = oft_store.is_rate_limiter(admin.key) @OFTError::Unauthorized
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        mut,
+        seeds = [PEER_SEED, oft_store.key().as_ref(), &params.remote_eid.to_be_bytes()],
+        bump = peer.bump
+    )]
+    pub peer: Account<'info, PeerConfig>,
+    /// CHECK: the peer's RateLimitState, updated only if it has been created
+    #[account(
+        mut,
+        seeds = [
+            RATE_LIMIT_STATE_SEED,
+            oft_store.key().as_ref(),
+            &params.remote_eid.to_be_bytes()
+        ],
+        bump
+    )]
+    pub rate_limit_state: UncheckedAccount<'info>,
+}
+
+impl ResetRateLimitConsumption<'_> {
+    pub fn apply(
+        ctx: &mut Context<ResetRateLimitConsumption>,
+        params: &ResetRateLimitConsumptionParams,
+    ) -> Result<()> {
+        let peer: &mut PeerConfig = &mut ctx.accounts.peer;
+        // reset the limits in effect, not ones an expired boost or launch stage has left behind
+        policy::refresh_rate_limits(&ctx.accounts.oft_store, peer)?;
+        let (rate_limiter, mode) = match params.direction {
+            RateLimitDirection::Outbound => {
+                (&mut peer.outbound_rate_limiter, peer.outbound_rate_limit_mode)
+            },
+            RateLimitDirection::Inbound => {
+                (&mut peer.inbound_rate_limiter, peer.inbound_rate_limit_mode)
+            },
+        };
+        let flushed_ld = match rate_limiter.as_mut() {
+            Some(rate_limiter) => Self::reset(rate_limiter, mode)?,
+            None => 0,
+        };
+        for entry in peer.msg_type_rate_limiters.iter_mut() {
+            if entry.direction == params.direction {
+                Self::reset(&mut entry.rate_limiter, mode)?;
+            }
+        }
+
+        RateLimitState::sync(&ctx.accounts.rate_limit_state, &ctx.accounts.peer)?;
+        emit!(RateLimitConsumptionReset {
+            remote_eid: params.remote_eid,
+            direction: params.direction,
+            flushed_ld,
+        });
+        Ok(())
+    }
+
+    /// Refills `rate_limiter` to capacity, returning how much of it had been consumed.
+    fn reset(rate_limiter: &mut RateLimiter, mode: RateLimitMode) -> Result<u64> {
+        rate_limiter.refill(mode, 0)?;
+        let flushed_ld = rate_limiter.capacity.saturating_sub(rate_limiter.tokens);
+        rate_limiter.tokens = rate_limiter.capacity;
+        Ok(flushed_ld)
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct ResetRateLimitConsumptionParams {
+    pub remote_eid: u32,
+    pub direction: RateLimitDirection,
+}
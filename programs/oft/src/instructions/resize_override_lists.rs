@@ -0,0 +1,55 @@
+use crate::*;
+
+/// Raises the caps of the override lists chosen at init, reallocating the store at the payer's
+/// expense, e.g. to whitelist a backlog of stuck guids in one go. The caps cannot be lowered.
+#[derive(Accounts)]
+#[instruction(params: ResizeOverrideListsParams)]
+pub struct ResizeOverrideLists<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump,
+        has_one = admin @OFTError::Unauthorized,
+        realloc = OFTStore::space(
+            params.max_rate_limit_overrides,
+            params.max_rate_limit_override_guid_count
+        ),
+        realloc::payer = payer,
+        realloc::zero = false
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    pub system_program: Program<'info, System>,
+}
+
+impl ResizeOverrideLists<'_> {
+    pub fn apply(
+        ctx: &mut Context<ResizeOverrideLists>,
+        params: &ResizeOverrideListsParams,
+    ) -> Result<()> {
+        let oft_store = &mut ctx.accounts.oft_store;
+        require!(
+            params.max_rate_limit_overrides >= oft_store.max_rate_limit_overrides
+                && params.max_rate_limit_overrides <= MAX_RATE_LIMIT_OVERRIDE_COUNT
+                && params.max_rate_limit_override_guid_count
+                    >= oft_store.max_rate_limit_override_guid_count
+                && params.max_rate_limit_override_guid_count <= MAX_RATE_LIMIT_OVERRIDE_GUID_COUNT,
+            OFTError::InvalidOverrideListSize
+        );
+        oft_store.max_rate_limit_overrides = params.max_rate_limit_overrides;
+        oft_store.max_rate_limit_override_guid_count = params.max_rate_limit_override_guid_count;
+        emit!(OverrideListsResized {
+            max_rate_limit_overrides: params.max_rate_limit_overrides,
+            max_rate_limit_override_guid_count: params.max_rate_limit_override_guid_count,
+        });
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct ResizeOverrideListsParams {
+    pub max_rate_limit_overrides: u8,
+    pub max_rate_limit_override_guid_count: u8,
+}
@@ -0,0 +1,212 @@
+use crate::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_2022::spl_token_2022::solana_program::program_option::COption,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+use oapp::endpoint::instructions::SendComposeParams;
+
+#[derive(Accounts)]
+pub struct ResolvePendingCredit<'info> {
+    /// credit verifier or admin, or the recipient for claim_pending_credit
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        mut,
+        seeds = [
+            PENDING_CREDIT_SEED,
+            oft_store.key().as_ref(),
+            pending_credit.to_address.as_ref(),
+            &pending_credit.guid
+        ],
+        bump
+    )]
+    pub pending_credit: Account<'info, PendingCredit>,
+    /// CHECK: the payer of the held lz_receive, refunded the pending credit rent
+    #[account(mut, address = pending_credit.payer)]
+    pub payer: AccountInfo<'info>,
+    /// CHECK: the wallet address to receive the token, refunded with HeldCreditRefund::Recipient
+    #[account(mut, address = pending_credit.to_address @OFTError::InvalidTokenDest)]
+    pub to_address: AccountInfo<'info>,
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = to_address,
+        associated_token::token_program = token_program
+    )]
+    pub token_dest: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        address = oft_store.token_escrow,
+        token::authority = oft_store,
+        token::mint = token_mint,
+        token::token_program = token_program
+    )]
+    pub token_escrow: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        address = oft_store.token_mint,
+        mint::token_program = token_program
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    // Only used for native mint, see LzReceive
+    #[account(constraint = token_mint.mint_authority == COption::Some(mint_authority.key()) @OFTError::InvalidMintAuthority)]
+    pub mint_authority: Option<AccountInfo<'info>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> ResolvePendingCredit<'info> {
+    pub fn apply(
+        ctx: &mut Context<ResolvePendingCredit>,
+        params: &ResolvePendingCreditParams,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.oft_store.is_credit_verifier(&ctx.accounts.signer.key()),
+            OFTError::Unauthorized
+        );
+        require!(ctx.accounts.pending_credit.awaiting_verification, OFTError::InvalidPendingCredit);
+        emit!(PendingCreditResolved {
+            guid: ctx.accounts.pending_credit.guid,
+            approved: params.approve,
+            resolver: ctx.accounts.signer.key(),
+        });
+
+        if !params.approve {
+            // the credit stays held; the admin can still release it later
+            ctx.accounts.pending_credit.rejected = true;
+            return Ok(());
+        }
+
+        ctx.accounts.pending_credit.awaiting_verification = false;
+        if ctx.accounts.pending_credit.awaiting_claim
+            || ctx.accounts.pending_credit.awaiting_capacity
+        {
+            // verified, but still up to the recipient to claim or waiting for rate limit capacity
+            return Ok(());
+        }
+        ctx.accounts.release(ctx.remaining_accounts)
+    }
+
+    /// Releases a credit held because the recipient opted out, once it is verified if needed, or
+    /// the vested part of a split credit.
+    pub fn apply_claim(ctx: &mut Context<ResolvePendingCredit>) -> Result<()> {
+        require!(
+            ctx.accounts.signer.key() == ctx.accounts.pending_credit.to_address,
+            OFTError::Unauthorized
+        );
+        if let Some(vesting) = ctx.accounts.pending_credit.vesting.clone() {
+            return Self::claim_vested(ctx, vesting);
+        }
+        require!(
+            ctx.accounts.pending_credit.awaiting_claim
+                && !ctx.accounts.pending_credit.awaiting_verification
+                && !ctx.accounts.pending_credit.awaiting_capacity,
+            OFTError::InvalidPendingCredit
+        );
+        ctx.accounts.release(ctx.remaining_accounts)
+    }
+
+    /// Credits the held amount, sends its compose message, if any, with `remaining_accounts` and
+    /// closes the pending credit.
+    pub fn release(&mut self, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        let guid = self.pending_credit.guid;
+        let amount_received_ld = credit(
+            &mut self.oft_store,
+            &self.token_escrow,
+            &self.token_dest,
+            &self.token_mint,
+            &self.mint_authority,
+            &self.token_program,
+            self.pending_credit.amount_ld,
+        )?;
+
+        if let Some(message) = self.pending_credit.compose_msg.clone() {
+            let oft_store_seed = self.token_escrow.key();
+            let seeds: &[&[u8]] = &[OFT_SEED, oft_store_seed.as_ref(), &[self.oft_store.bump]];
+            oapp::endpoint_cpi::send_compose(
+                self.oft_store.endpoint_program,
+                self.oft_store.key(),
+                remaining_accounts,
+                seeds,
+                SendComposeParams {
+                    to: self.to_address.key(),
+                    guid,
+                    index: 0, // only 1 compose msg per lzReceive
+                    message: compose_msg_codec::encode(
+                        self.pending_credit.nonce,
+                        self.pending_credit.src_eid,
+                        amount_received_ld,
+                        &message,
+                    ),
+                },
+            )?;
+        }
+
+        emit!(OFTReceived {
+            guid,
+            src_eid: self.pending_credit.src_eid,
+            to: self.to_address.key(),
+            amount_received_ld,
+            amount_sd: self.oft_store.ld2sd(self.pending_credit.amount_ld)?,
+            message_hash: self.pending_credit.message_hash,
+        });
+
+        self.close()
+    }
+
+    /// Credits the tranches vested since the last claim and closes the pending credit once all
+    /// of it has been claimed.
+    fn claim_vested(ctx: &mut Context<ResolvePendingCredit>, mut vesting: Vesting) -> Result<()> {
+        let amount_ld = ctx.accounts.pending_credit.amount_ld;
+        let vested_ld = vesting.vested_ld(amount_ld, Clock::get()?.unix_timestamp);
+        require!(vested_ld > vesting.claimed_ld, OFTError::NothingVested);
+        let amount_received_ld = credit(
+            &mut ctx.accounts.oft_store,
+            &ctx.accounts.token_escrow,
+            &ctx.accounts.token_dest,
+            &ctx.accounts.token_mint,
+            &ctx.accounts.mint_authority,
+            &ctx.accounts.token_program,
+            vested_ld - vesting.claimed_ld,
+        )?;
+        vesting.claimed_ld = vested_ld;
+
+        emit!(VestedCreditClaimed {
+            guid: ctx.accounts.pending_credit.guid,
+            to: ctx.accounts.to_address.key(),
+            amount_received_ld,
+            remaining_ld: amount_ld - vested_ld,
+        });
+        if vested_ld < amount_ld {
+            ctx.accounts.pending_credit.vesting = Some(vesting);
+            return Ok(());
+        }
+        ctx.accounts.close()
+    }
+
+    fn close(&mut self) -> Result<()> {
+        let guid = self.pending_credit.guid;
+        // the rent paid by the executor, plus anything sent to the address before it was held
+        let refund_to = match self.oft_store.held_credit_refund {
+            HeldCreditRefund::Payer => self.payer.to_account_info(),
+            HeldCreditRefund::Recipient => self.to_address.to_account_info(),
+        };
+        emit!(HeldCreditRefunded {
+            guid,
+            to: refund_to.key(),
+            lamports: self.pending_credit.to_account_info().lamports(),
+        });
+        self.pending_credit.close(refund_to)
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct ResolvePendingCreditParams {
+    pub approve: bool,
+}
@@ -8,6 +8,9 @@ use oapp::endpoint::{instructions::SendParams as EndpointSendParams, MessagingRe
 #[derive(Accounts)]
 #[instruction(params: SendParams)]
 pub struct Send<'info> {
+    /// The debit authority owning token_source, a program's PDA for sends made by CPI. Rate limit
+    /// overrides, flow tags and the sender rate limit are matched against it, never against the
+    /// transaction's fee payer.
     pub signer: Signer<'info>,
     #[account(
         mut,
@@ -47,6 +50,30 @@ pub struct Send<'info> {
     )]
     pub token_mint: InterfaceAccount<'info, Mint>,
     pub token_program: Interface<'info, TokenInterface>,
+    /// CHECK: the SendRecord of params.idempotency_key, only needed for sends with a key
+    #[account(
+        mut,
+        seeds = [
+            SEND_RECORD_SEED,
+            oft_store.key().as_ref(),
+            signer.key().as_ref(),
+            &params.idempotency_key.unwrap_or_default()
+        ],
+        bump
+    )]
+    pub send_record: Option<UncheckedAccount<'info>>,
+    /// CHECK: the TransferReceipt of the send's guid, only needed for sends of at least the
+    /// store's transfer_receipts.min_amount_ld
+    #[account(mut)]
+    pub transfer_receipt: Option<UncheckedAccount<'info>>,
+    /// CHECK: the signer's SenderRateLimit, only needed while the store has a sender_rate_limit
+    #[account(
+        mut,
+        seeds = [SENDER_RATE_LIMIT_SEED, oft_store.key().as_ref(), signer.key().as_ref()],
+        bump
+    )]
+    pub sender_rate_limit: Option<UncheckedAccount<'info>>,
+    pub system_program: Option<Program<'info, System>>,
 }
 
 impl Send<'_> {
@@ -54,27 +81,58 @@ impl Send<'_> {
         ctx: &mut Context<Send>,
         params: &SendParams,
     ) -> Result<(MessagingReceipt, OFTReceipt)> {
-        require!(!ctx.accounts.oft_store.paused, OFTError::Paused);
+        compute_profile::checkpoint("send:start");
+        policy::check_send(&ctx.accounts.oft_store, &ctx.accounts.peer, params.dst_eid)?;
+        let privileged_flow = policy::check_flow_tag(
+            &ctx.accounts.oft_store,
+            &ctx.accounts.signer.key(),
+            params.flow_tag,
+        )?;
+        policy::check_recipient(&ctx.accounts.peer, &params.to)?;
+        if params.idempotency_key.is_some() {
+            let send_record = ctx
+                .accounts
+                .send_record
+                .as_ref()
+                .ok_or_else(|| error!(OFTError::InvalidSendRecord))?;
+            SendRecord::check(send_record, Clock::get()?.unix_timestamp)?;
+        }
 
         let (amount_sent_ld, amount_received_ld, oft_fee_ld) = compute_fee_and_adjust_amount(
             params.amount_ld,
             &ctx.accounts.oft_store,
             &ctx.accounts.token_mint,
-            ctx.accounts.peer.fee_bps,
+            policy::send_fee_bps(
+                &ctx.accounts.oft_store,
+                &ctx.accounts.peer,
+                &ctx.accounts.signer.key(),
+                privileged_flow,
+            ),
         )?;
-        require!(amount_received_ld >= params.min_amount_ld, OFTError::SlippageExceeded);
-
-        // GMX only needs an inbound rate limiter.
-        // If the sender is in rate_limit_override then we do NOT want to refill the rate limiter.
-        if let Some(rate_limiter) = ctx.accounts.peer.inbound_rate_limiter.as_mut() {
-            if !ctx.accounts.oft_store.is_rate_limit_override(&ctx.accounts.signer.key()) {
-                rate_limiter.refill(amount_received_ld)?;
-            }
+        policy::check_slippage(amount_received_ld, params.min_amount_ld)?;
+        policy::check_probation(&ctx.accounts.oft_store, &ctx.accounts.peer, amount_received_ld)?;
+        policy::consume_outbound_rate_limit(
+            &mut ctx.accounts.oft_store,
+            &mut ctx.accounts.peer,
+            params.dst_eid,
+            &ctx.accounts.signer.key(),
+            msg_codec::msg_type(params.compose_msg.is_some()),
+            amount_received_ld,
+        )?;
+        if let Some(config) = ctx.accounts.oft_store.sender_rate_limit.clone() {
+            Self::consume_sender_rate_limit(ctx, &config, amount_received_ld)?;
         }
-        
+        policy::refill_rate_limit(
+            &ctx.accounts.oft_store,
+            &mut ctx.accounts.peer,
+            &ctx.accounts.signer.key(),
+            amount_received_ld,
+        )?;
+        compute_profile::checkpoint("send:policy");
+
         if ctx.accounts.oft_store.oft_type == OFTType::Adapter {
             // transfer all tokens to escrow with fee
-            ctx.accounts.oft_store.tvl_ld += amount_received_ld;
+            ctx.accounts.oft_store.increase_tvl(amount_received_ld)?;
             token_interface::transfer_checked(
                 CpiContext::new(
                     ctx.accounts.token_program.to_account_info(),
@@ -100,7 +158,9 @@ impl Send<'_> {
                         authority: ctx.accounts.signer.to_account_info(),
                     },
                 ),
-                amount_sent_ld - oft_fee_ld,
+                amount_sent_ld
+                    .checked_sub(oft_fee_ld)
+                    .ok_or_else(|| error!(OFTError::AmountUnderflow))?,
             )?;
 
             // transfer fee to escrow
@@ -120,13 +180,26 @@ impl Send<'_> {
                 )?;
             }
         }
+        ctx.accounts.oft_store.accrue_fees(oft_fee_ld)?;
+        compute_profile::checkpoint("send:token");
 
         // send message to endpoint
         require!(
             ctx.accounts.oft_store.key() == ctx.remaining_accounts[1].key(),
             OFTError::InvalidSender
         );
-        let amount_sd = ctx.accounts.oft_store.ld2sd(amount_received_ld);
+        let amount_sd = ctx.accounts.oft_store.ld2sd(amount_received_ld)?;
+        ctx.accounts.peer.outflow_stats.record(Clock::get()?.unix_timestamp, amount_received_ld);
+        if let Some(in_flight) = ctx.accounts.peer.in_flight.as_mut() {
+            in_flight.record_sent(ctx.accounts.oft_store.sd2ld(amount_sd)?);
+        }
+        let message = msg_codec::encode(
+            params.to,
+            amount_sd,
+            ctx.accounts.signer.key(),
+            &params.compose_msg,
+        );
+        let message_hash = msg_codec::hash(&message);
         let msg_receipt = oapp::endpoint_cpi::send(
             ctx.accounts.oft_store.endpoint_program,
             ctx.accounts.oft_store.key(),
@@ -135,32 +208,132 @@ impl Send<'_> {
             EndpointSendParams {
                 dst_eid: params.dst_eid,
                 receiver: ctx.accounts.peer.peer_address,
-                message: msg_codec::encode(
-                    params.to,
-                    amount_sd,
-                    ctx.accounts.signer.key(),
+                message,
+                options: ctx.accounts.peer.combine_options(
                     &params.compose_msg,
-                ),
-                options: ctx
-                    .accounts
-                    .peer
-                    .enforced_options
-                    .combine_options(&params.compose_msg, &params.options)?,
+                    &params.options,
+                    params.priority,
+                )?,
                 native_fee: params.native_fee,
                 lz_token_fee: params.lz_token_fee,
             },
         )?;
+        compute_profile::checkpoint("send:endpoint");
 
         emit_cpi!(OFTSent {
             guid: msg_receipt.guid,
             dst_eid: params.dst_eid,
             from: ctx.accounts.token_source.key(),
             amount_sent_ld,
-            amount_received_ld
+            amount_received_ld,
+            amount_sd,
+            message_hash,
+            flow_tag: params.flow_tag,
+            outflow: ctx.accounts.peer.outflow_stats.clone(),
         });
 
+        if let Some(idempotency_key) = params.idempotency_key {
+            Self::record(ctx, idempotency_key, msg_receipt.guid)?;
+        }
+        if ctx.accounts.oft_store.requires_transfer_receipt(amount_received_ld) {
+            Self::record_transfer(ctx, params, msg_receipt.guid, amount_received_ld)?;
+        }
+        compute_profile::checkpoint("send:records");
+
         Ok((msg_receipt, OFTReceipt { amount_sent_ld, amount_received_ld }))
     }
+
+    /// Charges the signer's own outbound limiter, which the signer pays for on its first send.
+    /// Senders overridden outbound skip it as they skip the peer's.
+    fn consume_sender_rate_limit(
+        ctx: &Context<Send>,
+        config: &SenderRateLimitConfig,
+        amount_ld: u64,
+    ) -> Result<()> {
+        let sender = ctx.accounts.signer.key();
+        if ctx.accounts.oft_store.is_rate_limit_override(&sender, RateLimitDirection::Outbound)? {
+            return Ok(());
+        }
+        let (sender_rate_limit, system_program) =
+            match (ctx.accounts.sender_rate_limit.as_ref(), ctx.accounts.system_program.as_ref()) {
+                (Some(sender_rate_limit), Some(system_program)) => {
+                    (sender_rate_limit, system_program)
+                },
+                _ => return err!(OFTError::InvalidSenderRateLimitAccount),
+            };
+        let mut state =
+            SenderRateLimit::load(sender_rate_limit, ctx.accounts.oft_store.key(), sender, config)?;
+        state.try_consume(config, amount_ld)?;
+        state.save(
+            sender_rate_limit,
+            &ctx.accounts.signer.to_account_info(),
+            &system_program.to_account_info(),
+            ctx.bumps.sender_rate_limit,
+        )
+    }
+
+    /// The signer pays for the record; it is writable as the endpoint payer.
+    fn record(ctx: &Context<Send>, idempotency_key: [u8; 32], guid: [u8; 32]) -> Result<()> {
+        let (send_record, system_program) =
+            match (ctx.accounts.send_record.as_ref(), ctx.accounts.system_program.as_ref()) {
+                (Some(send_record), Some(system_program)) => (send_record, system_program),
+                _ => return err!(OFTError::InvalidSendRecord),
+            };
+        SendRecord {
+            oft_store: ctx.accounts.oft_store.key(),
+            sender: ctx.accounts.signer.key(),
+            idempotency_key,
+            guid,
+            expires_at: Clock::get()?.unix_timestamp + SEND_RECORD_TTL_SECONDS,
+        }
+        .save(
+            send_record,
+            &ctx.accounts.signer.to_account_info(),
+            &system_program.to_account_info(),
+            ctx.bumps.send_record,
+        )
+    }
+
+    /// The guid is only known once the endpoint has sent the message, so the receipt address is
+    /// checked here instead of by a seeds constraint.
+    fn record_transfer(
+        ctx: &Context<Send>,
+        params: &SendParams,
+        guid: [u8; 32],
+        amount_ld: u64,
+    ) -> Result<()> {
+        let oft_store = ctx.accounts.oft_store.key();
+        let (address, bump) = TransferReceipt::address(&oft_store, &guid);
+        let (transfer_receipt, system_program) = match (
+            ctx.accounts.transfer_receipt.as_ref(),
+            ctx.accounts.system_program.as_ref(),
+        ) {
+            (Some(transfer_receipt), Some(system_program)) if transfer_receipt.key() == address => {
+                (transfer_receipt, system_program)
+            },
+            _ => return err!(OFTError::InvalidTransferReceipt),
+        };
+        let retention_seconds =
+            ctx.accounts.oft_store.transfer_receipts.as_ref().map_or(0, |p| p.retention_seconds);
+        let clock = Clock::get()?;
+        TransferReceipt {
+            oft_store,
+            guid,
+            remote_eid: params.dst_eid,
+            outbound: true,
+            recipient: params.to,
+            amount_ld,
+            slot: clock.slot,
+            payer: ctx.accounts.signer.key(),
+            closable_at: clock.unix_timestamp.saturating_add(retention_seconds),
+        }
+        .init(
+            transfer_receipt,
+            &ctx.accounts.signer.to_account_info(),
+            &system_program.to_account_info(),
+            bump,
+        )
+    }
 }
 
 #[derive(Clone, AnchorSerialize, AnchorDeserialize)]
@@ -173,4 +346,8 @@ pub struct SendParams {
     pub compose_msg: Option<Vec<u8>>,
     pub native_fee: u64,
     pub lz_token_fee: u64,
+    pub flow_tag: u8, // FLOW_TAG_USER unless the transfer is tagged, see policy::check_flow_tag
+    pub priority: u8, // PRIORITY_STANDARD or one of the peer's priority_tiers
+    // Rejects a second send with the same key within SEND_RECORD_TTL_SECONDS, see SendRecord
+    pub idempotency_key: Option<[u8; 32]>,
 }
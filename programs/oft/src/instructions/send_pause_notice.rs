@@ -0,0 +1,65 @@
+use crate::*;
+use oapp::endpoint::instructions::SendParams as EndpointSendParams;
+
+/// Tells a peer that this store is paused, so one incident response can reach every chain. Send
+/// one to each peer after set_pause. Solana peers pause too if they honor pause notices and
+/// otherwise emit PauseNoticeReceived; the EVM adapters have no pause and only emit it.
+#[derive(Accounts)]
+#[instruction(params: SendPauseNoticeParams)]
+pub struct SendPauseNotice<'info> {
+    pub pauser: Signer<'info>,
+    #[account(
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump,
+        constraint = oft_store.pauser == Some(pauser.key()) @OFTError::Unauthorized
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        seeds = [
+            PEER_SEED,
+            oft_store.key().as_ref(),
+            &params.dst_eid.to_be_bytes()
+        ],
+        bump = peer.bump
+    )]
+    pub peer: Account<'info, PeerConfig>,
+}
+
+impl SendPauseNotice<'_> {
+    pub fn apply(ctx: &mut Context<SendPauseNotice>, params: &SendPauseNoticeParams) -> Result<()> {
+        let oft_store = &ctx.accounts.oft_store;
+        require!(oft_store.paused, OFTError::NotPaused);
+        require!(oft_store.key() == ctx.remaining_accounts[1].key(), OFTError::InvalidSender);
+
+        let sent_at = Clock::get()?.unix_timestamp;
+        let msg_receipt = oapp::endpoint_cpi::send(
+            oft_store.endpoint_program,
+            oft_store.key(),
+            ctx.remaining_accounts,
+            &[OFT_SEED, oft_store.token_escrow.as_ref(), &[oft_store.bump]],
+            EndpointSendParams {
+                dst_eid: params.dst_eid,
+                receiver: ctx.accounts.peer.peer_address,
+                message: msg_codec::encode_pause_notice(oft_store.key().to_bytes(), sent_at),
+                options: ctx
+                    .accounts
+                    .peer
+                    .enforced_options
+                    .combine_options(&None, &params.options)?,
+                native_fee: params.native_fee,
+                lz_token_fee: params.lz_token_fee,
+            },
+        )?;
+
+        emit!(PauseNoticeSent { remote_eid: params.dst_eid, guid: msg_receipt.guid, sent_at });
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SendPauseNoticeParams {
+    pub dst_eid: u32,
+    pub options: Vec<u8>,
+    pub native_fee: u64,
+    pub lz_token_fee: u64,
+}
@@ -0,0 +1,40 @@
+use crate::*;
+use oapp::endpoint::MessagingReceipt;
+
+/// Same as `send`, but the LayerZero native fee is paid from the store's FeeVault so treasury
+/// operations do not depend on the signer's SOL balance.
+#[derive(Accounts)]
+#[instruction(params: SendParams)]
+pub struct SendWithFeeVault<'info> {
+    pub send: Send<'info>,
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, send.oft_store.key().as_ref()],
+        bump = fee_vault.bump,
+        constraint = send.oft_store.is_fee_vault_sender(&send.signer.key()) @OFTError::Unauthorized
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+}
+
+impl<'info> SendWithFeeVault<'info> {
+    pub fn apply(
+        ctx: &mut Context<'_, '_, '_, 'info, SendWithFeeVault<'info>>,
+        params: &SendParams,
+    ) -> Result<(MessagingReceipt, OFTReceipt)> {
+        // The signer is also the endpoint payer in the remaining accounts, so it is writable and
+        // pays the endpoint out of the lamports moved here.
+        FeeVault::transfer_lamports(
+            &ctx.accounts.fee_vault.to_account_info(),
+            &ctx.accounts.send.signer.to_account_info(),
+            params.native_fee,
+        )?;
+
+        let mut send_ctx = Context::new(
+            ctx.program_id,
+            &mut ctx.accounts.send,
+            ctx.remaining_accounts,
+            std::mem::take(&mut ctx.bumps.send),
+        );
+        Send::apply(&mut send_ctx, params)
+    }
+}
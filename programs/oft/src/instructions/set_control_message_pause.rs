@@ -0,0 +1,35 @@
+use crate::*;
+
+/// Pauses or unpauses one type of control message in lz_receive, with the same pauser and
+/// unpauser as set_pause, so a scare about one of them does not halt user transfers. Paused
+/// messages fail and stay in the endpoint until they are unpaused and retried.
+#[derive(Accounts)]
+#[instruction(params: SetControlMessagePauseParams)]
+pub struct SetControlMessagePause<'info> {
+    /// pauser or unpauser
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump,
+        constraint = is_valid_signer(signer.key(), &oft_store, params.paused) @OFTError::Unauthorized
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+}
+
+impl SetControlMessagePause<'_> {
+    pub fn apply(
+        ctx: &mut Context<SetControlMessagePause>,
+        params: &SetControlMessagePauseParams,
+    ) -> Result<()> {
+        ctx.accounts.oft_store.set_control_message_paused(params.message, params.paused);
+        emit!(ControlMessagePauseUpdated { message: params.message, paused: params.paused });
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SetControlMessagePauseParams {
+    pub message: ControlMessage,
+    pub paused: bool,
+}
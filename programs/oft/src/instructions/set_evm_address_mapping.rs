@@ -0,0 +1,41 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct SetEvmAddressMapping<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump,
+        has_one = admin @OFTError::Unauthorized
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + EvmAddressMap::INIT_SPACE,
+        seeds = [EVM_ADDRESS_MAP_SEED, oft_store.key().as_ref()],
+        bump
+    )]
+    pub evm_address_map: Account<'info, EvmAddressMap>,
+    pub system_program: Program<'info, System>,
+}
+
+impl SetEvmAddressMapping<'_> {
+    pub fn apply(
+        ctx: &mut Context<SetEvmAddressMapping>,
+        params: &SetEvmAddressMappingParams,
+    ) -> Result<()> {
+        ctx.accounts.evm_address_map.oft_store = ctx.accounts.oft_store.key();
+        ctx.accounts.evm_address_map.bump = ctx.bumps.evm_address_map;
+        ctx.accounts.evm_address_map.set(params.address, params.evm_address)?;
+        emit!(EvmAddressMappingSet { address: params.address, evm_address: params.evm_address });
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SetEvmAddressMappingParams {
+    pub address: Pubkey,
+    pub evm_address: Option<[u8; 20]>, // None removes the mapping
+}
@@ -0,0 +1,60 @@
+use crate::*;
+
+/// Sets the inbound rate limit of one peer, consumed by lz_receive. Same as
+/// PeerConfigParam::InboundRateLimit, but also keeps the peer's RateLimitState in sync.
+/// Receivers and guids on the store's rate limit override lists bypass it.
+#[derive(Accounts)]
+#[instruction(params: SetInboundRateLimitParams)]
+pub struct SetInboundRateLimit<'info> {
+    /// admin, or the store's rate limiter
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump,
+        constraint = oft_store.is_rate_limiter(admin.key) @OFTError::Unauthorized
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        mut,
+        seeds = [PEER_SEED, oft_store.key().as_ref(), &params.remote_eid.to_be_bytes()],
+        bump = peer.bump
+    )]
+    pub peer: Account<'info, PeerConfig>,
+    /// CHECK: the peer's RateLimitState, updated only if it has been created
+    #[account(
+        mut,
+        seeds = [
+            RATE_LIMIT_STATE_SEED,
+            oft_store.key().as_ref(),
+            &params.remote_eid.to_be_bytes()
+        ],
+        bump
+    )]
+    pub rate_limit_state: UncheckedAccount<'info>,
+}
+
+impl SetInboundRateLimit<'_> {
+    pub fn apply(
+        ctx: &mut Context<SetInboundRateLimit>,
+        params: &SetInboundRateLimitParams,
+    ) -> Result<()> {
+        let mode = ctx.accounts.peer.inbound_rate_limit_mode;
+        SetPeerConfig::update_rate_limiter(
+            &mut ctx.accounts.peer.inbound_rate_limiter,
+            mode,
+            &params.rate_limit,
+        )?;
+        RateLimitState::sync(&ctx.accounts.rate_limit_state, &ctx.accounts.peer)?;
+        emit!(InboundRateLimitUpdated {
+            src_eid: params.remote_eid,
+            rate_limiter: ctx.accounts.peer.inbound_rate_limiter.clone(),
+        });
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SetInboundRateLimitParams {
+    pub remote_eid: u32,
+    pub rate_limit: Option<RateLimitParams>, // None removes the limit
+}
@@ -23,10 +23,10 @@ impl SetOFTConfig<'_> {
                 let oft_store_seed = ctx.accounts.oft_store.token_escrow.key();
                 let seeds: &[&[u8]] =
                     &[OFT_SEED, &oft_store_seed.to_bytes(), &[ctx.accounts.oft_store.bump]];
-                let _ = oapp::endpoint_cpi::set_delegate(
+                oapp::endpoint_cpi::set_delegate(
                     ctx.accounts.oft_store.endpoint_program,
                     ctx.accounts.oft_store.key(),
-                    &ctx.remaining_accounts,
+                    ctx.remaining_accounts,
                     seeds,
                     SetDelegateParams { delegate },
                 )?;
@@ -44,6 +44,179 @@ impl SetOFTConfig<'_> {
             SetOFTConfigParams::Unpauser(unpauser) => {
                 ctx.accounts.oft_store.unpauser = unpauser;
             },
+            SetOFTConfigParams::LargeCreditThreshold(threshold_ld) => {
+                ctx.accounts.oft_store.large_credit_threshold_ld = threshold_ld;
+            },
+            SetOFTConfigParams::CreditVerifier(credit_verifier) => {
+                ctx.accounts.oft_store.credit_verifier = credit_verifier;
+            },
+            SetOFTConfigParams::OverrideManager(override_manager) => {
+                ctx.accounts.oft_store.override_manager = override_manager;
+            },
+            SetOFTConfigParams::OverrideApprovalThreshold(threshold_ld) => {
+                ctx.accounts.oft_store.override_approval_threshold_ld = threshold_ld;
+            },
+            SetOFTConfigParams::FeeVaultSenders(senders) => {
+                require!(
+                    senders.len() <= MAX_FEE_VAULT_SENDERS.into(),
+                    OFTError::FeeVaultSendersListFull
+                );
+                ctx.accounts.oft_store.fee_vault_senders = senders;
+            },
+            SetOFTConfigParams::CrankBounty(bounty_ld) => {
+                ctx.accounts.oft_store.crank_bounty_ld = bounty_ld;
+            },
+            SetOFTConfigParams::DustToFees(dust_to_fees) => {
+                ctx.accounts.oft_store.dust_to_fees = dust_to_fees;
+            },
+            SetOFTConfigParams::FeeRoundingMode(mode) => {
+                ctx.accounts.oft_store.fee_rounding_mode = mode;
+            },
+            SetOFTConfigParams::HeldCreditRefund(refund) => {
+                ctx.accounts.oft_store.held_credit_refund = refund;
+            },
+            SetOFTConfigParams::Metadata(metadata) => {
+                ctx.accounts.oft_store.metadata = metadata;
+            },
+            SetOFTConfigParams::OverrideCosigners(cosigners) => {
+                require!(
+                    cosigners.len() <= MAX_OVERRIDE_COSIGNERS,
+                    OFTError::OverrideCosignersListFull
+                );
+                ctx.accounts.oft_store.override_cosigners = cosigners;
+            },
+            SetOFTConfigParams::OverrideCosignThreshold(threshold_ld) => {
+                ctx.accounts.oft_store.override_cosign_threshold_ld = threshold_ld;
+            },
+            SetOFTConfigParams::TestMode(test_mode) => {
+                require!(
+                    !test_mode || cfg!(feature = "test-mode"),
+                    OFTError::TestModeUnavailable
+                );
+                ctx.accounts.oft_store.test_mode = test_mode;
+            },
+            SetOFTConfigParams::TvlAlert(tvl_alert) => {
+                if let Some(alert) = &tvl_alert {
+                    require!(
+                        alert.drop_bps > 0
+                            && alert.drop_bps <= MAX_FEE_BASIS_POINTS
+                            && alert.window_seconds > 0,
+                        OFTError::InvalidTvlAlert
+                    );
+                }
+                ctx.accounts.oft_store.tvl_alert = tvl_alert;
+                // start a new window under the new alert
+                ctx.accounts.oft_store.tvl_watermarks = TvlWatermarks::default();
+            },
+            SetOFTConfigParams::AllowedComposers(composers) => {
+                require!(
+                    composers.len() <= MAX_ALLOWED_COMPOSERS,
+                    OFTError::AllowedComposersListFull
+                );
+                ctx.accounts.oft_store.allowed_composers = composers;
+            },
+            SetOFTConfigParams::TransferReceipts(transfer_receipts) => {
+                if let Some(policy) = &transfer_receipts {
+                    require!(policy.retention_seconds >= 0, OFTError::InvalidTransferReceiptPolicy);
+                }
+                ctx.accounts.oft_store.transfer_receipts = transfer_receipts;
+            },
+            SetOFTConfigParams::AllowedDstEids(dst_eids) => {
+                require!(
+                    dst_eids.len() <= MAX_ALLOWED_DST_EIDS,
+                    OFTError::AllowedDstEidsListFull
+                );
+                ctx.accounts.oft_store.allowed_dst_eids = dst_eids;
+            },
+            SetOFTConfigParams::ZeroAmountPolicy(zero_amount_policy) => {
+                ctx.accounts.oft_store.zero_amount_policy = zero_amount_policy;
+            },
+            SetOFTConfigParams::RateLimiter(rate_limiter) => {
+                ctx.accounts.oft_store.rate_limiter = rate_limiter;
+            },
+            SetOFTConfigParams::FeeCollector(fee_collector) => {
+                ctx.accounts.oft_store.fee_collector = fee_collector;
+                emit!(FeeCollectorUpdated { fee_collector });
+            },
+            SetOFTConfigParams::Treasury(treasury) => {
+                ctx.accounts.oft_store.treasury = treasury;
+                emit!(TreasuryUpdated { treasury });
+            },
+            SetOFTConfigParams::SenderRateLimit(sender_rate_limit) => {
+                if let Some(config) = &sender_rate_limit {
+                    require!(config.capacity > 0, OFTError::InvalidSenderRateLimit);
+                }
+                ctx.accounts.oft_store.sender_rate_limit = sender_rate_limit;
+            },
+            SetOFTConfigParams::LaunchSchedule(stages) => {
+                LaunchStage::validate_schedule(&stages)?;
+                ctx.accounts.oft_store.launch_schedule = stages;
+            },
+            SetOFTConfigParams::OverridesConsumeCapacity(overrides_consume_capacity) => {
+                ctx.accounts.oft_store.overrides_consume_capacity = overrides_consume_capacity;
+            },
+            SetOFTConfigParams::QueueOverLimitCredits(queue_over_limit_credits) => {
+                ctx.accounts.oft_store.queue_over_limit_credits = queue_over_limit_credits;
+            },
+            SetOFTConfigParams::OverrideApprover(override_approver) => {
+                Self::check_approver(ctx)?;
+                ctx.accounts.oft_store.override_approver = override_approver;
+                emit!(OverrideApproverUpdated { override_approver });
+            },
+            SetOFTConfigParams::GovernanceEid(governance_eid) => {
+                // the governance peer can add guid overrides, so the approver has to agree
+                Self::check_approver(ctx)?;
+                ctx.accounts.oft_store.governance_eid = governance_eid;
+                emit!(GovernanceEidUpdated { governance_eid });
+            },
+            SetOFTConfigParams::HonorPauseNotices(honor_pause_notices) => {
+                ctx.accounts.oft_store.honor_pause_notices = honor_pause_notices;
+                emit!(HonorPauseNoticesUpdated { honor_pause_notices });
+            },
+            SetOFTConfigParams::FeeStream(fee_stream) => {
+                let fee_stream = match fee_stream {
+                    Some(params) => {
+                        require!(
+                            params.min_interval_seconds > 0
+                                && params.min_interval_seconds <= params.period_seconds,
+                            OFTError::InvalidFeeStream
+                        );
+                        // fees accrued so far start vesting now
+                        Some(FeeStream {
+                            recipient: params.recipient,
+                            period_seconds: params.period_seconds,
+                            min_interval_seconds: params.min_interval_seconds,
+                            last_paid_at: Clock::get()?.unix_timestamp,
+                        })
+                    },
+                    None => None,
+                };
+                ctx.accounts.oft_store.fee_stream = fee_stream;
+            },
+            SetOFTConfigParams::ProbationPolicy(probation_policy) => {
+                if let Some(policy) = &probation_policy {
+                    require!(
+                        policy.probation_seconds >= 0
+                            && policy.hold_above_ld <= policy.max_amount_ld,
+                        OFTError::InvalidProbationPolicy
+                    );
+                }
+                ctx.accounts.oft_store.probation_policy = probation_policy;
+            },
+        }
+        Ok(())
+    }
+
+    /// The current override approver, if any, signs as the first remaining account, so that the
+    /// admin cannot lift the approval on their own.
+    fn check_approver(ctx: &Context<SetOFTConfig>) -> Result<()> {
+        if let Some(current) = ctx.accounts.oft_store.override_approver {
+            require!(
+                ctx.remaining_accounts
+                    .first()
+                    .map_or(false, |info| info.is_signer && info.key() == current),
+                OFTError::OverrideApproverRequired
+            );
         }
         Ok(())
     }
@@ -57,4 +230,41 @@ pub enum SetOFTConfigParams {
     Paused(bool),
     Pauser(Option<Pubkey>),
     Unpauser(Option<Pubkey>),
+    LargeCreditThreshold(Option<u64>),
+    CreditVerifier(Option<Pubkey>),
+    OverrideManager(Option<Pubkey>),
+    OverrideApprovalThreshold(Option<u64>),
+    FeeVaultSenders(Vec<Pubkey>),
+    CrankBounty(Option<u64>),
+    DustToFees(bool),
+    FeeRoundingMode(RoundingMode),
+    ProbationPolicy(Option<ProbationPolicy>),
+    HeldCreditRefund(HeldCreditRefund),
+    Metadata(Option<OFTMetadata>),
+    OverrideCosigners(Vec<Pubkey>),
+    OverrideCosignThreshold(Option<u64>),
+    TestMode(bool),
+    TvlAlert(Option<TvlAlert>),
+    AllowedComposers(Vec<Pubkey>),
+    TransferReceipts(Option<TransferReceiptPolicy>),
+    AllowedDstEids(Vec<u32>),
+    ZeroAmountPolicy(ZeroAmountPolicy),
+    FeeStream(Option<FeeStreamParams>),
+    RateLimiter(Option<Pubkey>),
+    SenderRateLimit(Option<SenderRateLimitConfig>),
+    LaunchSchedule(Vec<LaunchStage>), // empty ends the launch limits
+    OverridesConsumeCapacity(bool),
+    QueueOverLimitCredits(bool),
+    OverrideApprover(Option<Pubkey>), // signed by the current approver, if any
+    GovernanceEid(Option<u32>),       // signed by the override approver, if any
+    FeeCollector(Option<Pubkey>),
+    Treasury(Option<Pubkey>), // a token account of the mint, see withdraw_fees
+    HonorPauseNotices(bool),
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct FeeStreamParams {
+    pub recipient: Pubkey,
+    pub period_seconds: i64,
+    pub min_interval_seconds: i64,
 }
@@ -0,0 +1,48 @@
+use crate::*;
+
+/// Sets the outbound rate limit of one peer, consumed by send, so each destination can be capped
+/// on its own. Senders on the store's rate limit override list bypass it, and transfers received
+/// from the peer free up capacity.
+#[derive(Accounts)]
+#[instruction(params: SetOutboundRateLimitParams)]
+pub struct SetOutboundRateLimit<'info> {
+    /// admin, or the store's rate limiter
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump,
+        constraint = oft_store.is_rate_limiter(admin.key) @OFTError::Unauthorized
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        mut,
+        seeds = [PEER_SEED, oft_store.key().as_ref(), &params.remote_eid.to_be_bytes()],
+        bump = peer.bump
+    )]
+    pub peer: Account<'info, PeerConfig>,
+}
+
+impl SetOutboundRateLimit<'_> {
+    pub fn apply(
+        ctx: &mut Context<SetOutboundRateLimit>,
+        params: &SetOutboundRateLimitParams,
+    ) -> Result<()> {
+        let mode = ctx.accounts.peer.outbound_rate_limit_mode;
+        SetPeerConfig::update_rate_limiter(
+            &mut ctx.accounts.peer.outbound_rate_limiter,
+            mode,
+            &params.rate_limit,
+        )?;
+        emit!(OutboundRateLimitUpdated {
+            dst_eid: params.remote_eid,
+            rate_limiter: ctx.accounts.peer.outbound_rate_limiter.clone(),
+        });
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SetOutboundRateLimitParams {
+    pub remote_eid: u32,
+    pub rate_limit: Option<RateLimitParams>, // None removes the limit
+}
@@ -26,7 +26,7 @@ pub struct SetPauseParams {
     pub paused: bool,
 }
 
-fn is_valid_signer(signer: Pubkey, oft_store: &OFTStore, paused: bool) -> bool {
+pub(crate) fn is_valid_signer(signer: Pubkey, oft_store: &OFTStore, paused: bool) -> bool {
     if paused {
         oft_store.pauser == Some(signer)
     } else {
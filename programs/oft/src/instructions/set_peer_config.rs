@@ -24,15 +24,34 @@ pub struct SetPeerConfig<'info> {
 
 impl SetPeerConfig<'_> {
     pub fn apply(ctx: &mut Context<SetPeerConfig>, params: &SetPeerConfigParams) -> Result<()> {
+        // a newly created peer is rate limited until set_rate_limit_enabled says otherwise
+        if ctx.accounts.peer.version == 0 {
+            ctx.accounts.peer.rate_limit_enabled = true;
+        }
         match params.config.clone() {
             PeerConfigParam::PeerAddress(peer_address) => {
+                // a newly added peer starts on probation
+                if ctx.accounts.peer.peer_address == [0; 32] {
+                    if let Some(policy) = &ctx.accounts.oft_store.probation_policy {
+                        let ends_at = Clock::get()?.unix_timestamp + policy.probation_seconds;
+                        ctx.accounts.peer.probation_ends_at = ends_at;
+                        emit!(PeerProbationUpdated { remote_eid: params.remote_eid, ends_at });
+                    }
+                }
+                // a new remote address has to prove control again before sends resume
+                if ctx.accounts.peer.peer_address != peer_address {
+                    ctx.accounts.peer.control_proven = false;
+                    ctx.accounts.peer.control_challenge = None;
+                }
                 ctx.accounts.peer.peer_address = peer_address;
+                ctx.accounts.peer.endpoint_version = ctx.accounts.oft_store.endpoint_version;
             },
             PeerConfigParam::FeeBps(fee_bps) => {
                 if let Some(fee_bps) = fee_bps {
                     require!(fee_bps < MAX_FEE_BASIS_POINTS, OFTError::InvalidFee);
                 }
                 ctx.accounts.peer.fee_bps = fee_bps;
+                emit!(PeerFeeUpdated { remote_eid: params.remote_eid, fee_bps });
             },
             PeerConfigParam::EnforcedOptions { send, send_and_call } => {
                 oapp::options::assert_type_3(&send)?;
@@ -42,30 +61,189 @@ impl SetPeerConfig<'_> {
             },
             PeerConfigParam::OutboundRateLimit(_rate_limit_params) => {
                 // Dummy implementation - do nothing for outbound rate limiting
-                // This is just for SDK compatibility
+                // This is just for SDK compatibility; outbound limits are set with
+                // set_outbound_rate_limit
             },
             PeerConfigParam::InboundRateLimit(rate_limit_params) => {
+                let mode = ctx.accounts.peer.inbound_rate_limit_mode;
                 Self::update_rate_limiter(
                     &mut ctx.accounts.peer.inbound_rate_limiter,
+                    mode,
                     &rate_limit_params,
                 )?;
             },
+            PeerConfigParam::VerifyEndpoint => {
+                // re-verify the peer after the store has been migrated to a new endpoint
+                ctx.accounts.peer.endpoint_version = ctx.accounts.oft_store.endpoint_version;
+            },
+            PeerConfigParam::NonceGapMonitor(monitor) => {
+                ctx.accounts.peer.nonce_gap_monitor = monitor;
+            },
+            PeerConfigParam::MinDstGas(min_dst_gas) => {
+                ctx.accounts.peer.min_dst_gas = min_dst_gas.clone();
+                emit!(MinDstGasUpdated { remote_eid: params.remote_eid, min_dst_gas });
+            },
+            PeerConfigParam::SourceSenderAllowlist(allowlist) => {
+                if let Some(allowlist) = &allowlist {
+                    require!(
+                        allowlist.senders.len() <= MAX_SOURCE_SENDERS,
+                        OFTError::SourceSenderAllowlistFull
+                    );
+                }
+                ctx.accounts.peer.source_sender_allowlist = allowlist.clone();
+                emit!(SourceSenderAllowlistUpdated { remote_eid: params.remote_eid, allowlist });
+            },
+            PeerConfigParam::PriorityTiers(tiers) => {
+                require!(tiers.len() <= MAX_PRIORITY_TIERS, OFTError::InvalidPriorityTiers);
+                for (i, tier) in tiers.iter().enumerate() {
+                    require!(
+                        tier.options.len() <= PRIORITY_TIER_OPTIONS_MAX_LEN
+                            && tiers[..i].iter().all(|other| other.tier != tier.tier),
+                        OFTError::InvalidPriorityTiers
+                    );
+                    oapp::options::assert_type_3(&tier.options)?;
+                }
+                ctx.accounts.peer.priority_tiers = tiers;
+            },
+            PeerConfigParam::ExpectedRemoteConfigHash(hash) => {
+                ctx.accounts.peer.expected_remote_config_hash = hash;
+            },
+            PeerConfigParam::InboundSplit(split) => {
+                if let Some(split) = &split {
+                    require!(
+                        split.immediate_bps < MAX_FEE_BASIS_POINTS
+                            && split.epoch_seconds > 0
+                            && split.epochs > 0,
+                        OFTError::InvalidInboundSplit
+                    );
+                }
+                ctx.accounts.peer.inbound_split = split;
+            },
+            PeerConfigParam::RemoteFeeBps(remote_fee_bps) => {
+                if let Some(fee_bps) = remote_fee_bps {
+                    require!(fee_bps < MAX_FEE_BASIS_POINTS, OFTError::InvalidFee);
+                }
+                ctx.accounts.peer.remote_fee_bps = remote_fee_bps;
+            },
+            PeerConfigParam::ExecutorAllowlist(executors) => {
+                require!(executors.len() <= MAX_EXECUTORS, OFTError::ExecutorAllowlistFull);
+                ctx.accounts.peer.executor_allowlist = executors.clone();
+                emit!(ExecutorAllowlistUpdated { remote_eid: params.remote_eid, executors });
+            },
+            PeerConfigParam::SilenceMonitor(max_silence_seconds) => {
+                ctx.accounts.peer.silence_monitor = match max_silence_seconds {
+                    Some(max_silence_seconds) => {
+                        require!(max_silence_seconds > 0, OFTError::InvalidSilenceMonitor);
+                        Some(SilenceMonitor {
+                            max_silence_seconds,
+                            since: Clock::get()?.unix_timestamp,
+                            reported: false,
+                        })
+                    },
+                    None => None,
+                };
+            },
+            PeerConfigParam::Paused(paused) => {
+                let peer = &mut ctx.accounts.peer;
+                peer.paused = paused;
+                if let Some(monitor) = peer.breach_monitor.as_mut().filter(|_| !paused) {
+                    // start counting afresh, so the peer is not paused again by past breaches
+                    monitor.breaches = 0;
+                }
+                emit!(PeerPauseUpdated { remote_eid: params.remote_eid, paused });
+            },
+            PeerConfigParam::BreachMonitor(monitor) => {
+                ctx.accounts.peer.breach_monitor = match monitor {
+                    Some(monitor) => {
+                        require!(monitor.window_seconds > 0, OFTError::InvalidBreachMonitor);
+                        Some(BreachMonitor { window_start: 0, breaches: 0, ..monitor })
+                    },
+                    None => None,
+                };
+            },
+            PeerConfigParam::InFlightTracking(enabled) => {
+                ctx.accounts.peer.in_flight = enabled.then(InFlight::default);
+            },
+            PeerConfigParam::RateLimitMode { direction, mode } => {
+                if let RateLimitMode::FixedWindow { window_seconds } = mode {
+                    require!(window_seconds > 0, OFTError::InvalidRateLimitMode);
+                }
+                let peer: &mut PeerConfig = &mut ctx.accounts.peer;
+                let (rate_limiter, current_mode) = match direction {
+                    RateLimitDirection::Inbound => {
+                        (&mut peer.inbound_rate_limiter, &mut peer.inbound_rate_limit_mode)
+                    },
+                    RateLimitDirection::Outbound => {
+                        (&mut peer.outbound_rate_limiter, &mut peer.outbound_rate_limit_mode)
+                    },
+                };
+                if let Some(rate_limiter) = rate_limiter.as_mut() {
+                    Self::switch_mode(rate_limiter, *current_mode, mode)?;
+                }
+                for entry in peer.msg_type_rate_limiters.iter_mut() {
+                    if entry.direction == direction {
+                        Self::switch_mode(&mut entry.rate_limiter, *current_mode, mode)?;
+                    }
+                }
+                *current_mode = mode;
+            },
+            PeerConfigParam::MsgTypeRateLimit { msg_type, direction, rate_limit } => {
+                require!(
+                    msg_type == MSG_TYPE_SEND || msg_type == MSG_TYPE_SEND_AND_CALL,
+                    OFTError::InvalidMsgType
+                );
+                let peer = &mut ctx.accounts.peer;
+                let mode = match direction {
+                    RateLimitDirection::Inbound => peer.inbound_rate_limit_mode,
+                    RateLimitDirection::Outbound => peer.outbound_rate_limit_mode,
+                };
+                let mut rate_limiter = peer.msg_type_rate_limiter(msg_type, direction).cloned();
+                Self::update_rate_limiter(&mut rate_limiter, mode, &rate_limit)?;
+                peer.msg_type_rate_limiters
+                    .retain(|entry| entry.msg_type != msg_type || entry.direction != direction);
+                if let Some(rate_limiter) = rate_limiter.clone() {
+                    peer.msg_type_rate_limiters.push(MsgTypeRateLimiter {
+                        msg_type,
+                        direction,
+                        rate_limiter,
+                    });
+                }
+                emit!(MsgTypeRateLimitUpdated {
+                    remote_eid: params.remote_eid,
+                    msg_type,
+                    direction,
+                    rate_limiter,
+                });
+            },
+            PeerConfigParam::ReservedCapacity(reserved_capacity_ld) => {
+                ctx.accounts.peer.reserved_capacity_ld = reserved_capacity_ld;
+                emit!(ReservedCapacityUpdated {
+                    remote_eid: params.remote_eid,
+                    reserved_capacity_ld,
+                });
+            },
+            PeerConfigParam::ProbationEndsAt(ends_at) => {
+                ctx.accounts.peer.probation_ends_at = ends_at;
+                emit!(PeerProbationUpdated { remote_eid: params.remote_eid, ends_at });
+            },
         }
         ctx.accounts.peer.bump = ctx.bumps.peer;
+        ctx.accounts.peer.version = PEER_CONFIG_VERSION;
         Ok(())
     }
 
-    fn update_rate_limiter(
+    pub fn update_rate_limiter(
         rate_limiter: &mut Option<RateLimiter>,
+        mode: RateLimitMode,
         params: &Option<RateLimitParams>,
     ) -> Result<()> {
         if let Some(param) = params {
             let mut limiter = rate_limiter.clone().unwrap_or_default();
             if let Some(capacity) = param.capacity {
-                limiter.set_capacity(capacity)?;
+                limiter.set_capacity(mode, capacity)?;
             }
             if let Some(refill_rate) = param.refill_per_second {
-                limiter.set_rate(refill_rate)?;
+                limiter.set_rate(mode, refill_rate)?;
             }
             *rate_limiter = Some(limiter);
         } else {
@@ -73,6 +251,25 @@ impl SetPeerConfig<'_> {
         }
         Ok(())
     }
+
+    /// Settles `rate_limiter` under the mode it was charged in and restarts it in `to`,
+    /// converting its refill rate between tokens per second and tokens per slot.
+    fn switch_mode(
+        rate_limiter: &mut RateLimiter,
+        from: RateLimitMode,
+        to: RateLimitMode,
+    ) -> Result<()> {
+        rate_limiter.refill(from, 0)?;
+        if (from == RateLimitMode::SlotBucket) != (to == RateLimitMode::SlotBucket) {
+            let rate = rate_limiter.refill_per_second;
+            rate_limiter.refill_per_second = match from {
+                RateLimitMode::SlotBucket => simulation::refill_per_second_from_slot(rate),
+                _ => to.refill_rate(rate),
+            };
+        }
+        rate_limiter.last_refill_time = to.now(&Clock::get()?);
+        Ok(())
+    }
 }
 
 #[derive(Clone, AnchorSerialize, AnchorDeserialize)]
@@ -88,10 +285,32 @@ pub enum PeerConfigParam {
     EnforcedOptions { send: Vec<u8>, send_and_call: Vec<u8> },
     OutboundRateLimit(Option<RateLimitParams>),  // Added to match deployed program
     InboundRateLimit(Option<RateLimitParams>),
+    VerifyEndpoint,
+    NonceGapMonitor(Option<NonceGapMonitor>),
+    MinDstGas(Option<MinDstGas>),
+    ProbationEndsAt(i64), // 0 ends the probation
+    SourceSenderAllowlist(Option<SourceSenderAllowlist>),
+    PriorityTiers(Vec<PriorityTier>),
+    ExpectedRemoteConfigHash(Option<[u8; 32]>),
+    InboundSplit(Option<InboundSplit>),
+    RemoteFeeBps(Option<u16>),
+    ExecutorAllowlist(Vec<Pubkey>),
+    SilenceMonitor(Option<i64>), // max_silence_seconds
+    RateLimitMode { direction: RateLimitDirection, mode: RateLimitMode },
+    ReservedCapacity(u64), // of each rate limiter, for override addresses
+    // None removes the message type's rate limiter in that direction
+    MsgTypeRateLimit {
+        msg_type: u8,
+        direction: RateLimitDirection,
+        rate_limit: Option<RateLimitParams>,
+    },
+    Paused(bool), // the peer only; unpausing restarts its breach monitor's count
+    BreachMonitor(Option<BreachMonitor>), // window_start and breaches are reset
+    InFlightTracking(bool), // the counters restart when enabled
 }
 
 #[derive(Clone, AnchorSerialize, AnchorDeserialize)]
 pub struct RateLimitParams {
-    pub refill_per_second: Option<u64>,
+    pub refill_per_second: Option<u64>, // tokens per slot under RateLimitMode::SlotBucket
     pub capacity: Option<u64>,
 }
@@ -0,0 +1,46 @@
+use crate::*;
+
+/// Turns rate limiting for one peer off or back on. While off, transfers with the peer skip its
+/// rate limiters and the launch cap in both directions, without being charged; the limiters keep
+/// their settings and refill in the meantime. Meant for trusted peers such as a canonical
+/// lockbox, instead of giving them capacities too large to matter.
+#[derive(Accounts)]
+#[instruction(params: SetRateLimitEnabledParams)]
+pub struct SetRateLimitEnabled<'info> {
+    /// admin, or the store's rate limiter
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump,
+        constraint = oft_store.is_rate_limiter(admin.key) @OFTError::Unauthorized
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        mut,
+        seeds = [PEER_SEED, oft_store.key().as_ref(), &params.remote_eid.to_be_bytes()],
+        bump = peer.bump
+    )]
+    pub peer: Account<'info, PeerConfig>,
+}
+
+impl SetRateLimitEnabled<'_> {
+    pub fn apply(
+        ctx: &mut Context<SetRateLimitEnabled>,
+        params: &SetRateLimitEnabledParams,
+    ) -> Result<()> {
+        if ctx.accounts.peer.rate_limit_enabled != params.enabled {
+            ctx.accounts.peer.rate_limit_enabled = params.enabled;
+            emit!(RateLimitEnabledUpdated {
+                remote_eid: params.remote_eid,
+                enabled: params.enabled,
+            });
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SetRateLimitEnabledParams {
+    pub remote_eid: u32,
+    pub enabled: bool,
+}
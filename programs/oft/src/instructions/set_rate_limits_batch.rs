@@ -0,0 +1,57 @@
+use crate::*;
+
+/// Sets the outbound rate limits of several peers in one transaction, as set_outbound_rate_limit
+/// does for one. The peers are passed writable in the remaining accounts, in the order of
+/// `rate_limits`.
+#[derive(Accounts)]
+pub struct SetRateLimitsBatch<'info> {
+    /// admin, or the store's rate limiter
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump,
+        constraint = oft_store.is_rate_limiter(admin.key) @OFTError::Unauthorized
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+}
+
+impl<'info> SetRateLimitsBatch<'info> {
+    pub fn apply(
+        ctx: &Context<'_, '_, 'info, 'info, SetRateLimitsBatch<'info>>,
+        params: &SetRateLimitsBatchParams,
+    ) -> Result<()> {
+        require!(
+            !params.rate_limits.is_empty()
+                && params.rate_limits.len() == ctx.remaining_accounts.len(),
+            OFTError::RateLimitBatchLengthMismatch
+        );
+        let oft_store = ctx.accounts.oft_store.key();
+        for (entry, info) in params.rate_limits.iter().zip(ctx.remaining_accounts.iter()) {
+            let mut peer = Account::<PeerConfig>::try_from(info)?;
+            let expected = Pubkey::create_program_address(
+                &[PEER_SEED, oft_store.as_ref(), &entry.remote_eid.to_be_bytes(), &[peer.bump]],
+                &crate::ID,
+            )
+            .map_err(|_| error!(OFTError::InvalidPeerAccount))?;
+            require!(info.key() == expected, OFTError::InvalidPeerAccount);
+
+            let mode = peer.outbound_rate_limit_mode;
+            SetPeerConfig::update_rate_limiter(
+                &mut peer.outbound_rate_limiter,
+                mode,
+                &entry.rate_limit,
+            )?;
+            peer.exit(&crate::ID)?;
+            emit!(OutboundRateLimitUpdated {
+                dst_eid: entry.remote_eid,
+                rate_limiter: peer.outbound_rate_limiter.clone(),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SetRateLimitsBatchParams {
+    pub rate_limits: Vec<SetOutboundRateLimitParams>, // one per peer in the remaining accounts
+}
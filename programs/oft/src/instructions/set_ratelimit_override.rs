@@ -2,26 +2,45 @@ use crate::*;
 
 #[derive(Accounts)]
 pub struct ManageRateLimitOverride<'info> {
+    /// admin, or the store's rate limiter
     pub admin: Signer<'info>,
     #[account(
         mut,
         seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
         bump = oft_store.bump,
-        has_one = admin @OFTError::Unauthorized
+        constraint = oft_store.is_rate_limiter(admin.key) @OFTError::Unauthorized
     )]
     pub oft_store: Account<'info, OFTStore>,
+    // Only needed for additions above the store's override_cosign_threshold_ld
+    pub cosigner: Option<Signer<'info>>,
+    // Only needed while the store has an override_approver, which must be this signer
+    pub approver: Option<Signer<'info>>,
 }
 
 #[derive(Clone, AnchorSerialize, AnchorDeserialize)]
 pub struct ManageRateLimitOverrideAddressParams {
     pub addresses: Vec<Pubkey>,
     pub actions: Vec<RateLimitOverrideAction>, // Add or Remove
+    // Either empty or one entry per address. Makes an added override lapse at that time.
+    pub expires_at: Vec<Option<i64>>,
+    // The rate limiters added overrides skip, None for both.
+    pub direction: Option<RateLimitDirection>,
+    // Lets added overrides move this many times the configured limit instead of skipping the
+    // rate limiters, None to skip them.
+    pub multiplier: Option<u16>,
+    // Whether added overrides match the address as the sender of sends, the recipient of
+    // credits, or both.
+    pub scope: RateLimitOverrideScope,
 }
 
 #[derive(Clone, AnchorSerialize, AnchorDeserialize)]
 pub struct ManageRateLimitOverrideGuidParams {
     pub guids: Vec<[u8; 32]>,
     pub actions: Vec<RateLimitOverrideAction>, // Add or Remove
+    // Either empty or one entry per guid. Pins the amount the override applies to on Add.
+    pub expected_amounts_ld: Vec<Option<u64>>,
+    // Either empty or one entry per guid. Pins the source the override applies to on Add.
+    pub src_eids: Vec<Option<u32>>,
 }
 
 #[derive(Clone, AnchorSerialize, AnchorDeserialize)]
@@ -35,13 +54,45 @@ impl ManageRateLimitOverride<'_> {
         ctx: &mut Context<ManageRateLimitOverride>,
         params: &ManageRateLimitOverrideAddressParams,
     ) -> Result<()> {
+        Self::check_approver(ctx)?;
         require!(
             params.actions.len() == params.addresses.len(),
             OFTError::ManageRateLimitOverrideParamsLengthMismatch
         );
+        require!(
+            params.expires_at.is_empty() || params.expires_at.len() == params.addresses.len(),
+            OFTError::ManageRateLimitOverrideParamsLengthMismatch
+        );
+        require!(
+            params.multiplier.map_or(true, |multiplier| multiplier >= 2),
+            OFTError::InvalidOverrideMultiplier
+        );
+        // an override for a party that is never on the side of the direction matches nothing
+        require!(
+            params.direction.map_or(true, |direction| params.scope.covers(direction)),
+            OFTError::InvalidOverrideScope
+        );
 
-        for (action, address) in params.actions.iter().zip(params.addresses.iter()) {
-            Self::process_address_action(ctx, action, address)?;
+        let now = Clock::get()?.unix_timestamp;
+        for address in ctx.accounts.oft_store.prune_rate_limit_overrides(now) {
+            emit!(RateLimitOverrideUpdated { address, action: RateLimitOverrideAction::Remove });
+        }
+        for (i, (action, address)) in params.actions.iter().zip(params.addresses.iter()).enumerate()
+        {
+            let expires_at = params.expires_at.get(i).copied().flatten();
+            require!(
+                expires_at.map_or(true, |expires_at| expires_at > now),
+                OFTError::InvalidTimestamp
+            );
+            Self::process_address_action(
+                ctx,
+                action,
+                address,
+                expires_at,
+                params.direction,
+                params.multiplier,
+                params.scope,
+            )?;
         }
         Ok(())
     }
@@ -50,13 +101,25 @@ impl ManageRateLimitOverride<'_> {
         ctx: &mut Context<ManageRateLimitOverride>,
         params: &ManageRateLimitOverrideGuidParams,
     ) -> Result<()> {
+        Self::check_approver(ctx)?;
         require!(
             params.actions.len() == params.guids.len(),
             OFTError::ManageRateLimitOverrideParamsLengthMismatch
         );
+        require!(
+            params.expected_amounts_ld.is_empty()
+                || params.expected_amounts_ld.len() == params.guids.len(),
+            OFTError::ManageRateLimitOverrideParamsLengthMismatch
+        );
+        require!(
+            params.src_eids.is_empty() || params.src_eids.len() == params.guids.len(),
+            OFTError::ManageRateLimitOverrideParamsLengthMismatch
+        );
 
-        for (action, guid) in params.actions.iter().zip(params.guids.iter()) {
-            Self::process_guid_action(ctx, action, guid)?;
+        for (i, (action, guid)) in params.actions.iter().zip(params.guids.iter()).enumerate() {
+            let expected_amount_ld = params.expected_amounts_ld.get(i).copied().flatten();
+            let src_eid = params.src_eids.get(i).copied().flatten();
+            Self::process_guid_action(ctx, action, guid, expected_amount_ld, src_eid)?;
         }
         Ok(())
     }
@@ -65,20 +128,21 @@ impl ManageRateLimitOverride<'_> {
         ctx: &mut Context<ManageRateLimitOverride>,
         action: &RateLimitOverrideAction,
         address: &Pubkey,
+        expires_at: Option<i64>,
+        direction: Option<RateLimitDirection>,
+        multiplier: Option<u16>,
+        scope: RateLimitOverrideScope,
     ) -> Result<()> {
         match action {
             RateLimitOverrideAction::Add => {
-                require!(
-                    ctx.accounts.oft_store.rate_limit_override.len()
-                        < ctx.accounts.oft_store.max_rate_limit_overrides.into(),
-                    OFTError::RateLimitOverrideListFull
-                );
-                require!(
-                    !ctx.accounts.oft_store.rate_limit_override.contains(address),
-                    OFTError::AlreadyInOverrideList
-                );
-
-                ctx.accounts.oft_store.rate_limit_override.push(*address);
+                Self::check_cosigner(ctx, None)?;
+                ctx.accounts.oft_store.add_rate_limit_override(RateLimitOverrideAddress {
+                    address: *address,
+                    expires_at,
+                    direction,
+                    multiplier,
+                    scope,
+                })?;
                 
                 emit!(RateLimitOverrideUpdated {
                     address: *address,
@@ -86,12 +150,7 @@ impl ManageRateLimitOverride<'_> {
                 });
             }
             RateLimitOverrideAction::Remove => {
-                let index = ctx.accounts.oft_store.rate_limit_override
-                    .iter()
-                    .position(|x| x == address)
-                    .ok_or(OFTError::NotInOverrideList)?;
-
-                ctx.accounts.oft_store.rate_limit_override.swap_remove(index);
+                ctx.accounts.oft_store.remove_rate_limit_override(address)?;
                 
                 emit!(RateLimitOverrideUpdated {
                     address: *address,
@@ -106,19 +165,17 @@ impl ManageRateLimitOverride<'_> {
         ctx: &mut Context<ManageRateLimitOverride>,
         action: &RateLimitOverrideAction,
         guid: &[u8; 32],
+        expected_amount_ld: Option<u64>,
+        src_eid: Option<u32>,
     ) -> Result<()> {
         match action {
             RateLimitOverrideAction::Add => {
-                require!(
-                    ctx.accounts.oft_store.rate_limit_override_guids.len() < ctx.accounts.oft_store.max_rate_limit_override_guid_count.into(),
-                    OFTError::RateLimitOverrideListFull
-                );
-                require!(
-                    !ctx.accounts.oft_store.rate_limit_override_guids.contains(guid),
-                    OFTError::AlreadyInOverrideList
-                );
-
-                ctx.accounts.oft_store.rate_limit_override_guids.push(*guid);
+                Self::check_cosigner(ctx, expected_amount_ld)?;
+                ctx.accounts.oft_store.add_rate_limit_override_guid(RateLimitOverrideGuid {
+                    guid: *guid,
+                    expected_amount_ld,
+                    src_eid,
+                })?;
                 
                 emit!(RateLimitOverrideGuidUpdated {
                     guid: *guid,
@@ -126,12 +183,7 @@ impl ManageRateLimitOverride<'_> {
                 });
             }
             RateLimitOverrideAction::Remove => {
-                let index = ctx.accounts.oft_store.rate_limit_override_guids
-                    .iter()
-                    .position(|x| x == guid)
-                    .ok_or(OFTError::NotInOverrideList)?;
-
-                ctx.accounts.oft_store.rate_limit_override_guids.swap_remove(index);
+                ctx.accounts.oft_store.remove_rate_limit_override_guid(guid)?;
                 
                 emit!(RateLimitOverrideGuidUpdated {
                     guid: *guid,
@@ -141,4 +193,43 @@ impl ManageRateLimitOverride<'_> {
         }
         Ok(())
     }
+
+    /// Any change to the override lists must be signed by the store's override_approver, if set.
+    fn check_approver(ctx: &Context<ManageRateLimitOverride>) -> Result<()> {
+        if let Some(override_approver) = ctx.accounts.oft_store.override_approver {
+            require!(
+                ctx.accounts
+                    .approver
+                    .as_ref()
+                    .map_or(false, |approver| approver.key() == override_approver),
+                OFTError::OverrideApproverRequired
+            );
+        }
+        Ok(())
+    }
+
+    /// An override expected to carry more than the co-sign threshold, or an unbounded one, must
+    /// be co-signed in the same transaction by someone on the store's override_cosigners other
+    /// than the admin and the signer.
+    fn check_cosigner(
+        ctx: &Context<ManageRateLimitOverride>,
+        expected_amount_ld: Option<u64>,
+    ) -> Result<()> {
+        let oft_store = &ctx.accounts.oft_store;
+        if !oft_store.requires_override_cosigner(expected_amount_ld) {
+            return Ok(());
+        }
+        require!(
+            ctx.accounts
+                .cosigner
+                .as_ref()
+                .map_or(false, |cosigner| {
+                    cosigner.key() != oft_store.admin
+                        && cosigner.key() != ctx.accounts.admin.key()
+                        && oft_store.is_override_cosigner(cosigner.key)
+                }),
+            OFTError::OverrideCosignerRequired
+        );
+        Ok(())
+    }
 }
@@ -0,0 +1,77 @@
+use crate::*;
+
+/// Changes the accounts fees and vault lamports may be withdrawn to. Changes only take effect
+/// WITHDRAW_DESTINATIONS_DELAY_SECONDS after they are proposed, so a compromised admin key
+/// cannot redirect funds before the change is noticed and cancelled.
+#[derive(Accounts)]
+pub struct SetWithdrawDestinations<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump,
+        has_one = admin @OFTError::Unauthorized
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+}
+
+impl SetWithdrawDestinations<'_> {
+    pub fn apply(
+        ctx: &mut Context<SetWithdrawDestinations>,
+        params: &SetWithdrawDestinationsParams,
+    ) -> Result<()> {
+        match params.clone() {
+            SetWithdrawDestinationsParams::Propose(destinations) => {
+                require!(
+                    destinations.len() <= MAX_WITHDRAW_DESTINATIONS,
+                    OFTError::WithdrawDestinationsListFull
+                );
+                let executable_at = Clock::get()?
+                    .unix_timestamp
+                    .checked_add(WITHDRAW_DESTINATIONS_DELAY_SECONDS)
+                    .ok_or_else(|| error!(OFTError::InvalidTimestamp))?;
+                // a new proposal replaces the pending one and restarts the timelock
+                let pending =
+                    PendingWithdrawDestinations { destinations: destinations.clone(), executable_at };
+                ctx.accounts.oft_store.pending_withdraw_destinations = Some(pending);
+
+                emit!(WithdrawDestinationsProposed { destinations, executable_at });
+            },
+            SetWithdrawDestinationsParams::Cancel => {
+                let pending = ctx
+                    .accounts
+                    .oft_store
+                    .pending_withdraw_destinations
+                    .take()
+                    .ok_or(OFTError::NoPendingWithdrawDestinations)?;
+
+                emit!(WithdrawDestinationsCancelled { destinations: pending.destinations });
+            },
+            SetWithdrawDestinationsParams::Execute => {
+                let pending = ctx
+                    .accounts
+                    .oft_store
+                    .pending_withdraw_destinations
+                    .clone()
+                    .ok_or(OFTError::NoPendingWithdrawDestinations)?;
+                require!(
+                    Clock::get()?.unix_timestamp >= pending.executable_at,
+                    OFTError::WithdrawDestinationsTimelocked
+                );
+
+                ctx.accounts.oft_store.withdraw_destinations = pending.destinations.clone();
+                ctx.accounts.oft_store.pending_withdraw_destinations = None;
+
+                emit!(WithdrawDestinationsExecuted { destinations: pending.destinations });
+            },
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub enum SetWithdrawDestinationsParams {
+    Propose(Vec<Pubkey>), // executable after WITHDRAW_DESTINATIONS_DELAY_SECONDS
+    Cancel,
+    Execute,
+}
@@ -0,0 +1,147 @@
+use crate::*;
+use anchor_spl::{
+    associated_token::get_associated_token_address_with_program_id, token_interface::Mint,
+};
+use simulation::CreditOutcome;
+
+/// Evaluates the delivery of a token transfer without executing it: the policies of lz_receive,
+/// whether the credit would be held or split, and the inbound capacity left for the peer. Fails
+/// with the error lz_receive would fail with. The nonce is not checked.
+#[derive(Accounts)]
+#[instruction(params: SimulateReceiveParams)]
+pub struct SimulateReceive<'info> {
+    #[account(
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        seeds = [
+            PEER_SEED,
+            oft_store.key().as_ref(),
+            &params.src_eid.to_be_bytes()
+        ],
+        bump = peer.bump,
+        // checked before inbound_opt_out decodes the message
+        constraint = msg_codec::validate(&params.message).is_ok() @OFTError::InvalidMessage,
+        // control messages credit no one
+        constraint = !msg_codec::is_control(&params.message, &peer.peer_address) @OFTError::InvalidMessage
+    )]
+    pub peer: Account<'info, PeerConfig>,
+    #[account(address = oft_store.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    /// CHECK: the recipient's InboundOptOut, which only has to exist
+    #[account(
+        seeds = [
+            INBOUND_OPT_OUT_SEED,
+            oft_store.key().as_ref(),
+            &msg_codec::send_to(&params.message)
+        ],
+        bump
+    )]
+    pub inbound_opt_out: UncheckedAccount<'info>,
+}
+
+impl SimulateReceive<'_> {
+    pub fn apply(
+        ctx: &Context<SimulateReceive>,
+        params: &SimulateReceiveParams,
+    ) -> Result<ReceiveSimulation> {
+        let oft_store = &ctx.accounts.oft_store;
+        let peer = &ctx.accounts.peer;
+        policy::check_paused(oft_store)?;
+        policy::check_peer_paused(peer)?;
+        policy::check_peer_verified(oft_store, peer)?;
+
+        let to_address = Pubkey::from(msg_codec::send_to(&params.message));
+        let amount_ld = oft_store.sd2ld(msg_codec::amount_sd(&params.message))?;
+        let source_sender_hold = policy::check_source_sender(peer, &params.message)?;
+        let ignored = !policy::check_zero_amount(oft_store, amount_ld)?;
+        let probation_hold = policy::check_probation(oft_store, peer, amount_ld)?;
+
+        // as policy::consume_rate_limit, without using up a guid override
+        let token_dest = get_associated_token_address_with_program_id(
+            &to_address,
+            &oft_store.token_mint,
+            ctx.accounts.token_mint.to_account_info().owner,
+        );
+        let now = peer.inbound_rate_limit_mode.now(&Clock::get()?);
+        let overridden = oft_store.inbound_rate_limit_override(&token_dest, &to_address)?.is_some();
+        let limited = !overridden
+            && !oft_store.matches_rate_limit_override_guid(&params.guid, params.src_eid, amount_ld)
+            && !oft_store.is_test_mode()
+            && peer.rate_limit_enabled;
+        let multiplier = oft_store.inbound_override_factor(&token_dest, &to_address)?;
+        let charge_ld = simulation::override_charge_ld(amount_ld, multiplier);
+        if limited {
+            policy::check_launch_cap(oft_store, charge_ld)?;
+        }
+        let mut peer = PeerConfig::clone(peer);
+        policy::refresh_rate_limits(oft_store, &mut peer)?;
+        let mode = peer.inbound_rate_limit_mode;
+        let rate_limiter = peer.inbound_rate_limiter.as_ref().filter(|_| limited);
+        let msg_type = msg_codec::msg_type(msg_codec::compose_msg(&params.message).is_some());
+        let msg_type_rate_limiter =
+            peer.msg_type_rate_limiter(msg_type, RateLimitDirection::Inbound).filter(|_| limited);
+        let reserved_ld = policy::reserved_capacity_ld(&peer, multiplier);
+        let within_rate_limit =
+            simulation::within_rate_limit(rate_limiter, mode, charge_ld, now, reserved_ld)
+                && simulation::within_rate_limit(msg_type_rate_limiter, mode, charge_ld, now, 0);
+        require!(
+            within_rate_limit || policy::queues_over_limit(oft_store, &peer),
+            OFTError::RateLimitExceeded
+        );
+        let awaiting_capacity = !within_rate_limit;
+
+        let awaiting_verification = source_sender_hold
+            || probation_hold
+            || policy::check_composer(oft_store, &to_address, &params.message)
+            || oft_store.requires_credit_verification(amount_ld);
+        let awaiting_claim = InboundOptOut::exists(&ctx.accounts.inbound_opt_out);
+        let outcome = simulation::credit_outcome(
+            amount_ld,
+            awaiting_verification || awaiting_claim || awaiting_capacity,
+            msg_codec::compose_msg(&params.message).is_some(),
+            peer.inbound_split.as_ref(),
+        );
+        Ok(ReceiveSimulation {
+            amount_ld,
+            outcome,
+            awaiting_verification,
+            awaiting_claim,
+            awaiting_capacity,
+            ignored,
+            inbound_available_ld: [
+                rate_limiter.map(|rate_limiter| {
+                    simulation::unreserved_ld(rate_limiter, mode, now, reserved_ld)
+                }),
+                msg_type_rate_limiter
+                    .map(|rate_limiter| simulation::available_ld(rate_limiter, mode, now)),
+            ]
+            .into_iter()
+            .flatten()
+            .min(),
+        })
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SimulateReceiveParams {
+    pub src_eid: u32,
+    pub guid: [u8; 32],
+    pub message: Vec<u8>,
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct ReceiveSimulation {
+    pub amount_ld: u64, // before any token2022 transfer fee
+    pub outcome: CreditOutcome,
+    pub awaiting_verification: bool,
+    pub awaiting_claim: bool,
+    // queued until the inbound rate limit refills, see policy::queues_over_limit
+    pub awaiting_capacity: bool,
+    pub ignored: bool, // a zero-amount message cleared under ZeroAmountPolicy::Ignore
+    // before this transfer, less the peer's reserved capacity, and at most what the rate limiter
+    // for its message type has left; None when it is not limited
+    pub inbound_available_ld: Option<u64>,
+}
@@ -0,0 +1,112 @@
+use crate::*;
+use anchor_spl::token_interface::Mint;
+
+/// Evaluates a send without executing it: the policies, fees and dust of send, and the outbound
+/// capacity left for the peer. Fails with the error the send would fail with.
+#[derive(Accounts)]
+#[instruction(params: SimulateSendParams)]
+pub struct SimulateSend<'info> {
+    #[account(
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        seeds = [
+            PEER_SEED,
+            oft_store.key().as_ref(),
+            &params.dst_eid.to_be_bytes()
+        ],
+        bump = peer.bump
+    )]
+    pub peer: Account<'info, PeerConfig>,
+    #[account(address = oft_store.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+}
+
+impl SimulateSend<'_> {
+    pub fn apply(
+        ctx: &Context<SimulateSend>,
+        params: &SimulateSendParams,
+    ) -> Result<SendSimulation> {
+        let oft_store = &ctx.accounts.oft_store;
+        let peer = &ctx.accounts.peer;
+        policy::check_send(oft_store, peer, params.dst_eid)?;
+        let privileged_flow = policy::check_flow_tag(oft_store, &params.sender, params.flow_tag)?;
+        policy::check_recipient(peer, &params.to)?;
+
+        let (amount_sent_ld, amount_received_ld, oft_fee_ld) = compute_fee_and_adjust_amount(
+            params.amount_ld,
+            oft_store,
+            &ctx.accounts.token_mint,
+            policy::send_fee_bps(oft_store, peer, &params.sender, privileged_flow),
+        )?;
+        policy::check_slippage(amount_received_ld, params.min_amount_ld)?;
+        policy::check_probation(oft_store, peer, amount_received_ld)?;
+
+        // as policy::consume_outbound_rate_limit
+        let now = peer.outbound_rate_limit_mode.now(&Clock::get()?);
+        let overridden = !peer.rate_limit_enabled
+            || oft_store.is_rate_limit_override(&params.sender, RateLimitDirection::Outbound)?;
+        let multiplier = oft_store.override_factor(&params.sender, RateLimitDirection::Outbound)?;
+        let charge_ld = simulation::override_charge_ld(amount_received_ld, multiplier);
+        if !overridden {
+            policy::check_launch_cap(oft_store, charge_ld)?;
+        }
+        let mut peer = PeerConfig::clone(peer);
+        policy::refresh_rate_limits(oft_store, &mut peer)?;
+        let mode = peer.outbound_rate_limit_mode;
+        let rate_limiter = peer.outbound_rate_limiter.as_ref().filter(|_| !overridden);
+        let msg_type_rate_limiter = peer
+            .msg_type_rate_limiter(
+                msg_codec::msg_type(params.composed),
+                RateLimitDirection::Outbound,
+            )
+            .filter(|_| !overridden);
+        let reserved_ld = policy::reserved_capacity_ld(&peer, multiplier);
+        require!(
+            simulation::within_rate_limit(rate_limiter, mode, charge_ld, now, reserved_ld)
+                && simulation::within_rate_limit(msg_type_rate_limiter, mode, charge_ld, now, 0),
+            OFTError::RateLimitExceeded
+        );
+
+        Ok(SendSimulation {
+            amount_sent_ld,
+            amount_received_ld,
+            oft_fee_ld,
+            dust_ld: params.amount_ld.saturating_sub(amount_sent_ld),
+            outbound_available_ld: [
+                rate_limiter.map(|rate_limiter| {
+                    simulation::unreserved_ld(rate_limiter, mode, now, reserved_ld)
+                }),
+                msg_type_rate_limiter
+                    .map(|rate_limiter| simulation::available_ld(rate_limiter, mode, now)),
+            ]
+            .into_iter()
+            .flatten()
+            .min(),
+        })
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SimulateSendParams {
+    pub dst_eid: u32,
+    pub sender: Pubkey,
+    pub to: [u8; 32],
+    pub amount_ld: u64,
+    pub min_amount_ld: u64,
+    pub flow_tag: u8,
+    pub composed: bool, // whether the send carries a compose message
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SendSimulation {
+    pub amount_sent_ld: u64,
+    pub amount_received_ld: u64,
+    pub oft_fee_ld: u64,
+    pub dust_ld: u64, // left with the sender
+    // before this send, less the peer's reserved capacity, and at most what the rate limiter for
+    // the send's message type has left; None when the send is not limited
+    pub outbound_available_ld: Option<u64>,
+}
@@ -0,0 +1,66 @@
+use crate::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+/// Moves the dust of the signer's token account into the escrow as fees, once the admin has
+/// enabled dust_to_fees.
+#[derive(Accounts)]
+pub struct SweepDust<'info> {
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump,
+        constraint = oft_store.dust_to_fees @OFTError::DustToFeesDisabled
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        mut,
+        token::authority = signer,
+        token::mint = token_mint,
+        token::token_program = token_program
+    )]
+    pub token_source: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        address = oft_store.token_escrow,
+        token::authority = oft_store,
+        token::mint = token_mint,
+        token::token_program = token_program
+    )]
+    pub token_escrow: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        address = oft_store.token_mint,
+        mint::token_program = token_program
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl SweepDust<'_> {
+    pub fn apply(ctx: &mut Context<SweepDust>) -> Result<()> {
+        let balance_ld = ctx.accounts.token_source.amount;
+        let dust_ld = balance_ld - ctx.accounts.oft_store.remove_dust(balance_ld)?;
+        if dust_ld == 0 {
+            return Ok(());
+        }
+
+        // the escrow holds TVL and fees; the dust is not added to TVL, so it counts as fees
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.token_source.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to: ctx.accounts.token_escrow.to_account_info(),
+                    authority: ctx.accounts.signer.to_account_info(),
+                },
+            ),
+            dust_ld,
+            ctx.accounts.token_mint.decimals,
+        )?;
+        ctx.accounts.oft_store.accrue_fees(dust_ld)?;
+
+        emit!(DustSwept { owner: ctx.accounts.signer.key(), dust_ld });
+        Ok(())
+    }
+}
@@ -0,0 +1,52 @@
+use crate::*;
+
+/// Creates or refreshes a peer's RateLimitState. Permissionless; the payer funds the account
+/// the first time.
+#[derive(Accounts)]
+#[instruction(params: SyncRateLimitStateParams)]
+pub struct SyncRateLimitState<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        seeds = [PEER_SEED, oft_store.key().as_ref(), &params.remote_eid.to_be_bytes()],
+        bump = peer.bump
+    )]
+    pub peer: Account<'info, PeerConfig>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + RateLimitState::INIT_SPACE,
+        seeds = [
+            RATE_LIMIT_STATE_SEED,
+            oft_store.key().as_ref(),
+            &params.remote_eid.to_be_bytes()
+        ],
+        bump
+    )]
+    pub rate_limit_state: Account<'info, RateLimitState>,
+    pub system_program: Program<'info, System>,
+}
+
+impl SyncRateLimitState<'_> {
+    pub fn apply(
+        ctx: &mut Context<SyncRateLimitState>,
+        params: &SyncRateLimitStateParams,
+    ) -> Result<()> {
+        let state = &mut ctx.accounts.rate_limit_state;
+        state.oft_store = ctx.accounts.oft_store.key();
+        state.remote_eid = params.remote_eid;
+        state.bump = ctx.bumps.rate_limit_state;
+        state.update(&ctx.accounts.peer);
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SyncRateLimitStateParams {
+    pub remote_eid: u32,
+}
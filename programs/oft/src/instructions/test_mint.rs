@@ -0,0 +1,68 @@
+use crate::*;
+use anchor_spl::{
+    token_2022::spl_token_2022::solana_program::program_option::COption,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+/// Faucet for devnet and QA deployments: mints to any token account while the store is in test
+/// mode, which can only be enabled in programs built with the `test-mode` feature.
+#[derive(Accounts)]
+pub struct TestMint<'info> {
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump,
+        constraint = oft_store.is_test_mode() @OFTError::TestModeDisabled
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        address = oft_store.token_escrow,
+        token::authority = oft_store,
+        token::mint = token_mint,
+        token::token_program = token_program
+    )]
+    pub token_escrow: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::token_program = token_program
+    )]
+    pub token_dest: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        address = oft_store.token_mint,
+        mint::token_program = token_program
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    /// CHECK: the mint authority, see LzReceive
+    #[account(constraint = token_mint.mint_authority == COption::Some(mint_authority.key()) @OFTError::InvalidMintAuthority)]
+    pub mint_authority: AccountInfo<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl TestMint<'_> {
+    pub fn apply(ctx: &mut Context<TestMint>, params: &TestMintParams) -> Result<()> {
+        // an Adapter can only unlock the escrow, which would take the tokens from the TVL
+        require!(
+            ctx.accounts.oft_store.oft_type == OFTType::Native,
+            OFTError::InvalidMintAuthority
+        );
+        credit(
+            &mut ctx.accounts.oft_store,
+            &ctx.accounts.token_escrow,
+            &ctx.accounts.token_dest,
+            &ctx.accounts.token_mint,
+            &Some(ctx.accounts.mint_authority.clone()),
+            &ctx.accounts.token_program,
+            params.amount_ld,
+        )?;
+        emit!(TestMinted { to: ctx.accounts.token_dest.key(), amount_ld: params.amount_ld });
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct TestMintParams {
+    pub amount_ld: u64,
+}
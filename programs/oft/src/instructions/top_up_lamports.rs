@@ -0,0 +1,68 @@
+use crate::*;
+use anchor_lang::system_program::{self, Transfer};
+
+/// Lets anyone fund the rent of the store or one of its peers, e.g. ahead of a realloc, instead
+/// of sending SOL to the accounts directly. Contributions are totalled in contributed_lamports.
+#[derive(Accounts)]
+#[instruction(params: TopUpLamportsParams)]
+pub struct TopUpLamports<'info> {
+    #[account(mut)]
+    pub contributor: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    // Tops up the store itself when omitted
+    #[account(
+        mut,
+        seeds = [
+            PEER_SEED,
+            oft_store.key().as_ref(),
+            &params.remote_eid.unwrap_or_default().to_be_bytes()
+        ],
+        bump = peer.bump
+    )]
+    pub peer: Option<Account<'info, PeerConfig>>,
+    pub system_program: Program<'info, System>,
+}
+
+impl TopUpLamports<'_> {
+    pub fn apply(ctx: &mut Context<TopUpLamports>, params: &TopUpLamportsParams) -> Result<()> {
+        require!(
+            ctx.accounts.peer.is_some() == params.remote_eid.is_some(),
+            OFTError::InvalidLamportsTarget
+        );
+        let target = match &ctx.accounts.peer {
+            Some(peer) => peer.to_account_info(),
+            None => ctx.accounts.oft_store.to_account_info(),
+        };
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer { from: ctx.accounts.contributor.to_account_info(), to: target.clone() },
+            ),
+            params.lamports,
+        )?;
+
+        let oft_store = &mut ctx.accounts.oft_store;
+        oft_store.contributed_lamports = oft_store
+            .contributed_lamports
+            .checked_add(params.lamports)
+            .ok_or_else(|| error!(OFTError::AmountOverflow))?;
+        emit!(LamportsToppedUp {
+            account: target.key(),
+            contributor: ctx.accounts.contributor.key(),
+            lamports: params.lamports,
+            contributed_lamports: oft_store.contributed_lamports,
+        });
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct TopUpLamportsParams {
+    pub remote_eid: Option<u32>, // the peer to top up, or None for the store
+    pub lamports: u64,
+}
@@ -1,10 +1,14 @@
 use crate::*;
 use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 
+/// Pays `fee_ld` of whatever the escrow holds above the TVL to any allowed withdraw destination.
+/// Admin only. Use withdraw_fees to pay the treasury within accrued_fees_ld; this remains for
+/// fees collected before the store tracked them, which accrued_fees_ld does not count.
 #[derive(Accounts)]
 pub struct WithdrawFee<'info> {
     pub admin: Signer<'info>,
     #[account(
+        mut,
         seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
         bump = oft_store.bump,
         has_one = admin @OFTError::Unauthorized
@@ -26,7 +30,8 @@ pub struct WithdrawFee<'info> {
     #[account(
         mut,
         token::mint = token_mint,
-        token::token_program = token_program
+        token::token_program = token_program,
+        constraint = oft_store.is_withdraw_destination(&token_dest.owner) @OFTError::WithdrawDestinationNotAllowed
     )]
     pub token_dest: InterfaceAccount<'info, TokenAccount>,
     pub token_program: Interface<'info, TokenInterface>,
@@ -34,10 +39,14 @@ pub struct WithdrawFee<'info> {
 
 impl WithdrawFee<'_> {
     pub fn apply(ctx: &mut Context<WithdrawFee>, params: &WithdrawFeeParams) -> Result<()> {
-        require!(
-            ctx.accounts.token_escrow.amount - ctx.accounts.oft_store.tvl_ld >= params.fee_ld,
-            OFTError::InvalidFee
-        );
+        require!(ctx.accounts.oft_store.fee_stream.is_none(), OFTError::FeeStreamActive);
+        let available_fee_ld = ctx
+            .accounts
+            .token_escrow
+            .amount
+            .checked_sub(ctx.accounts.oft_store.tvl_ld)
+            .ok_or_else(|| error!(OFTError::TvlUnderflow))?;
+        require!(available_fee_ld >= params.fee_ld, OFTError::InvalidFee);
         let seeds: &[&[u8]] = &[
             OFT_SEED,
             &ctx.accounts.token_escrow.key().to_bytes(),
@@ -57,6 +66,7 @@ impl WithdrawFee<'_> {
             params.fee_ld,
             ctx.accounts.token_mint.decimals,
         )?;
+        ctx.accounts.oft_store.pay_out_fees(params.fee_ld);
         Ok(())
     }
 }
@@ -0,0 +1,91 @@
+use crate::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+/// Pays `amount_ld` of the fees accrued in the escrow to the store's treasury. Unlike
+/// withdraw_fee, it is bounded by accrued_fees_ld rather than by whatever the escrow holds above
+/// the TVL, and the fee collector can call it as the treasury is the only destination.
+#[derive(Accounts)]
+pub struct WithdrawFees<'info> {
+    /// admin, or the store's fee collector
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump,
+        constraint = oft_store.is_fee_collector(authority.key) @OFTError::Unauthorized
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    #[account(
+        address = oft_store.token_mint,
+        mint::token_program = token_program
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        address = oft_store.token_escrow,
+        token::authority = oft_store,
+        token::mint = token_mint,
+        token::token_program = token_program
+    )]
+    pub token_escrow: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = oft_store.treasury == Some(treasury.key()) @OFTError::TreasuryNotSet,
+        token::mint = token_mint,
+        token::token_program = token_program,
+        constraint = oft_store.is_withdraw_destination(&treasury.owner) @OFTError::WithdrawDestinationNotAllowed
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl WithdrawFees<'_> {
+    pub fn apply(ctx: &mut Context<WithdrawFees>, params: &WithdrawFeesParams) -> Result<()> {
+        require!(ctx.accounts.oft_store.fee_stream.is_none(), OFTError::FeeStreamActive);
+        // the escrow should always hold the accrued fees above the TVL, but never pay out TVL
+        let available_fee_ld = ctx
+            .accounts
+            .token_escrow
+            .amount
+            .checked_sub(ctx.accounts.oft_store.tvl_ld)
+            .ok_or_else(|| error!(OFTError::TvlUnderflow))?;
+        require!(
+            params.amount_ld <= ctx.accounts.oft_store.accrued_fees_ld
+                && params.amount_ld <= available_fee_ld,
+            OFTError::InsufficientAccruedFees
+        );
+        let seeds: &[&[u8]] = &[
+            OFT_SEED,
+            &ctx.accounts.token_escrow.key().to_bytes(),
+            &[ctx.accounts.oft_store.bump],
+        ];
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.token_escrow.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to: ctx.accounts.treasury.to_account_info(),
+                    authority: ctx.accounts.oft_store.to_account_info(),
+                },
+            )
+            .with_signer(&[&seeds]),
+            params.amount_ld,
+            ctx.accounts.token_mint.decimals,
+        )?;
+        ctx.accounts.oft_store.pay_out_fees(params.amount_ld);
+
+        emit!(FeesWithdrawn {
+            authority: ctx.accounts.authority.key(),
+            treasury: ctx.accounts.treasury.key(),
+            amount_ld: params.amount_ld,
+            accrued_fees_ld: ctx.accounts.oft_store.accrued_fees_ld,
+        });
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct WithdrawFeesParams {
+    pub amount_ld: u64,
+}
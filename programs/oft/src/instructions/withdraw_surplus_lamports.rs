@@ -0,0 +1,70 @@
+use crate::*;
+
+/// Withdraws the lamports the store or one of its peers holds above rent exemption to the admin,
+/// which must be an allowed withdraw destination.
+#[derive(Accounts)]
+#[instruction(params: WithdrawSurplusLamportsParams)]
+pub struct WithdrawSurplusLamports<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [OFT_SEED, oft_store.token_escrow.as_ref()],
+        bump = oft_store.bump,
+        has_one = admin @OFTError::Unauthorized,
+        constraint = oft_store.is_withdraw_destination(&admin.key()) @OFTError::WithdrawDestinationNotAllowed
+    )]
+    pub oft_store: Account<'info, OFTStore>,
+    // Withdraws from the store itself when omitted
+    #[account(
+        mut,
+        seeds = [
+            PEER_SEED,
+            oft_store.key().as_ref(),
+            &params.remote_eid.unwrap_or_default().to_be_bytes()
+        ],
+        bump = peer.bump
+    )]
+    pub peer: Option<Account<'info, PeerConfig>>,
+}
+
+impl WithdrawSurplusLamports<'_> {
+    pub fn apply(
+        ctx: &mut Context<WithdrawSurplusLamports>,
+        params: &WithdrawSurplusLamportsParams,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.peer.is_some() == params.remote_eid.is_some(),
+            OFTError::InvalidLamportsTarget
+        );
+        let source = match &ctx.accounts.peer {
+            Some(peer) => peer.to_account_info(),
+            None => ctx.accounts.oft_store.to_account_info(),
+        };
+        let rent = Rent::get()?.minimum_balance(source.data_len());
+        require!(
+            source.lamports().saturating_sub(rent) >= params.lamports,
+            OFTError::InsufficientSurplusLamports
+        );
+
+        // the source is owned by this program, so it is debited without a system program CPI
+        let admin = ctx.accounts.admin.to_account_info();
+        **source.try_borrow_mut_lamports()? -= params.lamports;
+        **admin.try_borrow_mut_lamports()? = admin
+            .lamports()
+            .checked_add(params.lamports)
+            .ok_or_else(|| error!(OFTError::AmountOverflow))?;
+        emit!(SurplusLamportsWithdrawn {
+            account: source.key(),
+            to: admin.key(),
+            lamports: params.lamports,
+        });
+        Ok(())
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct WithdrawSurplusLamportsParams {
+    pub remote_eid: Option<u32>, // the peer to withdraw from, or None for the store
+    pub lamports: u64,
+}
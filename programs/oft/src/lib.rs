@@ -1,10 +1,13 @@
 use anchor_lang::prelude::*;
 
 pub mod compose_msg_codec;
+pub mod compute_profile;
 pub mod errors;
 pub mod events;
 pub mod instructions;
 pub mod msg_codec;
+pub mod policy;
+pub mod simulation;
 pub mod state;
 
 use errors::*;
@@ -25,6 +28,16 @@ declare_id!(Pubkey::new_from_array(program_id_from_env!(
 pub const OFT_SEED: &[u8] = b"OFT";
 pub const PEER_SEED: &[u8] = b"Peer";
 pub const ENFORCED_OPTIONS_SEED: &[u8] = b"EnforcedOptions";
+pub const PENDING_CREDIT_SEED: &[u8] = b"PendingCredit";
+pub const RATE_LIMIT_OVERRIDE_PROPOSAL_SEED: &[u8] = b"RateLimitOverrideProposal";
+pub const FEE_VAULT_SEED: &[u8] = b"FeeVault";
+pub const INBOUND_OPT_OUT_SEED: &[u8] = b"InboundOptOut";
+pub const RATE_LIMIT_STATE_SEED: &[u8] = b"RateLimitState";
+pub const SEND_RECORD_SEED: &[u8] = b"SendRecord";
+pub const TRANSFER_RECEIPT_SEED: &[u8] = b"TransferReceipt";
+pub const NOTIFICATION_HOOK_SEED: &[u8] = b"NotificationHook";
+pub const EVM_ADDRESS_MAP_SEED: &[u8] = b"EvmAddressMap";
+pub const SENDER_RATE_LIMIT_SEED: &[u8] = b"SenderRateLimit";
 pub const LZ_RECEIVE_TYPES_SEED: &[u8] = oapp::LZ_RECEIVE_TYPES_SEED;
 
 #[program]
@@ -58,10 +71,71 @@ pub mod oft {
         SetPause::apply(&mut ctx, &params)
     }
 
+    pub fn set_control_message_pause(
+        mut ctx: Context<SetControlMessagePause>,
+        params: SetControlMessagePauseParams,
+    ) -> Result<()> {
+        SetControlMessagePause::apply(&mut ctx, &params)
+    }
+
     pub fn withdraw_fee(mut ctx: Context<WithdrawFee>, params: WithdrawFeeParams) -> Result<()> {
         WithdrawFee::apply(&mut ctx, &params)
     }
 
+    pub fn withdraw_fees(mut ctx: Context<WithdrawFees>, params: WithdrawFeesParams) -> Result<()> {
+        WithdrawFees::apply(&mut ctx, &params)
+    }
+
+    pub fn set_inbound_rate_limit(
+        mut ctx: Context<SetInboundRateLimit>,
+        params: SetInboundRateLimitParams,
+    ) -> Result<()> {
+        SetInboundRateLimit::apply(&mut ctx, &params)
+    }
+
+    pub fn set_rate_limit_enabled(
+        mut ctx: Context<SetRateLimitEnabled>,
+        params: SetRateLimitEnabledParams,
+    ) -> Result<()> {
+        SetRateLimitEnabled::apply(&mut ctx, &params)
+    }
+
+    pub fn boost_rate_limit(
+        mut ctx: Context<BoostRateLimit>,
+        params: BoostRateLimitParams,
+    ) -> Result<()> {
+        BoostRateLimit::apply(&mut ctx, &params)
+    }
+
+    pub fn reset_rate_limit_consumption(
+        mut ctx: Context<ResetRateLimitConsumption>,
+        params: ResetRateLimitConsumptionParams,
+    ) -> Result<()> {
+        ResetRateLimitConsumption::apply(&mut ctx, &params)
+    }
+
+    pub fn set_outbound_rate_limit(
+        mut ctx: Context<SetOutboundRateLimit>,
+        params: SetOutboundRateLimitParams,
+    ) -> Result<()> {
+        SetOutboundRateLimit::apply(&mut ctx, &params)
+    }
+
+    pub fn set_rate_limits_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SetRateLimitsBatch<'info>>,
+        params: SetRateLimitsBatchParams,
+    ) -> Result<()> {
+        SetRateLimitsBatch::apply(&ctx, &params)
+    }
+
+    /// Deprecated, use set_outbound_rate_limit.
+    pub fn set_rate_limit(
+        mut ctx: Context<SetOutboundRateLimit>,
+        params: SetRateLimitParams,
+    ) -> Result<()> {
+        SetOutboundRateLimit::apply_deprecated(&mut ctx, &params)
+    }
+
     pub fn manage_rate_limit_override(
         mut ctx: Context<ManageRateLimitOverride>,
         params: ManageRateLimitOverrideAddressParams,
@@ -74,7 +148,128 @@ pub mod oft {
         params: ManageRateLimitOverrideGuidParams,
     ) -> Result<()> {
         ManageRateLimitOverride::apply_guid(&mut ctx, &params)
-    }   
+    }
+
+    pub fn propose_rate_limit_override(
+        mut ctx: Context<ProposeRateLimitOverride>,
+        params: ProposeRateLimitOverrideParams,
+    ) -> Result<()> {
+        ProposeRateLimitOverride::apply(&mut ctx, &params)
+    }
+
+    pub fn confirm_rate_limit_override(mut ctx: Context<ConfirmRateLimitOverride>) -> Result<()> {
+        ConfirmRateLimitOverride::apply(&mut ctx)
+    }
+
+    pub fn migrate_endpoint(
+        mut ctx: Context<MigrateEndpoint>,
+        params: MigrateEndpointParams,
+    ) -> Result<()> {
+        MigrateEndpoint::apply(&mut ctx, &params)
+    }
+
+    pub fn migrate_oft_store(mut ctx: Context<MigrateOFTStore>) -> Result<()> {
+        MigrateOFTStore::apply(&mut ctx)
+    }
+
+    pub fn resize_override_lists(
+        mut ctx: Context<ResizeOverrideLists>,
+        params: ResizeOverrideListsParams,
+    ) -> Result<()> {
+        ResizeOverrideLists::apply(&mut ctx, &params)
+    }
+
+    pub fn set_evm_address_mapping(
+        mut ctx: Context<SetEvmAddressMapping>,
+        params: SetEvmAddressMappingParams,
+    ) -> Result<()> {
+        SetEvmAddressMapping::apply(&mut ctx, &params)
+    }
+
+    pub fn mirror_override_list(
+        mut ctx: Context<MirrorOverrideList>,
+        params: MirrorOverrideListParams,
+    ) -> Result<()> {
+        MirrorOverrideList::apply(&mut ctx, &params)
+    }
+
+    pub fn challenge_peer(
+        mut ctx: Context<ChallengePeer>,
+        params: ChallengePeerParams,
+    ) -> Result<()> {
+        ChallengePeer::apply(&mut ctx, &params)
+    }
+
+    pub fn send_pause_notice(
+        mut ctx: Context<SendPauseNotice>,
+        params: SendPauseNoticeParams,
+    ) -> Result<()> {
+        SendPauseNotice::apply(&mut ctx, &params)
+    }
+
+    pub fn migrate_peer_config(
+        mut ctx: Context<MigratePeerConfig>,
+        params: MigratePeerConfigParams,
+    ) -> Result<()> {
+        MigratePeerConfig::apply(&mut ctx, &params)
+    }
+
+    pub fn burn_from_escrow(
+        mut ctx: Context<BurnFromEscrow>,
+        params: BurnFromEscrowParams,
+    ) -> Result<()> {
+        BurnFromEscrow::apply(&mut ctx, &params)
+    }
+
+    pub fn set_withdraw_destinations(
+        mut ctx: Context<SetWithdrawDestinations>,
+        params: SetWithdrawDestinationsParams,
+    ) -> Result<()> {
+        SetWithdrawDestinations::apply(&mut ctx, &params)
+    }
+
+    pub fn manage_fee_vault(
+        mut ctx: Context<ManageFeeVault>,
+        params: ManageFeeVaultParams,
+    ) -> Result<()> {
+        ManageFeeVault::apply(&mut ctx, &params)
+    }
+
+    pub fn manage_fee_exempt(
+        mut ctx: Context<ManageFeeExempt>,
+        params: ManageFeeExemptParams,
+    ) -> Result<()> {
+        ManageFeeExempt::apply(&mut ctx, &params)
+    }
+
+    pub fn top_up_lamports(
+        mut ctx: Context<TopUpLamports>,
+        params: TopUpLamportsParams,
+    ) -> Result<()> {
+        TopUpLamports::apply(&mut ctx, &params)
+    }
+
+    pub fn withdraw_surplus_lamports(
+        mut ctx: Context<WithdrawSurplusLamports>,
+        params: WithdrawSurplusLamportsParams,
+    ) -> Result<()> {
+        WithdrawSurplusLamports::apply(&mut ctx, &params)
+    }
+
+    pub fn resolve_pending_credit(
+        mut ctx: Context<ResolvePendingCredit>,
+        params: ResolvePendingCreditParams,
+    ) -> Result<()> {
+        ResolvePendingCredit::apply(&mut ctx, &params)
+    }
+
+    pub fn claim_pending_credit(mut ctx: Context<ResolvePendingCredit>) -> Result<()> {
+        ResolvePendingCredit::apply_claim(&mut ctx)
+    }
+
+    pub fn execute_pending_credit(mut ctx: Context<ExecutePendingCredit>) -> Result<()> {
+        ExecutePendingCredit::apply(&mut ctx)
+    }
 
     // ============================== Public ==============================
 
@@ -86,6 +281,20 @@ pub mod oft {
         QuoteSend::apply(&ctx, &params)
     }
 
+    pub fn simulate_send(
+        ctx: Context<SimulateSend>,
+        params: SimulateSendParams,
+    ) -> Result<SendSimulation> {
+        SimulateSend::apply(&ctx, &params)
+    }
+
+    pub fn simulate_receive(
+        ctx: Context<SimulateReceive>,
+        params: SimulateReceiveParams,
+    ) -> Result<ReceiveSimulation> {
+        SimulateReceive::apply(&ctx, &params)
+    }
+
     pub fn send(
         mut ctx: Context<Send>,
         params: SendParams,
@@ -93,6 +302,56 @@ pub mod oft {
         Send::apply(&mut ctx, &params)
     }
 
+    pub fn send_with_fee_vault<'info>(
+        mut ctx: Context<'_, '_, '_, 'info, SendWithFeeVault<'info>>,
+        params: SendParams,
+    ) -> Result<(MessagingReceipt, OFTReceipt)> {
+        SendWithFeeVault::apply(&mut ctx, &params)
+    }
+
+    pub fn register_inbound_opt_out(mut ctx: Context<RegisterInboundOptOut>) -> Result<()> {
+        RegisterInboundOptOut::apply(&mut ctx)
+    }
+
+    pub fn remove_inbound_opt_out(mut ctx: Context<RemoveInboundOptOut>) -> Result<()> {
+        RemoveInboundOptOut::apply(&mut ctx)
+    }
+
+    pub fn register_notification_hook(mut ctx: Context<RegisterNotificationHook>) -> Result<()> {
+        RegisterNotificationHook::apply(&mut ctx)
+    }
+
+    pub fn remove_notification_hook(mut ctx: Context<RemoveNotificationHook>) -> Result<()> {
+        RemoveNotificationHook::apply(&mut ctx)
+    }
+
+    pub fn sync_rate_limit_state(
+        mut ctx: Context<SyncRateLimitState>,
+        params: SyncRateLimitStateParams,
+    ) -> Result<()> {
+        SyncRateLimitState::apply(&mut ctx, &params)
+    }
+
+    pub fn close_send_record(mut ctx: Context<CloseSendRecord>) -> Result<()> {
+        CloseSendRecord::apply(&mut ctx)
+    }
+
+    pub fn close_transfer_receipt(mut ctx: Context<CloseTransferReceipt>) -> Result<()> {
+        CloseTransferReceipt::apply(&mut ctx)
+    }
+
+    pub fn sweep_dust(mut ctx: Context<SweepDust>) -> Result<()> {
+        SweepDust::apply(&mut ctx)
+    }
+
+    pub fn test_mint(mut ctx: Context<TestMint>, params: TestMintParams) -> Result<()> {
+        TestMint::apply(&mut ctx, &params)
+    }
+
+    pub fn crank(mut ctx: Context<Crank>, params: CrankParams) -> Result<()> {
+        Crank::apply(&mut ctx, &params)
+    }
+
     pub fn lz_receive(mut ctx: Context<LzReceive>, params: LzReceiveParams) -> Result<()> {
         LzReceive::apply(&mut ctx, &params)
     }
@@ -103,6 +362,55 @@ pub mod oft {
     ) -> Result<Vec<oapp::endpoint_cpi::LzAccount>> {
         LzReceiveTypes::apply(&ctx, &params)
     }
+
+    pub fn get_rate_limit_overrides(
+        ctx: Context<GetRateLimitOverrides>,
+    ) -> Result<RateLimitOverrides> {
+        GetRateLimitOverrides::apply(&ctx)
+    }
+
+    pub fn check_remote_config(
+        ctx: Context<CheckRemoteConfig>,
+        params: CheckRemoteConfigParams,
+    ) -> Result<bool> {
+        CheckRemoteConfig::apply(&ctx, &params)
+    }
+
+    pub fn get_oft_metadata(ctx: Context<GetOFTMetadata>) -> Result<OFTMetadataView> {
+        GetOFTMetadata::apply(&ctx)
+    }
+
+    pub fn get_dust(ctx: Context<GetDust>) -> Result<Dust> {
+        GetDust::apply(&ctx)
+    }
+
+    pub fn get_pending_credit_address(
+        ctx: Context<GetPendingCreditAddress>,
+        params: GetPendingCreditAddressParams,
+    ) -> Result<Pubkey> {
+        GetPendingCreditAddress::apply(&ctx, &params)
+    }
+
+    pub fn get_peer_health(
+        ctx: Context<GetPeerHealth>,
+        params: GetPeerHealthParams,
+    ) -> Result<PeerHealth> {
+        GetPeerHealth::apply(&ctx, &params)
+    }
+
+    pub fn get_in_flight(
+        ctx: Context<GetInFlight>,
+        params: GetInFlightParams,
+    ) -> Result<InFlightReport> {
+        GetInFlight::apply(&ctx, &params)
+    }
+
+    pub fn get_rate_limit_capacity(
+        ctx: Context<GetRateLimitCapacity>,
+        params: GetRateLimitCapacityParams,
+    ) -> Result<RateLimitCapacities> {
+        GetRateLimitCapacity::apply(&ctx, &params)
+    }
 }
 
 #[derive(Accounts)]
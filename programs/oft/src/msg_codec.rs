@@ -3,6 +3,7 @@ use crate::*;
 const SEND_TO_OFFSET: usize = 0;
 const SEND_AMOUNT_SD_OFFSET: usize = 32;
 const COMPOSE_MSG_OFFSET: usize = 40;
+const COMPOSE_FROM_LEN: usize = 32;
 
 pub fn encode(
     send_to: [u8; 32],
@@ -15,7 +16,7 @@ pub fn encode(
         encoded.extend_from_slice(&send_to);
         encoded.extend_from_slice(&amount_sd.to_be_bytes());
         encoded.extend_from_slice(sender.to_bytes().as_ref());
-        encoded.extend_from_slice(&msg);
+        encoded.extend_from_slice(msg);
         encoded
     } else {
         let mut encoded = Vec::with_capacity(40); // 32 + 8
@@ -25,6 +26,17 @@ pub fn encode(
     }
 }
 
+/// Checks the message is long enough to be decoded: either a plain transfer or a transfer
+/// followed by [composeFrom][composeMsg].
+pub fn validate(message: &[u8]) -> Result<()> {
+    require!(
+        message.len() == COMPOSE_MSG_OFFSET
+            || message.len() >= COMPOSE_MSG_OFFSET + COMPOSE_FROM_LEN,
+        OFTError::InvalidMessage
+    );
+    Ok(())
+}
+
 pub fn send_to(message: &[u8]) -> [u8; 32] {
     let mut send_to = [0; 32];
     send_to.copy_from_slice(&message[SEND_TO_OFFSET..SEND_AMOUNT_SD_OFFSET]);
@@ -44,3 +56,173 @@ pub fn compose_msg(message: &[u8]) -> Option<Vec<u8>> {
         None
     }
 }
+
+/// The LayerZero message type of a transfer, MSG_TYPE_SEND_AND_CALL when it carries a compose
+/// message and MSG_TYPE_SEND otherwise.
+pub fn msg_type(composed: bool) -> u8 {
+    if composed {
+        MSG_TYPE_SEND_AND_CALL
+    } else {
+        MSG_TYPE_SEND
+    }
+}
+
+/// The source sender embedded in a composed transfer. Plain transfers do not carry one.
+pub fn compose_from(message: &[u8]) -> Option<[u8; 32]> {
+    if message.len() >= COMPOSE_MSG_OFFSET + COMPOSE_FROM_LEN {
+        let mut compose_from = [0; 32];
+        compose_from
+            .copy_from_slice(&message[COMPOSE_MSG_OFFSET..COMPOSE_MSG_OFFSET + COMPOSE_FROM_LEN]);
+        Some(compose_from)
+    } else {
+        None
+    }
+}
+
+/// keccak256 of the full encoded message, matching the hash computed on EVM chains.
+pub fn hash(message: &[u8]) -> [u8; 32] {
+    anchor_lang::solana_program::keccak::hash(message).to_bytes()
+}
+
+/// send_to of every control message, the messages peers send besides token transfers. A control
+/// message is a zero amount transfer to CONTROL_SEND_TO composed by the sending OFT itself, which
+/// no user can send from, whose compose message is [controlType (1)][payload]. Deployments unaware
+/// of it credit nothing.
+pub const CONTROL_SEND_TO: [u8; 32] = *b"gmx-oft:control-message\0\0\0\0\0\0\0\0\0";
+const CONTROL_TYPE_OFFSET: usize = COMPOSE_MSG_OFFSET + COMPOSE_FROM_LEN;
+
+/// The rate limit override list mirrored to the EVM deployments, see encode_override_list.
+pub const CONTROL_OVERRIDE_LIST: u8 = 1;
+/// A control challenge or its echo, see encode_peer_challenge.
+pub const CONTROL_PEER_CHALLENGE: u8 = 2;
+/// Guid overrides added by the governance peer, see encode_override_guids.
+pub const CONTROL_OVERRIDE_GUIDS: u8 = 3;
+/// A peer's notice that it has paused, see encode_pause_notice.
+pub const CONTROL_PAUSE_NOTICE: u8 = 4;
+/// A peer's acknowledgement of a delivery, see encode_delivery_ack.
+pub const CONTROL_DELIVERY_ACK: u8 = 5;
+
+const OVERRIDE_GUID_ENTRY_LEN: usize = 36; // [guid (32)][srcEid (4)]
+
+/// A control message received from a peer.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Control {
+    PeerChallenge([u8; 32]),
+    OverrideGuids(Vec<([u8; 32], Option<u32>)>),
+    PauseNotice(i64),
+    DeliveryAck { guid: [u8; 32], amount_sd: u64 },
+}
+
+impl Control {
+    /// The ControlMessage under which the store pauses it.
+    pub fn message(&self) -> ControlMessage {
+        match self {
+            Control::PeerChallenge(_) => ControlMessage::PeerChallengeEcho,
+            Control::OverrideGuids(_) => ControlMessage::OverrideGuids,
+            Control::PauseNotice(_) => ControlMessage::PauseNotice,
+            Control::DeliveryAck { .. } => ControlMessage::DeliveryAck,
+        }
+    }
+}
+
+/// Encodes a control message of `control_type` composed by `sender`.
+pub fn encode_control(sender: [u8; 32], control_type: u8, payload: &[u8]) -> Vec<u8> {
+    encode(CONTROL_SEND_TO, 0, Pubkey::from(sender), &Some([&[control_type], payload].concat()))
+}
+
+/// Whether a validated message is a control message sent by `peer_address` itself rather than a
+/// token transfer.
+pub fn is_control(message: &[u8], peer_address: &[u8; 32]) -> bool {
+    send_to(message) == CONTROL_SEND_TO
+        && amount_sd(message) == 0
+        && compose_from(message).as_ref() == Some(peer_address)
+}
+
+/// The control message sent by `peer_address`, or None for a token transfer. Fails with
+/// InvalidMessage if its type is unknown or its payload malformed.
+pub fn decode_control(message: &[u8], peer_address: &[u8; 32]) -> Result<Option<Control>> {
+    if !is_control(message, peer_address) {
+        return Ok(None);
+    }
+    let (control_type, payload) =
+        message[CONTROL_TYPE_OFFSET..].split_first().ok_or(OFTError::InvalidMessage)?;
+    let control = match *control_type {
+        CONTROL_PEER_CHALLENGE => Control::PeerChallenge(
+            <[u8; 32]>::try_from(payload).map_err(|_| OFTError::InvalidMessage)?,
+        ),
+        CONTROL_PAUSE_NOTICE => Control::PauseNotice(i64::from_be_bytes(
+            <[u8; 8]>::try_from(payload).map_err(|_| OFTError::InvalidMessage)?,
+        )),
+        CONTROL_DELIVERY_ACK => {
+            require!(payload.len() == 40, OFTError::InvalidMessage);
+            let mut guid = [0; 32];
+            guid.copy_from_slice(&payload[..32]);
+            let mut amount_sd = [0; 8];
+            amount_sd.copy_from_slice(&payload[32..]);
+            Control::DeliveryAck { guid, amount_sd: u64::from_be_bytes(amount_sd) }
+        },
+        CONTROL_OVERRIDE_GUIDS => {
+            require!(
+                !payload.is_empty() && payload.len() % OVERRIDE_GUID_ENTRY_LEN == 0,
+                OFTError::InvalidMessage
+            );
+            let entries = payload
+                .chunks(OVERRIDE_GUID_ENTRY_LEN)
+                .map(|entry| {
+                    let mut guid = [0; 32];
+                    guid.copy_from_slice(&entry[..32]);
+                    let mut src_eid = [0; 4];
+                    src_eid.copy_from_slice(&entry[32..]);
+                    let src_eid = u32::from_be_bytes(src_eid);
+                    (guid, Some(src_eid).filter(|src_eid| *src_eid != 0))
+                })
+                .collect();
+            Control::OverrideGuids(entries)
+        },
+        _ => return err!(OFTError::InvalidMessage),
+    };
+    Ok(Some(control))
+}
+
+/// Encodes the mirrored override list as a CONTROL_OVERRIDE_LIST control message composed by the
+/// OFT store itself. The EVM adapters apply it only when the compose sender is their peer. Each
+/// entry is packed as [evmAddress (20)][isExempt (1)].
+pub fn encode_override_list(oft_store: Pubkey, entries: &[([u8; 20], bool)]) -> Vec<u8> {
+    let mut payload = Vec::with_capacity(entries.len() * 21);
+    for (evm_address, is_exempt) in entries {
+        payload.extend_from_slice(evm_address);
+        payload.push(*is_exempt as u8);
+    }
+    encode_control(oft_store.to_bytes(), CONTROL_OVERRIDE_LIST, &payload)
+}
+
+/// Encodes guid overrides as a CONTROL_OVERRIDE_GUIDS control message composed by `sender`, which
+/// has to be the governance peer itself. Each entry is packed as [guid (32)][srcEid (4)], a srcEid
+/// of 0 matching a delivery from any source.
+pub fn encode_override_guids(sender: [u8; 32], entries: &[([u8; 32], u32)]) -> Vec<u8> {
+    let mut payload = Vec::with_capacity(entries.len() * OVERRIDE_GUID_ENTRY_LEN);
+    for (guid, src_eid) in entries {
+        payload.extend_from_slice(guid);
+        payload.extend_from_slice(&src_eid.to_be_bytes());
+    }
+    encode_control(sender, CONTROL_OVERRIDE_GUIDS, &payload)
+}
+
+/// Encodes a control challenge as a CONTROL_PEER_CHALLENGE control message composed by `sender`
+/// with the 32 byte nonce as its payload. The peer echoes it in the same format, composed by
+/// itself.
+pub fn encode_peer_challenge(sender: [u8; 32], nonce: [u8; 32]) -> Vec<u8> {
+    encode_control(sender, CONTROL_PEER_CHALLENGE, &nonce)
+}
+
+/// Encodes a pause notice as a CONTROL_PAUSE_NOTICE control message composed by `sender`, with the
+/// unix timestamp it was sent at as an 8 byte big-endian payload.
+pub fn encode_pause_notice(sender: [u8; 32], sent_at: i64) -> Vec<u8> {
+    encode_control(sender, CONTROL_PAUSE_NOTICE, &sent_at.to_be_bytes())
+}
+
+/// Encodes the acknowledgement of the delivery of transfer `guid` of `amount_sd` as a
+/// CONTROL_DELIVERY_ACK control message composed by `sender`, the OFT it was delivered to.
+pub fn encode_delivery_ack(sender: [u8; 32], guid: [u8; 32], amount_sd: u64) -> Vec<u8> {
+    encode_control(sender, CONTROL_DELIVERY_ACK, &[&guid[..], &amount_sd.to_be_bytes()].concat())
+}
@@ -0,0 +1,554 @@
+//! Transfer policies shared by the send and receive paths.
+//!
+//! Policies are evaluated in this order, and each one fails with its own error:
+//!
+//! 1. pause          - `OFTError::Paused`, `OFTError::PeerPaused` (the peer alone, also set by
+//!                     its breach monitor)
+//! 2. endpoint       - `OFTError::EndpointMigrationPending` (outbound), `OFTError::PeerNotVerified`,
+//!                     `OFTError::PeerControlNotProven` (outbound, until challenge_peer is echoed)
+//! 3. destination    - `OFTError::DstEidNotAllowed` (outbound)
+//! 4. flow tag       - `OFTError::UnauthorizedFlowTag` (outbound)
+//! 5. recipient      - `OFTError::InvalidRecipient` (outbound)
+//! 6. nonce gap      - `OFTError::NonceGapDetected` (inbound)
+//! 7. slippage       - `OFTError::SlippageExceeded` (outbound, after fees are applied)
+//! 8. source sender  - `OFTError::SourceSenderNotAllowed` (inbound composed transfers)
+//! 9. zero amount    - `OFTError::ZeroAmountMessage` (inbound, under ZeroAmountPolicy::Reject;
+//!                     ignored messages are cleared here and message-only ones skip the rest)
+//! 10. probation     - `OFTError::ProbationLimitExceeded` (newly added peers only)
+//! 11. launch cap    - `OFTError::LaunchLimitExceeded` (while a launch stage is in effect, which
+//!                     also sets every peer's rate limits; overridden transfers are exempt)
+//! 12. rate limit    - `OFTError::RateLimitExceeded` (per peer and direction, netted: transfers
+//!                     in one direction refill the limit of the other; inbound is lifted in test
+//!                     mode; also per peer, direction and message type where set; outbound is
+//!                     also capped per sender across all peers by the store's sender_rate_limit)
+//! 13. credit hold   - `OFTError::InvalidPendingCredit` (inbound)
+//!
+//! Before any of them, lz_receive driven by an executor off the peer's executor_allowlist is
+//! skipped with an ExecutorRejected event rather than failed, so the rejection is visible on-chain
+//! and the message can still be executed by an allowed executor.
+//!
+//! Quotes never fail on a policy: quote_oft reports every outbound policy that would reject the
+//! transfer as a SendBlocker, so integrators can explain the rejection before submitting.
+//! simulate_send and simulate_receive evaluate all of them, except the nonce gap, without
+//! changing any state, and fail with the first rejection.
+use crate::*;
+
+pub const FLOW_TAG_USER: u8 = 0;
+// Tags from here up mark treasury flows and are reserved for the store's fee_vault_senders.
+pub const FLOW_TAG_PRIVILEGED_MIN: u8 = 128;
+
+/// Pause, endpoint and destination policies for anything sent to `peer` on `dst_eid`.
+pub fn check_send(oft_store: &OFTStore, peer: &PeerConfig, dst_eid: u32) -> Result<()> {
+    check_paused(oft_store)?;
+    check_peer_paused(peer)?;
+    require!(!oft_store.is_endpoint_migration_pending(), OFTError::EndpointMigrationPending);
+    check_peer_verified(oft_store, peer)?;
+    require!(peer.control_proven, OFTError::PeerControlNotProven);
+    require!(oft_store.is_allowed_dst_eid(dst_eid), OFTError::DstEidNotAllowed);
+    Ok(())
+}
+
+/// An outbound policy that would currently reject a send, see send_blockers.
+#[derive(Clone, AnchorSerialize, AnchorDeserialize, PartialEq, Eq, Debug)]
+pub enum SendBlocker {
+    Paused,
+    EndpointMigrationPending,
+    PeerNotVerified,      // re-verification after an endpoint migration is pending
+    PeerNotSet,           // the peer config exists but has no remote address yet
+    PeerControlNotProven, // the peer has not echoed a challenge_peer nonce yet
+    DstEidNotAllowed,
+    InvalidRecipient,
+    SlippageExceeded,
+    ProbationLimitExceeded,
+    LaunchLimitExceeded { max_amount_ld: u64 },
+    RateLimitExceeded { available_ld: u64 },
+    PeerPaused,
+}
+
+/// Every outbound policy that would reject sending `amount_received_ld` to `to` on `dst_eid`
+/// now, in policy order. The sender is not known, so flow tags and rate limit overrides are not
+/// taken into account: an overridden sender may still send past RateLimitExceeded.
+pub fn send_blockers(
+    oft_store: &OFTStore,
+    peer: &PeerConfig,
+    dst_eid: u32,
+    to: &[u8; 32],
+    amount_received_ld: u64,
+    min_amount_ld: u64,
+) -> Result<Vec<SendBlocker>> {
+    let mut blockers = vec![];
+    if check_paused(oft_store).is_err() {
+        blockers.push(SendBlocker::Paused);
+    }
+    if check_peer_paused(peer).is_err() {
+        blockers.push(SendBlocker::PeerPaused);
+    }
+    if oft_store.is_endpoint_migration_pending() {
+        blockers.push(SendBlocker::EndpointMigrationPending);
+    }
+    if check_peer_verified(oft_store, peer).is_err() {
+        blockers.push(SendBlocker::PeerNotVerified);
+    }
+    if peer.peer_address == [0; 32] {
+        blockers.push(SendBlocker::PeerNotSet);
+    } else if !peer.control_proven {
+        blockers.push(SendBlocker::PeerControlNotProven);
+    }
+    if !oft_store.is_allowed_dst_eid(dst_eid) {
+        blockers.push(SendBlocker::DstEidNotAllowed);
+    }
+    if check_recipient(peer, to).is_err() {
+        blockers.push(SendBlocker::InvalidRecipient);
+    }
+    if check_slippage(amount_received_ld, min_amount_ld).is_err() {
+        blockers.push(SendBlocker::SlippageExceeded);
+    }
+    if check_probation(oft_store, peer, amount_received_ld).is_err() {
+        blockers.push(SendBlocker::ProbationLimitExceeded);
+    }
+    let stage = oft_store.launch_stage(Clock::get()?.unix_timestamp);
+    if let Some(stage) = stage.filter(|stage| amount_received_ld > stage.max_amount_ld) {
+        blockers.push(SendBlocker::LaunchLimitExceeded { max_amount_ld: stage.max_amount_ld });
+    }
+    let mut peer = peer.clone();
+    refresh_rate_limits(oft_store, &mut peer)?;
+    if let Some(rate_limiter) = &peer.outbound_rate_limiter {
+        let now = peer.outbound_rate_limit_mode.now(&Clock::get()?);
+        let available_ld =
+            simulation::available_ld(rate_limiter, peer.outbound_rate_limit_mode, now);
+        if available_ld < amount_received_ld {
+            blockers.push(SendBlocker::RateLimitExceeded { available_ld });
+        }
+    }
+    Ok(blockers)
+}
+
+/// Zero amount policy for an inbound message worth `amount_ld`. Returns false when the message
+/// is to be cleared without being delivered.
+pub fn check_zero_amount(oft_store: &OFTStore, amount_ld: u64) -> Result<bool> {
+    if amount_ld > 0 {
+        return Ok(true);
+    }
+    match oft_store.zero_amount_policy {
+        ZeroAmountPolicy::MessageOnly => Ok(true),
+        ZeroAmountPolicy::Ignore => Ok(false),
+        ZeroAmountPolicy::Reject => err!(OFTError::ZeroAmountMessage),
+    }
+}
+
+/// Returns whether `executor` may execute messages from `peer`.
+pub fn check_executor(peer: &PeerConfig, executor: &Pubkey) -> bool {
+    peer.is_allowed_executor(executor)
+}
+
+/// Pause, endpoint and nonce gap policies for a message executed from `peer`. The nonce is
+/// recorded even when the message is let through with a NonceGapDetected event.
+pub fn check_receive(
+    oft_store: &OFTStore,
+    peer: &mut PeerConfig,
+    src_eid: u32,
+    nonce: u64,
+) -> Result<()> {
+    check_paused(oft_store)?;
+    check_peer_paused(peer)?;
+    check_peer_verified(oft_store, peer)?;
+    peer.record_inbound_at(Clock::get()?.unix_timestamp);
+    if let Some(highest_inbound_nonce) = peer.record_inbound_nonce(nonce) {
+        emit!(NonceGapDetected { src_eid, nonce, highest_inbound_nonce });
+        let hold = peer.nonce_gap_monitor.as_ref().map_or(false, |m| m.hold);
+        require!(!hold, OFTError::NonceGapDetected);
+    }
+    Ok(())
+}
+
+/// Returns whether the send is a privileged (treasury) flow, which is exempt from the OFT fee.
+/// Tags below FLOW_TAG_PRIVILEGED_MIN are free for anyone to use and only labelled in OFTSent.
+pub fn check_flow_tag(oft_store: &OFTStore, sender: &Pubkey, flow_tag: u8) -> Result<bool> {
+    if flow_tag < FLOW_TAG_PRIVILEGED_MIN {
+        return Ok(false);
+    }
+    require!(oft_store.is_fee_vault_sender(sender), OFTError::UnauthorizedFlowTag);
+    Ok(true)
+}
+
+/// The fee bps a send from `sender` is charged: none for privileged flows and fee exempt senders,
+/// the peer's otherwise, None falling back to the store's default.
+pub fn send_fee_bps(
+    oft_store: &OFTStore,
+    peer: &PeerConfig,
+    sender: &Pubkey,
+    privileged_flow: bool,
+) -> Option<u16> {
+    if privileged_flow || oft_store.is_fee_exempt(sender) {
+        Some(0)
+    } else {
+        peer.fee_bps
+    }
+}
+
+/// Tokens sent to the zero address or to the remote OFT itself can never be recovered.
+pub fn check_recipient(peer: &PeerConfig, to: &[u8; 32]) -> Result<()> {
+    require!(*to != [0; 32] && *to != peer.peer_address, OFTError::InvalidRecipient);
+    Ok(())
+}
+
+pub fn check_paused(oft_store: &OFTStore) -> Result<()> {
+    require!(!oft_store.paused, OFTError::Paused);
+    Ok(())
+}
+
+pub fn check_control_message_paused(oft_store: &OFTStore, message: ControlMessage) -> Result<()> {
+    require!(!oft_store.is_control_message_paused(message), OFTError::ControlMessagePaused);
+    Ok(())
+}
+
+pub fn check_peer_paused(peer: &PeerConfig) -> Result<()> {
+    require!(!peer.paused, OFTError::PeerPaused);
+    Ok(())
+}
+
+pub fn check_slippage(amount_received_ld: u64, min_amount_ld: u64) -> Result<()> {
+    require!(amount_received_ld >= min_amount_ld, OFTError::SlippageExceeded);
+    Ok(())
+}
+
+/// Checks the source sender embedded in `message` against the peer's allowlist and returns
+/// whether the credit must be held because the allowlist quarantines unknown senders.
+pub fn check_source_sender(peer: &PeerConfig, message: &[u8]) -> Result<bool> {
+    let (allowlist, compose_from) =
+        match (&peer.source_sender_allowlist, msg_codec::compose_from(message)) {
+            (Some(allowlist), Some(compose_from)) => (allowlist, compose_from),
+            _ => return Ok(false),
+        };
+    if allowlist.senders.contains(&compose_from) {
+        return Ok(false);
+    }
+    require!(allowlist.quarantine, OFTError::SourceSenderNotAllowed);
+    Ok(true)
+}
+
+/// Returns whether a composed credit must be held because the recipient, which the compose
+/// message is delivered to, is not one of the store's allowed composers.
+pub fn check_composer(oft_store: &OFTStore, to_address: &Pubkey, message: &[u8]) -> bool {
+    msg_codec::compose_msg(message).is_some() && !oft_store.is_allowed_composer(to_address)
+}
+
+/// Caps transfers with a peer still on probation and returns whether an inbound credit of
+/// `amount_ld` must be held for the credit verifier.
+pub fn check_probation(oft_store: &OFTStore, peer: &PeerConfig, amount_ld: u64) -> Result<bool> {
+    let policy = match &oft_store.probation_policy {
+        Some(policy) if peer.is_on_probation(Clock::get()?.unix_timestamp) => policy,
+        _ => return Ok(false),
+    };
+    require!(amount_ld <= policy.max_amount_ld, OFTError::ProbationLimitExceeded);
+    Ok(amount_ld > policy.hold_above_ld)
+}
+
+/// Brings the peer's rate limiters up to date before they are used: undoes expired boosts, then
+/// applies the launch stage.
+pub fn refresh_rate_limits(oft_store: &OFTStore, peer: &mut PeerConfig) -> Result<()> {
+    peer.expire_rate_limit_boosts(Clock::get()?.unix_timestamp)?;
+    apply_launch_stage(oft_store, peer)
+}
+
+/// While a launch stage is in effect, sets the peer's rate limiters in both directions to the
+/// stage's, creating them full for directions without one. Raising the capacity adds the
+/// difference to what is available, lowering it caps what is available.
+pub fn apply_launch_stage(oft_store: &OFTStore, peer: &mut PeerConfig) -> Result<()> {
+    let clock = Clock::get()?;
+    let stage = match oft_store.launch_stage(clock.unix_timestamp) {
+        Some(stage) => stage,
+        None => return Ok(()),
+    };
+    for (rate_limiter, mode) in [
+        (&mut peer.outbound_rate_limiter, peer.outbound_rate_limit_mode),
+        (&mut peer.inbound_rate_limiter, peer.inbound_rate_limit_mode),
+    ] {
+        let refill_per_second = mode.refill_rate(stage.refill_per_second);
+        let rate_limiter = rate_limiter.get_or_insert_with(|| RateLimiter {
+            capacity: stage.capacity,
+            tokens: stage.capacity,
+            refill_per_second,
+            last_refill_time: mode.now(&clock),
+        });
+        if rate_limiter.capacity != stage.capacity
+            || rate_limiter.refill_per_second != refill_per_second
+        {
+            let added = stage.capacity.saturating_sub(rate_limiter.capacity);
+            rate_limiter.refill(mode, 0)?;
+            rate_limiter.tokens =
+                simulation::refilled_tokens(rate_limiter.tokens, added as u128, stage.capacity);
+            rate_limiter.capacity = stage.capacity;
+            rate_limiter.refill_per_second = refill_per_second;
+        }
+    }
+    Ok(())
+}
+
+/// Caps a single transfer while a launch stage is in effect.
+pub fn check_launch_cap(oft_store: &OFTStore, amount_ld: u64) -> Result<()> {
+    if let Some(stage) = oft_store.launch_stage(Clock::get()?.unix_timestamp) {
+        require!(amount_ld <= stage.max_amount_ld, OFTError::LaunchLimitExceeded);
+    }
+    Ok(())
+}
+
+/// Charges the peer's outbound rate limiter, if set with set_outbound_rate_limit or by the launch
+/// schedule, and checks the launch cap, unless the sender, the send's debit authority, is
+/// overridden. Overridden sends are
+/// still deducted, without being checked, if the store's overrides consume capacity. Senders
+/// overridden with a multiplier are charged the scaled down amount. Sends of `msg_type` are also
+/// charged the peer's outbound rate limiter for that message type, if set. Peers with rate
+/// limiting disabled are not charged.
+pub fn consume_outbound_rate_limit(
+    oft_store: &mut OFTStore,
+    peer: &mut PeerConfig,
+    dst_eid: u32,
+    sender: &Pubkey,
+    msg_type: u8,
+    amount_ld: u64,
+) -> Result<()> {
+    if !peer.rate_limit_enabled {
+        return Ok(());
+    }
+    refresh_rate_limits(oft_store, peer)?;
+    if oft_store.is_rate_limit_override(sender, RateLimitDirection::Outbound)? {
+        emit!(RateLimitOverrideTriggered {
+            address: *sender,
+            amount_ld,
+            remote_eid: dst_eid,
+            direction: RateLimitDirection::Outbound,
+            available_ld: available_now_ld(
+                peer.outbound_rate_limiter.as_ref(),
+                peer.outbound_rate_limit_mode,
+            )?,
+        });
+        oft_store.record_override_trigger(amount_ld);
+        if oft_store.overrides_consume_capacity {
+            let mode = peer.outbound_rate_limit_mode;
+            if let Some(rate_limiter) = peer.outbound_rate_limiter.as_mut() {
+                rate_limiter.deduct(mode, amount_ld)?;
+            }
+            if let Some(rate_limiter) =
+                peer.msg_type_rate_limiter_mut(msg_type, RateLimitDirection::Outbound)
+            {
+                rate_limiter.deduct(mode, amount_ld)?;
+            }
+        }
+        return Ok(());
+    }
+    let multiplier = oft_store.override_factor(sender, RateLimitDirection::Outbound)?;
+    let charge_ld = simulation::override_charge_ld(amount_ld, multiplier);
+    check_launch_cap(oft_store, charge_ld)?;
+    let reserved_ld = reserved_capacity_ld(peer, multiplier);
+    let mode = peer.outbound_rate_limit_mode;
+    let direction = RateLimitDirection::Outbound;
+    if let Some(rate_limiter) = peer.outbound_rate_limiter.as_mut() {
+        try_consume_unreserved(rate_limiter, mode, charge_ld, reserved_ld)?;
+    }
+    if let Some(rate_limiter) = peer.msg_type_rate_limiter_mut(msg_type, direction) {
+        rate_limiter.try_consume(mode, charge_ld)?;
+    }
+    Ok(())
+}
+
+/// What a transfer has to leave of the peer's rate limiters: public transfers cannot use the
+/// peer's reserved capacity, which is kept for addresses overridden with a multiplier and for
+/// overridden transfers deducted when the store's overrides consume capacity.
+pub fn reserved_capacity_ld(peer: &PeerConfig, multiplier: Option<u16>) -> u64 {
+    match multiplier {
+        Some(_) => 0,
+        None => peer.reserved_capacity_ld,
+    }
+}
+
+/// Outbound sends free up the inbound capacity they consumed outbound, none for senders
+/// overridden outbound unless the store's overrides consume capacity.
+pub fn refill_rate_limit(
+    oft_store: &OFTStore,
+    peer: &mut PeerConfig,
+    sender: &Pubkey,
+    amount_ld: u64,
+) -> Result<()> {
+    if !peer.rate_limit_enabled {
+        return Ok(());
+    }
+    if let Some(rate_limiter) = peer.inbound_rate_limiter.as_mut() {
+        if oft_store.overrides_consume_capacity
+            || !oft_store.is_rate_limit_override(sender, RateLimitDirection::Outbound)?
+        {
+            let multiplier = oft_store.override_factor(sender, RateLimitDirection::Outbound)?;
+            let charge_ld = simulation::override_charge_ld(amount_ld, multiplier);
+            rate_limiter.refill(peer.inbound_rate_limit_mode, charge_ld)?;
+        }
+    }
+    Ok(())
+}
+
+/// Skips the inbound rate limiter and launch cap for overridden guids and credits to an overridden
+/// token account or `recipient` owning it, and applies them otherwise. Guid overrides are
+/// one-shot, a matching one is removed as it is used. Like refill_rate_limit the other way round,
+/// credits free up the outbound capacity they consumed. Overridden credits are deducted without
+/// being checked if the store's overrides consume capacity, and consume nothing otherwise.
+/// Receivers overridden with a multiplier are charged the scaled down amount. Credits of
+/// `msg_type` are also charged the peer's inbound rate limiter for that message type, if set.
+/// With `queue_over_limit`, a credit the inbound rate limiters cannot cover yet is left uncharged,
+/// reported with RateLimitExceeded and false returned, for lz_receive to queue it instead of
+/// failing. Nothing is checked or charged for peers with rate limiting disabled.
+pub fn consume_rate_limit(
+    oft_store: &mut OFTStore,
+    peer: &mut PeerConfig,
+    receiver: &Pubkey,
+    recipient: &Pubkey,
+    guid: &[u8; 32],
+    src_eid: u32,
+    msg_type: u8,
+    amount_ld: u64,
+    queue_over_limit: bool,
+) -> Result<bool> {
+    if !peer.rate_limit_enabled {
+        return Ok(true);
+    }
+    refresh_rate_limits(oft_store, peer)?;
+    let inbound_available_ld =
+        available_now_ld(peer.inbound_rate_limiter.as_ref(), peer.inbound_rate_limit_mode)?;
+    let overridden_address = oft_store.inbound_rate_limit_override(receiver, recipient)?;
+    let overridden = if let Some(address) = overridden_address {
+        emit!(RateLimitOverrideTriggered {
+            address,
+            amount_ld,
+            remote_eid: src_eid,
+            direction: RateLimitDirection::Inbound,
+            available_ld: inbound_available_ld,
+        });
+        true
+    } else if oft_store.consume_rate_limit_override_guid(guid, src_eid, amount_ld) {
+        emit!(RateLimitOverrideGuidTriggered {
+            guid: *guid,
+            amount_ld,
+            consumed: true,
+            src_eid,
+            available_ld: inbound_available_ld,
+        });
+        true
+    } else {
+        false
+    };
+    if overridden {
+        oft_store.record_override_trigger(amount_ld);
+    }
+    let multiplier = oft_store.inbound_override_factor(receiver, recipient)?;
+    let charge_ld = simulation::override_charge_ld(amount_ld, multiplier);
+    let mode = peer.inbound_rate_limit_mode;
+    let direction = RateLimitDirection::Inbound;
+    if overridden {
+        if !oft_store.overrides_consume_capacity {
+            return Ok(true);
+        }
+        if let Some(rate_limiter) = peer.inbound_rate_limiter.as_mut() {
+            rate_limiter.deduct(mode, charge_ld)?;
+        }
+        if let Some(rate_limiter) = peer.msg_type_rate_limiter_mut(msg_type, direction) {
+            rate_limiter.deduct(mode, charge_ld)?;
+        }
+    } else if !oft_store.is_test_mode() {
+        // devnet and QA stores are exempt from rate limits
+        check_launch_cap(oft_store, charge_ld)?;
+        let reserved_ld = reserved_capacity_ld(peer, multiplier);
+        if queue_over_limit {
+            let now = mode.now(&Clock::get()?);
+            let available_ld = [
+                peer.inbound_rate_limiter.as_ref().map(|rate_limiter| {
+                    simulation::unreserved_ld(rate_limiter, mode, now, reserved_ld)
+                }),
+                peer.msg_type_rate_limiter(msg_type, direction)
+                    .map(|rate_limiter| simulation::available_ld(rate_limiter, mode, now)),
+            ]
+            .into_iter()
+            .flatten()
+            .min();
+            if let Some(available_ld) =
+                available_ld.filter(|available_ld| *available_ld < charge_ld)
+            {
+                emit!(RateLimitExceeded {
+                    remote_eid: src_eid,
+                    direction,
+                    requested_ld: charge_ld,
+                    available_ld,
+                    actor: *receiver,
+                });
+                return Ok(false);
+            }
+        }
+        if let Some(rate_limiter) = peer.inbound_rate_limiter.as_mut() {
+            try_consume_unreserved(rate_limiter, mode, charge_ld, reserved_ld)?;
+        }
+        if let Some(rate_limiter) = peer.msg_type_rate_limiter_mut(msg_type, direction) {
+            rate_limiter.try_consume(mode, charge_ld)?;
+        }
+    }
+    if let Some(rate_limiter) = peer.outbound_rate_limiter.as_mut() {
+        rate_limiter.refill(peer.outbound_rate_limit_mode, charge_ld)?;
+    }
+    Ok(true)
+}
+
+/// Whether credits from `peer` the inbound rate limiters cannot cover are queued rather than
+/// failed: under OFTStore::queue_over_limit_credits, and always for a peer with a breach monitor,
+/// which can only count rejections that complete.
+pub fn queues_over_limit(oft_store: &OFTStore, peer: &PeerConfig) -> bool {
+    oft_store.queue_over_limit_credits || peer.breach_monitor.is_some()
+}
+
+/// Counts a credit the inbound rate limiters could not cover against the peer's breach monitor,
+/// emitting PeerAutoPaused if that pauses the peer.
+pub fn record_rate_limit_breach(peer: &mut PeerConfig, src_eid: u32) -> Result<()> {
+    if peer.record_rate_limit_breach(Clock::get()?.unix_timestamp) {
+        if let Some(monitor) = &peer.breach_monitor {
+            emit!(PeerAutoPaused {
+                remote_eid: src_eid,
+                breaches: monitor.breaches,
+                window_seconds: monitor.window_seconds,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// What is left in `rate_limiter` now, None if there is none.
+fn available_now_ld(
+    rate_limiter: Option<&RateLimiter>,
+    mode: RateLimitMode,
+) -> Result<Option<u64>> {
+    let now = mode.now(&Clock::get()?);
+    Ok(rate_limiter.map(|rate_limiter| simulation::available_ld(rate_limiter, mode, now)))
+}
+
+/// Charges `rate_limiter`, failing when it cannot cover `amount_ld` above `reserved_ld`.
+fn try_consume_unreserved(
+    rate_limiter: &mut RateLimiter,
+    mode: RateLimitMode,
+    amount_ld: u64,
+    reserved_ld: u64,
+) -> Result<()> {
+    let now = mode.now(&Clock::get()?);
+    require!(
+        simulation::unreserved_ld(rate_limiter, mode, now, reserved_ld) >= amount_ld,
+        OFTError::RateLimitExceeded
+    );
+    rate_limiter.try_consume(mode, amount_ld)
+}
+
+/// Large credits, credits above the probation hold, credits from quarantined source senders and
+/// credits composed into programs off the allowlist are held until the credit verifier confirms
+/// them, and credits to opted-out recipients until they claim them. A held or split credit needs
+/// the pending credit account; it is ignored otherwise.
+pub fn check_credit_hold(held: bool, has_pending_credit: bool) -> Result<()> {
+    require!(!held || has_pending_credit, OFTError::InvalidPendingCredit);
+    Ok(())
+}
+
+pub fn check_peer_verified(oft_store: &OFTStore, peer: &PeerConfig) -> Result<()> {
+    require!(peer.endpoint_version == oft_store.endpoint_version, OFTError::PeerNotVerified);
+    Ok(())
+}
@@ -0,0 +1,295 @@
+//! Pure fee, dust, rate limit and credit arithmetic.
+//!
+//! send, lz_receive and the stores' rate limiters are built on these functions, and the
+//! simulate_send and simulate_receive views report them, so wallets see the exact outcome a
+//! transfer would have. Nothing here reads accounts or sysvars; the time is always passed in.
+use crate::*;
+
+/// The target slot time, used to convert between per second and per slot refill rates.
+pub const MS_PER_SLOT: u64 = 400;
+
+/// Splits `amount_ld` into the part that can be bridged and the dust below the shared decimals.
+pub fn split_dust(amount_ld: u64, ld2sd_rate: u64) -> Result<(u64, u64)> {
+    let dust_ld =
+        amount_ld.checked_rem(ld2sd_rate).ok_or_else(|| error!(OFTError::InvalidDecimals))?;
+    // dust is always <= amount_ld
+    Ok((amount_ld - dust_ld, dust_ld))
+}
+
+/// The OFT fee of `fee_bps` on the dust free `amount_ld`, in local decimals rounded to shared
+/// decimals per `rounding_mode`.
+pub fn oft_fee_ld(
+    amount_ld: u64,
+    fee_bps: u16,
+    ld2sd_rate: u64,
+    rounding_mode: RoundingMode,
+) -> Result<u64> {
+    match rounding_mode {
+        RoundingMode::Floor => {
+            Ok(split_dust(calculate_fee(amount_ld, fee_bps, None)?, ld2sd_rate)?.0)
+        },
+        mode => {
+            // amount_ld * fee_bps / (ONE_IN_BASIS_POINTS * ld2sd_rate), rounded once
+            let numerator = (amount_ld as u128)
+                .checked_mul(fee_bps as u128)
+                .ok_or_else(|| error!(OFTError::AmountOverflow))?;
+            let fee_sd = mode
+                .div(numerator, ONE_IN_BASIS_POINTS * ld2sd_rate as u128)
+                .ok_or_else(|| error!(OFTError::InvalidDecimals))?;
+            u64::try_from(fee_sd)
+                .ok()
+                .and_then(|fee_sd| fee_sd.checked_mul(ld2sd_rate))
+                .ok_or_else(|| error!(OFTError::AmountOverflow))
+        },
+    }
+}
+
+/// Capacity of `rate_limiter` at `now`, refilled for the time since it was last charged, or back
+/// at capacity once a fixed window has rolled over. `now` is a slot for slot buckets.
+pub fn available_ld(rate_limiter: &RateLimiter, mode: RateLimitMode, now: u64) -> u64 {
+    match mode {
+        RateLimitMode::TokenBucket | RateLimitMode::SlotBucket => {
+            let elapsed = now.saturating_sub(rate_limiter.last_refill_time);
+            // at most u64::MAX squared, which fits in a u128
+            let refilled_ld = elapsed as u128 * rate_limiter.refill_per_second as u128;
+            refilled_tokens(rate_limiter.tokens, refilled_ld, rate_limiter.capacity)
+        },
+        RateLimitMode::FixedWindow { window_seconds } => {
+            let window_seconds = window_seconds.max(1);
+            if now / window_seconds > rate_limiter.last_refill_time / window_seconds {
+                rate_limiter.capacity
+            } else {
+                rate_limiter.tokens.min(rate_limiter.capacity)
+            }
+        },
+    }
+}
+
+/// `tokens` plus `added_ld`, capped at `capacity`. Summed in u128 so that large 18 decimal
+/// amounts neither wrap nor lose what does not fit in a u64 before the cap is applied.
+pub fn refilled_tokens(tokens: u64, added_ld: u128, capacity: u64) -> u64 {
+    let refilled = (tokens as u128).saturating_add(added_ld).min(capacity as u128);
+    // capped at a u64 above
+    u64::try_from(refilled).unwrap_or(capacity)
+}
+
+/// `refill_per_second` as tokens per slot at MS_PER_SLOT, rounded down.
+pub fn refill_per_slot(refill_per_second: u64) -> u64 {
+    // at most 0.4 * u64::MAX
+    (refill_per_second as u128 * MS_PER_SLOT as u128 / 1_000) as u64
+}
+
+/// `refill_per_slot` tokens per slot as tokens per second at MS_PER_SLOT, rounded down and capped
+/// at u64::MAX.
+pub fn refill_per_second_from_slot(refill_per_slot: u64) -> u64 {
+    u64::try_from(refill_per_slot as u128 * 1_000 / MS_PER_SLOT as u128).unwrap_or(u64::MAX)
+}
+
+/// How long `slots` take at MS_PER_SLOT, in seconds rounded up.
+pub fn slots_to_seconds(slots: u64) -> u64 {
+    // at most 0.4 * u64::MAX + 1
+    (slots as u128 * MS_PER_SLOT as u128).div_ceil(1_000) as u64
+}
+
+/// Seconds from `now` until `rate_limiter` is back at capacity, or None if it never refills.
+/// Slot buckets are estimated at MS_PER_SLOT.
+pub fn seconds_until_full(
+    rate_limiter: &RateLimiter,
+    mode: RateLimitMode,
+    now: u64,
+) -> Option<u64> {
+    let missing = rate_limiter.capacity.saturating_sub(available_ld(rate_limiter, mode, now));
+    match (missing, mode) {
+        (0, _) => Some(0),
+        (_, RateLimitMode::FixedWindow { window_seconds }) => {
+            let window_seconds = window_seconds.max(1);
+            Some(window_seconds - now % window_seconds)
+        },
+        _ if rate_limiter.refill_per_second == 0 => None,
+        (_, RateLimitMode::SlotBucket) => {
+            Some(slots_to_seconds(missing.div_ceil(rate_limiter.refill_per_second)))
+        },
+        _ => Some(missing.div_ceil(rate_limiter.refill_per_second)),
+    }
+}
+
+/// What a transfer that has to leave `reserved_ld` to override addresses can use of
+/// `rate_limiter` at `now`.
+pub fn unreserved_ld(
+    rate_limiter: &RateLimiter,
+    mode: RateLimitMode,
+    now: u64,
+    reserved_ld: u64,
+) -> u64 {
+    available_ld(rate_limiter, mode, now).saturating_sub(reserved_ld)
+}
+
+/// Whether `amount_ld` fits in `rate_limiter` at `now` above `reserved_ld`; no limiter means no
+/// limit.
+pub fn within_rate_limit(
+    rate_limiter: Option<&RateLimiter>,
+    mode: RateLimitMode,
+    amount_ld: u64,
+    now: u64,
+    reserved_ld: u64,
+) -> bool {
+    rate_limiter.map_or(true, |rate_limiter| {
+        unreserved_ld(rate_limiter, mode, now, reserved_ld) >= amount_ld
+    })
+}
+
+/// What a transfer of `amount_ld` is charged against the rate limiters and launch cap for an
+/// address overridden with `multiplier`, rounded up in the limit's favour.
+pub fn override_charge_ld(amount_ld: u64, multiplier: Option<u16>) -> u64 {
+    multiplier.map_or(amount_ld, |multiplier| amount_ld.div_ceil(multiplier.max(1) as u64))
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize, PartialEq, Eq, Debug)]
+pub enum CreditOutcome {
+    Immediate, // credited in full
+    Held,      // held in a PendingCredit for the credit verifier or the recipient
+    Split { immediate_ld: u64, vesting_ld: u64 }, // the rest vests in a PendingCredit
+}
+
+/// How lz_receive credits `amount_ld`. Held credits are never split, and neither are composed
+/// ones as the composer expects the full amount.
+pub fn credit_outcome(
+    amount_ld: u64,
+    held: bool,
+    composed: bool,
+    inbound_split: Option<&InboundSplit>,
+) -> CreditOutcome {
+    if held {
+        return CreditOutcome::Held;
+    }
+    match inbound_split {
+        Some(split) if !composed && split.applies_to(amount_ld) => {
+            let immediate_ld = split.immediate_ld(amount_ld);
+            CreditOutcome::Split { immediate_ld, vesting_ld: amount_ld - immediate_ld }
+        },
+        _ => CreditOutcome::Immediate,
+    }
+}
+
+#[test]
+fn test_split_dust() {
+    assert_eq!(split_dust(1_234_567, 1_000).unwrap(), (1_234_000, 567));
+    assert_eq!(split_dust(999, 1_000).unwrap(), (0, 999));
+    assert!(split_dust(1, 0).is_err());
+}
+
+#[test]
+fn test_oft_fee_ld() {
+    // 0.5% of 1_001_000 is 5_005, floored to 5_000 or rounded half to even to 5_000
+    assert_eq!(oft_fee_ld(1_001_000, 50, 1_000, RoundingMode::Floor).unwrap(), 5_000);
+    assert_eq!(oft_fee_ld(1_001_000, 50, 1_000, RoundingMode::HalfEven).unwrap(), 5_000);
+    // 0.5% of 1_301_000 is 6_505
+    assert_eq!(oft_fee_ld(1_301_000, 50, 1_000, RoundingMode::Floor).unwrap(), 6_000);
+    assert_eq!(oft_fee_ld(1_301_000, 50, 1_000, RoundingMode::HalfEven).unwrap(), 7_000);
+    assert_eq!(oft_fee_ld(1_301_000, 0, 1_000, RoundingMode::HalfEven).unwrap(), 0);
+}
+
+#[test]
+fn test_available_ld() {
+    let rate_limiter =
+        RateLimiter { capacity: 100, tokens: 40, refill_per_second: 5, last_refill_time: 1_000 };
+    let mode = RateLimitMode::TokenBucket;
+    assert_eq!(available_ld(&rate_limiter, mode, 999), 40);
+    assert_eq!(available_ld(&rate_limiter, mode, 1_010), 90);
+    assert_eq!(available_ld(&rate_limiter, mode, 1_020), 100);
+    assert!(within_rate_limit(Some(&rate_limiter), mode, 90, 1_010, 0));
+    assert!(!within_rate_limit(Some(&rate_limiter), mode, 91, 1_010, 0));
+    assert!(within_rate_limit(None, mode, u64::MAX, 0, 0));
+    // 20 of the 90 are reserved
+    assert_eq!(unreserved_ld(&rate_limiter, mode, 1_010, 20), 70);
+    assert!(!within_rate_limit(Some(&rate_limiter), mode, 71, 1_010, 20));
+    assert_eq!(unreserved_ld(&rate_limiter, mode, 1_010, 200), 0);
+    assert_eq!(seconds_until_full(&rate_limiter, mode, 1_000), Some(12));
+    assert_eq!(seconds_until_full(&rate_limiter, mode, 1_020), Some(0));
+    let no_refill = RateLimiter { refill_per_second: 0, ..rate_limiter };
+    assert_eq!(seconds_until_full(&no_refill, mode, 1_000), None);
+}
+
+#[test]
+fn test_refill_boundaries() {
+    assert_eq!(refilled_tokens(u64::MAX, u64::MAX as u128, u64::MAX), u64::MAX);
+    assert_eq!(refilled_tokens(u64::MAX - 1, 1, u64::MAX), u64::MAX);
+    assert_eq!(refilled_tokens(5, u128::MAX, 100), 100);
+    assert_eq!(refilled_tokens(200, 0, 100), 100);
+
+    // a refill rate of 1e18 per second for longer than u64::MAX / 1e18 seconds
+    let rate_limiter = RateLimiter {
+        capacity: u64::MAX,
+        tokens: 0,
+        refill_per_second: 1_000_000_000_000_000_000,
+        last_refill_time: 0,
+    };
+    let mode = RateLimitMode::TokenBucket;
+    assert_eq!(available_ld(&rate_limiter, mode, 18), 18_000_000_000_000_000_000);
+    assert_eq!(available_ld(&rate_limiter, mode, 19), u64::MAX);
+    let rate_limiter = RateLimiter { refill_per_second: u64::MAX, ..rate_limiter };
+    assert_eq!(available_ld(&rate_limiter, mode, u64::MAX), u64::MAX);
+    assert_eq!(seconds_until_full(&rate_limiter, mode, 0), Some(1));
+}
+
+#[test]
+fn test_available_ld_fixed_window() {
+    // charged during the window [1_000, 1_100)
+    let rate_limiter =
+        RateLimiter { capacity: 100, tokens: 40, refill_per_second: 5, last_refill_time: 1_050 };
+    let mode = RateLimitMode::FixedWindow { window_seconds: 100 };
+    assert_eq!(available_ld(&rate_limiter, mode, 1_099), 40);
+    assert_eq!(available_ld(&rate_limiter, mode, 1_100), 100);
+    assert_eq!(seconds_until_full(&rate_limiter, mode, 1_060), Some(40));
+    assert_eq!(seconds_until_full(&rate_limiter, mode, 1_100), Some(0));
+    assert!(!within_rate_limit(Some(&rate_limiter), mode, 41, 1_099, 0));
+}
+
+#[test]
+fn test_available_ld_slot_bucket() {
+    // 5 per second is 2 per 400ms slot
+    assert_eq!(refill_per_slot(5), 2);
+    assert_eq!(refill_per_second_from_slot(2), 5);
+    assert_eq!(refill_per_slot(u64::MAX), 7_378_697_629_483_820_646);
+    assert_eq!(refill_per_second_from_slot(u64::MAX), u64::MAX);
+    assert_eq!(slots_to_seconds(3), 2);
+
+    // the same limit as a token bucket and as a slot bucket, charged at 1_000 in slot 5_000
+    let seconds =
+        RateLimiter { capacity: 100, tokens: 40, refill_per_second: 5, last_refill_time: 1_000 };
+    let slots = RateLimiter { refill_per_second: 2, last_refill_time: 5_000, ..seconds.clone() };
+    let (token_bucket, slot_bucket) = (RateLimitMode::TokenBucket, RateLimitMode::SlotBucket);
+    // 10 seconds are 25 slots
+    assert_eq!(available_ld(&seconds, token_bucket, 1_010), 90);
+    assert_eq!(available_ld(&slots, slot_bucket, 5_025), 90);
+    assert_eq!(seconds_until_full(&seconds, token_bucket, 1_000), Some(12));
+    assert_eq!(seconds_until_full(&slots, slot_bucket, 5_000), Some(12));
+
+    // a clock that has stalled while slots went on refills only the slot bucket
+    let clock = Clock { slot: 5_025, unix_timestamp: 1_000, ..Clock::default() };
+    assert_eq!(available_ld(&seconds, token_bucket, token_bucket.now(&clock)), 40);
+    assert_eq!(available_ld(&slots, slot_bucket, slot_bucket.now(&clock)), 90);
+    assert_eq!(slot_bucket.refill_rate(5), 2);
+    assert_eq!(token_bucket.refill_rate(5), 5);
+}
+
+#[test]
+fn test_override_charge_ld() {
+    assert_eq!(override_charge_ld(90, None), 90);
+    assert_eq!(override_charge_ld(90, Some(3)), 30);
+    assert_eq!(override_charge_ld(91, Some(3)), 31);
+}
+
+#[test]
+fn test_credit_outcome() {
+    let split =
+        InboundSplit { threshold_ld: 50, immediate_bps: 2_500, epoch_seconds: 60, epochs: 3 };
+    assert_eq!(credit_outcome(100, true, false, Some(&split)), CreditOutcome::Held);
+    assert_eq!(credit_outcome(100, false, true, Some(&split)), CreditOutcome::Immediate);
+    assert_eq!(credit_outcome(50, false, false, Some(&split)), CreditOutcome::Immediate);
+    assert_eq!(
+        credit_outcome(100, false, false, Some(&split)),
+        CreditOutcome::Split { immediate_ld: 25, vesting_ld: 75 }
+    );
+    assert_eq!(credit_outcome(100, false, false, None), CreditOutcome::Immediate);
+}
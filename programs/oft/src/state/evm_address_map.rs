@@ -0,0 +1,33 @@
+use crate::*;
+
+pub const MAX_EVM_ADDRESS_MAPPINGS: usize = MAX_RATE_LIMIT_OVERRIDE_COUNT as usize;
+
+/// EvmAddressMap pairs the addresses the admin wants the rate limit override list mirrored for
+/// with their counterparts on the EVM deployments, see mirror_override_list. Solana accounts have
+/// no canonical EVM address, so only mapped addresses are ever mirrored.
+#[account]
+#[derive(InitSpace)]
+pub struct EvmAddressMap {
+    pub oft_store: Pubkey,
+    pub bump: u8,
+    #[max_len(MAX_EVM_ADDRESS_MAPPINGS)]
+    pub mappings: Vec<EvmAddressMapping>,
+}
+
+#[derive(InitSpace, Clone, AnchorSerialize, AnchorDeserialize, PartialEq, Eq, Debug)]
+pub struct EvmAddressMapping {
+    pub address: Pubkey,
+    pub evm_address: [u8; 20],
+}
+
+impl EvmAddressMap {
+    /// Maps `address` to `evm_address`, or removes its mapping for None.
+    pub fn set(&mut self, address: Pubkey, evm_address: Option<[u8; 20]>) -> Result<()> {
+        self.mappings.retain(|mapping| mapping.address != address);
+        if let Some(evm_address) = evm_address {
+            require!(self.mappings.len() < MAX_EVM_ADDRESS_MAPPINGS, OFTError::EvmAddressMapFull);
+            self.mappings.push(EvmAddressMapping { address, evm_address });
+        }
+        Ok(())
+    }
+}
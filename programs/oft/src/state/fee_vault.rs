@@ -0,0 +1,33 @@
+use crate::*;
+
+pub const MAX_FEE_VAULT_SENDERS: u8 = 8;
+
+/// FeeVault holds SOL the admin sets aside to pay LayerZero native fees for the store's
+/// fee_vault_senders. The lamports above rent exemption are spendable.
+#[account]
+#[derive(InitSpace)]
+pub struct FeeVault {
+    pub oft_store: Pubkey,
+    pub bump: u8,
+}
+
+impl FeeVault {
+    pub fn available_lamports(account: &AccountInfo) -> Result<u64> {
+        let rent = Rent::get()?.minimum_balance(account.data_len());
+        Ok(account.lamports().saturating_sub(rent))
+    }
+
+    /// Moves lamports out of the program-owned vault without a system program CPI.
+    pub fn transfer_lamports(from: &AccountInfo, to: &AccountInfo, lamports: u64) -> Result<()> {
+        require!(
+            Self::available_lamports(from)? >= lamports,
+            OFTError::InsufficientFeeVaultBalance
+        );
+        **from.try_borrow_mut_lamports()? -= lamports;
+        **to.try_borrow_mut_lamports()? = to
+            .lamports()
+            .checked_add(lamports)
+            .ok_or_else(|| error!(OFTError::AmountOverflow))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,19 @@
+use crate::*;
+
+/// InboundOptOut marks a recipient whose inbound credits are held as a PendingCredit until the
+/// recipient claims them, instead of being credited to its token account.
+#[account]
+#[derive(InitSpace)]
+pub struct InboundOptOut {
+    pub oft_store: Pubkey,
+    pub owner: Pubkey,
+    pub bump: u8,
+}
+
+impl InboundOptOut {
+    /// lz_receive always takes the recipient's InboundOptOut address, so the executor cannot skip
+    /// it; the recipient has opted out only if the account exists.
+    pub fn exists(account: &AccountInfo) -> bool {
+        account.owner == &crate::ID && !account.data_is_empty()
+    }
+}
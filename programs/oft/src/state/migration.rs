@@ -0,0 +1,188 @@
+use crate::*;
+use anchor_lang::system_program::{self, Transfer};
+
+// Stores and peers written before they had a version are rewritten in the current layout in
+// place by migrate_oft_store and migrate_peer_config. The readers below decode either that
+// unversioned layout or the current one.
+
+/// The version of the accounts written before stores and peers had one.
+pub const UNVERSIONED: u8 = 0;
+/// Unversioned peers end after bump. Versioned peers are always larger.
+pub const UNVERSIONED_PEER_CONFIG_SPACE: usize =
+    8 + 32 + EnforcedOptions::INIT_SPACE + 2 * (1 + RateLimiter::INIT_SPACE) + 3 + 1;
+
+fn read<T: AnchorDeserialize>(buf: &mut &[u8]) -> Result<T> {
+    Ok(T::deserialize(buf)?)
+}
+
+impl OFTStore {
+    /// Decodes a store written unversioned or at OFT_STORE_VERSION, returning the version it was
+    /// written at. Fields an unversioned store lacks are left at their defaults.
+    pub fn deserialize_versioned(buf: &mut &[u8]) -> Result<(u8, Self)> {
+        let start = *buf;
+        let mut oft_store = Self {
+            oft_type: read(buf)?,
+            ld2sd_rate: read(buf)?,
+            token_mint: read(buf)?,
+            token_escrow: read(buf)?,
+            endpoint_program: read(buf)?,
+            bump: read(buf)?,
+            tvl_ld: read(buf)?,
+            admin: read(buf)?,
+            default_fee_bps: read(buf)?,
+            paused: read(buf)?,
+            pauser: read(buf)?,
+            unpauser: read(buf)?,
+            ..Default::default()
+        };
+        // unversioned stores have the length of rate_limit_override, at most 16, here
+        match buf.first() {
+            Some(len) if *len < OFT_STORE_VERSION => {},
+            _ => {
+                *buf = start;
+                let oft_store = Self::deserialize(buf)?;
+                return Ok((oft_store.version, oft_store));
+            },
+        }
+        let addresses: Vec<Pubkey> = read(buf)?;
+        oft_store.rate_limit_override = addresses
+            .into_iter()
+            .map(|address| RateLimitOverrideAddress {
+                address,
+                expires_at: None,
+                direction: None,
+                multiplier: None,
+                scope: RateLimitOverrideScope::Either,
+            })
+            .collect();
+        oft_store.max_rate_limit_overrides = read(buf)?;
+        let guids: Vec<[u8; 32]> = read(buf)?;
+        oft_store.rate_limit_override_guids = guids
+            .into_iter()
+            .map(|guid| RateLimitOverrideGuid { guid, expected_amount_ld: None, src_eid: None })
+            .collect();
+        oft_store.max_rate_limit_override_guid_count = read(buf)?;
+        Ok((UNVERSIONED, oft_store))
+    }
+}
+
+impl PeerConfig {
+    /// Decodes a peer written unversioned or at PEER_CONFIG_VERSION from its `space` bytes,
+    /// returning the version it was written at. Fields an unversioned peer lacks are left at
+    /// their defaults.
+    pub fn deserialize_versioned(buf: &mut &[u8], space: usize) -> Result<(u8, Self)> {
+        if space != UNVERSIONED_PEER_CONFIG_SPACE {
+            let peer = Self::deserialize(buf)?;
+            return Ok((peer.version, peer));
+        }
+        let peer = Self {
+            peer_address: read(buf)?,
+            enforced_options: read(buf)?,
+            outbound_rate_limiter: read(buf)?,
+            inbound_rate_limiter: read(buf)?,
+            fee_bps: read(buf)?,
+            bump: read(buf)?,
+            // unversioned peers are already carrying traffic under their rate limits
+            control_proven: true,
+            rate_limit_enabled: true,
+            ..Default::default()
+        };
+        Ok((UNVERSIONED, peer))
+    }
+}
+
+/// Resizes the account at `info` to `space`, the payer covering any rent it is short of, and
+/// zeroes it past the discriminator so nothing of the earlier layout is left behind.
+pub fn realloc_zeroed<'info>(
+    info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+) -> Result<()> {
+    let top_up = Rent::get()?.minimum_balance(space).saturating_sub(info.lamports());
+    if top_up > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer { from: payer.clone(), to: info.clone() },
+            ),
+            top_up,
+        )?;
+    }
+    info.realloc(space, false)?;
+    info.try_borrow_mut_data()?[8..].fill(0);
+    Ok(())
+}
+
+#[test]
+fn test_migrate_unversioned() {
+    // the store as written before it had a version, with 2 address and 1 guid overrides
+    let admin = Pubkey::new_unique();
+    let overrides = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+    let mut data = Vec::new();
+    OFTType::Adapter.serialize(&mut data).unwrap();
+    1000u64.serialize(&mut data).unwrap();
+    [Pubkey::new_unique(); 3].serialize(&mut data).unwrap();
+    254u8.serialize(&mut data).unwrap();
+    5u64.serialize(&mut data).unwrap();
+    admin.serialize(&mut data).unwrap();
+    10u16.serialize(&mut data).unwrap();
+    true.serialize(&mut data).unwrap();
+    Some(admin).serialize(&mut data).unwrap();
+    None::<Pubkey>.serialize(&mut data).unwrap();
+    overrides.serialize(&mut data).unwrap();
+    16u8.serialize(&mut data).unwrap();
+    vec![[7u8; 32]].serialize(&mut data).unwrap();
+    8u8.serialize(&mut data).unwrap();
+
+    let buf = &mut &data[..];
+    let (version, mut oft_store) = OFTStore::deserialize_versioned(buf).unwrap();
+    assert!(buf.is_empty());
+    assert_eq!(version, UNVERSIONED);
+
+    // rewritten in the current layout, it is read back as it was migrated
+    oft_store.version = OFT_STORE_VERSION;
+    let data = oft_store.try_to_vec().unwrap();
+    let buf = &mut &data[..];
+    let (version, oft_store) = OFTStore::deserialize_versioned(buf).unwrap();
+    assert!(buf.is_empty());
+    assert_eq!(version, OFT_STORE_VERSION);
+    assert!(oft_store.oft_type == OFTType::Adapter);
+    assert_eq!((oft_store.bump, oft_store.tvl_ld, oft_store.admin), (254, 5, admin));
+    assert_eq!((oft_store.pauser, oft_store.unpauser), (Some(admin), None));
+    let addresses: Vec<_> = oft_store.rate_limit_override.iter().map(|x| x.address).collect();
+    assert_eq!(addresses, overrides);
+    assert_eq!(oft_store.rate_limit_override[0].expires_at, None);
+    assert_eq!(oft_store.rate_limit_override_guids.len(), 1);
+    assert_eq!(oft_store.rate_limit_override_guids[0].guid, [7u8; 32]);
+    assert_eq!(oft_store.rate_limit_override_guids[0].expected_amount_ld, None);
+    assert_eq!(oft_store.max_rate_limit_override_guid_count, 8);
+
+    // the peer as written before it had a version, followed by bytes left from a larger write
+    let mut data = Vec::new();
+    [1u8; 32].serialize(&mut data).unwrap();
+    EnforcedOptions { send: vec![3], send_and_call: vec![] }.serialize(&mut data).unwrap();
+    None::<RateLimiter>.serialize(&mut data).unwrap();
+    None::<RateLimiter>.serialize(&mut data).unwrap();
+    Some(5u16).serialize(&mut data).unwrap();
+    255u8.serialize(&mut data).unwrap();
+    data.resize(UNVERSIONED_PEER_CONFIG_SPACE - 8, 0xff);
+
+    let (version, mut peer) =
+        PeerConfig::deserialize_versioned(&mut &data[..], UNVERSIONED_PEER_CONFIG_SPACE).unwrap();
+    assert_eq!(version, UNVERSIONED);
+
+    peer.version = PEER_CONFIG_VERSION;
+    let space = 8 + PeerConfig::INIT_SPACE;
+    assert!(UNVERSIONED_PEER_CONFIG_SPACE < space);
+    let data = peer.try_to_vec().unwrap();
+    let buf = &mut &data[..];
+    let (version, peer) = PeerConfig::deserialize_versioned(buf, space).unwrap();
+    assert!(buf.is_empty());
+    assert_eq!(version, PEER_CONFIG_VERSION);
+    assert_eq!((peer.peer_address, peer.fee_bps, peer.bump), ([1; 32], Some(5), 255));
+    assert_eq!(peer.enforced_options.send, vec![3]);
+    assert!(peer.outbound_rate_limiter.is_none() && peer.inbound_rate_limiter.is_none());
+    assert!(peer.control_proven && peer.rate_limit_enabled);
+    assert_eq!(peer.endpoint_version, 0);
+}
@@ -1,5 +1,27 @@
+pub mod evm_address_map;
+pub mod fee_vault;
+pub mod migration;
+pub mod inbound_opt_out;
+pub mod notification_hook;
 pub mod oft;
+pub mod override_proposal;
 pub mod peer_config;
+pub mod pending_credit;
+pub mod rate_limit_state;
+pub mod send_record;
+pub mod sender_rate_limit;
+pub mod transfer_receipt;
 
+pub use evm_address_map::*;
+pub use fee_vault::*;
+pub use migration::*;
+pub use inbound_opt_out::*;
+pub use notification_hook::*;
 pub use oft::*;
+pub use override_proposal::*;
 pub use peer_config::*;
+pub use pending_credit::*;
+pub use rate_limit_state::*;
+pub use send_record::*;
+pub use sender_rate_limit::*;
+pub use transfer_receipt::*;
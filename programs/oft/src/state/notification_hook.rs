@@ -0,0 +1,37 @@
+use crate::*;
+
+/// NotificationHook lets a recipient, typically a protocol receiving bridged tokens, poll for its
+/// inbound transfers instead of indexing events: once the recipient registers it, lz_receive
+/// records every transfer to the recipient here. Callbacks into the recipient's program are not
+/// offered, as lz_receive_types cannot read which program and accounts a callback would need.
+#[account]
+#[derive(InitSpace)]
+pub struct NotificationHook {
+    pub oft_store: Pubkey,
+    pub owner: Pubkey,
+    pub bump: u8,
+    pub count: u64, // transfers recorded since the hook was registered
+    pub last: Option<CreditNotification>,
+}
+
+#[derive(InitSpace, Clone, AnchorSerialize, AnchorDeserialize, PartialEq, Eq, Debug)]
+pub struct CreditNotification {
+    pub guid: [u8; 32],
+    pub src_eid: u32,
+    pub amount_ld: u64,   // the amount of the transfer
+    pub credited_ld: u64, // received by the token account now; the rest is in a PendingCredit
+    pub slot: u64,
+}
+
+impl NotificationHook {
+    /// Records `notification` in the account at `info`, if the recipient has registered it.
+    pub fn notify(info: &AccountInfo, notification: CreditNotification) -> Result<()> {
+        if info.owner != &crate::ID || info.data_is_empty() {
+            return Ok(());
+        }
+        let mut hook = Self::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        hook.count = hook.count.saturating_add(1);
+        hook.last = Some(notification);
+        hook.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])
+    }
+}
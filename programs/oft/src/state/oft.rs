@@ -1,10 +1,24 @@
 use crate::*;
 
-pub const MAX_RATE_LIMIT_OVERRIDE_COUNT: u8 = 16;
-pub const MAX_RATE_LIMIT_OVERRIDE_GUID_COUNT: u8 = 8;
+// Protocol maximums; each store is sized for the counts chosen at init or resize_override_lists.
+pub const MAX_RATE_LIMIT_OVERRIDE_COUNT: u8 = 64;
+pub const MAX_RATE_LIMIT_OVERRIDE_GUID_COUNT: u8 = 32;
+pub const ENDPOINT_MIGRATION_DELAY_SECONDS: i64 = 2 * 24 * 60 * 60; // 2 days
+// The layout of OFTStore, see state/migration.rs. Unversioned stores have the length of
+// rate_limit_override, at most 16, where the version is now, so the version starts above it.
+pub const OFT_STORE_VERSION: u8 = 17;
+pub const MAX_WITHDRAW_DESTINATIONS: usize = 4;
+pub const WITHDRAW_DESTINATIONS_DELAY_SECONDS: i64 = 2 * 24 * 60 * 60; // 2 days
+pub const SUPPLY_ADJUSTMENT_DELAY_SECONDS: i64 = 2 * 24 * 60 * 60; // 2 days
+pub const MAX_OVERRIDE_COSIGNERS: usize = 4;
+pub const MAX_ALLOWED_COMPOSERS: usize = 8;
+pub const MAX_ALLOWED_DST_EIDS: usize = 32;
+pub const MAX_LAUNCH_STAGES: usize = 8;
+pub const MAX_FEE_EXEMPT_COUNT: usize = 16;
+pub const CONTROL_MESSAGE_COUNT: usize = 4;
 
 #[account]
-#[derive(InitSpace)]
+#[derive(InitSpace, Default)]
 pub struct OFTStore {
     // immutable
     pub oft_type: OFTType,
@@ -21,41 +35,726 @@ pub struct OFTStore {
     pub paused: bool,
     pub pauser: Option<Pubkey>,
     pub unpauser: Option<Pubkey>,
+    pub version: u8, // the layout the store was written in, see OFT_STORE_VERSION
     // One or more accounts that can override the rate limit. This should affect all peers.
+    // Expired entries stay until the next manage_rate_limit_override prunes them.
     #[max_len(MAX_RATE_LIMIT_OVERRIDE_COUNT)]
-    pub rate_limit_override: Vec<Pubkey>,
-    pub max_rate_limit_overrides: u8, // Hardcoded to MAX_RATE_LIMIT_OVERRIDE_COUNT (16)
-    // Ability to override the rate limit for a specific guid.
+    pub rate_limit_override: Vec<RateLimitOverrideAddress>,
+    pub max_rate_limit_overrides: u8, // Set at init, up to MAX_RATE_LIMIT_OVERRIDE_COUNT
+    // Ability to override the rate limit for a specific guid, once: lz_receive removes the
+    // override when it delivers the guid.
     #[max_len(MAX_RATE_LIMIT_OVERRIDE_GUID_COUNT)]
-    pub rate_limit_override_guids: Vec<[u8; 32]>,
-    pub max_rate_limit_override_guid_count: u8, // Hardcoded to MAX_RATE_LIMIT_OVERRIDE_GUID_COUNT (8)
+    pub rate_limit_override_guids: Vec<RateLimitOverrideGuid>,
+    pub max_rate_limit_override_guid_count: u8, // Set at init, up to MAX_RATE_LIMIT_OVERRIDE_GUID_COUNT
+    // Bumped every time the store is migrated to a new endpoint program. Peers must be re-verified
+    // against the current version before they can be used again.
+    pub endpoint_version: u8,
+    pub pending_endpoint_migration: Option<EndpointMigration>,
+    // Inbound credits above this amount are held in a PendingCredit until the credit verifier
+    // (or the admin) releases them.
+    pub large_credit_threshold_ld: Option<u64>,
+    pub credit_verifier: Option<Pubkey>,
+    // The override manager can add overrides directly up to override_approval_threshold_ld of
+    // expected volume; anything larger must be confirmed by the admin.
+    pub override_manager: Option<Pubkey>,
+    pub override_approval_threshold_ld: Option<u64>,
+    // Treasury senders: their LayerZero native fees may be paid from the store's FeeVault, and
+    // they may tag sends with privileged flow tags.
+    #[max_len(MAX_FEE_VAULT_SENDERS)]
+    pub fee_vault_senders: Vec<Pubkey>,
+    // Paid to keepers out of accrued fees for each crank call.
+    pub crank_bounty_ld: Option<u64>,
+    // Lets holders sweep the dust sends leave behind into the escrow as fees.
+    pub dust_to_fees: bool,
+    // Rounding of the OFT fee to shared decimals. Amounts are always floored by ld2sd.
+    pub fee_rounding_mode: RoundingMode,
+    // Limits applied to peers for probation_seconds after their address is first set.
+    pub probation_policy: Option<ProbationPolicy>,
+    // Who gets back the lamports the executor left in a held credit once it is released.
+    pub held_credit_refund: HeldCreditRefund,
+    pub metadata: Option<OFTMetadata>, // for off-chain discovery, see get_oft_metadata
+    // Wallets fees and fee vault lamports may be withdrawn to; empty allows any. Only changed
+    // through the set_withdraw_destinations timelock.
+    #[max_len(MAX_WITHDRAW_DESTINATIONS)]
+    pub withdraw_destinations: Vec<Pubkey>,
+    pub pending_withdraw_destinations: Option<PendingWithdrawDestinations>,
+    // A burn from the escrow awaiting its timelock, see burn_from_escrow.
+    pub pending_supply_adjustment: Option<SupplyAdjustment>,
+    // Overrides added by the admin above override_cosign_threshold_ld of expected usage must also
+    // be signed by one of these co-signers. Address overrides and guid overrides without an
+    // expected amount are unbounded and always need one once a threshold is set.
+    #[max_len(MAX_OVERRIDE_COSIGNERS)]
+    pub override_cosigners: Vec<Pubkey>,
+    pub override_cosign_threshold_ld: Option<u64>,
+    // Enables test_mint and lifts inbound rate limits; only settable in test-mode builds.
+    pub test_mode: bool,
+    // TvlDropAlert is emitted when the TVL falls drop_bps below its high within a window.
+    pub tvl_alert: Option<TvlAlert>,
+    pub tvl_watermarks: TvlWatermarks,
+    pub contributed_lamports: u64, // total topped up through top_up_lamports
+    // Programs composed transfers may be delivered to; empty allows any. Credits composed into
+    // other programs are held for the credit verifier.
+    #[max_len(MAX_ALLOWED_COMPOSERS)]
+    pub allowed_composers: Vec<Pubkey>,
+    // Sends and receives of at least min_amount_ld leave a TransferReceipt.
+    pub transfer_receipts: Option<TransferReceiptPolicy>,
+    // Destinations sends are allowed to, whichever peers exist; empty allows any peer.
+    #[max_len(MAX_ALLOWED_DST_EIDS)]
+    pub allowed_dst_eids: Vec<u32>,
+    // What lz_receive does with messages whose amount is zero in local decimals.
+    pub zero_amount_policy: ZeroAmountPolicy,
+    // Pays accrued fees out gradually through CrankTask::StreamFees; withdraw_fee is disabled
+    // while it is set.
+    pub fee_stream: Option<FeeStream>,
+    // Besides the admin, may set the per-peer rate limits and manage the override lists, so an
+    // operations key can tune limits while the admin key stays offline.
+    pub rate_limiter: Option<Pubkey>,
+    // Caps what each sender can send across all peers on top of the per-peer outbound limits, so
+    // a single compromised wallet cannot use up a peer's whole capacity.
+    pub sender_rate_limit: Option<SenderRateLimitConfig>,
+    // Rate limits that ramp up on their own after launch, in order of starts_at. The last stage
+    // stays in effect until the schedule is cleared with SetOFTConfigParams::LaunchSchedule.
+    #[max_len(MAX_LAUNCH_STAGES)]
+    pub launch_schedule: Vec<LaunchStage>,
+    // Whether transfers that skip the rate limits through an override are still deducted from
+    // them, so overridden volume counts against what everyone else can move.
+    pub overrides_consume_capacity: bool,
+    // Holds inbound credits the peer's inbound rate limit cannot cover in a PendingCredit, for
+    // execute_pending_credit once it refills, instead of failing lz_receive.
+    pub queue_over_limit_credits: bool,
+    // What has moved past the rate limits through address and guid overrides, and how often.
+    pub total_overridden_volume_ld: u64,
+    pub override_trigger_count: u64,
+    // Must co-sign every manage_rate_limit_override and manage_rate_limit_override_guid call, and
+    // any change of itself, so one compromised admin key cannot whitelist addresses alone.
+    pub override_approver: Option<Pubkey>,
+    // The peer whose OFT may add guid overrides with an OverrideGuids control message, so stuck
+    // messages can be whitelisted by its owner without an admin signer online here.
+    pub governance_eid: Option<u32>,
+    // Senders that pay no OFT fee, such as the treasury and protocol owned accounts. Matched
+    // against the debit authority of send, like the rate limit overrides.
+    #[max_len(MAX_FEE_EXEMPT_COUNT)]
+    pub fee_exempt: Vec<Pubkey>,
+    // Fees sends and sweep_dust have put in the escrow since init_oft or migrate_oft_store, less
+    // what has been paid out of it. withdraw_fees can take at most this much.
+    pub accrued_fees_ld: u64,
+    pub fee_collector: Option<Pubkey>, // may call withdraw_fees besides the admin
+    pub treasury: Option<Pubkey>,      // the token account withdraw_fees pays
+    // Control messages lz_receive refuses while transfers go on, see set_control_message_pause.
+    #[max_len(CONTROL_MESSAGE_COUNT)]
+    pub paused_control_messages: Vec<ControlMessage>,
+    // Pauses the store on a pause notice from a peer, see send_pause_notice. Otherwise notices
+    // are only reported with PauseNoticeReceived.
+    pub honor_pause_notices: bool,
+}
+
+#[derive(InitSpace, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct EndpointMigration {
+    pub new_endpoint_program: Pubkey,
+    pub executable_at: i64,
+}
+
+#[derive(InitSpace, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SupplyAdjustment {
+    pub amount_ld: u64,
+    pub executable_at: i64,
+}
+
+#[derive(InitSpace, Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct PendingWithdrawDestinations {
+    #[max_len(MAX_WITHDRAW_DESTINATIONS)]
+    pub destinations: Vec<Pubkey>,
+    pub executable_at: i64,
+}
+
+#[derive(InitSpace, Clone, AnchorSerialize, AnchorDeserialize, PartialEq, Eq, Debug)]
+pub struct TvlAlert {
+    pub drop_bps: u16,
+    pub window_seconds: i64,
+}
+
+/// Highest and lowest TVL since window_start. The window restarts at the first change after
+/// tvl_alert.window_seconds, from the TVL at that point.
+#[derive(InitSpace, Clone, Default, AnchorSerialize, AnchorDeserialize, PartialEq, Eq, Debug)]
+pub struct TvlWatermarks {
+    pub window_start: i64,
+    pub high_ld: u64,
+    pub low_ld: u64,
+    pub alerted: bool, // at most one TvlDropAlert per window
+}
+
+impl TvlWatermarks {
+    /// Records a change of the TVL and returns whether it crossed the alert threshold.
+    pub fn record(
+        &mut self,
+        now: i64,
+        previous_tvl_ld: u64,
+        tvl_ld: u64,
+        alert: &Option<TvlAlert>,
+    ) -> bool {
+        let window_seconds = alert.as_ref().map_or(i64::MAX, |alert| alert.window_seconds);
+        if now.saturating_sub(self.window_start) >= window_seconds || self.window_start == 0 {
+            *self = TvlWatermarks {
+                window_start: now,
+                high_ld: previous_tvl_ld,
+                low_ld: previous_tvl_ld,
+                alerted: false,
+            };
+        }
+        self.high_ld = self.high_ld.max(tvl_ld);
+        self.low_ld = self.low_ld.min(tvl_ld);
+
+        let alert = match alert {
+            Some(alert) if !self.alerted => alert,
+            _ => return false,
+        };
+        // tvl_ld < high_ld * (1 - drop_bps / 10000)
+        let floor = self.high_ld as u128 * (MAX_FEE_BASIS_POINTS - alert.drop_bps) as u128;
+        self.alerted = (tvl_ld as u128) * (MAX_FEE_BASIS_POINTS as u128) < floor;
+        self.alerted
+    }
+}
+
+/// Capacity and refill rate of every sender's own outbound rate limiter, see SenderRateLimit.
+#[derive(InitSpace, Clone, AnchorSerialize, AnchorDeserialize, PartialEq, Eq, Debug)]
+pub struct SenderRateLimitConfig {
+    pub capacity: u64,
+    pub refill_per_second: u64,
+}
+
+/// One stage of a guarded launch, in effect from starts_at until the next stage starts: every
+/// peer's rate limiters run at capacity and refill_per_second in both directions, and no transfer
+/// that is not overridden may exceed max_amount_ld.
+#[derive(InitSpace, Clone, AnchorSerialize, AnchorDeserialize, PartialEq, Eq, Debug)]
+pub struct LaunchStage {
+    pub starts_at: i64,
+    pub capacity: u64,
+    pub refill_per_second: u64,
+    pub max_amount_ld: u64,
+}
+
+impl LaunchStage {
+    pub fn validate_schedule(stages: &[LaunchStage]) -> Result<()> {
+        require!(stages.len() <= MAX_LAUNCH_STAGES, OFTError::InvalidLaunchSchedule);
+        for (i, stage) in stages.iter().enumerate() {
+            require!(
+                stage.capacity > 0 && (i == 0 || stages[i - 1].starts_at < stage.starts_at),
+                OFTError::InvalidLaunchSchedule
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Fees accrued in the escrow vest linearly to recipient over period_seconds: each payout is the
+/// share of the fees available that the time since the last payout is of the period. Payouts
+/// are at least min_interval_seconds apart, so keepers cannot crank the fees away in bounties.
+#[derive(InitSpace, Clone, AnchorSerialize, AnchorDeserialize, PartialEq, Eq, Debug)]
+pub struct FeeStream {
+    pub recipient: Pubkey, // a token account owned by one of the withdraw_destinations
+    pub period_seconds: i64,
+    pub min_interval_seconds: i64, // in 1..=period_seconds
+    pub last_paid_at: i64,
+}
+
+impl FeeStream {
+    /// The part of `available_fee_ld` vested at `now`.
+    pub fn vested_ld(&self, available_fee_ld: u64, now: i64) -> u64 {
+        let elapsed = now.saturating_sub(self.last_paid_at).clamp(0, self.period_seconds);
+        (available_fee_ld as u128 * elapsed as u128 / self.period_seconds as u128) as u64
+    }
+}
+
+#[derive(InitSpace, Clone, AnchorSerialize, AnchorDeserialize, PartialEq, Eq, Debug)]
+pub struct RateLimitOverrideAddress {
+    pub address: Pubkey,
+    pub expires_at: Option<i64>, // None until removed
+    // The rate limiters the address skips, None for both. A recipient trusted with unlimited
+    // inbound credits is not thereby trusted to drain outbound capacity.
+    pub direction: Option<RateLimitDirection>,
+    // None skips the rate limiters. Some(n) keeps them but charges a transfer of the address a
+    // n-th of its amount, letting it move n times the configured limit.
+    pub multiplier: Option<u16>,
+    pub scope: RateLimitOverrideScope, // Either for entries added before scopes existed
+}
+
+impl RateLimitOverrideAddress {
+    pub fn is_active(&self, now: i64) -> bool {
+        self.expires_at.map_or(true, |expires_at| now < expires_at)
+    }
+
+    pub fn applies_to(&self, direction: RateLimitDirection) -> bool {
+        self.direction.map_or(true, |scope| scope == direction) && self.scope.covers(direction)
+    }
+}
+
+/// Which party of a transfer an override address is matched against. Credits come from EVM
+/// senders, which are no Solana accounts, so only recipients can match inbound: the credited
+/// token account, or the wallet that owns it.
+#[derive(
+    InitSpace, Clone, Copy, Default, AnchorSerialize, AnchorDeserialize, PartialEq, Eq, Debug,
+)]
+pub enum RateLimitOverrideScope {
+    Sender,    // sends by the address
+    Recipient, // credits to the address or its token account
+    #[default]
+    Either,
+}
+
+impl RateLimitOverrideScope {
+    pub fn covers(&self, direction: RateLimitDirection) -> bool {
+        match self {
+            RateLimitOverrideScope::Sender => direction == RateLimitDirection::Outbound,
+            RateLimitOverrideScope::Recipient => direction == RateLimitDirection::Inbound,
+            RateLimitOverrideScope::Either => true,
+        }
+    }
+}
+
+#[derive(InitSpace, Clone, Copy, AnchorSerialize, AnchorDeserialize, PartialEq, Eq, Debug)]
+pub enum RateLimitDirection {
+    Inbound,  // lz_receive credits to the address
+    Outbound, // sends by the address
 }
 
 #[derive(InitSpace, Clone, AnchorSerialize, AnchorDeserialize, PartialEq, Eq)]
+pub struct RateLimitOverrideGuid {
+    pub guid: [u8; 32],
+    // When set, the override only applies to a delivery of exactly this amount
+    pub expected_amount_ld: Option<u64>,
+    // When set, the override only applies to a delivery from this source
+    pub src_eid: Option<u32>,
+}
+
+impl RateLimitOverrideGuid {
+    pub fn matches(&self, guid: &[u8; 32], src_eid: u32, amount_ld: u64) -> bool {
+        &self.guid == guid
+            && self.expected_amount_ld.map_or(true, |expected| expected == amount_ld)
+            && self.src_eid.map_or(true, |expected| expected == src_eid)
+    }
+}
+
+#[derive(InitSpace, Clone, AnchorSerialize, AnchorDeserialize, PartialEq, Eq, Debug)]
+pub struct ProbationPolicy {
+    pub probation_seconds: i64,
+    // Larger transfers to or from a peer on probation are rejected.
+    pub max_amount_ld: u64,
+    // Larger inbound credits from a peer on probation are held for the credit verifier.
+    pub hold_above_ld: u64,
+}
+
+// Hashes rather than strings keep the store size fixed; the preimages are published off-chain.
+#[derive(InitSpace, Clone, AnchorSerialize, AnchorDeserialize, PartialEq, Eq, Debug)]
+pub struct OFTMetadata {
+    pub label_hash: [u8; 32],
+    pub canonical_eid: u32, // where the canonical GMX token lives
+    pub frontend_uri_hash: [u8; 32],
+}
+
+#[derive(InitSpace, Clone, Copy, Default, AnchorSerialize, AnchorDeserialize, PartialEq, Eq, Debug)]
+pub enum HeldCreditRefund {
+    // the payer of the held lz_receive, usually the executor
+    #[default]
+    Payer,
+    // the recipient, who claims them together with the credit
+    Recipient,
+}
+
+// Dust removal on the peer side can leave nothing to transfer, and amounts convert to zero
+// whenever the peer has more shared decimals than this store.
+#[derive(InitSpace, Clone, Copy, Default, AnchorSerialize, AnchorDeserialize, PartialEq, Eq, Debug)]
+pub enum ZeroAmountPolicy {
+    // delivered without moving tokens: composed messages are still sent to the recipient
+    #[default]
+    MessageOnly,
+    // cleared with a ZeroAmountIgnored event, without delivering any compose message
+    Ignore,
+    // failed with OFTError::ZeroAmountMessage, leaving the message in the endpoint
+    Reject,
+}
+
+// How the OFT fee is rounded to shared decimals. Floor rounds down, in the sender's favour.
+// HalfEven rounds to the nearest shared decimal unit and breaks ties towards an even unit, so
+// over many sends the rounding evens out instead of always going one way.
+#[derive(InitSpace, Clone, Copy, Default, AnchorSerialize, AnchorDeserialize, PartialEq, Eq, Debug)]
+pub enum RoundingMode {
+    #[default]
+    Floor,
+    HalfEven,
+}
+
+impl RoundingMode {
+    pub fn div(self, numerator: u128, denominator: u128) -> Option<u128> {
+        let quotient = numerator.checked_div(denominator)?;
+        let remainder = numerator % denominator;
+        match self {
+            RoundingMode::Floor => Some(quotient),
+            RoundingMode::HalfEven => {
+                let twice_remainder = remainder.checked_mul(2)?;
+                let round_up = twice_remainder > denominator
+                    || (twice_remainder == denominator && quotient % 2 == 1);
+                Some(quotient + round_up as u128)
+            },
+        }
+    }
+}
+
+// Messages peers send besides token transfers, each of which can be paused on its own.
+#[derive(InitSpace, Clone, Copy, AnchorSerialize, AnchorDeserialize, PartialEq, Eq, Debug)]
+pub enum ControlMessage {
+    // the echo of a control challenge, see challenge_peer
+    PeerChallengeEcho,
+    // guid overrides added by the governance peer, see governance_eid
+    OverrideGuids,
+    // a peer's notice that it has paused, see honor_pause_notices
+    PauseNotice,
+    // a peer's acknowledgement of a delivery
+    DeliveryAck,
+}
+
+#[derive(InitSpace, Clone, Default, AnchorSerialize, AnchorDeserialize, PartialEq, Eq)]
 pub enum OFTType {
+    #[default]
     Native,
     Adapter,
 }
 
 impl OFTStore {
-    pub fn ld2sd(&self, amount_ld: u64) -> u64 {
-        amount_ld / self.ld2sd_rate
+    /// Account space for a store holding up to the given number of overrides.
+    pub fn space(max_rate_limit_overrides: u8, max_rate_limit_override_guid_count: u8) -> usize {
+        let unused_overrides =
+            MAX_RATE_LIMIT_OVERRIDE_COUNT.saturating_sub(max_rate_limit_overrides) as usize;
+        let unused_guids = MAX_RATE_LIMIT_OVERRIDE_GUID_COUNT
+            .saturating_sub(max_rate_limit_override_guid_count)
+            as usize;
+        8 + Self::INIT_SPACE
+            - unused_overrides * RateLimitOverrideAddress::INIT_SPACE
+            - unused_guids * RateLimitOverrideGuid::INIT_SPACE
     }
 
-    pub fn sd2ld(&self, amount_sd: u64) -> u64 {
-        amount_sd * self.ld2sd_rate
+    /// Floors: the dust below the shared decimals stays with the sender.
+    pub fn ld2sd(&self, amount_ld: u64) -> Result<u64> {
+        amount_ld.checked_div(self.ld2sd_rate).ok_or_else(|| error!(OFTError::InvalidDecimals))
     }
 
-    pub fn remove_dust(&self, amount_ld: u64) -> u64 {
-        amount_ld - amount_ld % self.ld2sd_rate
+    pub fn sd2ld(&self, amount_sd: u64) -> Result<u64> {
+        amount_sd.checked_mul(self.ld2sd_rate).ok_or_else(|| error!(OFTError::AmountOverflow))
     }
 
-    pub fn is_rate_limit_override(&self, account: &Pubkey) -> bool {
-        self.rate_limit_override.contains(account)
+    pub fn remove_dust(&self, amount_ld: u64) -> Result<u64> {
+        Ok(simulation::split_dust(amount_ld, self.ld2sd_rate)?.0)
+    }
+
+    pub fn increase_tvl(&mut self, amount_ld: u64) -> Result<()> {
+        let previous_tvl_ld = self.tvl_ld;
+        self.tvl_ld =
+            self.tvl_ld.checked_add(amount_ld).ok_or_else(|| error!(OFTError::TvlOverflow))?;
+        self.record_tvl(previous_tvl_ld)
+    }
+
+    pub fn decrease_tvl(&mut self, amount_ld: u64) -> Result<()> {
+        let previous_tvl_ld = self.tvl_ld;
+        self.tvl_ld =
+            self.tvl_ld.checked_sub(amount_ld).ok_or_else(|| error!(OFTError::TvlUnderflow))?;
+        self.record_tvl(previous_tvl_ld)
+    }
+
+    fn record_tvl(&mut self, previous_tvl_ld: u64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        if self.tvl_watermarks.record(now, previous_tvl_ld, self.tvl_ld, &self.tvl_alert) {
+            emit!(TvlDropAlert {
+                window_start: self.tvl_watermarks.window_start,
+                high_ld: self.tvl_watermarks.high_ld,
+                tvl_ld: self.tvl_ld,
+            });
+        }
+        Ok(())
+    }
+
+    /// Whether the account skips the rate limiters in the direction. Overrides with a multiplier
+    /// do not; see override_factor.
+    pub fn is_rate_limit_override(
+        &self,
+        account: &Pubkey,
+        direction: RateLimitDirection,
+    ) -> Result<bool> {
+        Ok(self.is_rate_limit_override_at(account, direction, Clock::get()?.unix_timestamp))
+    }
+
+    pub fn is_rate_limit_override_at(
+        &self,
+        account: &Pubkey,
+        direction: RateLimitDirection,
+        now: i64,
+    ) -> bool {
+        self.rate_limit_override.iter().any(|x| {
+            &x.address == account
+                && x.multiplier.is_none()
+                && x.applies_to(direction)
+                && x.is_active(now)
+        })
+    }
+
+    /// The multiplier of the account's active partial override in the direction, if any.
+    pub fn override_factor(
+        &self,
+        account: &Pubkey,
+        direction: RateLimitDirection,
+    ) -> Result<Option<u16>> {
+        Ok(self.override_factor_at(account, direction, Clock::get()?.unix_timestamp))
+    }
+
+    pub fn override_factor_at(
+        &self,
+        account: &Pubkey,
+        direction: RateLimitDirection,
+        now: i64,
+    ) -> Option<u16> {
+        self.rate_limit_override
+            .iter()
+            .find(|x| &x.address == account && x.applies_to(direction) && x.is_active(now))
+            .and_then(|x| x.multiplier)
+    }
+
+    /// Which of a credit's token account and the wallet owning it skips the inbound rate limiter,
+    /// if either does.
+    pub fn inbound_rate_limit_override(
+        &self,
+        token_dest: &Pubkey,
+        recipient: &Pubkey,
+    ) -> Result<Option<Pubkey>> {
+        let now = Clock::get()?.unix_timestamp;
+        Ok([token_dest, recipient]
+            .into_iter()
+            .find(|account| {
+                self.is_rate_limit_override_at(account, RateLimitDirection::Inbound, now)
+            })
+            .copied())
+    }
+
+    /// The multiplier of the partial inbound override of a credit's token account, or else of
+    /// the wallet owning it.
+    pub fn inbound_override_factor(
+        &self,
+        token_dest: &Pubkey,
+        recipient: &Pubkey,
+    ) -> Result<Option<u16>> {
+        let now = Clock::get()?.unix_timestamp;
+        let direction = RateLimitDirection::Inbound;
+        Ok(self
+            .override_factor_at(token_dest, direction, now)
+            .or_else(|| self.override_factor_at(recipient, direction, now)))
     }
 
     pub fn is_rate_limit_override_guid(&self, guid: &[u8; 32]) -> bool {
-        self.rate_limit_override_guids.contains(guid)
+        self.rate_limit_override_guids.iter().any(|x| &x.guid == guid)
+    }
+
+    /// Whether there is an override for the guid matching the delivery's source and amount.
+    pub fn matches_rate_limit_override_guid(
+        &self,
+        guid: &[u8; 32],
+        src_eid: u32,
+        amount_ld: u64,
+    ) -> bool {
+        self.rate_limit_override_guid_index(guid, src_eid, amount_ld).is_some()
+    }
+
+    /// Removes and returns true if there is an override for the guid matching the delivery's
+    /// source and amount.
+    pub fn consume_rate_limit_override_guid(
+        &mut self,
+        guid: &[u8; 32],
+        src_eid: u32,
+        amount_ld: u64,
+    ) -> bool {
+        if let Some(index) = self.rate_limit_override_guid_index(guid, src_eid, amount_ld) {
+            self.rate_limit_override_guids.swap_remove(index);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn rate_limit_override_guid_index(
+        &self,
+        guid: &[u8; 32],
+        src_eid: u32,
+        amount_ld: u64,
+    ) -> Option<usize> {
+        self.rate_limit_override_guids.iter().position(|x| x.matches(guid, src_eid, amount_ld))
+    }
+
+    pub fn add_rate_limit_override(&mut self, entry: RateLimitOverrideAddress) -> Result<()> {
+        require!(
+            self.rate_limit_override.len() < self.max_rate_limit_overrides.into(),
+            OFTError::RateLimitOverrideListFull
+        );
+        require!(
+            !self.rate_limit_override.iter().any(|x| x.address == entry.address),
+            OFTError::AlreadyInOverrideList
+        );
+        self.rate_limit_override.push(entry);
+        Ok(())
+    }
+
+    /// Removes the address overrides expired at `now` and returns their addresses.
+    pub fn prune_rate_limit_overrides(&mut self, now: i64) -> Vec<Pubkey> {
+        let expired = self
+            .rate_limit_override
+            .iter()
+            .filter(|x| !x.is_active(now))
+            .map(|x| x.address)
+            .collect();
+        self.rate_limit_override.retain(|x| x.is_active(now));
+        expired
+    }
+
+    pub fn remove_rate_limit_override(&mut self, address: &Pubkey) -> Result<()> {
+        let index = self
+            .rate_limit_override
+            .iter()
+            .position(|x| &x.address == address)
+            .ok_or(OFTError::NotInOverrideList)?;
+        self.rate_limit_override.swap_remove(index);
+        Ok(())
+    }
+
+    pub fn add_rate_limit_override_guid(&mut self, entry: RateLimitOverrideGuid) -> Result<()> {
+        require!(
+            self.rate_limit_override_guids.len() < self.max_rate_limit_override_guid_count.into(),
+            OFTError::RateLimitOverrideListFull
+        );
+        require!(
+            !self.is_rate_limit_override_guid(&entry.guid),
+            OFTError::AlreadyInOverrideList
+        );
+        self.rate_limit_override_guids.push(entry);
+        Ok(())
+    }
+
+    pub fn remove_rate_limit_override_guid(&mut self, guid: &[u8; 32]) -> Result<()> {
+        let index = self
+            .rate_limit_override_guids
+            .iter()
+            .position(|x| &x.guid == guid)
+            .ok_or(OFTError::NotInOverrideList)?;
+        self.rate_limit_override_guids.swap_remove(index);
+        Ok(())
+    }
+
+    pub fn is_endpoint_migration_pending(&self) -> bool {
+        self.pending_endpoint_migration.is_some()
+    }
+
+    pub fn is_allowed_composer(&self, composer: &Pubkey) -> bool {
+        self.allowed_composers.is_empty() || self.allowed_composers.contains(composer)
+    }
+
+    pub fn is_allowed_dst_eid(&self, dst_eid: u32) -> bool {
+        self.allowed_dst_eids.is_empty() || self.allowed_dst_eids.contains(&dst_eid)
+    }
+
+    pub fn is_withdraw_destination(&self, destination: &Pubkey) -> bool {
+        self.withdraw_destinations.is_empty() || self.withdraw_destinations.contains(destination)
+    }
+
+    pub fn requires_credit_verification(&self, amount_ld: u64) -> bool {
+        matches!(self.large_credit_threshold_ld, Some(threshold) if amount_ld > threshold)
+    }
+
+    pub fn requires_transfer_receipt(&self, amount_ld: u64) -> bool {
+        matches!(&self.transfer_receipts, Some(policy) if amount_ld >= policy.min_amount_ld)
+    }
+
+    pub fn requires_override_approval(&self, notional_ld: u64) -> bool {
+        matches!(self.override_approval_threshold_ld, Some(threshold) if notional_ld > threshold)
+    }
+
+    pub fn requires_override_cosigner(&self, expected_amount_ld: Option<u64>) -> bool {
+        match (self.override_cosign_threshold_ld, expected_amount_ld) {
+            (Some(threshold), Some(amount_ld)) => amount_ld > threshold,
+            (Some(_), None) => true,
+            (None, _) => false,
+        }
+    }
+
+    pub fn is_override_cosigner(&self, account: &Pubkey) -> bool {
+        self.override_cosigners.contains(account)
+    }
+
+    /// Tallies a transfer of `amount_ld` that skipped the rate limits through an override.
+    pub fn record_override_trigger(&mut self, amount_ld: u64) {
+        self.total_overridden_volume_ld = self.total_overridden_volume_ld.saturating_add(amount_ld);
+        self.override_trigger_count = self.override_trigger_count.saturating_add(1);
+    }
+
+    pub fn is_test_mode(&self) -> bool {
+        cfg!(feature = "test-mode") && self.test_mode
+    }
+
+    pub fn is_control_message_paused(&self, message: ControlMessage) -> bool {
+        self.paused_control_messages.contains(&message)
+    }
+
+    pub fn set_control_message_paused(&mut self, message: ControlMessage, paused: bool) {
+        self.paused_control_messages.retain(|paused_message| *paused_message != message);
+        if paused {
+            self.paused_control_messages.push(message);
+        }
+    }
+
+    pub fn is_fee_vault_sender(&self, address: &Pubkey) -> bool {
+        self.fee_vault_senders.contains(address)
+    }
+
+    pub fn is_fee_collector(&self, account: &Pubkey) -> bool {
+        self.admin == *account || self.fee_collector == Some(*account)
+    }
+
+    pub fn accrue_fees(&mut self, amount_ld: u64) -> Result<()> {
+        self.accrued_fees_ld = self
+            .accrued_fees_ld
+            .checked_add(amount_ld)
+            .ok_or_else(|| error!(OFTError::AmountOverflow))?;
+        Ok(())
+    }
+
+    /// Takes fees paid out of the escrow off accrued_fees_ld. Fees accrued before it was tracked
+    /// can still be paid out by the crank and withdraw_fee, so it only goes down to zero.
+    pub fn pay_out_fees(&mut self, amount_ld: u64) {
+        self.accrued_fees_ld = self.accrued_fees_ld.saturating_sub(amount_ld);
+    }
+
+    pub fn is_fee_exempt(&self, address: &Pubkey) -> bool {
+        self.fee_exempt.contains(address)
+    }
+
+    pub fn add_fee_exempt(&mut self, address: Pubkey) -> Result<()> {
+        require!(self.fee_exempt.len() < MAX_FEE_EXEMPT_COUNT, OFTError::FeeExemptListFull);
+        require!(!self.fee_exempt.contains(&address), OFTError::AlreadyFeeExempt);
+        self.fee_exempt.push(address);
+        Ok(())
+    }
+
+    pub fn remove_fee_exempt(&mut self, address: &Pubkey) -> Result<()> {
+        let index =
+            self.fee_exempt.iter().position(|x| x == address).ok_or(OFTError::NotFeeExempt)?;
+        self.fee_exempt.swap_remove(index);
+        Ok(())
+    }
+
+    pub fn is_credit_verifier(&self, account: &Pubkey) -> bool {
+        self.admin == *account || self.credit_verifier == Some(*account)
+    }
+
+    /// The launch stage in effect at `now`, None before the first one or without a schedule.
+    pub fn launch_stage(&self, now: i64) -> Option<&LaunchStage> {
+        self.launch_schedule.iter().rev().find(|stage| stage.starts_at <= now)
+    }
+
+    pub fn is_rate_limiter(&self, account: &Pubkey) -> bool {
+        self.admin == *account || self.rate_limiter == Some(*account)
     }
 }
 
@@ -83,13 +782,173 @@ fn test_rate_limit_override() {
         paused: false,
         pauser: None,
         unpauser: None,
+        version: OFT_STORE_VERSION,
         rate_limit_override: Vec::new(),
         max_rate_limit_overrides: 10,
         rate_limit_override_guids: Vec::new(), // No guids in the test
         max_rate_limit_override_guid_count: 8,
+        endpoint_version: 0,
+        pending_endpoint_migration: None,
+        large_credit_threshold_ld: None,
+        credit_verifier: None,
+        override_manager: None,
+        override_approval_threshold_ld: None,
+        fee_vault_senders: Vec::new(),
+        crank_bounty_ld: None,
+        dust_to_fees: false,
+        fee_rounding_mode: RoundingMode::Floor,
+        probation_policy: None,
+        held_credit_refund: HeldCreditRefund::Payer,
+        metadata: None,
+        withdraw_destinations: Vec::new(),
+        pending_withdraw_destinations: None,
+        pending_supply_adjustment: None,
+        override_cosigners: Vec::new(),
+        override_cosign_threshold_ld: None,
+        test_mode: false,
+        tvl_alert: None,
+        tvl_watermarks: TvlWatermarks::default(),
+        contributed_lamports: 0,
+        allowed_composers: Vec::new(),
+        transfer_receipts: None,
+        allowed_dst_eids: Vec::new(),
+        zero_amount_policy: ZeroAmountPolicy::MessageOnly,
+        fee_stream: None,
+        rate_limiter: None,
+        sender_rate_limit: None,
+        launch_schedule: vec![],
+        overrides_consume_capacity: false,
+        queue_over_limit_credits: false,
+        total_overridden_volume_ld: 0,
+        override_trigger_count: 0,
+        override_approver: None,
+        governance_eid: None,
+        fee_exempt: Vec::new(),
+        accrued_fees_ld: 0,
+        fee_collector: None,
+        treasury: None,
+        paused_control_messages: Vec::new(),
+        honor_pause_notices: false,
     };
 
+    let (inbound, outbound) = (RateLimitDirection::Inbound, RateLimitDirection::Outbound);
     let admin = Pubkey::new_unique();
-    oft_store.rate_limit_override.push(admin);
-    assert!(oft_store.is_rate_limit_override(&admin));
-}   
\ No newline at end of file
+    let entry = RateLimitOverrideAddress {
+        address: admin,
+        expires_at: None,
+        direction: None,
+        multiplier: None,
+        scope: RateLimitOverrideScope::Either,
+    };
+    oft_store.add_rate_limit_override(entry).unwrap();
+    assert!(oft_store.is_rate_limit_override_at(&admin, inbound, i64::MAX));
+    assert!(oft_store.is_rate_limit_override_at(&admin, outbound, i64::MAX));
+
+    let temporary = Pubkey::new_unique();
+    let entry = RateLimitOverrideAddress {
+        address: temporary,
+        expires_at: Some(100),
+        direction: None,
+        multiplier: None,
+        scope: RateLimitOverrideScope::Either,
+    };
+    oft_store.add_rate_limit_override(entry).unwrap();
+    assert!(oft_store.is_rate_limit_override_at(&temporary, inbound, 99));
+    assert!(!oft_store.is_rate_limit_override_at(&temporary, inbound, 100));
+    assert_eq!(oft_store.prune_rate_limit_overrides(99), vec![]);
+    assert_eq!(oft_store.prune_rate_limit_overrides(100), vec![temporary]);
+    assert_eq!(oft_store.rate_limit_override.len(), 1);
+
+    let recipient = Pubkey::new_unique();
+    let entry = RateLimitOverrideAddress {
+        address: recipient,
+        expires_at: None,
+        direction: Some(inbound),
+        multiplier: None,
+        scope: RateLimitOverrideScope::Either,
+    };
+    oft_store.add_rate_limit_override(entry).unwrap();
+    assert!(oft_store.is_rate_limit_override_at(&recipient, inbound, 0));
+    assert!(!oft_store.is_rate_limit_override_at(&recipient, outbound, 0));
+
+    let market_maker = Pubkey::new_unique();
+    let entry = RateLimitOverrideAddress {
+        address: market_maker,
+        expires_at: None,
+        direction: None,
+        multiplier: Some(3),
+        scope: RateLimitOverrideScope::Either,
+    };
+    oft_store.add_rate_limit_override(entry).unwrap();
+    assert!(!oft_store.is_rate_limit_override_at(&market_maker, inbound, 0));
+    assert_eq!(oft_store.override_factor_at(&market_maker, inbound, 0), Some(3));
+    assert_eq!(oft_store.override_factor_at(&admin, inbound, 0), None);
+
+    let wallet = Pubkey::new_unique();
+    let entry = RateLimitOverrideAddress {
+        address: wallet,
+        expires_at: None,
+        direction: None,
+        multiplier: None,
+        scope: RateLimitOverrideScope::Sender,
+    };
+    oft_store.add_rate_limit_override(entry).unwrap();
+    assert!(oft_store.is_rate_limit_override_at(&wallet, outbound, 0));
+    assert!(!oft_store.is_rate_limit_override_at(&wallet, inbound, 0));
+
+    // a program's PDA is matched like any other address
+    let (vault, _) = Pubkey::find_program_address(&[b"vault"], &Pubkey::new_unique());
+    assert!(!vault.is_on_curve());
+    let entry = RateLimitOverrideAddress {
+        address: vault,
+        expires_at: None,
+        direction: None,
+        multiplier: None,
+        scope: RateLimitOverrideScope::Either,
+    };
+    oft_store.add_rate_limit_override(entry).unwrap();
+    assert!(oft_store.is_rate_limit_override_at(&vault, outbound, 0));
+    assert!(oft_store.is_rate_limit_override_at(&vault, inbound, 0));
+}
+
+#[test]
+fn test_rate_limit_override_guid_is_one_shot() {
+    let mut oft_store = OFTStore { max_rate_limit_override_guid_count: 2, ..Default::default() };
+    let entry =
+        RateLimitOverrideGuid { guid: [1; 32], expected_amount_ld: Some(5), src_eid: Some(30110) };
+    oft_store.add_rate_limit_override_guid(entry).unwrap();
+    assert!(!oft_store.consume_rate_limit_override_guid(&[1; 32], 30110, 6));
+    assert!(!oft_store.consume_rate_limit_override_guid(&[1; 32], 30184, 5));
+    assert!(oft_store.consume_rate_limit_override_guid(&[1; 32], 30110, 5));
+    assert!(!oft_store.consume_rate_limit_override_guid(&[1; 32], 30110, 5));
+    assert!(oft_store.rate_limit_override_guids.is_empty());
+}
+
+#[test]
+fn test_tvl_watermarks() {
+    let alert = Some(TvlAlert { drop_bps: 2_000, window_seconds: 100 });
+    let mut watermarks = TvlWatermarks::default();
+    assert!(!watermarks.record(1000, 120, 100, &alert));
+    assert_eq!((watermarks.window_start, watermarks.high_ld, watermarks.low_ld), (1000, 120, 100));
+    assert!(!watermarks.record(1050, 100, 97, &alert));
+    assert!(watermarks.record(1060, 97, 95, &alert));
+    // only once per window
+    assert!(!watermarks.record(1070, 95, 10, &alert));
+    assert_eq!(watermarks.low_ld, 10);
+
+    // the next window starts from the TVL before the change
+    assert!(!watermarks.record(1100, 10, 9, &alert));
+    assert_eq!((watermarks.window_start, watermarks.high_ld, watermarks.low_ld), (1100, 10, 9));
+    assert!(watermarks.record(1150, 9, 7, &alert));
+    assert!(!watermarks.record(1200, 7, 0, &None));
+}
+
+#[test]
+fn test_rounding_mode() {
+    assert_eq!(RoundingMode::Floor.div(15, 10), Some(1));
+    assert_eq!(RoundingMode::HalfEven.div(14, 10), Some(1));
+    assert_eq!(RoundingMode::HalfEven.div(15, 10), Some(2));
+    assert_eq!(RoundingMode::HalfEven.div(25, 10), Some(2));
+    assert_eq!(RoundingMode::HalfEven.div(26, 10), Some(3));
+    assert_eq!(RoundingMode::HalfEven.div(1, 0), None);
+}
@@ -0,0 +1,61 @@
+use crate::*;
+
+pub const RATE_LIMIT_OVERRIDE_PROPOSAL_WINDOW_SECONDS: i64 = 24 * 60 * 60; // 1 day
+
+/// RateLimitOverrideProposal holds an override addition above the store's
+/// override_approval_threshold_ld until the admin confirms it.
+#[account]
+#[derive(InitSpace)]
+pub struct RateLimitOverrideProposal {
+    pub oft_store: Pubkey,
+    pub target: RateLimitOverrideTarget,
+    pub notional_ld: u64,
+    pub proposer: Pubkey, // receives the rent back once the proposal is confirmed or expired
+    pub expires_at: i64,
+}
+
+#[derive(InitSpace, Clone, AnchorSerialize, AnchorDeserialize, PartialEq, Eq)]
+pub enum RateLimitOverrideTarget {
+    Address(Pubkey),
+    Guid([u8; 32]),
+}
+
+impl RateLimitOverrideTarget {
+    pub fn seed(&self) -> [u8; 32] {
+        match self {
+            RateLimitOverrideTarget::Address(address) => address.to_bytes(),
+            RateLimitOverrideTarget::Guid(guid) => *guid,
+        }
+    }
+
+    /// Adds the target to the matching override list and emits the update event.
+    pub fn add_to(&self, oft_store: &mut OFTStore) -> Result<()> {
+        match self {
+            RateLimitOverrideTarget::Address(address) => {
+                oft_store.add_rate_limit_override(RateLimitOverrideAddress {
+                    address: *address,
+                    expires_at: None,
+                    direction: None,
+                    multiplier: None,
+                    scope: RateLimitOverrideScope::Either,
+                })?;
+                emit!(RateLimitOverrideUpdated {
+                    address: *address,
+                    action: RateLimitOverrideAction::Add,
+                });
+            },
+            RateLimitOverrideTarget::Guid(guid) => {
+                oft_store.add_rate_limit_override_guid(RateLimitOverrideGuid {
+                    guid: *guid,
+                    expected_amount_ld: None,
+                    src_eid: None,
+                })?;
+                emit!(RateLimitOverrideGuidUpdated {
+                    guid: *guid,
+                    action: RateLimitOverrideAction::Add,
+                });
+            },
+        }
+        Ok(())
+    }
+}
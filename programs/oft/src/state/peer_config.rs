@@ -2,9 +2,28 @@ use crate::*;
 
 pub const ENFORCED_OPTIONS_SEND_MAX_LEN: usize = 512;
 pub const ENFORCED_OPTIONS_SEND_AND_CALL_MAX_LEN: usize = 1024;
+// The layout of PeerConfig, see state/migration.rs
+pub const PEER_CONFIG_VERSION: u8 = 1;
+
+pub const MAX_SOURCE_SENDERS: usize = 4;
+pub const MAX_EXECUTORS: usize = 4;
+pub const MAX_PRIORITY_TIERS: usize = 3;
+pub const MAX_MSG_TYPE_RATE_LIMITERS: usize = 4; // both message types in both directions
+pub const PRIORITY_TIER_OPTIONS_MAX_LEN: usize = 128;
+// Sends at this priority use only the enforced and caller options unless a tier 0 is configured.
+pub const PRIORITY_STANDARD: u8 = 0;
+// LayerZero OFT message types: plain transfers and transfers carrying a compose message.
+pub const MSG_TYPE_SEND: u8 = 1;
+pub const MSG_TYPE_SEND_AND_CALL: u8 = 2;
+pub const OUTFLOW_SHORT_HALF_LIFE_SECONDS: i64 = 60 * 60; // 1 hour
+pub const OUTFLOW_LONG_HALF_LIFE_SECONDS: i64 = 24 * 60 * 60; // 1 day
+
+const OPTIONS_TYPE_3: u16 = 3;
+const EXECUTOR_WORKER_ID: u8 = 1;
+const EXECUTOR_OPTION_TYPE_LZRECEIVE: u8 = 1;
 
 #[account]
-#[derive(InitSpace)]
+#[derive(InitSpace, Default)]
 pub struct PeerConfig {
     pub peer_address: [u8; 32],
     pub enforced_options: EnforcedOptions,
@@ -12,6 +31,441 @@ pub struct PeerConfig {
     pub inbound_rate_limiter: Option<RateLimiter>,
     pub fee_bps: Option<u16>,
     pub bump: u8,
+    pub version: u8, // the layout the peer was written in, see PEER_CONFIG_VERSION
+    // The OFTStore endpoint_version this peer was last verified against.
+    pub endpoint_version: u8,
+    // Highest inbound nonce executed from this peer, used to detect skipped or censored messages.
+    pub highest_inbound_nonce: u64,
+    pub nonce_gap_monitor: Option<NonceGapMonitor>,
+    // Sends whose combined options carry less lzReceive gas than this are rejected.
+    pub min_dst_gas: Option<MinDstGas>,
+    // Until then the store's probation_policy applies to this peer.
+    pub probation_ends_at: i64,
+    pub source_sender_allowlist: Option<SourceSenderAllowlist>,
+    // Exponentially decayed outbound volume, reported in OFTSent.
+    pub outflow_stats: OutflowStats,
+    // Executor options offered as delivery tiers, selected by the priority of a send.
+    #[max_len(MAX_PRIORITY_TIERS)]
+    pub priority_tiers: Vec<PriorityTier>,
+    // RemoteConfig::hash of the peer's expected EVM-side parameters, see check_remote_config.
+    pub expected_remote_config_hash: Option<[u8; 32]>,
+    pub inbound_split: Option<InboundSplit>,
+    // The fee the peer deducts on delivery, mirrored from its RemoteConfig; only quoted.
+    pub remote_fee_bps: Option<u16>,
+    // Executors allowed to drive lz_receive for this peer; empty allows any.
+    #[max_len(MAX_EXECUTORS)]
+    pub executor_allowlist: Vec<Pubkey>,
+    // When a message from this peer was last executed, see get_peer_health.
+    pub last_inbound_at: i64,
+    pub silence_monitor: Option<SilenceMonitor>,
+    // How the rate limiters above regain capacity.
+    pub outbound_rate_limit_mode: RateLimitMode,
+    pub inbound_rate_limit_mode: RateLimitMode,
+    // Sends are rejected until the peer has echoed a challenge_peer nonce, proving the remote
+    // address is an OFT wired back to this store. Reset whenever peer_address changes.
+    pub control_proven: bool,
+    pub control_challenge: Option<[u8; 32]>, // the nonce awaiting its echo
+    // Temporary raises of the rate limiters above by boost_rate_limit, undone once they expire.
+    pub outbound_rate_limit_boost: Option<RateLimitBoost>,
+    pub inbound_rate_limit_boost: Option<RateLimitBoost>,
+    // Cleared with set_rate_limit_enabled for trusted peers, whose transfers then skip the rate
+    // limiters and launch cap in both directions. Set for new peers.
+    pub rate_limit_enabled: bool,
+    // Capacity of each rate limiter public transfers cannot use, so addresses overridden with a
+    // multiplier, and overridden transfers when overrides consume capacity, always have it.
+    pub reserved_capacity_ld: u64,
+    // Further rate limiters for one message type and direction, charged on top of the rate
+    // limiter of that direction, so composed transfers can be held to another policy than plain
+    // ones. They share the direction's mode and are neither boosted, netted nor set by the launch
+    // schedule.
+    #[max_len(MAX_MSG_TYPE_RATE_LIMITERS)]
+    pub msg_type_rate_limiters: Vec<MsgTypeRateLimiter>,
+    // Transfers with the peer fail in both directions while set, by set_peer_config or by the
+    // breach monitor.
+    pub paused: bool,
+    pub breach_monitor: Option<BreachMonitor>,
+    // Transfers sent to the peer against the deliveries it has acknowledged, see get_in_flight.
+    pub in_flight: Option<InFlight>,
+}
+
+impl PeerConfig {
+    pub fn is_allowed_executor(&self, executor: &Pubkey) -> bool {
+        self.executor_allowlist.is_empty() || self.executor_allowlist.contains(executor)
+    }
+
+    /// Completes the pending control challenge if `nonce` is the one it is waiting for.
+    pub fn prove_control(&mut self, nonce: &[u8; 32]) -> bool {
+        if self.control_challenge.as_ref() != Some(nonce) {
+            return false;
+        }
+        self.control_challenge = None;
+        self.control_proven = true;
+        true
+    }
+
+    /// Restores the limits of rate limiters whose boost has expired at `now`.
+    pub fn expire_rate_limit_boosts(&mut self, now: i64) -> Result<()> {
+        for (boost, rate_limiter, mode) in [
+            (
+                &mut self.outbound_rate_limit_boost,
+                &mut self.outbound_rate_limiter,
+                self.outbound_rate_limit_mode,
+            ),
+            (
+                &mut self.inbound_rate_limit_boost,
+                &mut self.inbound_rate_limiter,
+                self.inbound_rate_limit_mode,
+            ),
+        ] {
+            if boost.as_ref().map_or(true, |boost| now < boost.expires_at) {
+                continue;
+            }
+            if let (Some(boost), Some(rate_limiter)) = (boost.take(), rate_limiter.as_mut()) {
+                boost.restore(rate_limiter, mode)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The rate limiter set for `msg_type` in `direction`, if any.
+    pub fn msg_type_rate_limiter(
+        &self,
+        msg_type: u8,
+        direction: RateLimitDirection,
+    ) -> Option<&RateLimiter> {
+        self.msg_type_rate_limiters
+            .iter()
+            .find(|entry| entry.msg_type == msg_type && entry.direction == direction)
+            .map(|entry| &entry.rate_limiter)
+    }
+
+    pub fn msg_type_rate_limiter_mut(
+        &mut self,
+        msg_type: u8,
+        direction: RateLimitDirection,
+    ) -> Option<&mut RateLimiter> {
+        self.msg_type_rate_limiters
+            .iter_mut()
+            .find(|entry| entry.msg_type == msg_type && entry.direction == direction)
+            .map(|entry| &mut entry.rate_limiter)
+    }
+
+    /// Counts a rate limit rejection at `now` against the breach monitor, if any, and pauses the
+    /// peer once more than max_breaches fall within the window. Returns whether it paused it.
+    pub fn record_rate_limit_breach(&mut self, now: i64) -> bool {
+        let monitor = match self.breach_monitor.as_mut() {
+            Some(monitor) => monitor,
+            None => return false,
+        };
+        if now >= monitor.window_start.saturating_add(monitor.window_seconds) {
+            monitor.window_start = now;
+            monitor.breaches = 0;
+        }
+        monitor.breaches = monitor.breaches.saturating_add(1);
+        if self.paused || monitor.breaches <= monitor.max_breaches {
+            return false;
+        }
+        self.paused = true;
+        true
+    }
+
+    pub fn is_on_probation(&self, now: i64) -> bool {
+        now < self.probation_ends_at
+    }
+
+    /// Records an executed inbound nonce and returns the previous highest nonce if `nonce` is
+    /// further ahead of it than the monitor allows.
+    pub fn record_inbound_nonce(&mut self, nonce: u64) -> Option<u64> {
+        let previous = self.highest_inbound_nonce;
+        self.highest_inbound_nonce = previous.max(nonce);
+        match &self.nonce_gap_monitor {
+            Some(monitor) if nonce.saturating_sub(previous) > monitor.max_gap => Some(previous),
+            _ => None,
+        }
+    }
+
+    /// Records that a message from the peer was executed at `now`.
+    pub fn record_inbound_at(&mut self, now: i64) {
+        self.last_inbound_at = now;
+        if let Some(monitor) = self.silence_monitor.as_mut() {
+            monitor.reported = false;
+        }
+    }
+
+    /// Whether the peer has been silent for longer than its silence monitor allows.
+    pub fn is_silent(&self, now: i64) -> bool {
+        self.silence_monitor.as_ref().map_or(false, |monitor| {
+            now.saturating_sub(self.last_inbound_at.max(monitor.since))
+                > monitor.max_silence_seconds
+        })
+    }
+
+    /// Returns the tier selected by `priority`, failing for unknown tiers other than
+    /// PRIORITY_STANDARD.
+    pub fn priority_tier(&self, priority: u8) -> Result<Option<&PriorityTier>> {
+        let tier = self.priority_tiers.iter().find(|tier| tier.tier == priority);
+        require!(tier.is_some() || priority == PRIORITY_STANDARD, OFTError::UnknownPriorityTier);
+        Ok(tier)
+    }
+
+    /// Combines the enforced, priority tier and caller options and checks them against
+    /// min_dst_gas.
+    pub fn combine_options(
+        &self,
+        compose_msg: &Option<Vec<u8>>,
+        extra_options: &Vec<u8>,
+        priority: u8,
+    ) -> Result<Vec<u8>> {
+        let extra_options = match self.priority_tier(priority)? {
+            Some(tier) => oapp::options::combine_options(tier.options.clone(), extra_options)?,
+            None => extra_options.clone(),
+        };
+        let options = self.enforced_options.combine_options(compose_msg, &extra_options)?;
+        if let Some(min_dst_gas) = &self.min_dst_gas {
+            let required = if compose_msg.is_none() {
+                min_dst_gas.send
+            } else {
+                min_dst_gas.send_and_call
+            };
+            require!(lz_receive_gas(&options)? >= required, OFTError::InsufficientDstGas);
+        }
+        Ok(options)
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize, InitSpace, PartialEq, Eq, Debug)]
+pub struct PriorityTier {
+    pub tier: u8,
+    // type 3 executor options added to every send at this tier
+    #[max_len(PRIORITY_TIER_OPTIONS_MAX_LEN)]
+    pub options: Vec<u8>,
+    pub sla_seconds: u32, // expected delivery time, reported by quote_oft
+}
+
+/// Outbound volume decayed with a one hour and a one day half-life. A steady outflow of r per
+/// second settles at r * half_life / ln 2 in each, so comparing `short_ld / 3600` with
+/// `long_ld / 86400` flags bursts without any off-chain state.
+#[derive(Clone, Default, AnchorSerialize, AnchorDeserialize, InitSpace, PartialEq, Eq, Debug)]
+pub struct OutflowStats {
+    pub updated_at: i64,
+    pub short_ld: u64,
+    pub long_ld: u64,
+}
+
+impl OutflowStats {
+    pub fn record(&mut self, now: i64, amount_ld: u64) {
+        let elapsed = now.saturating_sub(self.updated_at).max(0);
+        self.short_ld = decay(self.short_ld, elapsed, OUTFLOW_SHORT_HALF_LIFE_SECONDS)
+            .saturating_add(amount_ld);
+        self.long_ld = decay(self.long_ld, elapsed, OUTFLOW_LONG_HALF_LIFE_SECONDS)
+            .saturating_add(amount_ld);
+        self.updated_at = now;
+    }
+}
+
+/// Halves `value` once per elapsed half-life, interpolating linearly within the last one.
+fn decay(value: u64, elapsed: i64, half_life: i64) -> u64 {
+    let halvings = elapsed / half_life;
+    if halvings >= 64 {
+        return 0;
+    }
+    let value = value >> halvings;
+    let remainder = (elapsed % half_life) as u128;
+    // value * (1 - remainder / (2 * half_life))
+    value - (value as u128 * remainder / (2 * half_life as u128)) as u64
+}
+
+// Restricts composed transfers from the peer to these embedded source senders (composeFrom).
+// Plain transfers carry no source sender and are not affected.
+#[derive(Clone, AnchorSerialize, AnchorDeserialize, InitSpace, PartialEq, Eq, Debug)]
+pub struct SourceSenderAllowlist {
+    #[max_len(MAX_SOURCE_SENDERS)]
+    pub senders: Vec<[u8; 32]>,
+    // Hold other senders' transfers for the credit verifier instead of rejecting them.
+    pub quarantine: bool,
+}
+
+// Inbound transfers above threshold_ld are credited in tranches: immediate_bps of the amount right
+// away and the rest vesting in a PendingCredit over `epochs` epochs, see claim_pending_credit.
+#[derive(Clone, AnchorSerialize, AnchorDeserialize, InitSpace, PartialEq, Eq, Debug)]
+pub struct InboundSplit {
+    pub threshold_ld: u64,
+    pub immediate_bps: u16,
+    pub epoch_seconds: i64,
+    pub epochs: u16,
+}
+
+impl InboundSplit {
+    pub fn applies_to(&self, amount_ld: u64) -> bool {
+        amount_ld > self.threshold_ld
+    }
+
+    pub fn immediate_ld(&self, amount_ld: u64) -> u64 {
+        (amount_ld as u128 * self.immediate_bps as u128 / MAX_FEE_BASIS_POINTS as u128) as u64
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize, InitSpace, PartialEq, Eq, Debug)]
+pub struct MinDstGas {
+    pub send: u128,
+    pub send_and_call: u128,
+}
+
+/// Sums the executor lzReceive gas in type 3 options, as the executor does on delivery.
+pub fn lz_receive_gas(options: &[u8]) -> Result<u128> {
+    if options.is_empty() {
+        return Ok(0);
+    }
+    require!(
+        options.len() >= 2 && u16::from_be_bytes([options[0], options[1]]) == OPTIONS_TYPE_3,
+        OFTError::InvalidOptions
+    );
+    let mut gas: u128 = 0;
+    let mut cursor = 2;
+    while cursor < options.len() {
+        // worker id (1) | option size (2) | option type (1) | params (size - 1)
+        require!(options.len() >= cursor + 4, OFTError::InvalidOptions);
+        let worker_id = options[cursor];
+        let size = u16::from_be_bytes([options[cursor + 1], options[cursor + 2]]) as usize;
+        let start = cursor + 3;
+        let end = start + size;
+        require!(size > 0 && end <= options.len(), OFTError::InvalidOptions);
+        if worker_id == EXECUTOR_WORKER_ID && options[start] == EXECUTOR_OPTION_TYPE_LZRECEIVE {
+            // gas (u128) followed by an optional value (u128)
+            require!(size >= 17, OFTError::InvalidOptions);
+            let mut option_gas = [0u8; 16];
+            option_gas.copy_from_slice(&options[start + 1..start + 17]);
+            gas = gas
+                .checked_add(u128::from_be_bytes(option_gas))
+                .ok_or_else(|| error!(OFTError::AmountOverflow))?;
+        }
+        cursor = end;
+    }
+    Ok(gas)
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct NonceGapMonitor {
+    pub max_gap: u64,
+    // Fail lz_receive instead of only emitting NonceGapDetected, leaving the message to be
+    // executed once the gap is filled or the monitor is relaxed.
+    pub hold: bool,
+}
+
+/// Flags the pathway from the peer as possibly halted once nothing has been received from it for
+/// max_silence_seconds, counted from when the monitor was set at the earliest.
+#[derive(Clone, AnchorSerialize, AnchorDeserialize, InitSpace, PartialEq, Eq, Debug)]
+pub struct SilenceMonitor {
+    pub max_silence_seconds: i64,
+    pub since: i64,
+    pub reported: bool, // PeerSilent is emitted once per silence, see CrankTask::ReportPeerSilence
+}
+
+/// Pauses the peer once more than max_breaches rate limit rejections are recorded within
+/// window_seconds of the first, see PeerConfig::record_rate_limit_breach. Only rejections that
+/// are not reverted can be counted, so inbound credits over the limit from a monitored peer are
+/// always queued, see policy::queues_over_limit. Send-side rejections revert and are not counted.
+#[derive(Clone, AnchorSerialize, AnchorDeserialize, InitSpace, PartialEq, Eq, Debug)]
+pub struct BreachMonitor {
+    pub max_breaches: u16,
+    pub window_seconds: i64,
+    pub window_start: i64,
+    pub breaches: u16, // recorded since window_start
+}
+
+/// Transfers sent to the peer and the deliveries it has acknowledged since tracking was set,
+/// in the amounts carried by their messages. The peer has to acknowledge deliveries with
+/// DeliveryAck control messages, so acks of transfers sent before are counted as well.
+#[derive(Clone, Default, AnchorSerialize, AnchorDeserialize, InitSpace, PartialEq, Eq, Debug)]
+pub struct InFlight {
+    pub sent_count: u64,
+    pub sent_ld: u64,
+    pub acknowledged_count: u64,
+    pub acknowledged_ld: u64,
+}
+
+impl InFlight {
+    pub fn record_sent(&mut self, amount_ld: u64) {
+        self.sent_count = self.sent_count.saturating_add(1);
+        self.sent_ld = self.sent_ld.saturating_add(amount_ld);
+    }
+
+    pub fn record_acknowledged(&mut self, amount_ld: u64) {
+        self.acknowledged_count = self.acknowledged_count.saturating_add(1);
+        self.acknowledged_ld = self.acknowledged_ld.saturating_add(amount_ld);
+    }
+}
+
+/// How a rate limiter regains capacity. A fixed window refills to capacity at every multiple of
+/// window_seconds since the unix epoch, so 86_400 allows capacity per UTC calendar day, and
+/// refill_per_second is ignored. A slot bucket is a token bucket refilled by slot height rather
+/// than by the validators' clock: refill_per_second is read as tokens per slot and
+/// last_refill_time holds a slot.
+#[derive(
+    Clone, Copy, Default, AnchorSerialize, AnchorDeserialize, InitSpace, PartialEq, Eq, Debug,
+)]
+pub enum RateLimitMode {
+    #[default]
+    TokenBucket,
+    FixedWindow {
+        window_seconds: u64,
+    },
+    SlotBucket,
+}
+
+impl RateLimitMode {
+    /// What rate limiters in this mode are refilled by: the slot for a slot bucket, the unix
+    /// timestamp otherwise.
+    pub fn now(self, clock: &Clock) -> u64 {
+        match self {
+            RateLimitMode::SlotBucket => clock.slot,
+            _ => clock.unix_timestamp.max(0) as u64,
+        }
+    }
+
+    /// A rate of `refill_per_second` in the refill unit of this mode.
+    pub fn refill_rate(self, refill_per_second: u64) -> u64 {
+        match self {
+            RateLimitMode::SlotBucket => simulation::refill_per_slot(refill_per_second),
+            _ => refill_per_second,
+        }
+    }
+}
+
+/// A rate limiter's own limits while boost_rate_limit has raised them until expires_at.
+#[derive(Clone, AnchorSerialize, AnchorDeserialize, InitSpace, PartialEq, Eq, Debug)]
+pub struct RateLimitBoost {
+    pub expires_at: i64,
+    pub capacity: u64,
+    pub refill_per_second: u64,
+    // The boosted limits; if the limiter was set to anything else since, it is left as it is.
+    pub boosted_capacity: u64,
+    pub boosted_refill_per_second: u64,
+}
+
+impl RateLimitBoost {
+    /// Puts `rate_limiter` back to its own limits, capping what is available at its capacity.
+    pub fn restore(&self, rate_limiter: &mut RateLimiter, mode: RateLimitMode) -> Result<()> {
+        if rate_limiter.capacity != self.boosted_capacity
+            || rate_limiter.refill_per_second != self.boosted_refill_per_second
+        {
+            return Ok(());
+        }
+        rate_limiter.refill(mode, 0)?;
+        rate_limiter.tokens = rate_limiter.tokens.min(self.capacity);
+        rate_limiter.capacity = self.capacity;
+        rate_limiter.refill_per_second = self.refill_per_second;
+        Ok(())
+    }
+}
+
+/// A rate limiter for transfers of one message type in one direction, see
+/// PeerConfig::msg_type_rate_limiters.
+#[derive(Clone, AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct MsgTypeRateLimiter {
+    pub msg_type: u8, // MSG_TYPE_SEND or MSG_TYPE_SEND_AND_CALL
+    pub direction: RateLimitDirection,
+    pub rate_limiter: RateLimiter,
 }
 
 #[derive(Clone, Default, AnchorSerialize, AnchorDeserialize, InitSpace)]
@@ -23,35 +477,33 @@ pub struct RateLimiter {
 }
 
 impl RateLimiter {
-    pub fn set_rate(&mut self, refill_per_second: u64) -> Result<()> {
-        self.refill(0)?;
+    pub fn set_rate(&mut self, mode: RateLimitMode, refill_per_second: u64) -> Result<()> {
+        self.refill(mode, 0)?;
         self.refill_per_second = refill_per_second;
         Ok(())
     }
 
-    pub fn set_capacity(&mut self, capacity: u64) -> Result<()> {
+    pub fn set_capacity(&mut self, mode: RateLimitMode, capacity: u64) -> Result<()> {
         self.capacity = capacity;
         self.tokens = capacity;
-        self.last_refill_time = Clock::get()?.unix_timestamp.try_into().unwrap();
+        self.last_refill_time = current_time(mode)?;
         Ok(())
     }
 
-    pub fn refill(&mut self, extra_tokens: u64) -> Result<()> {
-        let mut new_tokens = extra_tokens;
-        let current_time: u64 = Clock::get()?.unix_timestamp.try_into().unwrap();
-        if current_time > self.last_refill_time {
-            let time_elapsed_in_seconds = current_time - self.last_refill_time;
-            new_tokens = new_tokens
-                .saturating_add(time_elapsed_in_seconds.saturating_mul(self.refill_per_second));
-        }
-        self.tokens = std::cmp::min(self.capacity, self.tokens.saturating_add(new_tokens));
+    pub fn refill(&mut self, mode: RateLimitMode, extra_tokens: u64) -> Result<()> {
+        let current_time = current_time(mode)?;
+        self.tokens = simulation::refilled_tokens(
+            simulation::available_ld(self, mode, current_time),
+            extra_tokens as u128,
+            self.capacity,
+        );
 
         self.last_refill_time = current_time;
         Ok(())
     }
 
-    pub fn try_consume(&mut self, amount: u64) -> Result<()> {
-        self.refill(0)?;
+    pub fn try_consume(&mut self, mode: RateLimitMode, amount: u64) -> Result<()> {
+        self.refill(mode, 0)?;
         match self.tokens.checked_sub(amount) {
             Some(new_tokens) => {
                 self.tokens = new_tokens;
@@ -60,6 +512,21 @@ impl RateLimiter {
             None => Err(error!(OFTError::RateLimitExceeded)),
         }
     }
+
+    /// Charges `amount` without checking it, down to zero at most.
+    pub fn deduct(&mut self, mode: RateLimitMode, amount: u64) -> Result<()> {
+        self.refill(mode, 0)?;
+        self.tokens = self.tokens.saturating_sub(amount);
+        Ok(())
+    }
+}
+
+fn current_time(mode: RateLimitMode) -> Result<u64> {
+    let clock = Clock::get()?;
+    match mode {
+        RateLimitMode::SlotBucket => Ok(clock.slot),
+        _ => clock.unix_timestamp.try_into().map_err(|_| error!(OFTError::InvalidTimestamp)),
+    }
 }
 
 #[derive(Clone, Default, AnchorSerialize, AnchorDeserialize, InitSpace)]
@@ -90,3 +557,43 @@ impl EnforcedOptions {
 }
 
 utils::generate_account_size_test!(EnforcedOptions, enforced_options_test);
+
+#[test]
+fn test_lz_receive_gas() {
+    fn lz_receive_option(gas: u128) -> Vec<u8> {
+        let mut option = vec![EXECUTOR_WORKER_ID, 0, 17, EXECUTOR_OPTION_TYPE_LZRECEIVE];
+        option.extend_from_slice(&gas.to_be_bytes());
+        option
+    }
+
+    assert_eq!(lz_receive_gas(&[]).unwrap(), 0);
+    assert_eq!(lz_receive_gas(&[0, 3]).unwrap(), 0);
+
+    let mut options = vec![0, 3];
+    options.extend(lz_receive_option(150_000));
+    // native drop options are skipped
+    options.extend([EXECUTOR_WORKER_ID, 0, 49, 2]);
+    options.extend([0u8; 48]);
+    options.extend(lz_receive_option(50_000));
+    assert_eq!(lz_receive_gas(&options).unwrap(), 200_000);
+
+    assert!(lz_receive_gas(&options[..options.len() - 1]).is_err());
+    assert!(lz_receive_gas(&[0, 1, 0, 0]).is_err());
+}
+
+#[test]
+fn test_outflow_stats() {
+    let mut stats = OutflowStats::default();
+    stats.record(1_000, 1_000);
+    assert_eq!(stats, OutflowStats { updated_at: 1_000, short_ld: 1_000, long_ld: 1_000 });
+
+    stats.record(1_000 + OUTFLOW_SHORT_HALF_LIFE_SECONDS, 0);
+    assert_eq!(stats.short_ld, 500);
+    assert_eq!(stats.long_ld, 1_000 - 1_000 / 48);
+
+    stats.record(1_000 + OUTFLOW_SHORT_HALF_LIFE_SECONDS * 3 / 2, 0);
+    assert_eq!(stats.short_ld, 375);
+
+    stats.record(1_000 + OUTFLOW_LONG_HALF_LIFE_SECONDS * 64, 0);
+    assert_eq!((stats.short_ld, stats.long_ld), (0, 0));
+}
@@ -0,0 +1,138 @@
+use crate::*;
+use anchor_lang::system_program::{self, Allocate, Assign, CreateAccount, Transfer};
+
+/// PendingCredit holds an inbound credit above the store's large_credit_threshold_ld until the
+/// credit verifier confirms the source sender is not blocked on the canonical chain, and any
+/// credit to a recipient with an InboundOptOut until the recipient claims it. It also holds the
+/// vesting remainder of a transfer split by the peer's InboundSplit, and, if the store queues
+/// over-limit credits, credits the peer's inbound rate limit could not cover until it refills.
+#[account]
+#[derive(InitSpace)]
+pub struct PendingCredit {
+    pub oft_store: Pubkey,
+    pub guid: [u8; 32],
+    pub src_eid: u32,
+    pub nonce: u64,
+    pub to_address: Pubkey,
+    pub amount_ld: u64,
+    pub payer: Pubkey, // receives the rent back once the credit is released, see HeldCreditRefund
+    pub rejected: bool,
+    pub message_hash: [u8; 32], // of the held message, reported in OFTReceived once released
+    pub awaiting_verification: bool,
+    pub awaiting_claim: bool,
+    pub awaiting_capacity: bool, // charged to the inbound rate limit by execute_pending_credit
+    pub vesting: Option<Vesting>, // amount_ld is claimable as it vests instead of all at once
+    // the account is sized at init to fit the compose message, if any
+    #[max_len(0)]
+    pub compose_msg: Option<Vec<u8>>,
+}
+
+impl PendingCredit {
+    /// Pending credits are keyed by recipient and guid, so the address of a deposit can be
+    /// computed before it arrives.
+    pub fn address(oft_store: &Pubkey, to_address: &Pubkey, guid: &[u8; 32]) -> Pubkey {
+        Pubkey::find_program_address(
+            &[PENDING_CREDIT_SEED, oft_store.as_ref(), to_address.as_ref(), guid],
+            &crate::ID,
+        )
+        .0
+    }
+
+    /// Creates the account at `info` and writes the credit into it. lz_receive_types always lists
+    /// the address since it cannot tell whether the recipient has opted out, so lz_receive creates
+    /// the account itself only when the credit is held.
+    pub fn init<'info>(
+        &self,
+        info: &AccountInfo<'info>,
+        payer: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+        bump: u8,
+    ) -> Result<()> {
+        let space = 8 + Self::INIT_SPACE + self.compose_msg.as_ref().map_or(0, |msg| msg.len());
+        let seeds: &[&[u8]] = &[
+            PENDING_CREDIT_SEED,
+            self.oft_store.as_ref(),
+            self.to_address.as_ref(),
+            &self.guid,
+            &[bump],
+        ];
+        create_pda(info, payer, system_program, space, seeds)?;
+        self.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])
+    }
+}
+
+/// Creates a rent-exempt program account of `space` bytes at the PDA `info` derived from `seeds`.
+/// A pre-funded address cannot be created, so it is topped up and taken over instead.
+pub fn create_pda<'info>(
+    info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+    seeds: &[&[u8]],
+) -> Result<()> {
+    let lamports = Rent::get()?.minimum_balance(space);
+    let current_lamports = info.lamports();
+    if current_lamports == 0 {
+        return system_program::create_account(
+            CpiContext::new(
+                system_program.clone(),
+                CreateAccount { from: payer.clone(), to: info.clone() },
+            )
+            .with_signer(&[seeds]),
+            lamports,
+            space as u64,
+            &crate::ID,
+        );
+    }
+
+    let top_up = lamports.saturating_sub(current_lamports);
+    if top_up > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer { from: payer.clone(), to: info.clone() },
+            ),
+            top_up,
+        )?;
+    }
+    system_program::allocate(
+        CpiContext::new(system_program.clone(), Allocate { account_to_allocate: info.clone() })
+            .with_signer(&[seeds]),
+        space as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new(system_program.clone(), Assign { account_to_assign: info.clone() })
+            .with_signer(&[seeds]),
+        &crate::ID,
+    )
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize, InitSpace, PartialEq, Eq, Debug)]
+pub struct Vesting {
+    pub start: i64,
+    pub epoch_seconds: i64,
+    pub epochs: u16,
+    pub claimed_ld: u64,
+}
+
+impl Vesting {
+    /// The part of `amount_ld` vested at `now`, one equal tranche per elapsed epoch.
+    pub fn vested_ld(&self, amount_ld: u64, now: i64) -> u64 {
+        let elapsed = now.saturating_sub(self.start).max(0) / self.epoch_seconds;
+        if elapsed >= self.epochs as i64 {
+            return amount_ld;
+        }
+        (amount_ld as u128 * elapsed as u128 / self.epochs as u128) as u64
+    }
+}
+
+#[test]
+fn test_vesting() {
+    let vesting = Vesting { start: 1000, epoch_seconds: 100, epochs: 3, claimed_ld: 0 };
+    assert_eq!(vesting.vested_ld(90, 999), 0);
+    assert_eq!(vesting.vested_ld(90, 1099), 0);
+    assert_eq!(vesting.vested_ld(90, 1100), 30);
+    assert_eq!(vesting.vested_ld(100, 1250), 66);
+    assert_eq!(vesting.vested_ld(100, 1300), 100);
+    assert_eq!(vesting.vested_ld(100, i64::MAX), 100);
+}
@@ -0,0 +1,43 @@
+use crate::*;
+
+/// RateLimitState mirrors a peer's inbound rate limiter in a small account other programs can
+/// read directly. lz_receive keeps it current; sends only add capacity, so between receives it
+/// can understate what is available. Fixed window and slot bucket limiters are mirrored without
+/// their refill rate, so they read as a token bucket that does not refill until the next receive.
+#[account]
+#[derive(InitSpace)]
+pub struct RateLimitState {
+    pub oft_store: Pubkey,
+    pub remote_eid: u32,
+    pub bump: u8,
+    pub inbound_rate_limiter: Option<RateLimiter>, // None when inbound transfers are not limited
+}
+
+impl RateLimitState {
+    pub fn update(&mut self, peer: &PeerConfig) {
+        self.inbound_rate_limiter =
+            peer.inbound_rate_limiter.clone().map(|limiter| match peer.inbound_rate_limit_mode {
+                RateLimitMode::TokenBucket => limiter,
+                RateLimitMode::FixedWindow { .. } | RateLimitMode::SlotBucket => {
+                    RateLimiter { refill_per_second: 0, ..limiter }
+                },
+            });
+    }
+
+    /// Updates the account at `info` from the peer, if the account has been created.
+    pub fn sync(info: &AccountInfo, peer: &PeerConfig) -> Result<()> {
+        if info.owner != &crate::ID || info.data_is_empty() {
+            return Ok(());
+        }
+        let mut state = Self::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        state.update(peer);
+        state.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])
+    }
+
+    /// Inbound capacity available at `now`, or None if inbound transfers are not limited.
+    pub fn available_ld(&self, now: u64) -> Option<u64> {
+        self.inbound_rate_limiter
+            .as_ref()
+            .map(|limiter| simulation::available_ld(limiter, RateLimitMode::TokenBucket, now))
+    }
+}
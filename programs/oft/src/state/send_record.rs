@@ -0,0 +1,50 @@
+use crate::*;
+
+pub const SEND_RECORD_TTL_SECONDS: i64 = 10 * 60; // 10 minutes
+
+/// SendRecord remembers the idempotency key of a send until expires_at, so a backend retrying a
+/// send it already submitted cannot bridge the same withdrawal twice. Expired records are reused
+/// by the next send with the same key, or closed by anyone with close_send_record.
+#[account]
+#[derive(InitSpace)]
+pub struct SendRecord {
+    pub oft_store: Pubkey,
+    pub sender: Pubkey, // paid the rent, which is returned on close
+    pub idempotency_key: [u8; 32],
+    pub guid: [u8; 32],
+    pub expires_at: i64,
+}
+
+impl SendRecord {
+    /// Fails with DuplicateSend while an earlier send with the same key is still recorded at
+    /// `info`, which does not exist yet for a new key.
+    pub fn check(info: &AccountInfo, now: i64) -> Result<()> {
+        if info.owner != &crate::ID || info.data_is_empty() {
+            return Ok(());
+        }
+        let record = Self::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        require!(now >= record.expires_at, OFTError::DuplicateSend);
+        Ok(())
+    }
+
+    /// Writes the record to `info`, creating the account for a new key.
+    pub fn save<'info>(
+        &self,
+        info: &AccountInfo<'info>,
+        payer: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+        bump: u8,
+    ) -> Result<()> {
+        if info.owner != &crate::ID || info.data_is_empty() {
+            let seeds: &[&[u8]] = &[
+                SEND_RECORD_SEED,
+                self.oft_store.as_ref(),
+                self.sender.as_ref(),
+                &self.idempotency_key,
+                &[bump],
+            ];
+            create_pda(info, payer, system_program, 8 + Self::INIT_SPACE, seeds)?;
+        }
+        self.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])
+    }
+}
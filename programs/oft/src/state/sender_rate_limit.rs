@@ -0,0 +1,59 @@
+use crate::*;
+
+/// SenderRateLimit holds what is left of one sender's outbound capacity across all peers under
+/// the store's sender_rate_limit. It is created full by the sender's first send once the limit is
+/// set, with the sender paying the rent; the capacity and refill rate are always the store's
+/// current ones, so changing them applies to every sender at once.
+#[account]
+#[derive(InitSpace)]
+pub struct SenderRateLimit {
+    pub oft_store: Pubkey,
+    pub sender: Pubkey,
+    pub tokens: u64,
+    pub last_refill_time: u64,
+}
+
+impl SenderRateLimit {
+    /// Reads the sender's limiter at `info`, which starts full before the sender's first send.
+    pub fn load(
+        info: &AccountInfo,
+        oft_store: Pubkey,
+        sender: Pubkey,
+        config: &SenderRateLimitConfig,
+    ) -> Result<Self> {
+        if info.owner != &crate::ID || info.data_is_empty() {
+            return Ok(Self { oft_store, sender, tokens: config.capacity, last_refill_time: 0 });
+        }
+        Self::try_deserialize(&mut &info.try_borrow_data()?[..])
+    }
+
+    /// Charges `amount_ld` under the store's current `config`.
+    pub fn try_consume(&mut self, config: &SenderRateLimitConfig, amount_ld: u64) -> Result<()> {
+        let mut rate_limiter = RateLimiter {
+            capacity: config.capacity,
+            tokens: self.tokens,
+            refill_per_second: config.refill_per_second,
+            last_refill_time: self.last_refill_time,
+        };
+        rate_limiter.try_consume(RateLimitMode::TokenBucket, amount_ld)?;
+        self.tokens = rate_limiter.tokens;
+        self.last_refill_time = rate_limiter.last_refill_time;
+        Ok(())
+    }
+
+    /// Writes the limiter to `info`, creating the account on the sender's first send.
+    pub fn save<'info>(
+        &self,
+        info: &AccountInfo<'info>,
+        payer: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+        bump: u8,
+    ) -> Result<()> {
+        if info.owner != &crate::ID || info.data_is_empty() {
+            let seeds: &[&[u8]] =
+                &[SENDER_RATE_LIMIT_SEED, self.oft_store.as_ref(), self.sender.as_ref(), &[bump]];
+            create_pda(info, payer, system_program, 8 + Self::INIT_SPACE, seeds)?;
+        }
+        self.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])
+    }
+}
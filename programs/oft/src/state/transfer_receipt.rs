@@ -0,0 +1,49 @@
+use crate::*;
+
+/// TransferReceipt keeps the amount, counterparty and slot of a transfer of at least the store's
+/// transfer_receipts.min_amount_ld, so disputes over large transfers can be settled from chain
+/// state after the transaction history is pruned. Receipts are keyed by guid; inbound and
+/// outbound guids never collide. Anyone can close a receipt with close_transfer_receipt once its
+/// retention period is over.
+#[account]
+#[derive(InitSpace)]
+pub struct TransferReceipt {
+    pub oft_store: Pubkey,
+    pub guid: [u8; 32],
+    pub remote_eid: u32, // dst_eid of a send, src_eid of a receive
+    pub outbound: bool,
+    pub recipient: [u8; 32], // the remote recipient of a send, to_address of a receive
+    pub amount_ld: u64,
+    pub slot: u64,
+    pub payer: Pubkey, // receives the rent back on close
+    pub closable_at: i64,
+}
+
+#[derive(InitSpace, Clone, AnchorSerialize, AnchorDeserialize, PartialEq, Eq, Debug)]
+pub struct TransferReceiptPolicy {
+    pub min_amount_ld: u64,
+    pub retention_seconds: i64,
+}
+
+impl TransferReceipt {
+    pub fn address(oft_store: &Pubkey, guid: &[u8; 32]) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[TRANSFER_RECEIPT_SEED, oft_store.as_ref(), guid],
+            &crate::ID,
+        )
+    }
+
+    /// Creates the account at `info` and writes the receipt into it.
+    pub fn init<'info>(
+        &self,
+        info: &AccountInfo<'info>,
+        payer: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+        bump: u8,
+    ) -> Result<()> {
+        let seeds: &[&[u8]] =
+            &[TRANSFER_RECEIPT_SEED, self.oft_store.as_ref(), &self.guid, &[bump]];
+        create_pda(info, payer, system_program, 8 + Self::INIT_SPACE, seeds)?;
+        self.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])
+    }
+}
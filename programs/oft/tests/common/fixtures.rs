@@ -0,0 +1,55 @@
+use anchor_lang::prelude::Pubkey;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::Deserialize;
+use solana_program_test::ProgramTest;
+use solana_sdk::account::Account;
+use std::{fs, path::Path, str::FromStr};
+
+/// An account in the `solana account <pubkey> --output json` format, as written by the
+/// `lz:oft:solana:snapshot-accounts` task.
+#[derive(Deserialize)]
+struct Fixture {
+    pubkey: String,
+    account: FixtureAccount,
+}
+
+#[derive(Deserialize)]
+struct FixtureAccount {
+    lamports: u64,
+    data: (String, String),
+    owner: String,
+    executable: bool,
+}
+
+/// Adds every `*.json` fixture in `dir` to the program test, in file name order.
+/// Returns the loaded pubkeys.
+pub fn load_fixtures(program_test: &mut ProgramTest, dir: &Path) -> Vec<Pubkey> {
+    let mut paths: Vec<_> = fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().map_or(false, |ext| ext == "json"))
+        .collect();
+    paths.sort();
+
+    paths
+        .iter()
+        .map(|path| {
+            let fixture: Fixture = serde_json::from_slice(&fs::read(path).unwrap()).unwrap();
+            let (data, encoding) = fixture.account.data;
+            assert_eq!(encoding, "base64", "unsupported encoding in {:?}", path);
+
+            let pubkey = Pubkey::from_str(&fixture.pubkey).unwrap();
+            program_test.add_account(
+                pubkey,
+                Account {
+                    lamports: fixture.account.lamports,
+                    data: STANDARD.decode(data).unwrap(),
+                    owner: Pubkey::from_str(&fixture.account.owner).unwrap(),
+                    executable: fixture.account.executable,
+                    rent_epoch: 0,
+                },
+            );
+            pubkey
+        })
+        .collect()
+}
@@ -0,0 +1,64 @@
+#[cfg(test)]
+mod test_conversion_math {
+    use oft::instructions::{calculate_fee, MAX_FEE_BASIS_POINTS};
+    use oft::state::OFTStore;
+    use proptest::prelude::*;
+
+    fn oft_store(decimals_diff: u32) -> OFTStore {
+        OFTStore { ld2sd_rate: 10u64.pow(decimals_diff), ..Default::default() }
+    }
+
+    proptest! {
+        #[test]
+        fn ld2sd_sd2ld_round_trip_removes_dust(amount_ld: u64, decimals_diff in 0u32..=18) {
+            let oft_store = oft_store(decimals_diff);
+            let amount_sd = oft_store.ld2sd(amount_ld).unwrap();
+            prop_assert_eq!(oft_store.sd2ld(amount_sd).unwrap(), oft_store.remove_dust(amount_ld).unwrap());
+        }
+
+        #[test]
+        fn remove_dust_is_bounded(amount_ld: u64, decimals_diff in 0u32..=18) {
+            let oft_store = oft_store(decimals_diff);
+            let without_dust = oft_store.remove_dust(amount_ld).unwrap();
+            prop_assert!(without_dust <= amount_ld);
+            prop_assert!(amount_ld - without_dust < oft_store.ld2sd_rate);
+            prop_assert_eq!(without_dust % oft_store.ld2sd_rate, 0);
+        }
+
+        #[test]
+        fn ld2sd_is_monotonic(a: u64, b: u64, decimals_diff in 0u32..=18) {
+            let oft_store = oft_store(decimals_diff);
+            let (low, high) = if a <= b { (a, b) } else { (b, a) };
+            prop_assert!(oft_store.ld2sd(low).unwrap() <= oft_store.ld2sd(high).unwrap());
+            prop_assert!(oft_store.remove_dust(low).unwrap() <= oft_store.remove_dust(high).unwrap());
+        }
+
+        #[test]
+        fn sd2ld_reports_overflow(amount_sd: u64, decimals_diff in 0u32..=18) {
+            let oft_store = oft_store(decimals_diff);
+            let overflows = amount_sd > u64::MAX / oft_store.ld2sd_rate;
+            prop_assert_eq!(oft_store.sd2ld(amount_sd).is_err(), overflows);
+        }
+
+        #[test]
+        fn fee_never_exceeds_amount(
+            amount_ld: u64,
+            default_fee_bps in 0u16..MAX_FEE_BASIS_POINTS,
+            fee_bps in proptest::option::of(0u16..MAX_FEE_BASIS_POINTS),
+        ) {
+            let fee = calculate_fee(amount_ld, default_fee_bps, fee_bps).unwrap();
+            prop_assert!(fee <= amount_ld);
+            if fee_bps.unwrap_or(default_fee_bps) == 0 {
+                prop_assert_eq!(fee, 0);
+            }
+        }
+
+        #[test]
+        fn fee_is_monotonic(a: u64, b: u64, fee_bps in 0u16..MAX_FEE_BASIS_POINTS) {
+            let (low, high) = if a <= b { (a, b) } else { (b, a) };
+            prop_assert!(
+                calculate_fee(low, fee_bps, None).unwrap() <= calculate_fee(high, fee_bps, None).unwrap()
+            );
+        }
+    }
+}
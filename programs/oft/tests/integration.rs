@@ -0,0 +1,3901 @@
+// Full program tests against the mock endpoint. Both programs are loaded from their BPF builds:
+//   anchor build && BPF_OUT_DIR=$PWD/target/deploy cargo test -p oft --test integration
+mod common;
+
+mod test_integration {
+    use super::common::fixtures;
+    use anchor_lang::{prelude::Pubkey, Discriminator, InstructionData, ToAccountMetas};
+    use anchor_spl::{associated_token, token::spl_token};
+    use oapp::{
+        endpoint::{cpi::accounts::Send as EndpointSend, ConstructCPIContext},
+        LzReceiveParams,
+    };
+    use ::oft::{
+        self, errors::OFTError, instructions::*, msg_codec, policy::SendBlocker,
+        simulation::CreditOutcome, state::*,
+    };
+    use solana_program_test::{BanksClientError, ProgramTest, ProgramTestContext};
+    use solana_sdk::{
+        clock::Clock,
+        instruction::{AccountMeta, Instruction, InstructionError},
+        program_pack::Pack,
+        pubkey,
+        signature::{Keypair, Signer},
+        system_instruction, system_program,
+        transaction::{Transaction, TransactionError},
+    };
+    use std::path::Path;
+
+    const ENDPOINT_ID: Pubkey = pubkey!("4UswecAvXjNSHDAi2FDGZgBz9P4DdR7cabpmEXPCXhP3");
+    const REMOTE_EID: u32 = 30110;
+    const DECIMALS: u8 = 9;
+    const SHARED_DECIMALS: u8 = 6;
+
+    struct Harness {
+        ctx: ProgramTestContext,
+        admin: Keypair,
+        user: Keypair,
+        token_mint: Pubkey,
+        token_escrow: Pubkey,
+        token_source: Pubkey,
+        oft_store: Pubkey,
+        peer: Pubkey,
+        peer_address: [u8; 32],
+    }
+
+    impl Harness {
+        async fn new() -> Self {
+            Self::with_store(OFTType::Adapter).await
+        }
+
+        /// A harness whose store has `oft_type`.
+        async fn with_store(oft_type: OFTType) -> Self {
+            let mut program_test = ProgramTest::default();
+            program_test.prefer_bpf(true);
+            program_test.add_program("oft", oft::ID, None);
+            program_test.add_program("endpoint", ENDPOINT_ID, None);
+            let ctx = program_test.start_with_context().await;
+
+            let admin = Keypair::new();
+            let user = Keypair::new();
+            let mint = Keypair::new();
+            let escrow = Keypair::new();
+            let source = Keypair::new();
+            let (oft_store, _) = Pubkey::find_program_address(
+                &[oft::OFT_SEED, escrow.pubkey().as_ref()],
+                &oft::ID,
+            );
+            let (peer, _) = Pubkey::find_program_address(
+                &[oft::PEER_SEED, oft_store.as_ref(), &REMOTE_EID.to_be_bytes()],
+                &oft::ID,
+            );
+
+            let mut harness = Harness {
+                ctx,
+                admin,
+                user,
+                token_mint: mint.pubkey(),
+                token_escrow: escrow.pubkey(),
+                token_source: source.pubkey(),
+                oft_store,
+                peer,
+                peer_address: [7; 32],
+            };
+            let (admin, user) = (harness.admin.pubkey(), harness.user.pubkey());
+            harness.fund(&admin).await;
+            harness.fund(&user).await;
+            harness.create_mint_and_source(&mint, &source).await;
+            let native = oft_type == OFTType::Native;
+            harness.init_oft(&escrow, oft_type).await;
+            if native {
+                harness.hand_mint_authority_to_store().await;
+            }
+            harness
+                .set_peer_config(PeerConfigParam::PeerAddress(harness.peer_address))
+                .await
+                .unwrap();
+            harness.challenge_peer().await.unwrap();
+            let nonce = harness.peer().await.control_challenge.unwrap();
+            harness.echo_peer_challenge(nonce).await.unwrap();
+            harness
+        }
+
+        async fn process(
+            &mut self,
+            ixs: &[Instruction],
+            signers: &[&Keypair],
+        ) -> Result<(), BanksClientError> {
+            let blockhash = self.ctx.get_new_latest_blockhash().await.unwrap();
+            let mut all_signers = vec![&self.ctx.payer];
+            all_signers.extend_from_slice(signers);
+            let tx = Transaction::new_signed_with_payer(
+                ixs,
+                Some(&self.ctx.payer.pubkey()),
+                all_signers.as_slice(),
+                blockhash,
+            );
+            self.ctx.banks_client.process_transaction(tx).await
+        }
+
+        async fn fund(&mut self, to: &Pubkey) {
+            let ix = system_instruction::transfer(&self.ctx.payer.pubkey(), to, 10_000_000_000);
+            self.process(&[ix], &[]).await.unwrap();
+        }
+
+        async fn create_mint_and_source(&mut self, mint: &Keypair, source: &Keypair) {
+            let rent = self.ctx.banks_client.get_rent().await.unwrap();
+            let payer = self.ctx.payer.pubkey();
+            let ixs = [
+                system_instruction::create_account(
+                    &payer,
+                    &mint.pubkey(),
+                    rent.minimum_balance(spl_token::state::Mint::LEN),
+                    spl_token::state::Mint::LEN as u64,
+                    &spl_token::ID,
+                ),
+                spl_token::instruction::initialize_mint2(
+                    &spl_token::ID,
+                    &mint.pubkey(),
+                    &self.admin.pubkey(),
+                    None,
+                    DECIMALS,
+                )
+                .unwrap(),
+                system_instruction::create_account(
+                    &payer,
+                    &source.pubkey(),
+                    rent.minimum_balance(spl_token::state::Account::LEN),
+                    spl_token::state::Account::LEN as u64,
+                    &spl_token::ID,
+                ),
+                spl_token::instruction::initialize_account3(
+                    &spl_token::ID,
+                    &source.pubkey(),
+                    &mint.pubkey(),
+                    &self.user.pubkey(),
+                )
+                .unwrap(),
+                spl_token::instruction::mint_to(
+                    &spl_token::ID,
+                    &mint.pubkey(),
+                    &source.pubkey(),
+                    &self.admin.pubkey(),
+                    &[],
+                    1_000_000 * 10u64.pow(DECIMALS as u32),
+                )
+                .unwrap(),
+            ];
+            let admin = self.admin.insecure_clone();
+            self.process(&ixs, &[mint, source, &admin]).await.unwrap();
+        }
+
+        async fn init_oft(&mut self, escrow: &Keypair, oft_type: OFTType) {
+            let (lz_receive_types_accounts, _) = Pubkey::find_program_address(
+                &[oft::LZ_RECEIVE_TYPES_SEED, self.oft_store.as_ref()],
+                &oft::ID,
+            );
+            let (oapp_registry, _) =
+                Pubkey::find_program_address(&[b"OApp", self.oft_store.as_ref()], &ENDPOINT_ID);
+            let (endpoint_event_authority, _) =
+                Pubkey::find_program_address(&[b"__event_authority"], &ENDPOINT_ID);
+
+            let mut accounts = oft::accounts::InitOFT {
+                payer: self.admin.pubkey(),
+                oft_store: self.oft_store,
+                lz_receive_types_accounts,
+                token_mint: self.token_mint,
+                token_escrow: self.token_escrow,
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None);
+            accounts.extend([
+                AccountMeta::new_readonly(ENDPOINT_ID, false),
+                AccountMeta::new(self.admin.pubkey(), true),
+                AccountMeta::new_readonly(self.oft_store, false),
+                AccountMeta::new(oapp_registry, false),
+                AccountMeta::new_readonly(system_program::ID, false),
+                AccountMeta::new_readonly(endpoint_event_authority, false),
+                AccountMeta::new_readonly(ENDPOINT_ID, false),
+            ]);
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts,
+                data: oft::instruction::InitOft {
+                    params: InitOFTParams {
+                        oft_type,
+                        admin: self.admin.pubkey(),
+                        shared_decimals: SHARED_DECIMALS,
+                        endpoint_program: Some(ENDPOINT_ID),
+                        max_rate_limit_overrides: 16,
+                        max_rate_limit_override_guid_count: 8,
+                        launch_schedule: vec![],
+                    },
+                }
+                .data(),
+            };
+            let admin = self.admin.insecure_clone();
+            self.process(&[ix], &[&admin, escrow]).await.unwrap();
+        }
+
+        async fn hand_mint_authority_to_store(&mut self) {
+            let ix = spl_token::instruction::set_authority(
+                &spl_token::ID,
+                &self.token_mint,
+                Some(&self.oft_store),
+                spl_token::instruction::AuthorityType::MintTokens,
+                &self.admin.pubkey(),
+                &[],
+            )
+            .unwrap();
+            let admin = self.admin.insecure_clone();
+            self.process(&[ix], &[&admin]).await.unwrap();
+        }
+
+        async fn set_peer_config(&mut self, config: PeerConfigParam) -> Result<(), BanksClientError> {
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts: oft::accounts::SetPeerConfig {
+                    admin: self.admin.pubkey(),
+                    peer: self.peer,
+                    oft_store: self.oft_store,
+                    system_program: system_program::ID,
+                }
+                .to_account_metas(None),
+                data: oft::instruction::SetPeerConfig {
+                    params: SetPeerConfigParams { remote_eid: REMOTE_EID, config },
+                }
+                .data(),
+            };
+            let admin = self.admin.insecure_clone();
+            self.process(&[ix], &[&admin]).await
+        }
+
+        async fn set_oft_config(&mut self, params: SetOFTConfigParams) -> Result<(), BanksClientError> {
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts: oft::accounts::SetOFTConfig {
+                    admin: self.admin.pubkey(),
+                    oft_store: self.oft_store,
+                }
+                .to_account_metas(None),
+                data: oft::instruction::SetOftConfig { params }.data(),
+            };
+            let admin = self.admin.insecure_clone();
+            self.process(&[ix], &[&admin]).await
+        }
+
+        /// set_oft_config with `signer` as the first remaining account.
+        async fn set_oft_config_signed(
+            &mut self,
+            params: SetOFTConfigParams,
+            signer: &Keypair,
+        ) -> Result<(), BanksClientError> {
+            let mut accounts = oft::accounts::SetOFTConfig {
+                admin: self.admin.pubkey(),
+                oft_store: self.oft_store,
+            }
+            .to_account_metas(None);
+            accounts.push(AccountMeta::new_readonly(signer.pubkey(), true));
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts,
+                data: oft::instruction::SetOftConfig { params }.data(),
+            };
+            let admin = self.admin.insecure_clone();
+            self.process(&[ix], &[&admin, signer]).await
+        }
+
+        async fn resize_override_lists(
+            &mut self,
+            max_rate_limit_overrides: u8,
+            max_rate_limit_override_guid_count: u8,
+        ) -> Result<(), BanksClientError> {
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts: oft::accounts::ResizeOverrideLists {
+                    payer: self.ctx.payer.pubkey(),
+                    admin: self.admin.pubkey(),
+                    oft_store: self.oft_store,
+                    system_program: system_program::ID,
+                }
+                .to_account_metas(None),
+                data: oft::instruction::ResizeOverrideLists {
+                    params: ResizeOverrideListsParams {
+                        max_rate_limit_overrides,
+                        max_rate_limit_override_guid_count,
+                    },
+                }
+                .data(),
+            };
+            let admin = self.admin.insecure_clone();
+            self.process(&[ix], &[&admin]).await
+        }
+
+        async fn manage_rate_limit_override(&mut self, address: Pubkey) -> Result<(), BanksClientError> {
+            self.manage_rate_limit_override_cosigned(address, None).await
+        }
+
+        async fn manage_rate_limit_override_cosigned(
+            &mut self,
+            address: Pubkey,
+            cosigner: Option<&Keypair>,
+        ) -> Result<(), BanksClientError> {
+            let params = ManageRateLimitOverrideAddressParams {
+                addresses: vec![address],
+                actions: vec![RateLimitOverrideAction::Add],
+                expires_at: vec![],
+                direction: None,
+                multiplier: None,
+                scope: RateLimitOverrideScope::Either,
+            };
+            self.manage_rate_limit_override_with_params(params, cosigner).await
+        }
+
+        async fn manage_rate_limit_override_with_params(
+            &mut self,
+            params: ManageRateLimitOverrideAddressParams,
+            cosigner: Option<&Keypair>,
+        ) -> Result<(), BanksClientError> {
+            self.manage_rate_limit_override_approved(params, cosigner, None).await
+        }
+
+        async fn manage_rate_limit_override_approved(
+            &mut self,
+            params: ManageRateLimitOverrideAddressParams,
+            cosigner: Option<&Keypair>,
+            approver: Option<&Keypair>,
+        ) -> Result<(), BanksClientError> {
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts: oft::accounts::ManageRateLimitOverride {
+                    admin: self.admin.pubkey(),
+                    oft_store: self.oft_store,
+                    cosigner: cosigner.map(|cosigner| cosigner.pubkey()),
+                    approver: approver.map(|approver| approver.pubkey()),
+                }
+                .to_account_metas(None),
+                data: oft::instruction::ManageRateLimitOverride { params }.data(),
+            };
+            let admin = self.admin.insecure_clone();
+            let mut signers = vec![&admin];
+            signers.extend(cosigner);
+            signers.extend(approver);
+            self.process(&[ix], &signers).await
+        }
+
+        async fn send(&mut self, amount_ld: u64, min_amount_ld: u64) -> Result<(), BanksClientError> {
+            self.send_with_options(amount_ld, min_amount_ld, vec![]).await
+        }
+
+        async fn send_with_options(
+            &mut self,
+            amount_ld: u64,
+            min_amount_ld: u64,
+            options: Vec<u8>,
+        ) -> Result<(), BanksClientError> {
+            self.send_with_params(SendParams {
+                dst_eid: REMOTE_EID,
+                to: [1; 32],
+                amount_ld,
+                min_amount_ld,
+                options,
+                compose_msg: None,
+                native_fee: 0,
+                lz_token_fee: 0,
+                flow_tag: oft::policy::FLOW_TAG_USER,
+                priority: PRIORITY_STANDARD,
+                idempotency_key: None,
+            })
+            .await
+        }
+
+        async fn send_with_params(&mut self, params: SendParams) -> Result<(), BanksClientError> {
+            let send_record = params.idempotency_key.map(|key| self.send_record(&key));
+            let mut accounts = oft::accounts::Send {
+                signer: self.user.pubkey(),
+                peer: self.peer,
+                oft_store: self.oft_store,
+                token_source: self.token_source,
+                token_escrow: self.token_escrow,
+                token_mint: self.token_mint,
+                token_program: spl_token::ID,
+                send_record,
+                transfer_receipt: Some(self.transfer_receipt(&self.outbound_guid(&params))),
+                sender_rate_limit: Some(self.sender_rate_limit()),
+                system_program: Some(system_program::ID),
+                event_authority: event_authority(),
+                program: oft::ID,
+            }
+            .to_account_metas(None);
+            // the signer pays for the send record and the transfer receipt
+            accounts[0].is_writable = true;
+            // the mock endpoint only reads the sender, the remaining accounts are placeholders
+            accounts.push(AccountMeta::new_readonly(ENDPOINT_ID, false));
+            accounts.push(AccountMeta::new_readonly(self.oft_store, false));
+            for _ in 2..EndpointSend::MIN_ACCOUNTS_LEN {
+                accounts.push(AccountMeta::new(Pubkey::new_unique(), false));
+            }
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts,
+                data: oft::instruction::Send { params }.data(),
+            };
+            let user = self.user.insecure_clone();
+            self.process(&[ix], &[&user]).await
+        }
+
+        // the guid the mock endpoint assigns to a send without fees
+        fn outbound_guid(&self, params: &SendParams) -> [u8; 32] {
+            let amount_sd = params.amount_ld / 10u64.pow((DECIMALS - SHARED_DECIMALS) as u32);
+            let message =
+                msg_codec::encode(params.to, amount_sd, self.user.pubkey(), &params.compose_msg);
+            solana_sdk::hash::hashv(&[
+                self.oft_store.as_ref(),
+                &params.dst_eid.to_be_bytes(),
+                &self.peer_address,
+                &message,
+            ])
+            .to_bytes()
+        }
+
+        fn transfer_receipt(&self, guid: &[u8; 32]) -> Pubkey {
+            TransferReceipt::address(&self.oft_store, guid).0
+        }
+
+        async fn read_transfer_receipt(&mut self, guid: &[u8; 32]) -> Option<TransferReceipt> {
+            let address = self.transfer_receipt(guid);
+            let account = self.ctx.banks_client.get_account(address).await.unwrap()?;
+            Some(
+                anchor_lang::AccountDeserialize::try_deserialize(&mut account.data.as_slice())
+                    .unwrap(),
+            )
+        }
+
+        async fn close_transfer_receipt(
+            &mut self,
+            guid: &[u8; 32],
+            payer: Pubkey,
+        ) -> Result<(), BanksClientError> {
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts: oft::accounts::CloseTransferReceipt {
+                    oft_store: self.oft_store,
+                    transfer_receipt: self.transfer_receipt(guid),
+                    payer,
+                }
+                .to_account_metas(None),
+                data: oft::instruction::CloseTransferReceipt {}.data(),
+            };
+            self.process(&[ix], &[]).await
+        }
+
+        fn send_record(&self, idempotency_key: &[u8; 32]) -> Pubkey {
+            Pubkey::find_program_address(
+                &[
+                    oft::SEND_RECORD_SEED,
+                    self.oft_store.as_ref(),
+                    self.user.pubkey().as_ref(),
+                    idempotency_key,
+                ],
+                &oft::ID,
+            )
+            .0
+        }
+
+        fn sender_rate_limit(&self) -> Pubkey {
+            Pubkey::find_program_address(
+                &[
+                    oft::SENDER_RATE_LIMIT_SEED,
+                    self.oft_store.as_ref(),
+                    self.user.pubkey().as_ref(),
+                ],
+                &oft::ID,
+            )
+            .0
+        }
+
+        async fn read_sender_rate_limit(&mut self) -> Option<SenderRateLimit> {
+            let address = self.sender_rate_limit();
+            let account = self.ctx.banks_client.get_account(address).await.unwrap()?;
+            Some(
+                anchor_lang::AccountDeserialize::try_deserialize(&mut account.data.as_slice())
+                    .unwrap(),
+            )
+        }
+
+        async fn close_send_record(
+            &mut self,
+            idempotency_key: &[u8; 32],
+        ) -> Result<(), BanksClientError> {
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts: oft::accounts::CloseSendRecord {
+                    oft_store: self.oft_store,
+                    send_record: self.send_record(idempotency_key),
+                    sender: self.user.pubkey(),
+                }
+                .to_account_metas(None),
+                data: oft::instruction::CloseSendRecord {}.data(),
+            };
+            self.process(&[ix], &[]).await
+        }
+
+        async fn lz_receive(&mut self, amount_sd: u64, nonce: u64) -> Result<(), BanksClientError> {
+            let to_address = self.user.pubkey().to_bytes();
+            let message = msg_codec::encode(to_address, amount_sd, Pubkey::default(), &None);
+            self.lz_receive_message(message, nonce).await
+        }
+
+        async fn lz_receive_message(
+            &mut self,
+            message: Vec<u8>,
+            nonce: u64,
+        ) -> Result<(), BanksClientError> {
+            let to_address = Pubkey::from(msg_codec::send_to(&message));
+            let token_dest = associated_token::get_associated_token_address_with_program_id(
+                &to_address,
+                &self.token_mint,
+                &spl_token::ID,
+            );
+            // control messages omit the accounts of the recipient, as lz_receive_types does
+            let transfer = !msg_codec::is_control(&message, &self.peer_address);
+            let params = LzReceiveParams {
+                src_eid: REMOTE_EID,
+                sender: self.peer_address,
+                nonce,
+                guid: [nonce as u8; 32],
+                message,
+                extra_data: vec![],
+            };
+            let mut accounts = oft::accounts::LzReceive {
+                payer: self.ctx.payer.pubkey(),
+                peer: self.peer,
+                oft_store: self.oft_store,
+                token_escrow: self.token_escrow,
+                to_address: Some(to_address).filter(|_| transfer),
+                token_dest: Some(token_dest).filter(|_| transfer),
+                token_mint: self.token_mint,
+                mint_authority: None,
+                token_program: spl_token::ID,
+                associated_token_program: associated_token::ID,
+                system_program: system_program::ID,
+                // always listed for transfers, as lz_receive_types does
+                pending_credit: Some(self.pending_credit(&to_address, &params.guid))
+                    .filter(|_| transfer),
+                inbound_opt_out: Some(self.inbound_opt_out(&to_address)).filter(|_| transfer),
+                rate_limit_state: self.rate_limit_state(),
+                transfer_receipt: Some(self.transfer_receipt(&params.guid)).filter(|_| transfer),
+                notification_hook: Some(self.notification_hook(&to_address)).filter(|_| transfer),
+                event_authority: event_authority(),
+                program: oft::ID,
+            }
+            .to_account_metas(None);
+            accounts.extend(
+                oapp::endpoint_cpi::get_accounts_for_clear(
+                    ENDPOINT_ID,
+                    &self.oft_store,
+                    REMOTE_EID,
+                    &self.peer_address,
+                    nonce,
+                )
+                .into_iter()
+                .map(|account| AccountMeta {
+                    pubkey: account.pubkey,
+                    is_signer: false,
+                    is_writable: account.is_writable,
+                }),
+            );
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts,
+                data: oft::instruction::LzReceive { params }.data(),
+            };
+            self.process(&[ix], &[]).await
+        }
+
+        async fn lz_receive_types(&mut self, message: Vec<u8>) -> Vec<Pubkey> {
+            let params = LzReceiveParams {
+                src_eid: REMOTE_EID,
+                sender: self.peer_address,
+                nonce: 1,
+                guid: [1; 32],
+                message,
+                extra_data: vec![],
+            };
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts: oft::accounts::LzReceiveTypes {
+                    oft_store: self.oft_store,
+                    token_mint: self.token_mint,
+                }
+                .to_account_metas(None),
+                data: oft::instruction::LzReceiveTypes { params }.data(),
+            };
+            let accounts: Vec<oapp::endpoint_cpi::LzAccount> = self.view(ix).await;
+            accounts.into_iter().map(|account| account.pubkey).collect()
+        }
+
+        fn pending_credit(&self, to_address: &Pubkey, guid: &[u8; 32]) -> Pubkey {
+            Pubkey::find_program_address(
+                &[oft::PENDING_CREDIT_SEED, self.oft_store.as_ref(), to_address.as_ref(), guid],
+                &oft::ID,
+            )
+            .0
+        }
+
+        fn inbound_opt_out(&self, owner: &Pubkey) -> Pubkey {
+            Pubkey::find_program_address(
+                &[oft::INBOUND_OPT_OUT_SEED, self.oft_store.as_ref(), owner.as_ref()],
+                &oft::ID,
+            )
+            .0
+        }
+
+        fn notification_hook(&self, owner: &Pubkey) -> Pubkey {
+            Pubkey::find_program_address(
+                &[oft::NOTIFICATION_HOOK_SEED, self.oft_store.as_ref(), owner.as_ref()],
+                &oft::ID,
+            )
+            .0
+        }
+
+        async fn register_notification_hook(&mut self) -> Result<(), BanksClientError> {
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts: oft::accounts::RegisterNotificationHook {
+                    owner: self.user.pubkey(),
+                    oft_store: self.oft_store,
+                    notification_hook: self.notification_hook(&self.user.pubkey()),
+                    system_program: system_program::ID,
+                }
+                .to_account_metas(None),
+                data: oft::instruction::RegisterNotificationHook {}.data(),
+            };
+            let user = self.user.insecure_clone();
+            self.process(&[ix], &[&user]).await
+        }
+
+        async fn read_notification_hook(&mut self) -> Option<NotificationHook> {
+            let address = self.notification_hook(&self.user.pubkey());
+            let account = self.ctx.banks_client.get_account(address).await.unwrap()?;
+            Some(
+                anchor_lang::AccountDeserialize::try_deserialize(&mut account.data.as_slice())
+                    .unwrap(),
+            )
+        }
+
+        fn rate_limit_state(&self) -> Pubkey {
+            Pubkey::find_program_address(
+                &[oft::RATE_LIMIT_STATE_SEED, self.oft_store.as_ref(), &REMOTE_EID.to_be_bytes()],
+                &oft::ID,
+            )
+            .0
+        }
+
+        async fn sync_rate_limit_state(&mut self) -> Result<(), BanksClientError> {
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts: oft::accounts::SyncRateLimitState {
+                    payer: self.user.pubkey(),
+                    oft_store: self.oft_store,
+                    peer: self.peer,
+                    rate_limit_state: self.rate_limit_state(),
+                    system_program: system_program::ID,
+                }
+                .to_account_metas(None),
+                data: oft::instruction::SyncRateLimitState {
+                    params: SyncRateLimitStateParams { remote_eid: REMOTE_EID },
+                }
+                .data(),
+            };
+            let user = self.user.insecure_clone();
+            self.process(&[ix], &[&user]).await
+        }
+
+        async fn register_inbound_opt_out(&mut self) -> Result<(), BanksClientError> {
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts: oft::accounts::RegisterInboundOptOut {
+                    owner: self.user.pubkey(),
+                    oft_store: self.oft_store,
+                    inbound_opt_out: self.inbound_opt_out(&self.user.pubkey()),
+                    system_program: system_program::ID,
+                }
+                .to_account_metas(None),
+                data: oft::instruction::RegisterInboundOptOut {}.data(),
+            };
+            let user = self.user.insecure_clone();
+            self.process(&[ix], &[&user]).await
+        }
+
+        async fn claim_pending_credit(
+            &mut self,
+            guid: [u8; 32],
+            signer: &Keypair,
+        ) -> Result<(), BanksClientError> {
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts: oft::accounts::ResolvePendingCredit {
+                    signer: signer.pubkey(),
+                    oft_store: self.oft_store,
+                    pending_credit: self.pending_credit(&self.user.pubkey(), &guid),
+                    payer: self.ctx.payer.pubkey(),
+                    to_address: self.user.pubkey(),
+                    token_dest: self.token_dest(),
+                    token_escrow: self.token_escrow,
+                    token_mint: self.token_mint,
+                    mint_authority: None,
+                    token_program: spl_token::ID,
+                    associated_token_program: associated_token::ID,
+                }
+                .to_account_metas(None),
+                data: oft::instruction::ClaimPendingCredit {}.data(),
+            };
+            self.process(&[ix], &[signer]).await
+        }
+
+        async fn execute_pending_credit(&mut self, guid: [u8; 32]) -> Result<(), BanksClientError> {
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts: oft::accounts::ExecutePendingCredit {
+                    release: oft::accounts::ResolvePendingCredit {
+                        signer: self.admin.pubkey(),
+                        oft_store: self.oft_store,
+                        pending_credit: self.pending_credit(&self.user.pubkey(), &guid),
+                        payer: self.ctx.payer.pubkey(),
+                        to_address: self.user.pubkey(),
+                        token_dest: self.token_dest(),
+                        token_escrow: self.token_escrow,
+                        token_mint: self.token_mint,
+                        mint_authority: None,
+                        token_program: spl_token::ID,
+                        associated_token_program: associated_token::ID,
+                    },
+                    peer: self.peer,
+                    rate_limit_state: self.rate_limit_state(),
+                }
+                .to_account_metas(None),
+                data: oft::instruction::ExecutePendingCredit {}.data(),
+            };
+            // anyone can execute it
+            let cranker = self.admin.insecure_clone();
+            self.process(&[ix], &[&cranker]).await
+        }
+
+        async fn set_withdraw_destinations(
+            &mut self,
+            params: SetWithdrawDestinationsParams,
+        ) -> Result<(), BanksClientError> {
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts: oft::accounts::SetWithdrawDestinations {
+                    admin: self.admin.pubkey(),
+                    oft_store: self.oft_store,
+                }
+                .to_account_metas(None),
+                data: oft::instruction::SetWithdrawDestinations { params }.data(),
+            };
+            let admin = self.admin.insecure_clone();
+            self.process(&[ix], &[&admin]).await
+        }
+
+        async fn burn_from_escrow(&mut self, params: BurnFromEscrowParams) -> Result<(), BanksClientError> {
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts: oft::accounts::BurnFromEscrow {
+                    admin: self.admin.pubkey(),
+                    oft_store: self.oft_store,
+                    token_mint: self.token_mint,
+                    token_escrow: self.token_escrow,
+                    token_program: spl_token::ID,
+                }
+                .to_account_metas(None),
+                data: oft::instruction::BurnFromEscrow { params }.data(),
+            };
+            let admin = self.admin.insecure_clone();
+            self.process(&[ix], &[&admin]).await
+        }
+
+        fn fee_vault(&self) -> Pubkey {
+            Pubkey::find_program_address(&[oft::FEE_VAULT_SEED, self.oft_store.as_ref()], &oft::ID).0
+        }
+
+        async fn manage_fee_vault(&mut self, params: ManageFeeVaultParams) -> Result<(), BanksClientError> {
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts: oft::accounts::ManageFeeVault {
+                    admin: self.admin.pubkey(),
+                    oft_store: self.oft_store,
+                    fee_vault: self.fee_vault(),
+                    system_program: system_program::ID,
+                }
+                .to_account_metas(None),
+                data: oft::instruction::ManageFeeVault { params }.data(),
+            };
+            let admin = self.admin.insecure_clone();
+            self.process(&[ix], &[&admin]).await
+        }
+
+        fn evm_address_map(&self) -> Pubkey {
+            Pubkey::find_program_address(
+                &[oft::EVM_ADDRESS_MAP_SEED, self.oft_store.as_ref()],
+                &oft::ID,
+            )
+            .0
+        }
+
+        async fn read_evm_address_map(&mut self) -> EvmAddressMap {
+            let address = self.evm_address_map();
+            let account = self.ctx.banks_client.get_account(address).await.unwrap().unwrap();
+            anchor_lang::AccountDeserialize::try_deserialize(&mut account.data.as_slice()).unwrap()
+        }
+
+        async fn set_evm_address_mapping(
+            &mut self,
+            address: Pubkey,
+            evm_address: Option<[u8; 20]>,
+        ) -> Result<(), BanksClientError> {
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts: oft::accounts::SetEvmAddressMapping {
+                    admin: self.admin.pubkey(),
+                    oft_store: self.oft_store,
+                    evm_address_map: self.evm_address_map(),
+                    system_program: system_program::ID,
+                }
+                .to_account_metas(None),
+                data: oft::instruction::SetEvmAddressMapping {
+                    params: SetEvmAddressMappingParams { address, evm_address },
+                }
+                .data(),
+            };
+            let admin = self.admin.insecure_clone();
+            self.process(&[ix], &[&admin]).await
+        }
+
+        async fn mirror_override_list(&mut self) -> Result<(), BanksClientError> {
+            let mut accounts = oft::accounts::MirrorOverrideList {
+                admin: self.admin.pubkey(),
+                oft_store: self.oft_store,
+                peer: self.peer,
+                evm_address_map: self.evm_address_map(),
+            }
+            .to_account_metas(None);
+            // the mock endpoint only reads the sender, the remaining accounts are placeholders
+            accounts.push(AccountMeta::new_readonly(ENDPOINT_ID, false));
+            accounts.push(AccountMeta::new_readonly(self.oft_store, false));
+            for _ in 2..EndpointSend::MIN_ACCOUNTS_LEN {
+                accounts.push(AccountMeta::new(Pubkey::new_unique(), false));
+            }
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts,
+                data: oft::instruction::MirrorOverrideList {
+                    params: MirrorOverrideListParams {
+                        dst_eid: REMOTE_EID,
+                        options: vec![],
+                        native_fee: 0,
+                        lz_token_fee: 0,
+                    },
+                }
+                .data(),
+            };
+            let admin = self.admin.insecure_clone();
+            self.process(&[ix], &[&admin]).await
+        }
+
+        async fn set_control_message_pause(
+            &mut self,
+            signer: &Keypair,
+            message: ControlMessage,
+            paused: bool,
+        ) -> Result<(), BanksClientError> {
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts: oft::accounts::SetControlMessagePause {
+                    signer: signer.pubkey(),
+                    oft_store: self.oft_store,
+                }
+                .to_account_metas(None),
+                data: oft::instruction::SetControlMessagePause {
+                    params: SetControlMessagePauseParams { message, paused },
+                }
+                .data(),
+            };
+            self.process(&[ix], &[signer]).await
+        }
+
+        async fn set_pause(
+            &mut self,
+            signer: &Keypair,
+            paused: bool,
+        ) -> Result<(), BanksClientError> {
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts: oft::accounts::SetPause {
+                    signer: signer.pubkey(),
+                    oft_store: self.oft_store,
+                }
+                .to_account_metas(None),
+                data: oft::instruction::SetPause { params: SetPauseParams { paused } }.data(),
+            };
+            self.process(&[ix], &[signer]).await
+        }
+
+        async fn send_pause_notice(&mut self, pauser: &Keypair) -> Result<(), BanksClientError> {
+            let mut accounts = oft::accounts::SendPauseNotice {
+                pauser: pauser.pubkey(),
+                oft_store: self.oft_store,
+                peer: self.peer,
+            }
+            .to_account_metas(None);
+            accounts.push(AccountMeta::new_readonly(ENDPOINT_ID, false));
+            accounts.push(AccountMeta::new_readonly(self.oft_store, false));
+            for _ in 2..EndpointSend::MIN_ACCOUNTS_LEN {
+                accounts.push(AccountMeta::new(Pubkey::new_unique(), false));
+            }
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts,
+                data: oft::instruction::SendPauseNotice {
+                    params: SendPauseNoticeParams {
+                        dst_eid: REMOTE_EID,
+                        options: vec![],
+                        native_fee: 0,
+                        lz_token_fee: 0,
+                    },
+                }
+                .data(),
+            };
+            self.process(&[ix], &[pauser]).await
+        }
+
+        async fn challenge_peer(&mut self) -> Result<(), BanksClientError> {
+            let mut accounts = oft::accounts::ChallengePeer {
+                admin: self.admin.pubkey(),
+                oft_store: self.oft_store,
+                peer: self.peer,
+            }
+            .to_account_metas(None);
+            accounts.push(AccountMeta::new_readonly(ENDPOINT_ID, false));
+            accounts.push(AccountMeta::new_readonly(self.oft_store, false));
+            for _ in 2..EndpointSend::MIN_ACCOUNTS_LEN {
+                accounts.push(AccountMeta::new(Pubkey::new_unique(), false));
+            }
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts,
+                data: oft::instruction::ChallengePeer {
+                    params: ChallengePeerParams {
+                        dst_eid: REMOTE_EID,
+                        options: vec![],
+                        native_fee: 0,
+                        lz_token_fee: 0,
+                    },
+                }
+                .data(),
+            };
+            let admin = self.admin.insecure_clone();
+            self.process(&[ix], &[&admin]).await
+        }
+
+        // received at nonce 0, which no test uses
+        async fn echo_peer_challenge(&mut self, nonce: [u8; 32]) -> Result<(), BanksClientError> {
+            let message = msg_codec::encode_peer_challenge(self.peer_address, nonce);
+            self.lz_receive_message(message, 0).await
+        }
+
+        async fn send_with_fee_vault(&mut self, amount_ld: u64, native_fee: u64) -> Result<(), BanksClientError> {
+            let mut accounts = oft::accounts::SendWithFeeVault {
+                send: oft::accounts::Send {
+                    signer: self.user.pubkey(),
+                    peer: self.peer,
+                    oft_store: self.oft_store,
+                    token_source: self.token_source,
+                    token_escrow: self.token_escrow,
+                    token_mint: self.token_mint,
+                    token_program: spl_token::ID,
+                    send_record: None,
+                    transfer_receipt: None,
+                    sender_rate_limit: None,
+                    system_program: None,
+                    event_authority: event_authority(),
+                    program: oft::ID,
+                },
+                fee_vault: self.fee_vault(),
+            }
+            .to_account_metas(None);
+            // the signer is the endpoint payer, so it is writable in the transaction
+            accounts[0].is_writable = true;
+            accounts.push(AccountMeta::new_readonly(ENDPOINT_ID, false));
+            accounts.push(AccountMeta::new_readonly(self.oft_store, false));
+            for _ in 2..EndpointSend::MIN_ACCOUNTS_LEN {
+                accounts.push(AccountMeta::new(Pubkey::new_unique(), false));
+            }
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts,
+                data: oft::instruction::SendWithFeeVault {
+                    params: SendParams {
+                        dst_eid: REMOTE_EID,
+                        to: [1; 32],
+                        amount_ld,
+                        min_amount_ld: 0,
+                        options: vec![],
+                        compose_msg: None,
+                        native_fee,
+                        lz_token_fee: 0,
+                        flow_tag: oft::policy::FLOW_TAG_USER,
+                        priority: PRIORITY_STANDARD,
+                        idempotency_key: None,
+                    },
+                }
+                .data(),
+            };
+            let user = self.user.insecure_clone();
+            self.process(&[ix], &[&user]).await
+        }
+
+        async fn top_up_lamports(
+            &mut self,
+            remote_eid: Option<u32>,
+            lamports: u64,
+        ) -> Result<(), BanksClientError> {
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts: oft::accounts::TopUpLamports {
+                    contributor: self.user.pubkey(),
+                    oft_store: self.oft_store,
+                    peer: remote_eid.map(|_| self.peer),
+                    system_program: system_program::ID,
+                }
+                .to_account_metas(None),
+                data: oft::instruction::TopUpLamports {
+                    params: TopUpLamportsParams { remote_eid, lamports },
+                }
+                .data(),
+            };
+            let user = self.user.insecure_clone();
+            self.process(&[ix], &[&user]).await
+        }
+
+        async fn withdraw_surplus_lamports(
+            &mut self,
+            remote_eid: Option<u32>,
+            lamports: u64,
+        ) -> Result<(), BanksClientError> {
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts: oft::accounts::WithdrawSurplusLamports {
+                    admin: self.admin.pubkey(),
+                    oft_store: self.oft_store,
+                    peer: remote_eid.map(|_| self.peer),
+                }
+                .to_account_metas(None),
+                data: oft::instruction::WithdrawSurplusLamports {
+                    params: WithdrawSurplusLamportsParams { remote_eid, lamports },
+                }
+                .data(),
+            };
+            let admin = self.admin.insecure_clone();
+            self.process(&[ix], &[&admin]).await
+        }
+
+        async fn set_inbound_rate_limit(
+            &mut self,
+            rate_limit: Option<RateLimitParams>,
+        ) -> Result<(), BanksClientError> {
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts: oft::accounts::SetInboundRateLimit {
+                    admin: self.admin.pubkey(),
+                    oft_store: self.oft_store,
+                    peer: self.peer,
+                    rate_limit_state: self.rate_limit_state(),
+                }
+                .to_account_metas(None),
+                data: oft::instruction::SetInboundRateLimit {
+                    params: SetInboundRateLimitParams { remote_eid: REMOTE_EID, rate_limit },
+                }
+                .data(),
+            };
+            let admin = self.admin.insecure_clone();
+            self.process(&[ix], &[&admin]).await
+        }
+
+        async fn set_outbound_rate_limit(
+            &mut self,
+            rate_limit: Option<RateLimitParams>,
+        ) -> Result<(), BanksClientError> {
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts: oft::accounts::SetOutboundRateLimit {
+                    admin: self.admin.pubkey(),
+                    oft_store: self.oft_store,
+                    peer: self.peer,
+                }
+                .to_account_metas(None),
+                data: oft::instruction::SetOutboundRateLimit {
+                    params: SetOutboundRateLimitParams { remote_eid: REMOTE_EID, rate_limit },
+                }
+                .data(),
+            };
+            let admin = self.admin.insecure_clone();
+            self.process(&[ix], &[&admin]).await
+        }
+
+        async fn set_rate_limit_enabled(&mut self, enabled: bool) -> Result<(), BanksClientError> {
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts: oft::accounts::SetRateLimitEnabled {
+                    admin: self.admin.pubkey(),
+                    oft_store: self.oft_store,
+                    peer: self.peer,
+                }
+                .to_account_metas(None),
+                data: oft::instruction::SetRateLimitEnabled {
+                    params: SetRateLimitEnabledParams { remote_eid: REMOTE_EID, enabled },
+                }
+                .data(),
+            };
+            let admin = self.admin.insecure_clone();
+            self.process(&[ix], &[&admin]).await
+        }
+
+        async fn manage_fee_exempt(
+            &mut self,
+            address: Pubkey,
+            action: RateLimitOverrideAction,
+        ) -> Result<(), BanksClientError> {
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts: oft::accounts::ManageFeeExempt {
+                    admin: self.admin.pubkey(),
+                    oft_store: self.oft_store,
+                }
+                .to_account_metas(None),
+                data: oft::instruction::ManageFeeExempt {
+                    params: ManageFeeExemptParams { addresses: vec![address], action },
+                }
+                .data(),
+            };
+            let admin = self.admin.insecure_clone();
+            self.process(&[ix], &[&admin]).await
+        }
+
+        async fn boost_rate_limit(
+            &mut self,
+            capacity: u64,
+            duration_seconds: i64,
+        ) -> Result<(), BanksClientError> {
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts: oft::accounts::BoostRateLimit {
+                    admin: self.admin.pubkey(),
+                    oft_store: self.oft_store,
+                    peer: self.peer,
+                    rate_limit_state: self.rate_limit_state(),
+                }
+                .to_account_metas(None),
+                data: oft::instruction::BoostRateLimit {
+                    params: BoostRateLimitParams {
+                        remote_eid: REMOTE_EID,
+                        direction: RateLimitDirection::Outbound,
+                        capacity,
+                        refill_per_second: 0,
+                        duration_seconds,
+                    },
+                }
+                .data(),
+            };
+            let admin = self.admin.insecure_clone();
+            self.process(&[ix], &[&admin]).await
+        }
+
+        async fn reset_rate_limit_consumption(
+            &mut self,
+            direction: RateLimitDirection,
+        ) -> Result<(), BanksClientError> {
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts: oft::accounts::ResetRateLimitConsumption {
+                    admin: self.admin.pubkey(),
+                    oft_store: self.oft_store,
+                    peer: self.peer,
+                    rate_limit_state: self.rate_limit_state(),
+                }
+                .to_account_metas(None),
+                data: oft::instruction::ResetRateLimitConsumption {
+                    params: ResetRateLimitConsumptionParams { remote_eid: REMOTE_EID, direction },
+                }
+                .data(),
+            };
+            let admin = self.admin.insecure_clone();
+            self.process(&[ix], &[&admin]).await
+        }
+
+        async fn set_rate_limits_batch(
+            &mut self,
+            rate_limits: Vec<SetOutboundRateLimitParams>,
+            peers: &[Pubkey],
+        ) -> Result<(), BanksClientError> {
+            let mut accounts = oft::accounts::SetRateLimitsBatch {
+                admin: self.admin.pubkey(),
+                oft_store: self.oft_store,
+            }
+            .to_account_metas(None);
+            accounts.extend(peers.iter().map(|peer| AccountMeta::new(*peer, false)));
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts,
+                data: oft::instruction::SetRateLimitsBatch {
+                    params: SetRateLimitsBatchParams { rate_limits },
+                }
+                .data(),
+            };
+            let admin = self.admin.insecure_clone();
+            self.process(&[ix], &[&admin]).await
+        }
+
+        async fn lamports(&mut self, account: Pubkey) -> u64 {
+            self.ctx.banks_client.get_balance(account).await.unwrap()
+        }
+
+        async fn peer(&mut self) -> PeerConfig {
+            let account = self.ctx.banks_client.get_account(self.peer).await.unwrap().unwrap();
+            anchor_lang::AccountDeserialize::try_deserialize(&mut account.data.as_slice()).unwrap()
+        }
+
+        fn proposal(&self, target: &RateLimitOverrideTarget) -> Pubkey {
+            Pubkey::find_program_address(
+                &[oft::RATE_LIMIT_OVERRIDE_PROPOSAL_SEED, self.oft_store.as_ref(), &target.seed()],
+                &oft::ID,
+            )
+            .0
+        }
+
+        async fn propose_rate_limit_override(
+            &mut self,
+            target: RateLimitOverrideTarget,
+            notional_ld: u64,
+        ) -> Result<(), BanksClientError> {
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts: oft::accounts::ProposeRateLimitOverride {
+                    signer: self.user.pubkey(),
+                    oft_store: self.oft_store,
+                    proposal: Some(self.proposal(&target)),
+                    system_program: system_program::ID,
+                }
+                .to_account_metas(None),
+                data: oft::instruction::ProposeRateLimitOverride {
+                    params: ProposeRateLimitOverrideParams { target, notional_ld },
+                }
+                .data(),
+            };
+            let user = self.user.insecure_clone();
+            self.process(&[ix], &[&user]).await
+        }
+
+        async fn crank_expire_proposal(&mut self, proposal: Pubkey) -> Result<(), BanksClientError> {
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts: oft::accounts::Crank {
+                    keeper: self.user.pubkey(),
+                    oft_store: self.oft_store,
+                    token_mint: self.token_mint,
+                    token_escrow: self.token_escrow,
+                    keeper_token_account: self.token_source,
+                    token_program: spl_token::ID,
+                    proposal: Some(proposal),
+                    proposer: Some(self.user.pubkey()),
+                    fee_stream_recipient: None,
+                    peer: None,
+                }
+                .to_account_metas(None),
+                data: oft::instruction::Crank {
+                    params: CrankParams { task: CrankTask::ExpireRateLimitOverrideProposal },
+                }
+                .data(),
+            };
+            let user = self.user.insecure_clone();
+            self.process(&[ix], &[&user]).await
+        }
+
+        async fn crank_stream_fees(&mut self, recipient: Pubkey) -> Result<(), BanksClientError> {
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts: oft::accounts::Crank {
+                    keeper: self.user.pubkey(),
+                    oft_store: self.oft_store,
+                    token_mint: self.token_mint,
+                    token_escrow: self.token_escrow,
+                    keeper_token_account: self.token_source,
+                    token_program: spl_token::ID,
+                    proposal: None,
+                    proposer: None,
+                    fee_stream_recipient: Some(recipient),
+                    peer: None,
+                }
+                .to_account_metas(None),
+                data: oft::instruction::Crank {
+                    params: CrankParams { task: CrankTask::StreamFees },
+                }
+                .data(),
+            };
+            let user = self.user.insecure_clone();
+            self.process(&[ix], &[&user]).await
+        }
+
+        async fn withdraw_fees(
+            &mut self,
+            authority: &Keypair,
+            treasury: Pubkey,
+            amount_ld: u64,
+        ) -> Result<(), BanksClientError> {
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts: oft::accounts::WithdrawFees {
+                    authority: authority.pubkey(),
+                    oft_store: self.oft_store,
+                    token_mint: self.token_mint,
+                    token_escrow: self.token_escrow,
+                    treasury,
+                    token_program: spl_token::ID,
+                }
+                .to_account_metas(None),
+                data: oft::instruction::WithdrawFees { params: WithdrawFeesParams { amount_ld } }
+                    .data(),
+            };
+            self.process(&[ix], &[authority]).await
+        }
+
+        async fn withdraw_fee(
+            &mut self,
+            token_dest: Pubkey,
+            fee_ld: u64,
+        ) -> Result<(), BanksClientError> {
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts: oft::accounts::WithdrawFee {
+                    admin: self.admin.pubkey(),
+                    oft_store: self.oft_store,
+                    token_mint: self.token_mint,
+                    token_escrow: self.token_escrow,
+                    token_dest,
+                    token_program: spl_token::ID,
+                }
+                .to_account_metas(None),
+                data: oft::instruction::WithdrawFee { params: WithdrawFeeParams { fee_ld } }.data(),
+            };
+            let admin = self.admin.insecure_clone();
+            self.process(&[ix], &[&admin]).await
+        }
+
+        #[cfg(feature = "test-mode")]
+        async fn test_mint(
+            &mut self,
+            token_dest: Pubkey,
+            amount_ld: u64,
+        ) -> Result<(), BanksClientError> {
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts: oft::accounts::TestMint {
+                    signer: self.user.pubkey(),
+                    oft_store: self.oft_store,
+                    token_escrow: self.token_escrow,
+                    token_dest,
+                    token_mint: self.token_mint,
+                    mint_authority: self.oft_store,
+                    token_program: spl_token::ID,
+                }
+                .to_account_metas(None),
+                data: oft::instruction::TestMint { params: TestMintParams { amount_ld } }.data(),
+            };
+            let user = self.user.insecure_clone();
+            self.process(&[ix], &[&user]).await
+        }
+
+        async fn crank_report_peer_silence(&mut self) -> Result<(), BanksClientError> {
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts: oft::accounts::Crank {
+                    keeper: self.user.pubkey(),
+                    oft_store: self.oft_store,
+                    token_mint: self.token_mint,
+                    token_escrow: self.token_escrow,
+                    keeper_token_account: self.token_source,
+                    token_program: spl_token::ID,
+                    proposal: None,
+                    proposer: None,
+                    fee_stream_recipient: None,
+                    peer: Some(self.peer),
+                }
+                .to_account_metas(None),
+                data: oft::instruction::Crank {
+                    params: CrankParams {
+                        task: CrankTask::ReportPeerSilence { remote_eid: REMOTE_EID },
+                    },
+                }
+                .data(),
+            };
+            let user = self.user.insecure_clone();
+            self.process(&[ix], &[&user]).await
+        }
+
+        async fn get_peer_health(&mut self) -> PeerHealth {
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts: oft::accounts::GetPeerHealth { oft_store: self.oft_store, peer: self.peer }
+                    .to_account_metas(None),
+                data: oft::instruction::GetPeerHealth {
+                    params: GetPeerHealthParams { remote_eid: REMOTE_EID },
+                }
+                .data(),
+            };
+            self.view(ix).await
+        }
+
+        async fn get_in_flight(&mut self) -> InFlightReport {
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts: oft::accounts::GetInFlight { oft_store: self.oft_store, peer: self.peer }
+                    .to_account_metas(None),
+                data: oft::instruction::GetInFlight {
+                    params: GetInFlightParams { remote_eid: REMOTE_EID },
+                }
+                .data(),
+            };
+            self.view(ix).await
+        }
+
+        async fn get_rate_limit_capacity(&mut self) -> RateLimitCapacities {
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts: oft::accounts::GetRateLimitCapacity {
+                    oft_store: self.oft_store,
+                    peer: self.peer,
+                }
+                .to_account_metas(None),
+                data: oft::instruction::GetRateLimitCapacity {
+                    params: GetRateLimitCapacityParams { remote_eid: REMOTE_EID },
+                }
+                .data(),
+            };
+            self.view(ix).await
+        }
+
+        async fn get_dust(&mut self) -> Dust {
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts: oft::accounts::GetDust {
+                    oft_store: self.oft_store,
+                    token_source: self.token_source,
+                }
+                .to_account_metas(None),
+                data: oft::instruction::GetDust {}.data(),
+            };
+            self.view(ix).await
+        }
+
+        async fn sweep_dust(&mut self) -> Result<(), BanksClientError> {
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts: oft::accounts::SweepDust {
+                    signer: self.user.pubkey(),
+                    oft_store: self.oft_store,
+                    token_source: self.token_source,
+                    token_escrow: self.token_escrow,
+                    token_mint: self.token_mint,
+                    token_program: spl_token::ID,
+                }
+                .to_account_metas(None),
+                data: oft::instruction::SweepDust {}.data(),
+            };
+            let user = self.user.insecure_clone();
+            self.process(&[ix], &[&user]).await
+        }
+
+        async fn warp(&mut self, seconds: i64) {
+            let mut clock: Clock = self.ctx.banks_client.get_sysvar().await.unwrap();
+            clock.unix_timestamp += seconds;
+            self.ctx.set_sysvar(&clock);
+        }
+
+        fn token_dest(&self) -> Pubkey {
+            associated_token::get_associated_token_address_with_program_id(
+                &self.user.pubkey(),
+                &self.token_mint,
+                &spl_token::ID,
+            )
+        }
+
+        async fn token_balance(&mut self, account: Pubkey) -> u64 {
+            match self.ctx.banks_client.get_account(account).await.unwrap() {
+                Some(account) => spl_token::state::Account::unpack(&account.data).unwrap().amount,
+                None => 0,
+            }
+        }
+
+        async fn rate_limit_overrides(&mut self) -> RateLimitOverrides {
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts: oft::accounts::GetRateLimitOverrides { oft_store: self.oft_store }
+                    .to_account_metas(None),
+                data: oft::instruction::GetRateLimitOverrides {}.data(),
+            };
+            self.view(ix).await
+        }
+
+        async fn oft_metadata(&mut self) -> OFTMetadataView {
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts: oft::accounts::GetOFTMetadata { oft_store: self.oft_store }
+                    .to_account_metas(None),
+                data: oft::instruction::GetOftMetadata {}.data(),
+            };
+            self.view(ix).await
+        }
+
+        async fn quote_oft(&mut self, amount_ld: u64, priority: u8) -> QuoteOFTResult {
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts: oft::accounts::QuoteOFT {
+                    oft_store: self.oft_store,
+                    peer: self.peer,
+                    token_mint: self.token_mint,
+                }
+                .to_account_metas(None),
+                data: oft::instruction::QuoteOft {
+                    params: QuoteOFTParams {
+                        dst_eid: REMOTE_EID,
+                        to: [1; 32],
+                        amount_ld,
+                        min_amount_ld: 0,
+                        options: vec![],
+                        compose_msg: None,
+                        pay_in_lz_token: false,
+                        priority,
+                    },
+                }
+                .data(),
+            };
+            self.view(ix).await
+        }
+
+        async fn check_remote_config(&mut self, config: RemoteConfig) -> bool {
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts: oft::accounts::CheckRemoteConfig { oft_store: self.oft_store, peer: self.peer }
+                    .to_account_metas(None),
+                data: oft::instruction::CheckRemoteConfig {
+                    params: CheckRemoteConfigParams { remote_eid: REMOTE_EID, config },
+                }
+                .data(),
+            };
+            self.view(ix).await
+        }
+
+        async fn pending_credit_address(&mut self, guid: [u8; 32]) -> Pubkey {
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts: oft::accounts::GetPendingCreditAddress { oft_store: self.oft_store }
+                    .to_account_metas(None),
+                data: oft::instruction::GetPendingCreditAddress {
+                    params: GetPendingCreditAddressParams { to_address: self.user.pubkey(), guid },
+                }
+                .data(),
+            };
+            self.view(ix).await
+        }
+
+        async fn simulate_send(&mut self, amount_ld: u64) -> SendSimulation {
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts: oft::accounts::SimulateSend {
+                    oft_store: self.oft_store,
+                    peer: self.peer,
+                    token_mint: self.token_mint,
+                }
+                .to_account_metas(None),
+                data: oft::instruction::SimulateSend {
+                    params: SimulateSendParams {
+                        dst_eid: REMOTE_EID,
+                        sender: self.user.pubkey(),
+                        to: [1; 32],
+                        amount_ld,
+                        min_amount_ld: 0,
+                        flow_tag: oft::policy::FLOW_TAG_USER,
+                        composed: false,
+                    },
+                }
+                .data(),
+            };
+            self.view(ix).await
+        }
+
+        async fn simulate_receive(&mut self, amount_sd: u64, nonce: u64) -> ReceiveSimulation {
+            let to_address = self.user.pubkey();
+            let message =
+                msg_codec::encode(to_address.to_bytes(), amount_sd, Pubkey::default(), &None);
+            let ix = Instruction {
+                program_id: oft::ID,
+                accounts: oft::accounts::SimulateReceive {
+                    oft_store: self.oft_store,
+                    peer: self.peer,
+                    token_mint: self.token_mint,
+                    inbound_opt_out: self.inbound_opt_out(&to_address),
+                }
+                .to_account_metas(None),
+                data: oft::instruction::SimulateReceive {
+                    params: SimulateReceiveParams {
+                        src_eid: REMOTE_EID,
+                        guid: [nonce as u8; 32],
+                        message,
+                    },
+                }
+                .data(),
+            };
+            self.view(ix).await
+        }
+
+        // Simulates a view instruction and decodes its return data.
+        async fn view<T: anchor_lang::AnchorDeserialize>(&mut self, ix: Instruction) -> T {
+            let blockhash = self.ctx.get_new_latest_blockhash().await.unwrap();
+            let tx = Transaction::new_signed_with_payer(
+                &[ix],
+                Some(&self.ctx.payer.pubkey()),
+                &[&self.ctx.payer],
+                blockhash,
+            );
+            let result = self.ctx.banks_client.simulate_transaction(tx).await.unwrap();
+            let return_data = result.simulation_details.unwrap().return_data.unwrap();
+            anchor_lang::AnchorDeserialize::deserialize(&mut return_data.data.as_slice()).unwrap()
+        }
+
+        async fn read_rate_limit_state(&mut self) -> RateLimitState {
+            let address = self.rate_limit_state();
+            let account = self.ctx.banks_client.get_account(address).await.unwrap().unwrap();
+            anchor_lang::AccountDeserialize::try_deserialize(&mut account.data.as_slice()).unwrap()
+        }
+
+        async fn oft_store(&mut self) -> OFTStore {
+            let account = self.ctx.banks_client.get_account(self.oft_store).await.unwrap().unwrap();
+            anchor_lang::AccountDeserialize::try_deserialize(&mut account.data.as_slice()).unwrap()
+        }
+    }
+
+    fn event_authority() -> Pubkey {
+        Pubkey::find_program_address(&[b"__event_authority"], &oft::ID).0
+    }
+
+    // type 3 options with a single executor lzReceive option
+    fn lz_receive_options(gas: u128) -> Vec<u8> {
+        let mut options = vec![0, 3, 1, 0, 17, 1];
+        options.extend_from_slice(&gas.to_be_bytes());
+        options
+    }
+
+    fn ld(amount: u64) -> u64 {
+        amount * 10u64.pow(DECIMALS as u32)
+    }
+
+    fn assert_oft_error(result: Result<(), BanksClientError>, error: OFTError) {
+        match result.unwrap_err().unwrap() {
+            TransactionError::InstructionError(_, InstructionError::Custom(code)) => {
+                assert_eq!(code, u32::from(error))
+            },
+            err => panic!("unexpected error: {:?}", err),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_send_and_receive_round_trip() {
+        let mut harness = Harness::new().await;
+        let source_before = harness.token_balance(harness.token_source).await;
+
+        harness.send(ld(1_000), ld(1_000)).await.unwrap();
+        assert_eq!(harness.token_balance(harness.token_escrow).await, ld(1_000));
+        assert_eq!(harness.token_balance(harness.token_source).await, source_before - ld(1_000));
+        assert_eq!(harness.oft_store().await.tvl_ld, ld(1_000));
+
+        let ld2sd_rate = 10u64.pow((DECIMALS - SHARED_DECIMALS) as u32);
+        harness.lz_receive(ld(1_000) / ld2sd_rate, 1).await.unwrap();
+        assert_eq!(harness.token_balance(harness.token_dest()).await, ld(1_000));
+        assert_eq!(harness.token_balance(harness.token_escrow).await, 0);
+        assert_eq!(harness.oft_store().await.tvl_ld, 0);
+    }
+
+    #[tokio::test]
+    async fn test_send_removes_dust() {
+        let mut harness = Harness::new().await;
+        harness.send(ld(1) + 1, 0).await.unwrap();
+        assert_eq!(harness.token_balance(harness.token_escrow).await, ld(1));
+    }
+
+    #[tokio::test]
+    async fn test_sweep_dust_into_fees() {
+        let mut harness = Harness::new().await;
+        harness.send(ld(1_000_000) - ld(1) - 1, 0).await.unwrap();
+        let dust = harness.get_dust().await;
+        assert_eq!(dust.balance_ld, ld(1) + 1);
+        assert_eq!(dust.dust_ld, 1);
+
+        assert_oft_error(harness.sweep_dust().await, OFTError::DustToFeesDisabled);
+
+        harness.set_oft_config(SetOFTConfigParams::DustToFees(true)).await.unwrap();
+        let escrow_before = harness.token_balance(harness.token_escrow).await;
+        harness.sweep_dust().await.unwrap();
+        assert_eq!(harness.token_balance(harness.token_source).await, ld(1));
+        assert_eq!(harness.token_balance(harness.token_escrow).await, escrow_before + 1);
+        assert_eq!(harness.get_dust().await.dust_ld, 0);
+        assert_eq!(harness.oft_store().await.tvl_ld, escrow_before);
+    }
+
+    #[tokio::test]
+    async fn test_send_records_decayed_outflow() {
+        let mut harness = Harness::new().await;
+        harness.send(ld(1_000), 0).await.unwrap();
+        harness.warp(OUTFLOW_SHORT_HALF_LIFE_SECONDS).await;
+        harness.send(ld(1_000), 0).await.unwrap();
+
+        let outflow = harness.peer().await.outflow_stats;
+        assert_eq!(outflow.short_ld, ld(1_500));
+        assert_eq!(outflow.long_ld, ld(2_000) - ld(1_000) / 48);
+    }
+
+    #[tokio::test]
+    async fn test_send_rejects_unrecoverable_recipients() {
+        let mut harness = Harness::new().await;
+        let mut params = SendParams {
+            dst_eid: REMOTE_EID,
+            to: [0; 32],
+            amount_ld: ld(1),
+            min_amount_ld: 0,
+            options: vec![],
+            compose_msg: None,
+            native_fee: 0,
+            lz_token_fee: 0,
+            flow_tag: oft::policy::FLOW_TAG_USER,
+            priority: PRIORITY_STANDARD,
+            idempotency_key: None,
+        };
+        assert_oft_error(harness.send_with_params(params.clone()).await, OFTError::InvalidRecipient);
+        params.to = harness.peer_address;
+        assert_oft_error(harness.send_with_params(params).await, OFTError::InvalidRecipient);
+    }
+
+    #[tokio::test]
+    async fn test_send_fails_on_slippage() {
+        let mut harness = Harness::new().await;
+        harness.set_oft_config(SetOFTConfigParams::DefaultFee(100)).await.unwrap();
+        assert_oft_error(harness.send(ld(1_000), ld(1_000)).await, OFTError::SlippageExceeded);
+    }
+
+    #[tokio::test]
+    async fn test_send_and_receive_fail_when_paused() {
+        let mut harness = Harness::new().await;
+        harness.send(ld(10), 0).await.unwrap();
+        harness.set_oft_config(SetOFTConfigParams::Paused(true)).await.unwrap();
+
+        assert_oft_error(harness.send(ld(10), 0).await, OFTError::Paused);
+        assert_oft_error(harness.lz_receive(10_000_000, 1).await, OFTError::Paused);
+
+        harness.set_oft_config(SetOFTConfigParams::Paused(false)).await.unwrap();
+        harness.lz_receive(10_000_000, 1).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_fee_accrues_in_escrow() {
+        let mut harness = Harness::new().await;
+        harness.set_oft_config(SetOFTConfigParams::DefaultFee(100)).await.unwrap();
+        harness.send(ld(1_000), 0).await.unwrap();
+
+        let oft_store = harness.oft_store().await;
+        assert_eq!(oft_store.tvl_ld, ld(990));
+        assert_eq!(harness.token_balance(harness.token_escrow).await - oft_store.tvl_ld, ld(10));
+    }
+
+    #[tokio::test]
+    async fn test_peer_fee_overrides_default() {
+        let mut harness = Harness::new().await;
+        harness.set_oft_config(SetOFTConfigParams::DefaultFee(100)).await.unwrap();
+        assert_oft_error(
+            harness.set_peer_config(PeerConfigParam::FeeBps(Some(10_000))).await,
+            OFTError::InvalidFee,
+        );
+
+        harness.set_peer_config(PeerConfigParam::FeeBps(Some(50))).await.unwrap();
+        assert_eq!(harness.peer().await.fee_bps, Some(50));
+        harness.send(ld(1_000), 0).await.unwrap();
+        assert_eq!(harness.oft_store().await.tvl_ld, ld(995));
+
+        // a zero fee peer pays nothing, clearing it charges the default again
+        harness.set_peer_config(PeerConfigParam::FeeBps(Some(0))).await.unwrap();
+        harness.send(ld(1_000), 0).await.unwrap();
+        assert_eq!(harness.oft_store().await.tvl_ld, ld(1_995));
+        harness.set_peer_config(PeerConfigParam::FeeBps(None)).await.unwrap();
+        harness.send(ld(1_000), 0).await.unwrap();
+        assert_eq!(harness.oft_store().await.tvl_ld, ld(2_985));
+    }
+
+    #[tokio::test]
+    async fn test_fee_rounding_mode() {
+        let mut harness = Harness::new().await;
+        harness.set_oft_config(SetOFTConfigParams::DefaultFee(50)).await.unwrap();
+        // a 0.5% fee on 300 shared units is 1.5 units
+        let ld2sd_rate = 10u64.pow((DECIMALS - SHARED_DECIMALS) as u32);
+        harness.send(300 * ld2sd_rate, 0).await.unwrap();
+        assert_eq!(harness.oft_store().await.tvl_ld, 299 * ld2sd_rate);
+
+        harness.set_oft_config(SetOFTConfigParams::FeeRoundingMode(RoundingMode::HalfEven)).await.unwrap();
+        harness.send(300 * ld2sd_rate, 0).await.unwrap();
+        assert_eq!(harness.oft_store().await.tvl_ld, (299 + 298) * ld2sd_rate);
+    }
+
+    #[tokio::test]
+    async fn test_privileged_flow_tag_is_fee_exempt() {
+        let mut harness = Harness::new().await;
+        harness.set_oft_config(SetOFTConfigParams::DefaultFee(100)).await.unwrap();
+        let treasury_send = SendParams {
+            dst_eid: REMOTE_EID,
+            to: [1; 32],
+            amount_ld: ld(1_000),
+            min_amount_ld: ld(1_000),
+            options: vec![],
+            compose_msg: None,
+            native_fee: 0,
+            lz_token_fee: 0,
+            flow_tag: oft::policy::FLOW_TAG_PRIVILEGED_MIN,
+            priority: PRIORITY_STANDARD,
+            idempotency_key: None,
+        };
+        assert_oft_error(
+            harness.send_with_params(treasury_send.clone()).await,
+            OFTError::UnauthorizedFlowTag,
+        );
+
+        let user = harness.user.pubkey();
+        harness.set_oft_config(SetOFTConfigParams::FeeVaultSenders(vec![user])).await.unwrap();
+        harness.send_with_params(treasury_send).await.unwrap();
+        assert_eq!(harness.oft_store().await.tvl_ld, ld(1_000));
+
+        // non-privileged tags only label the transfer
+        harness
+            .send_with_params(SendParams {
+                dst_eid: REMOTE_EID,
+                to: [1; 32],
+                amount_ld: ld(1_000),
+                min_amount_ld: 0,
+                options: vec![],
+                compose_msg: None,
+                native_fee: 0,
+                lz_token_fee: 0,
+                flow_tag: 1,
+                priority: PRIORITY_STANDARD,
+                idempotency_key: None,
+            })
+            .await
+            .unwrap();
+        assert_eq!(harness.oft_store().await.tvl_ld, ld(1_990));
+    }
+
+    #[tokio::test]
+    async fn test_fee_exempt_sender() {
+        let mut harness = Harness::new().await;
+        harness.set_oft_config(SetOFTConfigParams::DefaultFee(100)).await.unwrap();
+        let user = harness.user.pubkey();
+        harness.manage_fee_exempt(user, RateLimitOverrideAction::Add).await.unwrap();
+        assert_oft_error(
+            harness.manage_fee_exempt(user, RateLimitOverrideAction::Add).await,
+            OFTError::AlreadyFeeExempt,
+        );
+        assert!(harness.oft_store().await.is_fee_exempt(&user));
+        harness.send(ld(1_000), ld(1_000)).await.unwrap();
+        assert_eq!(harness.oft_store().await.tvl_ld, ld(1_000));
+
+        harness.manage_fee_exempt(user, RateLimitOverrideAction::Remove).await.unwrap();
+        assert_oft_error(
+            harness.manage_fee_exempt(user, RateLimitOverrideAction::Remove).await,
+            OFTError::NotFeeExempt,
+        );
+        harness.send(ld(1_000), 0).await.unwrap();
+        assert_eq!(harness.oft_store().await.tvl_ld, ld(1_990));
+    }
+
+    #[tokio::test]
+    async fn test_inbound_rate_limit_and_override() {
+        let mut harness = Harness::new().await;
+        harness.send(ld(1_000), 0).await.unwrap();
+        harness
+            .set_peer_config(PeerConfigParam::InboundRateLimit(Some(RateLimitParams {
+                refill_per_second: Some(0),
+                capacity: Some(ld(100)),
+            })))
+            .await
+            .unwrap();
+
+        // within capacity
+        harness.lz_receive(100_000_000, 1).await.unwrap();
+        // over capacity
+        assert_oft_error(harness.lz_receive(1_000_000, 2).await, OFTError::RateLimitExceeded);
+
+        // the override list is matched against the token destination
+        let token_dest = harness.token_dest();
+        harness.manage_rate_limit_override(token_dest).await.unwrap();
+        harness.lz_receive(500_000_000, 2).await.unwrap();
+        assert_eq!(harness.token_balance(token_dest).await, ld(600));
+    }
+
+    #[tokio::test]
+    async fn test_get_rate_limit_capacity() {
+        let mut harness = Harness::new().await;
+        harness.send(ld(1_000), 0).await.unwrap();
+        let capacities = harness.get_rate_limit_capacity().await;
+        assert!(capacities.outbound.is_none() && capacities.inbound.is_none());
+
+        harness
+            .set_peer_config(PeerConfigParam::InboundRateLimit(Some(RateLimitParams {
+                refill_per_second: Some(ld(1)),
+                capacity: Some(ld(100)),
+            })))
+            .await
+            .unwrap();
+        harness.lz_receive(40_000_000, 1).await.unwrap();
+        let inbound = harness.get_rate_limit_capacity().await.inbound.unwrap();
+        assert_eq!(inbound.tokens, ld(60));
+        assert_eq!(inbound.capacity, ld(100));
+        assert_eq!(inbound.seconds_until_full, Some(40));
+
+        harness.warp(40).await;
+        let inbound = harness.get_rate_limit_capacity().await.inbound.unwrap();
+        assert_eq!(inbound.tokens, ld(100));
+        assert_eq!(inbound.seconds_until_full, Some(0));
+    }
+
+    #[tokio::test]
+    async fn test_fixed_window_rate_limit() {
+        let mut harness = Harness::new().await;
+        harness.send(ld(1_000), 0).await.unwrap();
+        harness
+            .set_peer_config(PeerConfigParam::InboundRateLimit(Some(RateLimitParams {
+                refill_per_second: Some(ld(1)),
+                capacity: Some(ld(100)),
+            })))
+            .await
+            .unwrap();
+        let mode = |window_seconds| PeerConfigParam::RateLimitMode {
+            direction: RateLimitDirection::Inbound,
+            mode: RateLimitMode::FixedWindow { window_seconds },
+        };
+        assert_oft_error(harness.set_peer_config(mode(0)).await, OFTError::InvalidRateLimitMode);
+        harness.set_peer_config(mode(86_400)).await.unwrap();
+
+        // start ten seconds into a day
+        let clock: Clock = harness.ctx.banks_client.get_sysvar().await.unwrap();
+        harness.warp(86_400 - clock.unix_timestamp % 86_400 + 10).await;
+        harness.lz_receive(60_000_000, 1).await.unwrap();
+        assert_oft_error(harness.lz_receive(50_000_000, 2).await, OFTError::RateLimitExceeded);
+
+        // refill_per_second is ignored within the day
+        harness.warp(3_600).await;
+        let inbound = harness.get_rate_limit_capacity().await.inbound.unwrap();
+        assert_eq!(inbound.tokens, ld(40));
+        assert_eq!(inbound.seconds_until_full, Some(86_400 - 3_610));
+        assert_oft_error(harness.lz_receive(50_000_000, 2).await, OFTError::RateLimitExceeded);
+
+        harness.warp(86_400 - 3_610).await;
+        assert_eq!(harness.get_rate_limit_capacity().await.inbound.unwrap().tokens, ld(100));
+        harness.lz_receive(50_000_000, 2).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_state_tracks_peer() {
+        let mut harness = Harness::new().await;
+        harness.send(ld(1_000), 0).await.unwrap();
+        // not created yet, so lz_receive leaves it alone
+        harness.lz_receive(1_000_000, 1).await.unwrap();
+
+        harness
+            .set_peer_config(PeerConfigParam::InboundRateLimit(Some(RateLimitParams {
+                refill_per_second: Some(0),
+                capacity: Some(ld(100)),
+            })))
+            .await
+            .unwrap();
+        harness.sync_rate_limit_state().await.unwrap();
+        let state = harness.read_rate_limit_state().await;
+        assert_eq!(state.remote_eid, REMOTE_EID);
+        assert_eq!(state.available_ld(u64::MAX), Some(ld(100)));
+
+        harness.lz_receive(30_000_000, 2).await.unwrap();
+        let state = harness.read_rate_limit_state().await;
+        assert_eq!(state.available_ld(u64::MAX), Some(ld(70)));
+    }
+
+    #[tokio::test]
+    async fn test_outbound_rate_limit_per_peer() {
+        let mut harness = Harness::new().await;
+        let rate_limit = RateLimitParams { refill_per_second: Some(0), capacity: Some(ld(15)) };
+        harness.set_outbound_rate_limit(Some(rate_limit)).await.unwrap();
+
+        harness.send(ld(10), 0).await.unwrap();
+        assert_oft_error(harness.send(ld(10), 0).await, OFTError::RateLimitExceeded);
+        let limiter = harness.peer().await.outbound_rate_limiter.unwrap();
+        assert_eq!(limiter.tokens, ld(5));
+
+        // overridden senders bypass it
+        let user = harness.user.pubkey();
+        harness.manage_rate_limit_override(user).await.unwrap();
+        harness.send(ld(10), 0).await.unwrap();
+
+        harness.set_outbound_rate_limit(None).await.unwrap();
+        assert!(harness.peer().await.outbound_rate_limiter.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_sender_rate_limit() {
+        let mut harness = Harness::new().await;
+        let config = SenderRateLimitConfig { capacity: 0, refill_per_second: 0 };
+        assert_oft_error(
+            harness.set_oft_config(SetOFTConfigParams::SenderRateLimit(Some(config))).await,
+            OFTError::InvalidSenderRateLimit,
+        );
+        harness.send(ld(10), 0).await.unwrap();
+        // not created without a sender rate limit
+        assert!(harness.read_sender_rate_limit().await.is_none());
+
+        let config = SenderRateLimitConfig { capacity: ld(15), refill_per_second: 0 };
+        harness.set_oft_config(SetOFTConfigParams::SenderRateLimit(Some(config))).await.unwrap();
+        harness.send(ld(10), 0).await.unwrap();
+        let state = harness.read_sender_rate_limit().await.unwrap();
+        assert_eq!((state.sender, state.tokens), (harness.user.pubkey(), ld(5)));
+        assert_oft_error(harness.send(ld(10), 0).await, OFTError::RateLimitExceeded);
+
+        // overridden senders skip their own limit too
+        let user = harness.user.pubkey();
+        harness.manage_rate_limit_override(user).await.unwrap();
+        harness.send(ld(10), 0).await.unwrap();
+        assert_eq!(harness.read_sender_rate_limit().await.unwrap().tokens, ld(5));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_role() {
+        let mut harness = Harness::new().await;
+        let rate_limiter = Keypair::new();
+        harness
+            .set_oft_config(SetOFTConfigParams::RateLimiter(Some(rate_limiter.pubkey())))
+            .await
+            .unwrap();
+
+        // the harness signs admin instructions with the rate limiter from here on
+        let admin = std::mem::replace(&mut harness.admin, rate_limiter);
+        let rate_limit = RateLimitParams { refill_per_second: Some(0), capacity: Some(ld(5)) };
+        harness.set_inbound_rate_limit(Some(rate_limit.clone())).await.unwrap();
+        harness.set_outbound_rate_limit(Some(rate_limit)).await.unwrap();
+        harness.manage_rate_limit_override(Pubkey::new_unique()).await.unwrap();
+        assert_oft_error(
+            harness.set_oft_config(SetOFTConfigParams::DefaultFee(100)).await,
+            OFTError::Unauthorized,
+        );
+
+        harness.admin = admin;
+        harness.set_oft_config(SetOFTConfigParams::RateLimiter(None)).await.unwrap();
+        harness.admin = Keypair::new();
+        assert_oft_error(harness.set_inbound_rate_limit(None).await, OFTError::Unauthorized);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_override_direction() {
+        let mut harness = Harness::new().await;
+        harness.send(ld(10), 0).await.unwrap();
+        let rate_limit = RateLimitParams { refill_per_second: Some(0), capacity: Some(ld(5)) };
+        harness.set_outbound_rate_limit(Some(rate_limit.clone())).await.unwrap();
+        harness.set_inbound_rate_limit(Some(rate_limit)).await.unwrap();
+        let (user, token_dest) = (harness.user.pubkey(), harness.token_dest());
+        let params = ManageRateLimitOverrideAddressParams {
+            addresses: vec![user, token_dest],
+            actions: vec![RateLimitOverrideAction::Add; 2],
+            expires_at: vec![],
+            direction: Some(RateLimitDirection::Inbound),
+            multiplier: None,
+            scope: RateLimitOverrideScope::Either,
+        };
+        harness.manage_rate_limit_override_with_params(params, None).await.unwrap();
+
+        // trusted to receive without limits, not to send
+        harness.lz_receive(8_000_000, 1).await.unwrap();
+        assert_oft_error(harness.send(ld(8), 0).await, OFTError::RateLimitExceeded);
+        let overrides = harness.rate_limit_overrides().await;
+        assert_eq!(overrides.address_directions, vec![Some(RateLimitDirection::Inbound); 2]);
+        // only the credit moved past the limits
+        assert_eq!(overrides.total_overridden_volume_ld, ld(8));
+        assert_eq!(overrides.override_trigger_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_override_recipient() {
+        let mut harness = Harness::new().await;
+        harness.send(ld(10), 0).await.unwrap();
+        let rate_limit = RateLimitParams { refill_per_second: Some(0), capacity: Some(ld(5)) };
+        harness.set_outbound_rate_limit(Some(rate_limit.clone())).await.unwrap();
+        harness.set_inbound_rate_limit(Some(rate_limit)).await.unwrap();
+        let mut params = ManageRateLimitOverrideAddressParams {
+            addresses: vec![harness.user.pubkey()],
+            actions: vec![RateLimitOverrideAction::Add],
+            expires_at: vec![],
+            direction: Some(RateLimitDirection::Outbound),
+            multiplier: None,
+            scope: RateLimitOverrideScope::Recipient,
+        };
+        assert_oft_error(
+            harness.manage_rate_limit_override_with_params(params.clone(), None).await,
+            OFTError::InvalidOverrideScope,
+        );
+        params.direction = None;
+        harness.manage_rate_limit_override_with_params(params, None).await.unwrap();
+
+        // the wallet is matched as the owner of the credited token account, but not as a sender
+        harness.lz_receive(8_000_000, 1).await.unwrap();
+        assert_oft_error(harness.send(ld(8), 0).await, OFTError::RateLimitExceeded);
+        let overrides = harness.rate_limit_overrides().await;
+        assert_eq!(overrides.address_scopes, vec![RateLimitOverrideScope::Recipient]);
+        assert_eq!(overrides.override_trigger_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_reserved_capacity() {
+        let mut harness = Harness::new().await;
+        let rate_limit = RateLimitParams { refill_per_second: Some(0), capacity: Some(ld(15)) };
+        harness.set_outbound_rate_limit(Some(rate_limit)).await.unwrap();
+        harness.set_peer_config(PeerConfigParam::ReservedCapacity(ld(10))).await.unwrap();
+
+        // public sends stop at the reserved capacity
+        assert_oft_error(harness.send(ld(6), 0).await, OFTError::RateLimitExceeded);
+        harness.send(ld(5), 0).await.unwrap();
+        assert_oft_error(harness.send(ld(1), 0).await, OFTError::RateLimitExceeded);
+
+        // which is left to the override addresses
+        let params = ManageRateLimitOverrideAddressParams {
+            addresses: vec![harness.user.pubkey()],
+            actions: vec![RateLimitOverrideAction::Add],
+            expires_at: vec![],
+            direction: None,
+            multiplier: Some(2),
+            scope: RateLimitOverrideScope::Either,
+        };
+        harness.manage_rate_limit_override_with_params(params, None).await.unwrap();
+        harness.send(ld(20), 0).await.unwrap();
+        assert_eq!(harness.peer().await.outbound_rate_limiter.unwrap().tokens, 0);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_override_pda() {
+        let mut harness = Harness::new().await;
+        harness.send(ld(10), 0).await.unwrap();
+        let rate_limit = RateLimitParams { refill_per_second: Some(0), capacity: Some(ld(1)) };
+        harness.set_inbound_rate_limit(Some(rate_limit.clone())).await.unwrap();
+        harness.set_outbound_rate_limit(Some(rate_limit)).await.unwrap();
+
+        // sends are matched on the wallet debited, not on the fee payer
+        let fee_payer = harness.ctx.payer.pubkey();
+        harness.manage_rate_limit_override(fee_payer).await.unwrap();
+        assert_oft_error(harness.send(ld(2), 0).await, OFTError::RateLimitExceeded);
+
+        // a program's vault PDA receives past the limit, its token account created on the way
+        let (vault, _) = Pubkey::find_program_address(&[b"vault"], &Pubkey::new_unique());
+        let params = ManageRateLimitOverrideAddressParams {
+            addresses: vec![vault],
+            actions: vec![RateLimitOverrideAction::Add],
+            expires_at: vec![],
+            direction: None,
+            multiplier: None,
+            scope: RateLimitOverrideScope::Recipient,
+        };
+        harness.manage_rate_limit_override_with_params(params, None).await.unwrap();
+        let message = msg_codec::encode(vault.to_bytes(), 2_000_000, Pubkey::new_unique(), &None);
+        harness.lz_receive_message(message, 1).await.unwrap();
+        let token_dest = associated_token::get_associated_token_address_with_program_id(
+            &vault,
+            &harness.token_mint,
+            &spl_token::ID,
+        );
+        assert_eq!(harness.token_balance(token_dest).await, ld(2));
+        assert_eq!(harness.peer().await.inbound_rate_limiter.unwrap().tokens, ld(1));
+    }
+
+    #[tokio::test]
+    async fn test_msg_type_rate_limits() {
+        let mut harness = Harness::new().await;
+        harness.send(ld(10), 0).await.unwrap();
+        // composed credits are held, so nothing is composed in the test
+        let composer = Pubkey::new_unique();
+        harness.set_oft_config(SetOFTConfigParams::AllowedComposers(vec![composer])).await.unwrap();
+        let rate_limit = RateLimitParams { refill_per_second: Some(0), capacity: Some(ld(1)) };
+        assert_oft_error(
+            harness
+                .set_peer_config(PeerConfigParam::MsgTypeRateLimit {
+                    msg_type: 3,
+                    direction: RateLimitDirection::Inbound,
+                    rate_limit: Some(rate_limit.clone()),
+                })
+                .await,
+            OFTError::InvalidMsgType,
+        );
+        harness
+            .set_peer_config(PeerConfigParam::MsgTypeRateLimit {
+                msg_type: MSG_TYPE_SEND_AND_CALL,
+                direction: RateLimitDirection::Inbound,
+                rate_limit: Some(rate_limit),
+            })
+            .await
+            .unwrap();
+
+        // plain transfers are not charged the composed limit
+        harness.lz_receive(2_000_000, 1).await.unwrap();
+        assert_eq!(harness.token_balance(harness.token_dest()).await, ld(2));
+
+        let to_address = harness.user.pubkey().to_bytes();
+        let sender = Pubkey::new_unique();
+        let composed = msg_codec::encode(to_address, 2_000_000, sender, &Some(vec![1]));
+        assert_oft_error(
+            harness.lz_receive_message(composed, 2).await,
+            OFTError::RateLimitExceeded,
+        );
+        let composed = msg_codec::encode(to_address, 1_000_000, sender, &Some(vec![1]));
+        harness.lz_receive_message(composed, 2).await.unwrap();
+        let peer = harness.peer().await;
+        assert_eq!(peer.msg_type_rate_limiters[0].rate_limiter.tokens, 0);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_override_multiplier() {
+        let mut harness = Harness::new().await;
+        let rate_limit = RateLimitParams { refill_per_second: Some(0), capacity: Some(ld(5)) };
+        harness.set_outbound_rate_limit(Some(rate_limit)).await.unwrap();
+        let mut params = ManageRateLimitOverrideAddressParams {
+            addresses: vec![harness.user.pubkey()],
+            actions: vec![RateLimitOverrideAction::Add],
+            expires_at: vec![],
+            direction: None,
+            multiplier: Some(1),
+            scope: RateLimitOverrideScope::Either,
+        };
+        assert_oft_error(
+            harness.manage_rate_limit_override_with_params(params.clone(), None).await,
+            OFTError::InvalidOverrideMultiplier,
+        );
+        params.multiplier = Some(3);
+        harness.manage_rate_limit_override_with_params(params, None).await.unwrap();
+
+        // charged a third of each send, so three times the limit gets through
+        harness.send(ld(12), 0).await.unwrap();
+        assert_eq!(harness.peer().await.outbound_rate_limiter.unwrap().tokens, ld(1));
+        assert_oft_error(harness.send(ld(6), 0).await, OFTError::RateLimitExceeded);
+        harness.send(ld(3), 0).await.unwrap();
+        let overrides = harness.rate_limit_overrides().await;
+        assert_eq!(overrides.address_multipliers, vec![Some(3)]);
+    }
+
+    #[tokio::test]
+    async fn test_overrides_consume_capacity() {
+        let mut harness = Harness::new().await;
+        let rate_limit = RateLimitParams { refill_per_second: Some(0), capacity: Some(ld(15)) };
+        harness.set_outbound_rate_limit(Some(rate_limit.clone())).await.unwrap();
+        harness.set_inbound_rate_limit(Some(rate_limit)).await.unwrap();
+        harness.set_oft_config(SetOFTConfigParams::OverridesConsumeCapacity(true)).await.unwrap();
+        let (user, token_dest) = (harness.user.pubkey(), harness.token_dest());
+        harness.manage_rate_limit_override(user).await.unwrap();
+        harness.manage_rate_limit_override(token_dest).await.unwrap();
+
+        // overridden sends are never rejected but still use up the limit
+        harness.send(ld(10), 0).await.unwrap();
+        harness.send(ld(9), 0).await.unwrap();
+        let peer = harness.peer().await;
+        assert_eq!(peer.outbound_rate_limiter.unwrap().tokens, 0);
+        assert_eq!(peer.inbound_rate_limiter.unwrap().tokens, ld(15));
+
+        // and so do overridden credits, which free up outbound capacity in turn
+        harness.lz_receive(8_000_000, 1).await.unwrap();
+        let peer = harness.peer().await;
+        assert_eq!(peer.inbound_rate_limiter.unwrap().tokens, ld(7));
+        assert_eq!(peer.outbound_rate_limiter.unwrap().tokens, ld(8));
+    }
+
+    #[tokio::test]
+    async fn test_set_rate_limit_enabled() {
+        let mut harness = Harness::new().await;
+        assert!(harness.peer().await.rate_limit_enabled);
+        harness.send(ld(10), 0).await.unwrap();
+        let rate_limit = RateLimitParams { refill_per_second: Some(0), capacity: Some(ld(5)) };
+        harness.set_outbound_rate_limit(Some(rate_limit.clone())).await.unwrap();
+        harness.set_inbound_rate_limit(Some(rate_limit)).await.unwrap();
+        assert_oft_error(harness.send(ld(8), 0).await, OFTError::RateLimitExceeded);
+
+        // a trusted peer moves any amount without using up its limits
+        harness.set_rate_limit_enabled(false).await.unwrap();
+        harness.send(ld(8), 0).await.unwrap();
+        harness.lz_receive(8_000_000, 1).await.unwrap();
+        let peer = harness.peer().await;
+        assert_eq!(peer.outbound_rate_limiter.unwrap().tokens, ld(5));
+        assert_eq!(peer.inbound_rate_limiter.unwrap().tokens, ld(5));
+
+        harness.set_rate_limit_enabled(true).await.unwrap();
+        assert_oft_error(harness.send(ld(8), 0).await, OFTError::RateLimitExceeded);
+    }
+
+    #[tokio::test]
+    async fn test_boost_rate_limit() {
+        let mut harness = Harness::new().await;
+        // only existing rate limiters can be boosted
+        assert_oft_error(
+            harness.boost_rate_limit(ld(30), 3_600).await,
+            OFTError::InvalidRateLimitBoost,
+        );
+        let rate_limit = RateLimitParams { refill_per_second: Some(0), capacity: Some(ld(15)) };
+        harness.set_outbound_rate_limit(Some(rate_limit)).await.unwrap();
+        assert_oft_error(
+            harness.boost_rate_limit(ld(10), 3_600).await,
+            OFTError::InvalidRateLimitBoost,
+        );
+        assert_oft_error(harness.boost_rate_limit(ld(30), 0).await, OFTError::InvalidRateLimitBoost);
+
+        harness.send(ld(10), 0).await.unwrap();
+        harness.boost_rate_limit(ld(30), 3_600).await.unwrap();
+        let peer = harness.peer().await;
+        let rate_limiter = peer.outbound_rate_limiter.unwrap();
+        assert_eq!((rate_limiter.capacity, rate_limiter.tokens), (ld(30), ld(20)));
+        harness.send(ld(2), 0).await.unwrap();
+
+        // once expired, sends are back to the own limits, with what is available capped at them
+        harness.warp(3_600).await;
+        assert_oft_error(harness.send(ld(16), 0).await, OFTError::RateLimitExceeded);
+        harness.send(ld(15), 0).await.unwrap();
+        let peer = harness.peer().await;
+        let rate_limiter = peer.outbound_rate_limiter.unwrap();
+        assert_eq!((rate_limiter.capacity, rate_limiter.tokens), (ld(15), 0));
+        assert!(peer.outbound_rate_limit_boost.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_reset_rate_limit_consumption() {
+        let mut harness = Harness::new().await;
+        let rate_limit = RateLimitParams { refill_per_second: Some(1), capacity: Some(ld(15)) };
+        harness.set_outbound_rate_limit(Some(rate_limit)).await.unwrap();
+        harness.send(ld(10), 0).await.unwrap();
+        assert_oft_error(harness.send(ld(10), 0).await, OFTError::RateLimitExceeded);
+
+        // the inbound direction has no rate limiter and is left as it is
+        harness.reset_rate_limit_consumption(RateLimitDirection::Inbound).await.unwrap();
+        assert_oft_error(harness.send(ld(10), 0).await, OFTError::RateLimitExceeded);
+
+        harness.reset_rate_limit_consumption(RateLimitDirection::Outbound).await.unwrap();
+        let rate_limiter = harness.peer().await.outbound_rate_limiter.unwrap();
+        assert_eq!((rate_limiter.capacity, rate_limiter.tokens), (ld(15), ld(15)));
+        assert_eq!(rate_limiter.refill_per_second, 1);
+        harness.send(ld(15), 0).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_set_rate_limits_batch() {
+        let mut harness = Harness::new().await;
+        let rate_limit = RateLimitParams { refill_per_second: Some(0), capacity: Some(ld(15)) };
+        let rate_limits = vec![SetOutboundRateLimitParams {
+            remote_eid: REMOTE_EID,
+            rate_limit: Some(rate_limit),
+        }];
+        assert_oft_error(
+            harness.set_rate_limits_batch(rate_limits.clone(), &[]).await,
+            OFTError::RateLimitBatchLengthMismatch,
+        );
+        // the peer of another eid
+        let mut wrong_eid = rate_limits.clone();
+        wrong_eid[0].remote_eid = REMOTE_EID + 1;
+        let peer = harness.peer;
+        assert_oft_error(
+            harness.set_rate_limits_batch(wrong_eid, &[peer]).await,
+            OFTError::InvalidPeerAccount,
+        );
+
+        harness.set_rate_limits_batch(rate_limits, &[peer]).await.unwrap();
+        assert_eq!(harness.peer().await.outbound_rate_limiter.unwrap().capacity, ld(15));
+    }
+
+    #[tokio::test]
+    async fn test_deprecated_set_rate_limit() {
+        let mut harness = Harness::new().await;
+        let rate_limit = RateLimitParams { refill_per_second: Some(0), capacity: Some(ld(15)) };
+        let ix = Instruction {
+            program_id: oft::ID,
+            accounts: oft::accounts::SetOutboundRateLimit {
+                admin: harness.admin.pubkey(),
+                oft_store: harness.oft_store,
+                peer: harness.peer,
+            }
+            .to_account_metas(None),
+            data: oft::instruction::SetRateLimit {
+                params: SetRateLimitParams { remote_eid: REMOTE_EID, rate_limit: Some(rate_limit) },
+            }
+            .data(),
+        };
+        let admin = harness.admin.insecure_clone();
+        harness.process(&[ix], &[&admin]).await.unwrap();
+        // forwarded to set_outbound_rate_limit
+        assert_eq!(harness.peer().await.outbound_rate_limiter.unwrap().capacity, ld(15));
+    }
+
+    #[tokio::test]
+    async fn test_net_rate_limit() {
+        let mut harness = Harness::new().await;
+        let rate_limit = RateLimitParams { refill_per_second: Some(0), capacity: Some(ld(15)) };
+        harness.set_outbound_rate_limit(Some(rate_limit.clone())).await.unwrap();
+        harness.set_inbound_rate_limit(Some(rate_limit)).await.unwrap();
+
+        // sending refills the inbound limit, which stays capped
+        harness.send(ld(10), 0).await.unwrap();
+        assert_oft_error(harness.send(ld(10), 0).await, OFTError::RateLimitExceeded);
+        assert_eq!(harness.peer().await.inbound_rate_limiter.unwrap().tokens, ld(15));
+
+        // receiving refills the outbound limit
+        harness.lz_receive(8_000_000, 1).await.unwrap();
+        let peer = harness.peer().await;
+        assert_eq!(peer.inbound_rate_limiter.unwrap().tokens, ld(7));
+        assert_eq!(peer.outbound_rate_limiter.unwrap().tokens, ld(13));
+        harness.send(ld(10), 0).await.unwrap();
+
+        // overridden receivers neither consume nor free up capacity
+        let token_dest = harness.token_dest();
+        harness.manage_rate_limit_override(token_dest).await.unwrap();
+        harness.lz_receive(10_000_000, 2).await.unwrap();
+        assert_eq!(harness.peer().await.outbound_rate_limiter.unwrap().tokens, ld(3));
+    }
+
+    #[tokio::test]
+    async fn test_inbound_rate_limit_per_peer() {
+        let mut harness = Harness::new().await;
+        harness.sync_rate_limit_state().await.unwrap();
+        let rate_limit = RateLimitParams { refill_per_second: Some(0), capacity: Some(ld(20)) };
+        harness.set_inbound_rate_limit(Some(rate_limit)).await.unwrap();
+        let state = harness.read_rate_limit_state().await;
+        assert_eq!(state.available_ld(u64::MAX), Some(ld(20)));
+        // independent of the outbound limit
+        assert!(harness.peer().await.outbound_rate_limiter.is_none());
+
+        assert_oft_error(harness.lz_receive(30_000_000, 1).await, OFTError::RateLimitExceeded);
+
+        harness.set_inbound_rate_limit(None).await.unwrap();
+        assert!(harness.peer().await.inbound_rate_limiter.is_none());
+        assert_eq!(harness.read_rate_limit_state().await.available_ld(u64::MAX), None);
+    }
+
+    #[tokio::test]
+    async fn test_get_rate_limit_overrides() {
+        let mut harness = Harness::new().await;
+        let address = Pubkey::new_unique();
+        harness.manage_rate_limit_override(address).await.unwrap();
+
+        let overrides = harness.rate_limit_overrides().await;
+        assert_eq!(overrides.version, RATE_LIMIT_OVERRIDES_VIEW_VERSION);
+        assert_eq!(overrides.addresses, vec![address]);
+        assert_eq!(overrides.address_expires_at, vec![None]);
+        assert_eq!(overrides.max_addresses, 16);
+        assert!(overrides.guids.is_empty());
+        assert_eq!(overrides.max_guids, 8);
+    }
+
+    #[tokio::test]
+    async fn test_mirror_override_list() {
+        let mut harness = Harness::new().await;
+        let (exempt, mapped) = (Pubkey::new_unique(), Pubkey::new_unique());
+        harness.manage_rate_limit_override(exempt).await.unwrap();
+        harness.set_evm_address_mapping(exempt, Some([1; 20])).await.unwrap();
+        harness.set_evm_address_mapping(mapped, Some([2; 20])).await.unwrap();
+        // remapping replaces the entry
+        harness.set_evm_address_mapping(mapped, Some([3; 20])).await.unwrap();
+        let mappings = harness.read_evm_address_map().await.mappings;
+        assert_eq!(
+            mappings,
+            vec![
+                EvmAddressMapping { address: exempt, evm_address: [1; 20] },
+                EvmAddressMapping { address: mapped, evm_address: [3; 20] },
+            ]
+        );
+        harness.mirror_override_list().await.unwrap();
+
+        harness.set_evm_address_mapping(exempt, None).await.unwrap();
+        harness.set_evm_address_mapping(mapped, None).await.unwrap();
+        assert_oft_error(harness.mirror_override_list().await, OFTError::EmptyOverrideListMirror);
+
+        harness.admin = Keypair::new();
+        assert_oft_error(
+            harness.set_evm_address_mapping(exempt, Some([1; 20])).await,
+            OFTError::Unauthorized,
+        );
+    }
+
+    #[tokio::test]
+    async fn test_launch_schedule() {
+        let mut harness = Harness::new().await;
+        let clock: Clock = harness.ctx.banks_client.get_sysvar().await.unwrap();
+        let week = 7 * 24 * 60 * 60;
+        let stage = |starts_at, capacity, max_amount_ld| LaunchStage {
+            starts_at,
+            capacity,
+            refill_per_second: 0,
+            max_amount_ld,
+        };
+        let unordered = vec![stage(clock.unix_timestamp, ld(100), ld(60)); 2];
+        assert_oft_error(
+            harness.set_oft_config(SetOFTConfigParams::LaunchSchedule(unordered)).await,
+            OFTError::InvalidLaunchSchedule,
+        );
+        let schedule = vec![
+            stage(clock.unix_timestamp, ld(100), ld(60)),
+            stage(clock.unix_timestamp + week, ld(1_000), ld(500)),
+        ];
+        harness.set_oft_config(SetOFTConfigParams::LaunchSchedule(schedule)).await.unwrap();
+
+        // the first stage limits the peer even though it has no rate limits of its own
+        assert_oft_error(harness.send(ld(70), 0).await, OFTError::LaunchLimitExceeded);
+        harness.send(ld(60), 0).await.unwrap();
+        assert_oft_error(harness.send(ld(50), 0).await, OFTError::RateLimitExceeded);
+        assert_oft_error(harness.lz_receive(70_000_000, 1).await, OFTError::LaunchLimitExceeded);
+
+        // the second stage adds its extra capacity
+        harness.warp(week).await;
+        let outbound = harness.get_rate_limit_capacity().await.outbound.unwrap();
+        assert_eq!((outbound.tokens, outbound.capacity), (ld(940), ld(1_000)));
+        harness.send(ld(500), 0).await.unwrap();
+
+        harness.set_oft_config(SetOFTConfigParams::LaunchSchedule(vec![])).await.unwrap();
+        // the limits of the last stage are kept as the peer's own
+        assert_eq!(harness.peer().await.outbound_rate_limiter.unwrap().capacity, ld(1_000));
+    }
+
+    #[tokio::test]
+    async fn test_peer_control_challenge() {
+        let mut harness = Harness::new().await;
+        assert!(harness.peer().await.control_proven);
+
+        // a new address has to prove control again
+        harness.peer_address = [8; 32];
+        harness.set_peer_config(PeerConfigParam::PeerAddress([8; 32])).await.unwrap();
+        assert_oft_error(harness.send(ld(10), 0).await, OFTError::PeerControlNotProven);
+        let quote = harness.quote_oft(ld(10), 0).await;
+        assert_eq!(quote.blockers, vec![SendBlocker::PeerControlNotProven]);
+
+        harness.challenge_peer().await.unwrap();
+        let nonce = harness.peer().await.control_challenge.unwrap();
+        // a wrong nonce is cleared without proving anything
+        harness.echo_peer_challenge([1; 32]).await.unwrap();
+        assert!(!harness.peer().await.control_proven);
+
+        harness.echo_peer_challenge(nonce).await.unwrap();
+        let peer = harness.peer().await;
+        assert!(peer.control_proven && peer.control_challenge.is_none());
+        harness.send(ld(10), 0).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_control_message_accounts() {
+        let mut harness = Harness::new().await;
+        let to_address = harness.user.pubkey();
+        let transfer = msg_codec::encode(to_address.to_bytes(), 1, Pubkey::default(), &None);
+        let accounts = harness.lz_receive_types(transfer).await;
+        assert_eq!(accounts[4], to_address);
+        assert_eq!(accounts[5], harness.token_dest());
+        assert_eq!(accounts[12], harness.inbound_opt_out(&to_address));
+        assert_eq!(accounts[15], harness.notification_hook(&to_address));
+        let transfer_len = accounts.len();
+
+        // control messages list neither the recipient's accounts nor compose accounts
+        let challenge = msg_codec::encode_peer_challenge(harness.peer_address, [1; 32]);
+        let accounts = harness.lz_receive_types(challenge).await;
+        for index in [4, 5, 7, 11, 12, 14, 15] {
+            assert_eq!(accounts[index], oft::ID);
+        }
+        assert_eq!(accounts[13], harness.rate_limit_state());
+        assert_eq!(accounts.len(), transfer_len);
+
+        // and fail if the type is unknown
+        let unknown = msg_codec::encode_control(harness.peer_address, 0, &[]);
+        assert_oft_error(harness.lz_receive_message(unknown, 1).await, OFTError::InvalidMessage);
+    }
+
+    #[tokio::test]
+    async fn test_governance_override_guids() {
+        let mut harness = Harness::new().await;
+        let entries = [([5; 32], REMOTE_EID), ([6; 32], 0)];
+        let message = msg_codec::encode_override_guids(harness.peer_address, &entries);
+
+        // only from the governance peer
+        assert_oft_error(
+            harness.lz_receive_message(message.clone(), 1).await,
+            OFTError::InvalidSender,
+        );
+        let config = SetOFTConfigParams::GovernanceEid(Some(REMOTE_EID));
+        harness.set_oft_config(config).await.unwrap();
+        // messages not composed by the peer itself are cleared as zero amount transfers
+        let config = SetOFTConfigParams::ZeroAmountPolicy(ZeroAmountPolicy::Ignore);
+        harness.set_oft_config(config).await.unwrap();
+        let forged = msg_codec::encode_override_guids([9; 32], &entries);
+        harness.lz_receive_message(forged, 2).await.unwrap();
+        assert!(harness.rate_limit_overrides().await.guids.is_empty());
+
+        harness.lz_receive_message(message.clone(), 3).await.unwrap();
+        let guids = harness.rate_limit_overrides().await.guids;
+        assert_eq!(guids.len(), 2);
+        assert_eq!((guids[0].guid, guids[0].src_eid), ([5; 32], Some(REMOTE_EID)));
+        assert_eq!((guids[1].guid, guids[1].src_eid), ([6; 32], None));
+        // guids already overridden are skipped
+        harness.lz_receive_message(message, 4).await.unwrap();
+        assert_eq!(harness.rate_limit_overrides().await.guids.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_pause_notice() {
+        let mut harness = Harness::new().await;
+        let pauser = Keypair::new();
+        harness.set_oft_config(SetOFTConfigParams::Pauser(Some(pauser.pubkey()))).await.unwrap();
+        harness.set_oft_config(SetOFTConfigParams::Unpauser(Some(pauser.pubkey()))).await.unwrap();
+        assert_oft_error(harness.send_pause_notice(&pauser).await, OFTError::NotPaused);
+        harness.set_pause(&pauser, true).await.unwrap();
+        let admin = harness.admin.insecure_clone();
+        assert_oft_error(harness.send_pause_notice(&admin).await, OFTError::Unauthorized);
+        harness.send_pause_notice(&pauser).await.unwrap();
+        harness.set_pause(&pauser, false).await.unwrap();
+
+        // notices from the peer are only reported unless the store honors them
+        let notice = msg_codec::encode_pause_notice(harness.peer_address, 5);
+        harness.lz_receive_message(notice.clone(), 1).await.unwrap();
+        assert!(!harness.oft_store().await.paused);
+        // they credit no one, so no token account is created for CONTROL_SEND_TO
+        let control_dest = associated_token::get_associated_token_address_with_program_id(
+            &Pubkey::from(msg_codec::CONTROL_SEND_TO),
+            &harness.token_mint,
+            &spl_token::ID,
+        );
+        assert!(harness.ctx.banks_client.get_account(control_dest).await.unwrap().is_none());
+        harness.set_oft_config(SetOFTConfigParams::HonorPauseNotices(true)).await.unwrap();
+        let config = SetOFTConfigParams::ZeroAmountPolicy(ZeroAmountPolicy::Ignore);
+        harness.set_oft_config(config).await.unwrap();
+        let forged = msg_codec::encode_pause_notice([9; 32], 5);
+        harness.lz_receive_message(forged, 2).await.unwrap();
+        assert!(!harness.oft_store().await.paused);
+        harness.lz_receive_message(notice, 3).await.unwrap();
+        assert!(harness.oft_store().await.paused);
+        assert_oft_error(harness.send(ld(1), 0).await, OFTError::Paused);
+    }
+
+    #[tokio::test]
+    async fn test_delivery_ack() {
+        let mut harness = Harness::new().await;
+        let pauser = Keypair::new();
+        harness.set_oft_config(SetOFTConfigParams::Pauser(Some(pauser.pubkey()))).await.unwrap();
+        // only from the peer itself, others are cleared as zero amount transfers
+        let config = SetOFTConfigParams::ZeroAmountPolicy(ZeroAmountPolicy::Ignore);
+        harness.set_oft_config(config).await.unwrap();
+        let forged = msg_codec::encode_delivery_ack([9; 32], [1; 32], 5);
+        harness.lz_receive_message(forged, 1).await.unwrap();
+
+        let ack = msg_codec::encode_delivery_ack(harness.peer_address, [1; 32], 5);
+        harness.lz_receive_message(ack.clone(), 2).await.unwrap();
+        harness
+            .set_control_message_pause(&pauser, ControlMessage::DeliveryAck, true)
+            .await
+            .unwrap();
+        assert_oft_error(harness.lz_receive_message(ack, 3).await, OFTError::ControlMessagePaused);
+    }
+
+    #[tokio::test]
+    async fn test_in_flight() {
+        let mut harness = Harness::new().await;
+        harness.set_peer_config(PeerConfigParam::InFlightTracking(true)).await.unwrap();
+        harness.send(ld(10), 0).await.unwrap();
+        harness.send(ld(5), 0).await.unwrap();
+        let report = harness.get_in_flight().await;
+        assert_eq!((report.counters.sent_count, report.in_flight_count), (2, 2));
+        assert_eq!(report.in_flight_ld, report.counters.sent_ld);
+
+        // acknowledged deliveries are no longer in flight
+        let ld2sd_rate = 10u64.pow((DECIMALS - SHARED_DECIMALS) as u32);
+        let ack =
+            msg_codec::encode_delivery_ack(harness.peer_address, [1; 32], ld(10) / ld2sd_rate);
+        harness.lz_receive_message(ack, 1).await.unwrap();
+        let report = harness.get_in_flight().await;
+        assert_eq!((report.counters.acknowledged_count, report.in_flight_count), (1, 1));
+        assert_eq!(report.in_flight_ld, report.counters.sent_ld - ld(10));
+
+        // enabling the tracking again restarts the counters
+        harness.set_peer_config(PeerConfigParam::InFlightTracking(false)).await.unwrap();
+        assert_eq!(harness.peer().await.in_flight, None);
+        harness.set_peer_config(PeerConfigParam::InFlightTracking(true)).await.unwrap();
+        assert_eq!(harness.get_in_flight().await.counters, InFlight::default());
+    }
+
+    #[tokio::test]
+    async fn test_control_message_pause() {
+        let mut harness = Harness::new().await;
+        let pauser = Keypair::new();
+        harness.set_oft_config(SetOFTConfigParams::Pauser(Some(pauser.pubkey()))).await.unwrap();
+        harness.set_oft_config(SetOFTConfigParams::Unpauser(Some(pauser.pubkey()))).await.unwrap();
+        harness.set_oft_config(SetOFTConfigParams::GovernanceEid(Some(REMOTE_EID))).await.unwrap();
+        let admin = harness.admin.insecure_clone();
+        assert_oft_error(
+            harness.set_control_message_pause(&admin, ControlMessage::OverrideGuids, true).await,
+            OFTError::Unauthorized,
+        );
+        harness
+            .set_control_message_pause(&pauser, ControlMessage::OverrideGuids, true)
+            .await
+            .unwrap();
+        assert_eq!(
+            harness.oft_store().await.paused_control_messages,
+            vec![ControlMessage::OverrideGuids]
+        );
+
+        // paused messages fail, to be retried once unpaused
+        let message = msg_codec::encode_override_guids(harness.peer_address, &[([5; 32], 0)]);
+        assert_oft_error(
+            harness.lz_receive_message(message.clone(), 1).await,
+            OFTError::ControlMessagePaused,
+        );
+        // token transfers and the other control messages go on
+        harness.send(ld(10), 0).await.unwrap();
+        harness.lz_receive(1_000_000, 2).await.unwrap();
+        harness.challenge_peer().await.unwrap();
+        let nonce = harness.peer().await.control_challenge.unwrap();
+        harness.echo_peer_challenge(nonce).await.unwrap();
+
+        harness
+            .set_control_message_pause(&pauser, ControlMessage::OverrideGuids, false)
+            .await
+            .unwrap();
+        harness.lz_receive_message(message, 1).await.unwrap();
+        assert_eq!(harness.rate_limit_overrides().await.guids.len(), 1);
+
+        harness
+            .set_control_message_pause(&pauser, ControlMessage::PeerChallengeEcho, true)
+            .await
+            .unwrap();
+        harness.challenge_peer().await.unwrap();
+        let nonce = harness.peer().await.control_challenge.unwrap();
+        assert_oft_error(harness.echo_peer_challenge(nonce).await, OFTError::ControlMessagePaused);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_override_expires() {
+        let mut harness = Harness::new().await;
+        harness
+            .set_peer_config(PeerConfigParam::InboundRateLimit(Some(RateLimitParams {
+                refill_per_second: Some(0),
+                capacity: Some(ld(100)),
+            })))
+            .await
+            .unwrap();
+        let clock: Clock = harness.ctx.banks_client.get_sysvar().await.unwrap();
+        let token_dest = harness.token_dest();
+        let params = ManageRateLimitOverrideAddressParams {
+            addresses: vec![token_dest],
+            actions: vec![RateLimitOverrideAction::Add],
+            expires_at: vec![Some(clock.unix_timestamp)],
+            direction: None,
+            multiplier: None,
+            scope: RateLimitOverrideScope::Either,
+        };
+        assert_oft_error(
+            harness.manage_rate_limit_override_with_params(params, None).await,
+            OFTError::InvalidTimestamp,
+        );
+        let params = ManageRateLimitOverrideAddressParams {
+            addresses: vec![token_dest],
+            actions: vec![RateLimitOverrideAction::Add],
+            expires_at: vec![Some(clock.unix_timestamp + 100)],
+            direction: None,
+            multiplier: None,
+            scope: RateLimitOverrideScope::Either,
+        };
+        harness.manage_rate_limit_override_with_params(params, None).await.unwrap();
+        harness.lz_receive(500_000_000, 1).await.unwrap();
+
+        harness.warp(100).await;
+        assert_oft_error(harness.lz_receive(500_000_000, 2).await, OFTError::RateLimitExceeded);
+        assert_eq!(harness.oft_store().await.rate_limit_override.len(), 1);
+        // pruned by the next change to the list
+        let address = Pubkey::new_unique();
+        harness.manage_rate_limit_override(address).await.unwrap();
+        let overrides = harness.rate_limit_overrides().await;
+        assert_eq!(overrides.addresses, vec![address]);
+    }
+
+    #[tokio::test]
+    async fn test_override_cosigner_required_above_threshold() {
+        let mut harness = Harness::new().await;
+        let cosigner = Keypair::new();
+        harness
+            .set_oft_config(SetOFTConfigParams::OverrideCosigners(vec![cosigner.pubkey()]))
+            .await
+            .unwrap();
+        harness
+            .set_oft_config(SetOFTConfigParams::OverrideCosignThreshold(Some(ld(1_000))))
+            .await
+            .unwrap();
+
+        // address overrides are unbounded, so they always exceed the threshold
+        let address = Pubkey::new_unique();
+        assert_oft_error(
+            harness.manage_rate_limit_override(address).await,
+            OFTError::OverrideCosignerRequired,
+        );
+        let stranger = Keypair::new();
+        assert_oft_error(
+            harness.manage_rate_limit_override_cosigned(address, Some(&stranger)).await,
+            OFTError::OverrideCosignerRequired,
+        );
+        harness.manage_rate_limit_override_cosigned(address, Some(&cosigner)).await.unwrap();
+        assert!(harness.oft_store().await.is_rate_limit_override_at(&address, RateLimitDirection::Inbound, 0));
+
+        harness.set_oft_config(SetOFTConfigParams::OverrideCosignThreshold(None)).await.unwrap();
+        harness.manage_rate_limit_override(Pubkey::new_unique()).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_override_approver() {
+        let mut harness = Harness::new().await;
+        let approver = Keypair::new();
+        harness
+            .set_oft_config(SetOFTConfigParams::OverrideApprover(Some(approver.pubkey())))
+            .await
+            .unwrap();
+
+        // the admin alone can no longer change the override lists
+        let address = Pubkey::new_unique();
+        assert_oft_error(
+            harness.manage_rate_limit_override(address).await,
+            OFTError::OverrideApproverRequired,
+        );
+        let params = ManageRateLimitOverrideAddressParams {
+            addresses: vec![address],
+            actions: vec![RateLimitOverrideAction::Add],
+            expires_at: vec![],
+            direction: None,
+            multiplier: None,
+            scope: RateLimitOverrideScope::Either,
+        };
+        let stranger = Keypair::new();
+        assert_oft_error(
+            harness
+                .manage_rate_limit_override_approved(params.clone(), None, Some(&stranger))
+                .await,
+            OFTError::OverrideApproverRequired,
+        );
+        harness.manage_rate_limit_override_approved(params, None, Some(&approver)).await.unwrap();
+        assert_eq!(harness.rate_limit_overrides().await.addresses, vec![address]);
+
+        // nor lift the approval
+        assert_oft_error(
+            harness.set_oft_config(SetOFTConfigParams::OverrideApprover(None)).await,
+            OFTError::OverrideApproverRequired,
+        );
+        harness
+            .set_oft_config_signed(SetOFTConfigParams::OverrideApprover(None), &approver)
+            .await
+            .unwrap();
+        harness.manage_rate_limit_override(Pubkey::new_unique()).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_get_oft_metadata() {
+        let mut harness = Harness::new().await;
+        assert_eq!(harness.oft_metadata().await.metadata, None);
+
+        let metadata =
+            OFTMetadata { label_hash: [1; 32], canonical_eid: 30110, frontend_uri_hash: [2; 32] };
+        harness.set_oft_config(SetOFTConfigParams::Metadata(Some(metadata.clone()))).await.unwrap();
+        let view = harness.oft_metadata().await;
+        assert_eq!(view.version, OFT_METADATA_VIEW_VERSION);
+        assert_eq!(view.metadata, Some(metadata));
+        assert_eq!(view.token_mint, harness.token_mint);
+        assert_eq!(view.ld2sd_rate, 10u64.pow((DECIMALS - SHARED_DECIMALS) as u32));
+    }
+
+    #[tokio::test]
+    async fn test_nonce_gap_monitor() {
+        let mut harness = Harness::new().await;
+        harness.send(ld(10), 0).await.unwrap();
+        harness
+            .set_peer_config(PeerConfigParam::NonceGapMonitor(Some(NonceGapMonitor {
+                max_gap: 2,
+                hold: true,
+            })))
+            .await
+            .unwrap();
+
+        harness.lz_receive(1_000_000, 1).await.unwrap();
+        assert_oft_error(harness.lz_receive(1_000_000, 4).await, OFTError::NonceGapDetected);
+        harness.lz_receive(1_000_000, 3).await.unwrap();
+        harness.lz_receive(1_000_000, 4).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_withdraw_destinations_timelock() {
+        let mut harness = Harness::new().await;
+        harness.manage_fee_vault(ManageFeeVaultParams::Fund(1_000_000)).await.unwrap();
+        let treasury = Pubkey::new_unique();
+        harness
+            .set_withdraw_destinations(SetWithdrawDestinationsParams::Propose(vec![treasury]))
+            .await
+            .unwrap();
+        assert_oft_error(
+            harness.set_withdraw_destinations(SetWithdrawDestinationsParams::Execute).await,
+            OFTError::WithdrawDestinationsTimelocked,
+        );
+        // unrestricted until the change executes
+        harness.manage_fee_vault(ManageFeeVaultParams::Withdraw(100_000)).await.unwrap();
+
+        harness.warp(WITHDRAW_DESTINATIONS_DELAY_SECONDS).await;
+        harness.set_withdraw_destinations(SetWithdrawDestinationsParams::Execute).await.unwrap();
+        assert_eq!(harness.oft_store().await.withdraw_destinations, vec![treasury]);
+        assert_oft_error(
+            harness.manage_fee_vault(ManageFeeVaultParams::Withdraw(100_000)).await,
+            OFTError::WithdrawDestinationNotAllowed,
+        );
+    }
+
+    #[tokio::test]
+    async fn test_burn_from_escrow_after_timelock() {
+        let mut harness = Harness::new().await;
+        harness.send(ld(100), 0).await.unwrap();
+        harness.burn_from_escrow(BurnFromEscrowParams::Propose(ld(40))).await.unwrap();
+        assert_oft_error(
+            harness.burn_from_escrow(BurnFromEscrowParams::Execute).await,
+            OFTError::SupplyAdjustmentTimelocked,
+        );
+
+        harness.warp(SUPPLY_ADJUSTMENT_DELAY_SECONDS).await;
+        harness.burn_from_escrow(BurnFromEscrowParams::Execute).await.unwrap();
+        assert_eq!(harness.oft_store().await.tvl_ld, ld(60));
+        assert_eq!(harness.token_balance(harness.token_escrow).await, ld(60));
+        assert_oft_error(
+            harness.burn_from_escrow(BurnFromEscrowParams::Execute).await,
+            OFTError::NoPendingSupplyAdjustment,
+        );
+    }
+
+    #[tokio::test]
+    async fn test_send_with_fee_vault() {
+        let mut harness = Harness::new().await;
+        harness.manage_fee_vault(ManageFeeVaultParams::Fund(1_000_000)).await.unwrap();
+        let fee_vault = harness.fee_vault();
+        let vault_balance = harness.lamports(fee_vault).await;
+
+        // only whitelisted senders may draw on the vault
+        assert_oft_error(
+            harness.send_with_fee_vault(ld(10), 400_000).await,
+            OFTError::Unauthorized,
+        );
+
+        let user = harness.user.pubkey();
+        harness.set_oft_config(SetOFTConfigParams::FeeVaultSenders(vec![user])).await.unwrap();
+        harness.send_with_fee_vault(ld(10), 400_000).await.unwrap();
+        assert_eq!(harness.lamports(fee_vault).await, vault_balance - 400_000);
+
+        assert_oft_error(
+            harness.send_with_fee_vault(ld(10), 700_000).await,
+            OFTError::InsufficientFeeVaultBalance,
+        );
+        assert_oft_error(
+            harness.manage_fee_vault(ManageFeeVaultParams::Withdraw(700_000)).await,
+            OFTError::InsufficientFeeVaultBalance,
+        );
+        harness.manage_fee_vault(ManageFeeVaultParams::Withdraw(600_000)).await.unwrap();
+        assert_eq!(harness.lamports(fee_vault).await, vault_balance - 1_000_000);
+    }
+
+    #[tokio::test]
+    async fn test_crank_expires_proposal_and_pays_bounty() {
+        let mut harness = Harness::new().await;
+        let user = harness.user.pubkey();
+        harness.set_oft_config(SetOFTConfigParams::DefaultFee(100)).await.unwrap();
+        harness.set_oft_config(SetOFTConfigParams::OverrideManager(Some(user))).await.unwrap();
+        harness.set_oft_config(SetOFTConfigParams::OverrideApprovalThreshold(Some(0))).await.unwrap();
+        harness.set_oft_config(SetOFTConfigParams::CrankBounty(Some(ld(1)))).await.unwrap();
+        harness.send(ld(1_000), 0).await.unwrap();
+
+        let target = RateLimitOverrideTarget::Address(Pubkey::new_unique());
+        let proposal = harness.proposal(&target);
+        harness.propose_rate_limit_override(target, ld(100)).await.unwrap();
+        assert_oft_error(harness.crank_expire_proposal(proposal).await, OFTError::CrankTaskNotDue);
+
+        harness.warp(RATE_LIMIT_OVERRIDE_PROPOSAL_WINDOW_SECONDS + 1).await;
+        let balance = harness.token_balance(harness.token_source).await;
+        harness.crank_expire_proposal(proposal).await.unwrap();
+        assert!(harness.ctx.banks_client.get_account(proposal).await.unwrap().is_none());
+        assert_eq!(harness.token_balance(harness.token_source).await, balance + ld(1));
+        let oft_store = harness.oft_store().await;
+        assert_eq!(harness.token_balance(harness.token_escrow).await - oft_store.tvl_ld, ld(9));
+    }
+
+    #[tokio::test]
+    async fn test_crank_streams_fees() {
+        let mut harness = Harness::new().await;
+        harness.set_oft_config(SetOFTConfigParams::DefaultFee(100)).await.unwrap();
+        harness.send(ld(1_000), 0).await.unwrap();
+
+        let recipient = harness.token_source;
+        let fee_stream =
+            FeeStreamParams { recipient, period_seconds: 1_000, min_interval_seconds: 100 };
+        harness.set_oft_config(SetOFTConfigParams::FeeStream(Some(fee_stream))).await.unwrap();
+        assert_oft_error(harness.crank_stream_fees(recipient).await, OFTError::CrankTaskNotDue);
+        assert_oft_error(
+            harness.crank_stream_fees(harness.token_escrow).await,
+            OFTError::InvalidCrankTask,
+        );
+
+        // half the fees after half the period, the rest once the period is over
+        let balance = harness.token_balance(recipient).await;
+        harness.warp(500).await;
+        harness.crank_stream_fees(recipient).await.unwrap();
+        assert_eq!(harness.token_balance(recipient).await, balance + ld(5));
+        harness.warp(2_000).await;
+        harness.crank_stream_fees(recipient).await.unwrap();
+        assert_eq!(harness.token_balance(recipient).await, balance + ld(10));
+
+        for (period_seconds, min_interval_seconds) in [(0, 0), (1_000, 0), (1_000, 1_001)] {
+            let fee_stream = FeeStreamParams { recipient, period_seconds, min_interval_seconds };
+            assert_oft_error(
+                harness.set_oft_config(SetOFTConfigParams::FeeStream(Some(fee_stream))).await,
+                OFTError::InvalidFeeStream,
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_crank_streams_fees_back_to_back() {
+        let mut harness = Harness::new().await;
+        harness.set_oft_config(SetOFTConfigParams::DefaultFee(100)).await.unwrap();
+        harness.set_oft_config(SetOFTConfigParams::CrankBounty(Some(ld(1)))).await.unwrap();
+        harness.send(ld(1_000), 0).await.unwrap();
+
+        // the keeper is the recipient here, so both are paid into the same account
+        let recipient = harness.token_source;
+        let fee_stream =
+            FeeStreamParams { recipient, period_seconds: 1_000, min_interval_seconds: 100 };
+        harness.set_oft_config(SetOFTConfigParams::FeeStream(Some(fee_stream))).await.unwrap();
+        harness.warp(500).await;
+        let balance = harness.token_balance(recipient).await;
+        harness.crank_stream_fees(recipient).await.unwrap();
+
+        // the bounty came out of the half vested, the unvested half is still accrued
+        assert_eq!(harness.token_balance(recipient).await, balance + ld(5));
+        assert_eq!(harness.oft_store().await.accrued_fees_ld, ld(5));
+
+        // cranking again before min_interval_seconds pays nothing
+        for _ in 0..3 {
+            harness.warp(1).await;
+            assert_oft_error(harness.crank_stream_fees(recipient).await, OFTError::CrankTaskNotDue);
+        }
+        assert_eq!(harness.token_balance(recipient).await, balance + ld(5));
+        assert_eq!(harness.oft_store().await.accrued_fees_ld, ld(5));
+
+        // a tenth of what is left vests over the interval, all of it going to the bounty
+        harness.warp(97).await;
+        harness.crank_stream_fees(recipient).await.unwrap();
+        assert_eq!(harness.token_balance(recipient).await, balance + ld(5) + ld(5) / 10);
+        assert_eq!(harness.oft_store().await.accrued_fees_ld, ld(5) - ld(5) / 10);
+    }
+
+    #[tokio::test]
+    async fn test_withdraw_fees_to_treasury() {
+        let mut harness = Harness::new().await;
+        harness.set_oft_config(SetOFTConfigParams::DefaultFee(100)).await.unwrap();
+        harness.send(ld(1_000), 0).await.unwrap();
+        assert_eq!(harness.oft_store().await.accrued_fees_ld, ld(10));
+
+        let treasury = harness.token_source;
+        let admin = harness.admin.insecure_clone();
+        assert_oft_error(
+            harness.withdraw_fees(&admin, treasury, ld(5)).await,
+            OFTError::TreasuryNotSet,
+        );
+        harness.set_oft_config(SetOFTConfigParams::Treasury(Some(treasury))).await.unwrap();
+
+        // the fee collector can pay the treasury up to the accrued fees, never the TVL
+        let collector = Keypair::new();
+        assert_oft_error(
+            harness.withdraw_fees(&collector, treasury, ld(5)).await,
+            OFTError::Unauthorized,
+        );
+        let fee_collector = SetOFTConfigParams::FeeCollector(Some(collector.pubkey()));
+        harness.set_oft_config(fee_collector).await.unwrap();
+        let balance = harness.token_balance(treasury).await;
+        harness.withdraw_fees(&collector, treasury, ld(6)).await.unwrap();
+        assert_eq!(harness.token_balance(treasury).await, balance + ld(6));
+        let oft_store = harness.oft_store().await;
+        assert_eq!((oft_store.accrued_fees_ld, oft_store.tvl_ld), (ld(4), ld(990)));
+        assert_oft_error(
+            harness.withdraw_fees(&collector, treasury, ld(5)).await,
+            OFTError::InsufficientAccruedFees,
+        );
+    }
+
+    #[tokio::test]
+    async fn test_withdraw_fee_drains_legacy_fees() {
+        let mut harness = Harness::new().await;
+        harness.send(ld(1_000), 0).await.unwrap();
+        // fees collected before the store was migrated sit in the escrow, uncounted
+        let legacy_fees = spl_token::instruction::transfer(
+            &spl_token::ID,
+            &harness.token_source,
+            &harness.token_escrow,
+            &harness.user.pubkey(),
+            &[],
+            ld(10),
+        )
+        .unwrap();
+        let user = harness.user.insecure_clone();
+        harness.process(&[legacy_fees], &[&user]).await.unwrap();
+        assert_eq!(harness.oft_store().await.accrued_fees_ld, 0);
+
+        let treasury = harness.token_source;
+        harness.set_oft_config(SetOFTConfigParams::Treasury(Some(treasury))).await.unwrap();
+        let admin = harness.admin.insecure_clone();
+        assert_oft_error(
+            harness.withdraw_fees(&admin, treasury, ld(10)).await,
+            OFTError::InsufficientAccruedFees,
+        );
+
+        // withdraw_fee drains them, still never touching the TVL
+        assert_oft_error(harness.withdraw_fee(treasury, ld(11)).await, OFTError::InvalidFee);
+        let balance = harness.token_balance(treasury).await;
+        harness.withdraw_fee(treasury, ld(10)).await.unwrap();
+        assert_eq!(harness.token_balance(treasury).await, balance + ld(10));
+        let oft_store = harness.oft_store().await;
+        assert_eq!((oft_store.accrued_fees_ld, oft_store.tvl_ld), (0, ld(1_000)));
+    }
+
+    #[tokio::test]
+    async fn test_peer_silence_monitor() {
+        let mut harness = Harness::new().await;
+        harness.send(ld(10), 0).await.unwrap();
+        assert_oft_error(harness.crank_report_peer_silence().await, OFTError::CrankTaskNotDue);
+        harness.set_peer_config(PeerConfigParam::SilenceMonitor(Some(3_600))).await.unwrap();
+        assert!(!harness.get_peer_health().await.silent);
+
+        // silence is counted from when the monitor was set for peers never heard from
+        harness.warp(3_601).await;
+        assert!(harness.get_peer_health().await.silent);
+        harness.crank_report_peer_silence().await.unwrap();
+        // reported once per silence
+        assert_oft_error(harness.crank_report_peer_silence().await, OFTError::CrankTaskNotDue);
+
+        harness.lz_receive(1_000_000, 1).await.unwrap();
+        let health = harness.get_peer_health().await;
+        assert!(!health.silent);
+        assert_eq!(health.highest_inbound_nonce, 1);
+        harness.warp(3_601).await;
+        harness.crank_report_peer_silence().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_send_enforces_min_dst_gas() {
+        let mut harness = Harness::new().await;
+        let min_dst_gas = MinDstGas { send: 200_000, send_and_call: 500_000 };
+        harness.set_peer_config(PeerConfigParam::MinDstGas(Some(min_dst_gas.clone()))).await.unwrap();
+        assert_eq!(harness.peer().await.min_dst_gas, Some(min_dst_gas));
+
+        assert_oft_error(harness.send(ld(10), 0).await, OFTError::InsufficientDstGas);
+        assert_oft_error(
+            harness.send_with_options(ld(10), 0, lz_receive_options(199_999)).await,
+            OFTError::InsufficientDstGas,
+        );
+        harness.send_with_options(ld(10), 0, lz_receive_options(200_000)).await.unwrap();
+
+        harness.set_peer_config(PeerConfigParam::MinDstGas(None)).await.unwrap();
+        harness.send(ld(10), 0).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_quote_oft_estimates_destination_fee() {
+        let mut harness = Harness::new().await;
+        let quote = harness.quote_oft(ld(10), PRIORITY_STANDARD).await;
+        assert_eq!(quote.estimated_dst_amount_ld, quote.oft_receipt.amount_received_ld);
+        assert!(quote.oft_fee_details.is_empty());
+
+        harness.set_peer_config(PeerConfigParam::RemoteFeeBps(Some(100))).await.unwrap();
+        let quote = harness.quote_oft(ld(10), PRIORITY_STANDARD).await;
+        assert_eq!(quote.oft_receipt.amount_received_ld, ld(10));
+        assert_eq!(quote.estimated_dst_amount_ld, ld(10) - ld(10) / 100);
+        assert_eq!(quote.oft_fee_details.last().unwrap().fee_amount_ld, ld(10) / 100);
+    }
+
+    #[tokio::test]
+    async fn test_quote_oft_reports_blockers() {
+        let mut harness = Harness::new().await;
+        assert!(harness.quote_oft(ld(10), PRIORITY_STANDARD).await.blockers.is_empty());
+
+        harness.set_oft_config(SetOFTConfigParams::Paused(true)).await.unwrap();
+        let config = SetOFTConfigParams::AllowedDstEids(vec![REMOTE_EID + 1]);
+        harness.set_oft_config(config).await.unwrap();
+        let rate_limit = RateLimitParams { refill_per_second: Some(0), capacity: Some(ld(5)) };
+        harness.set_outbound_rate_limit(Some(rate_limit)).await.unwrap();
+        // quotes still go through, listing every policy the send would fail on
+        let quote = harness.quote_oft(ld(10), PRIORITY_STANDARD).await;
+        assert_eq!(quote.oft_receipt.amount_received_ld, ld(10));
+        assert_eq!(
+            quote.blockers,
+            vec![
+                SendBlocker::Paused,
+                SendBlocker::DstEidNotAllowed,
+                SendBlocker::RateLimitExceeded { available_ld: ld(5) },
+            ]
+        );
+        assert_eq!(harness.quote_oft(ld(5), PRIORITY_STANDARD).await.blockers.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_quote_oft_limits() {
+        let mut harness = Harness::new().await;
+        let limits = harness.quote_oft(ld(10), PRIORITY_STANDARD).await.oft_limits;
+        assert_eq!(limits.min_amount_ld, ld(1) / 1_000_000); // one unit in shared decimals
+        assert_eq!(limits.max_amount_ld, u64::MAX);
+
+        let rate_limit = RateLimitParams { refill_per_second: Some(0), capacity: Some(ld(5)) };
+        harness.set_outbound_rate_limit(Some(rate_limit)).await.unwrap();
+        harness.set_peer_config(PeerConfigParam::ReservedCapacity(ld(1))).await.unwrap();
+        let limits = harness.quote_oft(ld(10), PRIORITY_STANDARD).await.oft_limits;
+        assert_eq!(limits.max_amount_ld, ld(4));
+        harness.send(ld(3), 0).await.unwrap();
+        let limits = harness.quote_oft(ld(10), PRIORITY_STANDARD).await.oft_limits;
+        assert_eq!(limits.max_amount_ld, ld(1));
+    }
+
+    #[tokio::test]
+    async fn test_priority_tiers() {
+        let mut harness = Harness::new().await;
+        let fast = PriorityTier { tier: 1, options: lz_receive_options(300_000), sla_seconds: 30 };
+        harness.set_peer_config(PeerConfigParam::PriorityTiers(vec![fast])).await.unwrap();
+        let min_dst_gas = MinDstGas { send: 200_000, send_and_call: 200_000 };
+        harness.set_peer_config(PeerConfigParam::MinDstGas(Some(min_dst_gas))).await.unwrap();
+
+        assert_eq!(harness.quote_oft(ld(10), PRIORITY_STANDARD).await.delivery_sla_seconds, None);
+        assert_eq!(harness.quote_oft(ld(10), 1).await.delivery_sla_seconds, Some(30));
+
+        let mut params = SendParams {
+            dst_eid: REMOTE_EID,
+            to: [1; 32],
+            amount_ld: ld(10),
+            min_amount_ld: 0,
+            options: vec![],
+            compose_msg: None,
+            native_fee: 0,
+            lz_token_fee: 0,
+            flow_tag: oft::policy::FLOW_TAG_USER,
+            priority: PRIORITY_STANDARD,
+            idempotency_key: None,
+        };
+        assert_oft_error(harness.send_with_params(params.clone()).await, OFTError::InsufficientDstGas);
+        params.priority = 2;
+        assert_oft_error(harness.send_with_params(params.clone()).await, OFTError::UnknownPriorityTier);
+        // the tier's options carry enough gas on their own
+        params.priority = 1;
+        harness.send_with_params(params).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_check_remote_config() {
+        let mut harness = Harness::new().await;
+        let expected =
+            RemoteConfig { fee_bps: 10, rate_limit_capacity: ld(1_000), rate_limit_refill_per_second: 1 };
+        harness
+            .set_peer_config(PeerConfigParam::ExpectedRemoteConfigHash(Some(expected.hash())))
+            .await
+            .unwrap();
+
+        assert!(harness.check_remote_config(expected.clone()).await);
+        assert!(!harness.check_remote_config(RemoteConfig { fee_bps: 20, ..expected }).await);
+    }
+
+    #[tokio::test]
+    async fn test_inbound_opt_out_holds_credit_until_claimed() {
+        let mut harness = Harness::new().await;
+        harness.send(ld(10), 0).await.unwrap();
+        harness.register_inbound_opt_out().await.unwrap();
+
+        let pending_credit = harness.pending_credit_address([1; 32]).await;
+        assert_eq!(pending_credit, harness.pending_credit(&harness.user.pubkey(), &[1; 32]));
+
+        harness.lz_receive(1_000_000, 1).await.unwrap();
+        let token_dest = harness.token_dest();
+        assert_eq!(harness.token_balance(token_dest).await, 0);
+        assert!(harness.ctx.banks_client.get_account(pending_credit).await.unwrap().is_some());
+
+        // only the recipient can claim
+        let (admin, user) = (harness.admin.insecure_clone(), harness.user.insecure_clone());
+        assert_oft_error(
+            harness.claim_pending_credit([1; 32], &admin).await,
+            OFTError::Unauthorized,
+        );
+        harness.claim_pending_credit([1; 32], &user).await.unwrap();
+        assert_eq!(harness.token_balance(token_dest).await, ld(1));
+        assert!(harness.ctx.banks_client.get_account(pending_credit).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_queue_over_limit_credits() {
+        let mut harness = Harness::new().await;
+        harness.send(ld(20), 0).await.unwrap();
+        let rate_limit = RateLimitParams { refill_per_second: Some(ld(1)), capacity: Some(ld(10)) };
+        harness.set_inbound_rate_limit(Some(rate_limit)).await.unwrap();
+        harness.set_oft_config(SetOFTConfigParams::QueueOverLimitCredits(true)).await.unwrap();
+        harness.lz_receive(6_000_000, 1).await.unwrap();
+        let token_dest = harness.token_dest();
+        let balance = harness.token_balance(token_dest).await;
+
+        // queued instead of failing, until the limit has refilled enough to charge it
+        harness.lz_receive(8_000_000, 2).await.unwrap();
+        assert_eq!(harness.token_balance(token_dest).await, balance);
+        let user = harness.user.insecure_clone();
+        assert_oft_error(
+            harness.claim_pending_credit([2; 32], &user).await,
+            OFTError::InvalidPendingCredit,
+        );
+        assert_oft_error(
+            harness.execute_pending_credit([2; 32]).await,
+            OFTError::RateLimitExceeded,
+        );
+        harness.warp(4).await;
+        harness.execute_pending_credit([2; 32]).await.unwrap();
+        assert_eq!(harness.token_balance(token_dest).await, balance + ld(8));
+        assert_eq!(harness.peer().await.inbound_rate_limiter.unwrap().tokens, 0);
+        let pending_credit = harness.pending_credit(&harness.user.pubkey(), &[2; 32]);
+        assert!(harness.ctx.banks_client.get_account(pending_credit).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_breach_monitor_pauses_peer() {
+        let mut harness = Harness::new().await;
+        harness.send(ld(20), 0).await.unwrap();
+        let rate_limit = RateLimitParams { refill_per_second: Some(0), capacity: Some(ld(10)) };
+        harness.set_inbound_rate_limit(Some(rate_limit)).await.unwrap();
+        harness.set_oft_config(SetOFTConfigParams::QueueOverLimitCredits(true)).await.unwrap();
+        let monitor =
+            BreachMonitor { max_breaches: 1, window_seconds: 0, window_start: 0, breaches: 0 };
+        assert_oft_error(
+            harness.set_peer_config(PeerConfigParam::BreachMonitor(Some(monitor.clone()))).await,
+            OFTError::InvalidBreachMonitor,
+        );
+        let monitor = BreachMonitor { window_seconds: 3_600, ..monitor };
+        harness.set_peer_config(PeerConfigParam::BreachMonitor(Some(monitor))).await.unwrap();
+
+        // the second credit queued over the limit within the window pauses the peer
+        harness.lz_receive(6_000_000, 1).await.unwrap();
+        harness.lz_receive(8_000_000, 2).await.unwrap();
+        assert!(!harness.peer().await.paused);
+        harness.lz_receive(8_000_000, 3).await.unwrap();
+        let peer = harness.peer().await;
+        assert!(peer.paused);
+        assert_eq!(peer.breach_monitor.unwrap().breaches, 2);
+        assert_oft_error(harness.lz_receive(1_000_000, 4).await, OFTError::PeerPaused);
+        assert_oft_error(harness.send(ld(1), 0).await, OFTError::PeerPaused);
+
+        harness.set_peer_config(PeerConfigParam::Paused(false)).await.unwrap();
+        assert_eq!(harness.peer().await.breach_monitor.unwrap().breaches, 0);
+        harness.send(ld(1), 0).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_breach_monitor_pauses_default_config_peer() {
+        let mut harness = Harness::new().await;
+        harness.send(ld(20), 0).await.unwrap();
+        let rate_limit = RateLimitParams { refill_per_second: Some(0), capacity: Some(ld(10)) };
+        harness.set_inbound_rate_limit(Some(rate_limit)).await.unwrap();
+        assert!(!harness.oft_store().await.queue_over_limit_credits);
+
+        // without a monitor, over-limit credits revert as before
+        assert_oft_error(harness.lz_receive(11_000_000, 1).await, OFTError::RateLimitExceeded);
+
+        let monitor =
+            BreachMonitor { max_breaches: 1, window_seconds: 3_600, window_start: 0, breaches: 0 };
+        harness.set_peer_config(PeerConfigParam::BreachMonitor(Some(monitor))).await.unwrap();
+
+        // with one, they are held instead and counted
+        harness.lz_receive(11_000_000, 2).await.unwrap();
+        assert!(!harness.peer().await.paused);
+        assert_eq!(harness.token_balance(harness.token_dest()).await, 0);
+        harness.lz_receive(11_000_000, 3).await.unwrap();
+        let peer = harness.peer().await;
+        assert!(peer.paused);
+        assert_eq!(peer.breach_monitor.unwrap().breaches, 2);
+        assert_oft_error(harness.lz_receive(1_000_000, 4).await, OFTError::PeerPaused);
+    }
+
+    #[tokio::test]
+    async fn test_notification_hook_records_inbound_transfers() {
+        let mut harness = Harness::new().await;
+        harness.send(ld(10), 0).await.unwrap();
+        // unregistered recipients receive as before
+        harness.lz_receive(1_000_000, 1).await.unwrap();
+        assert!(harness.read_notification_hook().await.is_none());
+
+        harness.register_notification_hook().await.unwrap();
+        harness.lz_receive(2_000_000, 2).await.unwrap();
+        let hook = harness.read_notification_hook().await.unwrap();
+        assert_eq!(hook.count, 1);
+        let last = hook.last.unwrap();
+        assert_eq!((last.guid, last.src_eid), ([2; 32], REMOTE_EID));
+        assert_eq!((last.amount_ld, last.credited_ld), (ld(2), ld(2)));
+
+        // held transfers are recorded with nothing credited yet
+        harness.register_inbound_opt_out().await.unwrap();
+        harness.lz_receive(3_000_000, 3).await.unwrap();
+        let hook = harness.read_notification_hook().await.unwrap();
+        assert_eq!(hook.count, 2);
+        let last = hook.last.unwrap();
+        assert_eq!((last.amount_ld, last.credited_ld), (ld(3), 0));
+    }
+
+    #[tokio::test]
+    async fn test_peer_probation_caps_and_holds_transfers() {
+        let mut harness = Harness::new().await;
+        let policy = ProbationPolicy {
+            probation_seconds: 3600,
+            max_amount_ld: ld(100),
+            hold_above_ld: ld(10),
+        };
+        harness.set_oft_config(SetOFTConfigParams::ProbationPolicy(Some(policy))).await.unwrap();
+        let clock: Clock = harness.ctx.banks_client.get_sysvar().await.unwrap();
+        harness
+            .set_peer_config(PeerConfigParam::ProbationEndsAt(clock.unix_timestamp + 3600))
+            .await
+            .unwrap();
+
+        assert_oft_error(harness.send(ld(200), 0).await, OFTError::ProbationLimitExceeded);
+        harness.send(ld(100), 0).await.unwrap();
+
+        let token_dest = harness.token_dest();
+        harness.lz_receive(5_000_000, 1).await.unwrap();
+        assert_eq!(harness.token_balance(token_dest).await, ld(5));
+        harness.lz_receive(20_000_000, 2).await.unwrap();
+        assert_eq!(harness.token_balance(token_dest).await, ld(5));
+        let pending_credit = harness.pending_credit(&harness.user.pubkey(), &[2; 32]);
+        assert!(harness.ctx.banks_client.get_account(pending_credit).await.unwrap().is_some());
+
+        harness.warp(3600).await;
+        harness.lz_receive(20_000_000, 3).await.unwrap();
+        assert_eq!(harness.token_balance(token_dest).await, ld(25));
+    }
+
+    #[tokio::test]
+    async fn test_source_sender_allowlist() {
+        let mut harness = Harness::new().await;
+        harness.send(ld(10), 0).await.unwrap();
+        let mut allowlist = SourceSenderAllowlist { senders: vec![[7; 32]], quarantine: false };
+        harness
+            .set_peer_config(PeerConfigParam::SourceSenderAllowlist(Some(allowlist.clone())))
+            .await
+            .unwrap();
+
+        let to_address = harness.user.pubkey().to_bytes();
+        let rogue = msg_codec::encode(to_address, 1_000_000, Pubkey::new_unique(), &Some(vec![1]));
+        assert_oft_error(
+            harness.lz_receive_message(rogue.clone(), 1).await,
+            OFTError::SourceSenderNotAllowed,
+        );
+        // plain transfers carry no source sender
+        harness.lz_receive(1_000_000, 1).await.unwrap();
+        assert_eq!(harness.token_balance(harness.token_dest()).await, ld(1));
+
+        allowlist.quarantine = true;
+        harness
+            .set_peer_config(PeerConfigParam::SourceSenderAllowlist(Some(allowlist)))
+            .await
+            .unwrap();
+        harness.lz_receive_message(rogue, 2).await.unwrap();
+        assert_eq!(harness.token_balance(harness.token_dest()).await, ld(1));
+        let pending_credit = harness.pending_credit(&harness.user.pubkey(), &[2; 32]);
+        assert!(harness.ctx.banks_client.get_account(pending_credit).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_compose_to_unlisted_composer_is_held() {
+        let mut harness = Harness::new().await;
+        harness.send(ld(10), 0).await.unwrap();
+        let composer = Pubkey::new_unique();
+        harness
+            .set_oft_config(SetOFTConfigParams::AllowedComposers(vec![composer]))
+            .await
+            .unwrap();
+
+        // plain transfers are not composed into anything
+        harness.lz_receive(1_000_000, 1).await.unwrap();
+        assert_eq!(harness.token_balance(harness.token_dest()).await, ld(1));
+
+        let to_address = harness.user.pubkey().to_bytes();
+        let compose_msg = Some(vec![1]);
+        let composed = msg_codec::encode(to_address, 1_000_000, Pubkey::new_unique(), &compose_msg);
+        harness.lz_receive_message(composed, 2).await.unwrap();
+        assert_eq!(harness.token_balance(harness.token_dest()).await, ld(1));
+        let pending_credit = harness.pending_credit(&harness.user.pubkey(), &[2; 32]);
+        let account = harness.ctx.banks_client.get_account(pending_credit).await.unwrap();
+        assert!(account.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_executor_allowlist() {
+        let mut harness = Harness::new().await;
+        harness.send(ld(10), 0).await.unwrap();
+        let executor = Pubkey::new_unique();
+        harness.set_peer_config(PeerConfigParam::ExecutorAllowlist(vec![executor])).await.unwrap();
+
+        // skipped without failing, and without recording the nonce
+        harness.lz_receive(1_000_000, 1).await.unwrap();
+        assert_eq!(harness.token_balance(harness.token_dest()).await, 0);
+        assert_eq!(harness.peer().await.highest_inbound_nonce, 0);
+
+        let payer = harness.ctx.payer.pubkey();
+        let executors = vec![executor, payer];
+        harness.set_peer_config(PeerConfigParam::ExecutorAllowlist(executors)).await.unwrap();
+        harness.lz_receive(1_000_000, 1).await.unwrap();
+        assert_eq!(harness.token_balance(harness.token_dest()).await, ld(1));
+    }
+
+    #[tokio::test]
+    async fn test_held_credit_refund_to_recipient() {
+        let mut harness = Harness::new().await;
+        let refund = SetOFTConfigParams::HeldCreditRefund(HeldCreditRefund::Recipient);
+        harness.set_oft_config(refund).await.unwrap();
+        harness.send(ld(10), 0).await.unwrap();
+        harness.register_inbound_opt_out().await.unwrap();
+        harness.lz_receive(1_000_000, 1).await.unwrap();
+
+        let user = harness.user.insecure_clone();
+        let rent = harness.lamports(harness.pending_credit(&user.pubkey(), &[1; 32])).await;
+        let user_before = harness.lamports(user.pubkey()).await;
+        harness.claim_pending_credit([1; 32], &user).await.unwrap();
+        assert_eq!(harness.lamports(user.pubkey()).await, user_before + rent);
+    }
+
+    #[tokio::test]
+    async fn test_inbound_split_vests_remainder() {
+        let mut harness = Harness::new().await;
+        let split = InboundSplit {
+            threshold_ld: ld(50),
+            immediate_bps: 2_500,
+            epoch_seconds: 3600,
+            epochs: 3,
+        };
+        harness.set_peer_config(PeerConfigParam::InboundSplit(Some(split))).await.unwrap();
+        harness.send(ld(200), 0).await.unwrap();
+
+        let token_dest = harness.token_dest();
+        harness.lz_receive(40_000_000, 1).await.unwrap();
+        assert_eq!(harness.token_balance(token_dest).await, ld(40));
+
+        harness.lz_receive(120_000_000, 2).await.unwrap();
+        assert_eq!(harness.token_balance(token_dest).await, ld(70));
+
+        let user = harness.user.insecure_clone();
+        let result = harness.claim_pending_credit([2; 32], &user).await;
+        assert_oft_error(result, OFTError::NothingVested);
+        harness.warp(3600).await;
+        harness.claim_pending_credit([2; 32], &user).await.unwrap();
+        assert_eq!(harness.token_balance(token_dest).await, ld(100));
+
+        harness.warp(2 * 3600).await;
+        harness.claim_pending_credit([2; 32], &user).await.unwrap();
+        assert_eq!(harness.token_balance(token_dest).await, ld(160));
+        let pending_credit = harness.pending_credit(&user.pubkey(), &[2; 32]);
+        assert!(harness.ctx.banks_client.get_account(pending_credit).await.unwrap().is_none());
+    }
+
+    #[cfg(not(feature = "test-mode"))]
+    #[tokio::test]
+    async fn test_test_mode_unavailable() {
+        let mut harness = Harness::new().await;
+        assert_oft_error(
+            harness.set_oft_config(SetOFTConfigParams::TestMode(true)).await,
+            OFTError::TestModeUnavailable,
+        );
+        harness.set_oft_config(SetOFTConfigParams::TestMode(false)).await.unwrap();
+        assert!(!harness.oft_store().await.is_test_mode());
+    }
+
+    #[cfg(feature = "test-mode")]
+    #[tokio::test]
+    async fn test_test_mode_mints_and_lifts_inbound_limits() {
+        let mut harness = Harness::with_store(OFTType::Native).await;
+        let token_dest = harness.token_source;
+        assert_oft_error(harness.test_mint(token_dest, ld(5)).await, OFTError::TestModeDisabled);
+        harness.set_oft_config(SetOFTConfigParams::TestMode(true)).await.unwrap();
+        let balance = harness.token_balance(token_dest).await;
+        harness.test_mint(token_dest, ld(5)).await.unwrap();
+        assert_eq!(harness.token_balance(token_dest).await, balance + ld(5));
+
+        // an adapter can only unlock the escrow, so it has no faucet, but its limits are lifted
+        let mut harness = Harness::new().await;
+        harness.send(ld(20), 0).await.unwrap();
+        let rate_limit = RateLimitParams { refill_per_second: Some(0), capacity: Some(ld(1)) };
+        harness.set_inbound_rate_limit(Some(rate_limit)).await.unwrap();
+        harness.set_oft_config(SetOFTConfigParams::TestMode(true)).await.unwrap();
+        let token_dest = harness.token_source;
+        assert_oft_error(
+            harness.test_mint(token_dest, ld(5)).await,
+            OFTError::InvalidMintAuthority,
+        );
+        harness.lz_receive(10_000_000, 1).await.unwrap();
+        harness.set_oft_config(SetOFTConfigParams::TestMode(false)).await.unwrap();
+        assert_oft_error(harness.lz_receive(10_000_000, 2).await, OFTError::RateLimitExceeded);
+    }
+
+    #[tokio::test]
+    async fn test_tvl_drop_alert() {
+        let mut harness = Harness::new().await;
+        let alert = TvlAlert { drop_bps: 2_000, window_seconds: 86_400 };
+        harness.set_oft_config(SetOFTConfigParams::TvlAlert(Some(alert))).await.unwrap();
+        harness.send(ld(100), 0).await.unwrap();
+
+        harness.lz_receive(20_000_000, 1).await.unwrap();
+        assert!(!harness.oft_store().await.tvl_watermarks.alerted);
+        harness.lz_receive(1_000_000, 2).await.unwrap();
+        let watermarks = harness.oft_store().await.tvl_watermarks;
+        assert!(watermarks.alerted);
+        assert_eq!((watermarks.high_ld, watermarks.low_ld), (ld(100), ld(79)));
+    }
+
+    #[tokio::test]
+    async fn test_send_idempotency_key() {
+        let mut harness = Harness::new().await;
+        let params = SendParams {
+            dst_eid: REMOTE_EID,
+            to: [1; 32],
+            amount_ld: ld(10),
+            min_amount_ld: 0,
+            options: vec![],
+            compose_msg: None,
+            native_fee: 0,
+            lz_token_fee: 0,
+            flow_tag: oft::policy::FLOW_TAG_USER,
+            priority: PRIORITY_STANDARD,
+            idempotency_key: Some([3; 32]),
+        };
+        harness.send_with_params(params.clone()).await.unwrap();
+        assert_oft_error(harness.send_with_params(params.clone()).await, OFTError::DuplicateSend);
+        assert_oft_error(harness.close_send_record(&[3; 32]).await, OFTError::DuplicateSend);
+        harness
+            .send_with_params(SendParams { idempotency_key: Some([4; 32]), ..params.clone() })
+            .await
+            .unwrap();
+
+        harness.warp(SEND_RECORD_TTL_SECONDS).await;
+        harness.send_with_params(params).await.unwrap();
+        harness.warp(SEND_RECORD_TTL_SECONDS).await;
+        let user = harness.user.pubkey();
+        let before = harness.lamports(user).await;
+        harness.close_send_record(&[3; 32]).await.unwrap();
+        assert!(harness.lamports(user).await > before);
+    }
+
+    #[tokio::test]
+    async fn test_top_up_and_withdraw_surplus_lamports() {
+        let mut harness = Harness::new().await;
+        let peer = harness.peer;
+        let peer_before = harness.lamports(peer).await;
+        harness.top_up_lamports(Some(REMOTE_EID), 5_000).await.unwrap();
+        harness.top_up_lamports(None, 3_000).await.unwrap();
+        assert_eq!(harness.lamports(peer).await, peer_before + 5_000);
+        assert_eq!(harness.oft_store().await.contributed_lamports, 8_000);
+
+        // accounts are created rent exempt, so only the top up is surplus
+        assert_oft_error(
+            harness.withdraw_surplus_lamports(Some(REMOTE_EID), 5_001).await,
+            OFTError::InsufficientSurplusLamports,
+        );
+        let admin = harness.admin.pubkey();
+        let admin_before = harness.lamports(admin).await;
+        harness.withdraw_surplus_lamports(Some(REMOTE_EID), 5_000).await.unwrap();
+        harness.withdraw_surplus_lamports(None, 3_000).await.unwrap();
+        assert_eq!(harness.lamports(peer).await, peer_before);
+        assert_eq!(harness.lamports(admin).await, admin_before + 8_000);
+    }
+
+    #[tokio::test]
+    async fn test_transfer_receipts() {
+        let mut harness = Harness::new().await;
+        let policy = TransferReceiptPolicy { min_amount_ld: ld(50), retention_seconds: 3600 };
+        harness.set_oft_config(SetOFTConfigParams::TransferReceipts(Some(policy))).await.unwrap();
+        let params = SendParams {
+            dst_eid: REMOTE_EID,
+            to: [1; 32],
+            amount_ld: ld(10),
+            min_amount_ld: 0,
+            options: vec![],
+            compose_msg: None,
+            native_fee: 0,
+            lz_token_fee: 0,
+            flow_tag: oft::policy::FLOW_TAG_USER,
+            priority: PRIORITY_STANDARD,
+            idempotency_key: None,
+        };
+        harness.send_with_params(params.clone()).await.unwrap();
+        assert!(harness.read_transfer_receipt(&harness.outbound_guid(&params)).await.is_none());
+
+        let params = SendParams { amount_ld: ld(100), ..params };
+        harness.send_with_params(params.clone()).await.unwrap();
+        let guid = harness.outbound_guid(&params);
+        let receipt = harness.read_transfer_receipt(&guid).await.unwrap();
+        assert!(receipt.outbound);
+        assert_eq!(receipt.remote_eid, REMOTE_EID);
+        assert_eq!(receipt.recipient, [1; 32]);
+        assert_eq!(receipt.amount_ld, ld(100));
+        assert_eq!(receipt.payer, harness.user.pubkey());
+
+        harness.lz_receive(60_000_000, 1).await.unwrap();
+        let receipt = harness.read_transfer_receipt(&[1; 32]).await.unwrap();
+        assert!(!receipt.outbound);
+        assert_eq!(receipt.recipient, harness.user.pubkey().to_bytes());
+        assert_eq!(receipt.amount_ld, ld(60));
+        assert_eq!(receipt.payer, harness.ctx.payer.pubkey());
+
+        let user = harness.user.pubkey();
+        assert_oft_error(
+            harness.close_transfer_receipt(&guid, user).await,
+            OFTError::TransferReceiptRetained,
+        );
+        harness.warp(3600).await;
+        harness.close_transfer_receipt(&guid, user).await.unwrap();
+        assert!(harness.read_transfer_receipt(&guid).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_simulate_send_and_receive() {
+        let mut harness = Harness::new().await;
+        harness.set_oft_config(SetOFTConfigParams::DefaultFee(100)).await.unwrap();
+        let rate_limit = RateLimitParams { refill_per_second: Some(0), capacity: Some(ld(50)) };
+        harness.set_outbound_rate_limit(Some(rate_limit)).await.unwrap();
+        let split = InboundSplit {
+            threshold_ld: ld(50),
+            immediate_bps: 2_500,
+            epoch_seconds: 3600,
+            epochs: 3,
+        };
+        harness.set_peer_config(PeerConfigParam::InboundSplit(Some(split))).await.unwrap();
+
+        let simulation = harness.simulate_send(ld(10) + 123).await;
+        assert_eq!(simulation.amount_sent_ld, ld(10));
+        assert_eq!(simulation.oft_fee_ld, ld(1) / 10);
+        assert_eq!(simulation.amount_received_ld, ld(10) - ld(1) / 10);
+        assert_eq!(simulation.dust_ld, 123);
+        assert_eq!(simulation.outbound_available_ld, Some(ld(50)));
+        // matches what the send does
+        harness.send(ld(10) + 123, 0).await.unwrap();
+        let limiter = harness.peer().await.outbound_rate_limiter.unwrap();
+        assert_eq!(limiter.tokens, ld(50) - simulation.amount_received_ld);
+
+        let simulation = harness.simulate_receive(120_000_000, 1).await;
+        assert_eq!(simulation.amount_ld, ld(120));
+        assert_eq!(
+            simulation.outcome,
+            CreditOutcome::Split { immediate_ld: ld(30), vesting_ld: ld(90) }
+        );
+        assert!(!simulation.awaiting_verification && !simulation.awaiting_claim);
+        assert_eq!(simulation.inbound_available_ld, None);
+        let simulation = harness.simulate_receive(20_000_000, 1).await;
+        assert_eq!(simulation.outcome, CreditOutcome::Immediate);
+    }
+
+    #[tokio::test]
+    async fn test_allowed_dst_eids() {
+        let mut harness = Harness::new().await;
+        let config = SetOFTConfigParams::AllowedDstEids(vec![REMOTE_EID + 1]);
+        harness.set_oft_config(config).await.unwrap();
+        // the peer exists, but its route is not open
+        assert_oft_error(harness.send(ld(10), 0).await, OFTError::DstEidNotAllowed);
+
+        let config = SetOFTConfigParams::AllowedDstEids(vec![REMOTE_EID + 1, REMOTE_EID]);
+        harness.set_oft_config(config).await.unwrap();
+        harness.send(ld(10), 0).await.unwrap();
+
+        let config = SetOFTConfigParams::AllowedDstEids(vec![0; 33]);
+        assert_oft_error(harness.set_oft_config(config).await, OFTError::AllowedDstEidsListFull);
+    }
+
+    #[tokio::test]
+    async fn test_resize_override_lists() {
+        let mut harness = Harness::new().await;
+        let addresses: Vec<Pubkey> = (0..16).map(|_| Pubkey::new_unique()).collect();
+        let params = ManageRateLimitOverrideAddressParams {
+            actions: vec![RateLimitOverrideAction::Add; addresses.len()],
+            addresses,
+            expires_at: vec![],
+            direction: None,
+            multiplier: None,
+            scope: RateLimitOverrideScope::Either,
+        };
+        harness.manage_rate_limit_override_with_params(params, None).await.unwrap();
+        assert_oft_error(
+            harness.manage_rate_limit_override(Pubkey::new_unique()).await,
+            OFTError::RateLimitOverrideListFull,
+        );
+
+        harness.resize_override_lists(20, 8).await.unwrap();
+        harness.manage_rate_limit_override(Pubkey::new_unique()).await.unwrap();
+        let account = harness.ctx.banks_client.get_account(harness.oft_store).await.unwrap();
+        assert_eq!(account.unwrap().data.len(), OFTStore::space(20, 8));
+
+        // the caps cannot be lowered
+        assert_oft_error(
+            harness.resize_override_lists(20, 4).await,
+            OFTError::InvalidOverrideListSize,
+        );
+        assert_oft_error(
+            harness.resize_override_lists(MAX_RATE_LIMIT_OVERRIDE_COUNT + 1, 8).await,
+            OFTError::InvalidOverrideListSize,
+        );
+    }
+
+    #[tokio::test]
+    async fn test_zero_amount_policy() {
+        let mut harness = Harness::new().await;
+        harness.send(ld(10), 0).await.unwrap();
+        let token_dest = harness.token_dest();
+        // message-only by default
+        harness.lz_receive(0, 1).await.unwrap();
+        assert_eq!(harness.token_balance(token_dest).await, 0);
+
+        let config = SetOFTConfigParams::ZeroAmountPolicy(ZeroAmountPolicy::Ignore);
+        harness.set_oft_config(config).await.unwrap();
+        harness.lz_receive(0, 2).await.unwrap();
+        harness.lz_receive(1_000_000, 3).await.unwrap();
+        assert_eq!(harness.token_balance(token_dest).await, ld(1));
+
+        let config = SetOFTConfigParams::ZeroAmountPolicy(ZeroAmountPolicy::Reject);
+        harness.set_oft_config(config).await.unwrap();
+        assert_oft_error(harness.lz_receive(0, 4).await, OFTError::ZeroAmountMessage);
+        // the message stays in the endpoint until the policy lets it through
+        let config = SetOFTConfigParams::ZeroAmountPolicy(ZeroAmountPolicy::MessageOnly);
+        harness.set_oft_config(config).await.unwrap();
+        harness.lz_receive(0, 4).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_receive_from_unknown_peer_fails() {
+        let mut harness = Harness::new().await;
+        harness.send(ld(10), 0).await.unwrap();
+        harness.peer_address = [8; 32];
+        assert_oft_error(harness.lz_receive(1_000_000, 1).await, OFTError::InvalidSender);
+    }
+
+    // Loads accounts snapshotted with `lz:oft:solana:snapshot-accounts` next to the program build,
+    // e.g. to check a new build still reads production state. Build with OFT_ID set to the deployed
+    // program id so the snapshotted accounts are owned by the loaded program.
+    #[tokio::test]
+    async fn test_fixture_accounts_load() {
+        let Ok(dir) = std::env::var("OFT_FIXTURES_DIR") else {
+            return;
+        };
+        let mut program_test = ProgramTest::default();
+        program_test.prefer_bpf(true);
+        program_test.add_program("oft", oft::ID, None);
+        let pubkeys = fixtures::load_fixtures(&mut program_test, Path::new(&dir));
+        let mut ctx = program_test.start_with_context().await;
+
+        for pubkey in pubkeys {
+            let account = ctx.banks_client.get_account(pubkey).await.unwrap().unwrap();
+            if account.owner == oft::ID && account.data.starts_with(&OFTStore::DISCRIMINATOR) {
+                let oft_store: OFTStore =
+                    anchor_lang::AccountDeserialize::try_deserialize(&mut account.data.as_slice())
+                        .unwrap();
+                assert_eq!(
+                    Pubkey::find_program_address(
+                        &[oft::OFT_SEED, oft_store.token_escrow.as_ref()],
+                        &oft::ID
+                    )
+                    .0,
+                    pubkey
+                );
+            }
+        }
+    }
+}
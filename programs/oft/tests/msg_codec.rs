@@ -16,8 +16,9 @@ mod test_msg_codec {
         assert_eq!(msg_codec::amount_sd(&encoded), amount_sd);
         assert_eq!(
             msg_codec::compose_msg(&encoded),
-            Some([sender.to_bytes().as_ref(), compose_msg.unwrap().as_slice()].concat())
+            compose_msg.map(|msg| [sender.to_bytes().as_ref(), msg.as_slice()].concat())
         );
+        assert_eq!(msg_codec::compose_from(&encoded), Some(sender.to_bytes()));
     }
 
     #[test]
@@ -31,6 +32,92 @@ mod test_msg_codec {
         assert_eq!(msg_codec::send_to(&encoded), send_to);
         assert_eq!(msg_codec::amount_sd(&encoded), amount_sd);
         assert_eq!(msg_codec::compose_msg(&encoded), None);
+        assert_eq!(msg_codec::compose_from(&encoded), None);
+    }
+
+    #[test]
+    fn test_msg_codec_validate() {
+        let sender: Pubkey = Pubkey::new_unique();
+        let plain = msg_codec::encode([1; 32], 1, sender, &None);
+        let composed = msg_codec::encode([1; 32], 1, sender, &Some(vec![]));
+        assert!(msg_codec::validate(&plain).is_ok());
+        assert!(msg_codec::validate(&composed).is_ok());
+        assert!(msg_codec::validate(&plain[..39]).is_err());
+        assert!(msg_codec::validate(&composed[..71]).is_err());
+        assert!(msg_codec::validate(&[]).is_err());
+    }
+
+    #[test]
+    fn test_msg_codec_hash() {
+        // keccak256 of the empty message, as computed on EVM chains
+        assert_eq!(
+            msg_codec::hash(&[]),
+            [
+                0xc5, 0xd2, 0x46, 0x01, 0x86, 0xf7, 0x23, 0x3c,
+                0x92, 0x7e, 0x7d, 0xb2, 0xdc, 0xc7, 0x03, 0xc0,
+                0xe5, 0x00, 0xb6, 0x53, 0xca, 0x82, 0x27, 0x3b,
+                0x7b, 0xfa, 0xd8, 0x04, 0x5d, 0x85, 0xa4, 0x70,
+            ]
+        );
+        let encoded = msg_codec::encode([1; 32], 1, Pubkey::new_unique(), &None);
+        assert_ne!(msg_codec::hash(&encoded), msg_codec::hash(&encoded[..39]));
+    }
+
+    #[test]
+    fn test_override_list_codec() {
+        let oft_store: Pubkey = Pubkey::new_unique();
+        let entries = [([1; 20], true), ([2; 20], false)];
+        let encoded = msg_codec::encode_override_list(oft_store, &entries);
+        assert_eq!(msg_codec::send_to(&encoded), msg_codec::CONTROL_SEND_TO);
+        assert_eq!(msg_codec::amount_sd(&encoded), 0);
+        assert_eq!(msg_codec::compose_from(&encoded), Some(oft_store.to_bytes()));
+        assert_eq!(encoded[72], msg_codec::CONTROL_OVERRIDE_LIST);
+        assert_eq!(&encoded[73..94], [[1; 20].as_slice(), &[1]].concat().as_slice());
+        assert_eq!(&encoded[94..], [[2; 20].as_slice(), &[0]].concat().as_slice());
+    }
+
+    #[test]
+    fn test_control_codec() {
+        let peer_address = [7; 32];
+        let encoded = msg_codec::encode_peer_challenge(peer_address, [9; 32]);
+        assert_eq!(&msg_codec::send_to(&encoded)[..23], b"gmx-oft:control-message");
+        assert_eq!(
+            msg_codec::decode_control(&encoded, &peer_address).unwrap(),
+            Some(msg_codec::Control::PeerChallenge([9; 32]))
+        );
+        // only messages composed by the peer itself are control messages
+        assert_eq!(msg_codec::decode_control(&encoded, &[8; 32]).unwrap(), None);
+        let transfer = msg_codec::encode(
+            msg_codec::CONTROL_SEND_TO,
+            1,
+            peer_address.into(),
+            &Some([&[msg_codec::CONTROL_PEER_CHALLENGE], [9; 32].as_slice()].concat()),
+        );
+        assert_eq!(msg_codec::decode_control(&transfer, &peer_address).unwrap(), None);
+
+        let notice = msg_codec::encode_pause_notice(peer_address, 5);
+        assert_eq!(
+            msg_codec::decode_control(&notice, &peer_address).unwrap(),
+            Some(msg_codec::Control::PauseNotice(5))
+        );
+        let guids = msg_codec::encode_override_guids(peer_address, &[([1; 32], 0), ([2; 32], 3)]);
+        assert_eq!(
+            msg_codec::decode_control(&guids, &peer_address).unwrap(),
+            Some(msg_codec::Control::OverrideGuids(vec![([1; 32], None), ([2; 32], Some(3))]))
+        );
+        let ack = msg_codec::encode_delivery_ack(peer_address, [1; 32], 5);
+        assert_eq!(
+            msg_codec::decode_control(&ack, &peer_address).unwrap(),
+            Some(msg_codec::Control::DeliveryAck { guid: [1; 32], amount_sd: 5 })
+        );
+
+        // unknown types and malformed payloads are rejected
+        let unknown = msg_codec::encode_control(peer_address, 0, &[]);
+        assert!(msg_codec::decode_control(&unknown, &peer_address).is_err());
+        assert!(msg_codec::decode_control(&encoded[..100], &peer_address).is_err());
+        assert!(msg_codec::decode_control(&encoded[..72], &peer_address).is_err());
+        let list = msg_codec::encode_override_list(peer_address.into(), &[([1; 20], true)]);
+        assert!(msg_codec::decode_control(&list, &peer_address).is_err());
     }
 
     #[test]
@@ -18,4 +18,5 @@ import './solana/getRateLimitOverrides'
 import './solana/setGuidRateLimitOverride'
 import './solana/executePayload'
 import './solana/validateAnchorData'
+import './solana/snapshotAccounts'
 import './utils/hexToBase58'
@@ -78,26 +78,70 @@ function parseOFTStoreRaw(data: Buffer) {
         offset += 32
     }
 
-    // rate_limit_override: Vec<Pubkey>
+    // version: u8
+    const version = data.readUInt8(offset)
+    offset += 1
+
+    // rate_limit_override: Vec<RateLimitOverrideAddress { address: Pubkey, expires_at: Option<i64>, direction: Option<RateLimitDirection>, multiplier: Option<u16>, scope: RateLimitOverrideScope }>
     const overrideCount = data.readUInt32LE(offset)
     offset += 4
     const rateLimitOverrides = []
+    const rateLimitOverrideExpiries: (bigint | null)[] = []
+    const rateLimitOverrideDirections: (string | null)[] = []
+    const rateLimitOverrideMultipliers: (number | null)[] = []
+    const rateLimitOverrideScopes: string[] = []
     for (let i = 0; i < overrideCount; i++) {
         rateLimitOverrides.push(new PublicKey(data.slice(offset, offset + 32)))
         offset += 32
+        const hasExpiry = data.readUInt8(offset) === 1
+        offset += 1
+        if (hasExpiry) {
+            rateLimitOverrideExpiries.push(data.readBigInt64LE(offset))
+            offset += 8
+        } else {
+            rateLimitOverrideExpiries.push(null)
+        }
+        const hasDirection = data.readUInt8(offset) === 1
+        offset += 1
+        if (hasDirection) {
+            rateLimitOverrideDirections.push(data.readUInt8(offset) === 0 ? 'inbound' : 'outbound')
+            offset += 1
+        } else {
+            rateLimitOverrideDirections.push(null)
+        }
+        const hasMultiplier = data.readUInt8(offset) === 1
+        offset += 1
+        if (hasMultiplier) {
+            rateLimitOverrideMultipliers.push(data.readUInt16LE(offset))
+            offset += 2
+        } else {
+            rateLimitOverrideMultipliers.push(null)
+        }
+        rateLimitOverrideScopes.push(['sender', 'recipient', 'either'][data.readUInt8(offset)])
+        offset += 1
     }
 
     // max_rate_limit_overrides: u8
     const maxRateLimitOverrides = data.readUInt8(offset)
     offset += 1
 
-    // rate_limit_override_guids: Vec<[u8; 32]>
+    // rate_limit_override_guids: Vec<RateLimitOverrideGuid { guid: [u8; 32], expected_amount_ld: Option<u64>, src_eid: Option<u32> }>
     const guidCount = data.readUInt32LE(offset)
     offset += 4
     const rateLimitOverrideGuids = []
     for (let i = 0; i < guidCount; i++) {
         rateLimitOverrideGuids.push(data.slice(offset, offset + 32))
         offset += 32
+        const hasExpectedAmount = data.readUInt8(offset) === 1
+        offset += 1
+        if (hasExpectedAmount) {
+            offset += 8
+        }
+        const hasSrcEid = data.readUInt8(offset) === 1
+        offset += 1
+        if (hasSrcEid) {
+            offset += 4
+        }
     }
 
     // max_rate_limit_override_guid_count: u8
@@ -117,7 +161,12 @@ function parseOFTStoreRaw(data: Buffer) {
         paused,
         pauser,
         unpauser,
+        version,
         rateLimitOverrides,
+        rateLimitOverrideExpiries,
+        rateLimitOverrideDirections,
+        rateLimitOverrideMultipliers,
+        rateLimitOverrideScopes,
         maxRateLimitOverrides,
         rateLimitOverrideGuids,
         maxRateLimitOverrideGuidCount,
@@ -162,7 +211,16 @@ task('lz:oft:solana:get-rate-limit-overrides', 'Gets rate limit overrides using
             if (oftStoreData.rateLimitOverrides.length > 0) {
                 console.log(`✅ ${oftStoreData.rateLimitOverrides.length} address(es) whitelisted:`)
                 oftStoreData.rateLimitOverrides.forEach((address, index) => {
-                    console.log(`   ${index + 1}. ${address.toBase58()}`)
+                    const expiresAt = oftStoreData.rateLimitOverrideExpiries[index]
+                    const expiry =
+                        expiresAt === null ? '' : ` (expires ${new Date(Number(expiresAt) * 1000).toISOString()})`
+                    const direction = oftStoreData.rateLimitOverrideDirections[index]
+                    const scope = direction === null ? '' : ` (${direction} only)`
+                    const multiplier = oftStoreData.rateLimitOverrideMultipliers[index]
+                    const factor = multiplier === null ? '' : ` (${multiplier}x limit)`
+                    const party = oftStoreData.rateLimitOverrideScopes[index]
+                    const matched = party === 'either' ? '' : ` (as ${party} only)`
+                    console.log(`   ${index + 1}. ${address.toBase58()}${scope}${matched}${factor}${expiry}`)
                 })
             } else {
                 console.log('❌ No addresses whitelisted')
@@ -208,6 +266,12 @@ task('lz:oft:solana:get-rate-limit-overrides', 'Gets rate limit overrides using
 
             return {
                 addressOverrides: oftStoreData.rateLimitOverrides.map((addr) => addr.toBase58()),
+                addressOverrideExpiries: oftStoreData.rateLimitOverrideExpiries.map((expiresAt) =>
+                    expiresAt === null ? null : Number(expiresAt)
+                ),
+                addressOverrideDirections: oftStoreData.rateLimitOverrideDirections,
+                addressOverrideMultipliers: oftStoreData.rateLimitOverrideMultipliers,
+                addressOverrideScopes: oftStoreData.rateLimitOverrideScopes,
                 guidOverrides: oftStoreData.rateLimitOverrideGuids.map((guid) => guid.toString('hex')),
                 maxAddresses: oftStoreData.maxRateLimitOverrides,
                 maxGuids: oftStoreData.maxRateLimitOverrideGuidCount,
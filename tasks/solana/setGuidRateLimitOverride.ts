@@ -21,6 +21,9 @@ interface SetGuidOverrideArgs extends MultisigOptions {
     oftStore: string
     guids: string[]
     actions: ('add' | 'remove')[]
+    expectedAmounts: string[]
+    srcEids: string[]
+    approver?: string
 }
 
 // Convert hex or base58 GUID string to [u8; 32] array
@@ -67,6 +70,24 @@ task('lz:oft:solana:set-guid-rate-limit-override', 'Manages GUID-based rate limi
         undefined,
         types.csv
     )
+    .addOptionalParam(
+        'expectedAmounts',
+        'Comma-separated list of exact amounts (base units, "-" for any) each GUID override applies to',
+        [],
+        types.csv
+    )
+    .addOptionalParam(
+        'srcEids',
+        'Comma-separated list of source EIDs ("-" for any) each GUID override applies to',
+        [],
+        types.csv
+    )
+    .addOptionalParam(
+        'approver',
+        "The store's override approver, required once one is set; signs with SOLANA_OVERRIDE_APPROVER_PRIVATE_KEY when executing immediately",
+        undefined,
+        types.string
+    )
     .addOptionalParam('multisigKey', 'Multisig vault/authority public key (if using multisig)', undefined, types.string)
     .addOptionalParam('multisigPda', 'Squads multisig PDA (required if using --create-proposal)', undefined, types.string)
     .addOptionalParam(
@@ -92,6 +113,17 @@ task('lz:oft:solana:set-guid-rate-limit-override', 'Manages GUID-based rate limi
             }
         }
 
+        if (taskArgs.expectedAmounts.length > 0 && taskArgs.expectedAmounts.length !== taskArgs.guids.length) {
+            throw new Error('Number of expected amounts must match number of GUIDs')
+        }
+        const expectedAmounts = taskArgs.expectedAmounts.map((amount) =>
+            amount === '-' ? null : new anchor.BN(amount)
+        )
+        if (taskArgs.srcEids.length > 0 && taskArgs.srcEids.length !== taskArgs.guids.length) {
+            throw new Error('Number of source EIDs must match number of GUIDs')
+        }
+        const srcEids = taskArgs.srcEids.map((srcEid) => (srcEid === '-' ? null : Number(srcEid)))
+
         const keypair = Keypair.fromSecretKey(bs58.decode(privateKey))
         const connectionFactory = createSolanaConnectionFactory()
         const connection = await connectionFactory(taskArgs.eid)
@@ -133,10 +165,13 @@ task('lz:oft:solana:set-guid-rate-limit-override', 'Manages GUID-based rate limi
                 .manageRateLimitOverrideGuid({
                     guids: guidArrays,
                     actions: actionEnums,
+                    expectedAmountsLd: expectedAmounts,
+                    srcEids,
                 })
                 .accounts({
                     admin: adminPubkey,
                     oftStore: new PublicKey(taskArgs.oftStore),
+                    approver: taskArgs.approver ? new PublicKey(taskArgs.approver) : null,
                 })
                 .instruction()
 
@@ -201,7 +236,15 @@ task('lz:oft:solana:set-guid-rate-limit-override', 'Manages GUID-based rate limi
                 // Execute immediately
                 console.log('\n⚡ Executing GUID rate limit override...')
 
-                const txId = await sendAndConfirmTransaction(connection, transaction, [keypair])
+                const signers = [keypair]
+                if (taskArgs.approver) {
+                    const approverKey = process.env.SOLANA_OVERRIDE_APPROVER_PRIVATE_KEY
+                    assert(!!approverKey, 'SOLANA_OVERRIDE_APPROVER_PRIVATE_KEY is not defined in the environment variables.')
+                    const approver = Keypair.fromSecretKey(bs58.decode(approverKey))
+                    assert(approver.publicKey.toBase58() === taskArgs.approver, 'The approver key does not match --approver')
+                    signers.push(approver)
+                }
+                const txId = await sendAndConfirmTransaction(connection, transaction, signers)
 
                 console.log('✅ Transaction successful!')
                 console.log(`Transaction ID: ${txId}`)
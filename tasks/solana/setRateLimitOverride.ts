@@ -21,6 +21,11 @@ interface SetOverrideArgs extends MultisigOptions {
     oftStore: string
     addresses: string[]
     actions: ('add' | 'remove')[]
+    expiresAt: string[]
+    direction?: 'inbound' | 'outbound'
+    multiplier?: number
+    scope?: 'sender' | 'recipient' | 'either'
+    approver?: string
 }
 
 task('lz:oft:solana:set-rate-limit-override', 'Manages rate limit override addresses (whitelist) for Solana OFT')
@@ -34,6 +39,36 @@ task('lz:oft:solana:set-rate-limit-override', 'Manages rate limit override addre
         undefined,
         types.csv
     )
+    .addOptionalParam(
+        'expiresAt',
+        'Comma-separated list of unix timestamps (or "-" for never) at which each added override lapses',
+        [],
+        types.csv
+    )
+    .addOptionalParam(
+        'direction',
+        'Rate limiter added overrides skip (inbound/outbound); both when omitted',
+        undefined,
+        types.string
+    )
+    .addOptionalParam(
+        'multiplier',
+        'Lets added overrides move this many times the rate limit (at least 2) instead of skipping it',
+        undefined,
+        types.int
+    )
+    .addOptionalParam(
+        'scope',
+        'Whether added overrides match the address as sender of sends, recipient of credits (also by token account owner) or either',
+        'either',
+        types.string
+    )
+    .addOptionalParam(
+        'approver',
+        "The store's override approver, required once one is set; signs with SOLANA_OVERRIDE_APPROVER_PRIVATE_KEY when executing immediately",
+        undefined,
+        types.string
+    )
     .addOptionalParam('multisigKey', 'Multisig vault/authority public key (if using multisig)', undefined, types.string)
     .addOptionalParam('multisigPda', 'Squads multisig PDA (required if using --create-proposal)', undefined, types.string)
     .addOptionalParam(
@@ -59,6 +94,26 @@ task('lz:oft:solana:set-rate-limit-override', 'Manages rate limit override addre
             }
         }
 
+        if (taskArgs.expiresAt.length > 0 && taskArgs.expiresAt.length !== taskArgs.addresses.length) {
+            throw new Error('Number of expiries must match number of addresses')
+        }
+        const expiresAt = taskArgs.expiresAt.map((timestamp) =>
+            timestamp === '-' ? null : new anchor.BN(timestamp)
+        )
+        if (taskArgs.direction !== undefined && taskArgs.direction !== 'inbound' && taskArgs.direction !== 'outbound') {
+            throw new Error('Direction must be either "inbound" or "outbound"')
+        }
+        const direction = taskArgs.direction === undefined ? null : { [taskArgs.direction]: {} } // Anchor enum format
+        if (taskArgs.multiplier !== undefined && taskArgs.multiplier < 2) {
+            throw new Error('Multiplier must be at least 2')
+        }
+        const multiplier = taskArgs.multiplier ?? null
+        const scopeArg = taskArgs.scope ?? 'either'
+        if (scopeArg !== 'sender' && scopeArg !== 'recipient' && scopeArg !== 'either') {
+            throw new Error('Scope must be "sender", "recipient" or "either"')
+        }
+        const scope = { [scopeArg]: {} } // Anchor enum format
+
         const keypair = Keypair.fromSecretKey(bs58.decode(privateKey))
         const connectionFactory = createSolanaConnectionFactory()
         const connection = await connectionFactory(taskArgs.eid)
@@ -79,7 +134,9 @@ task('lz:oft:solana:set-rate-limit-override', 'Manages rate limit override addre
         console.log(`Program ID: ${taskArgs.programId}`)
         console.log('\nOperations:')
         for (let i = 0; i < taskArgs.addresses.length; i++) {
-            console.log(`  ${i + 1}. ${taskArgs.actions[i].toUpperCase()}: ${taskArgs.addresses[i]}`)
+            // PDAs have no private key: they send by CPI, signing as the owner of the debited account
+            const pda = PublicKey.isOnCurve(new PublicKey(taskArgs.addresses[i]).toBytes()) ? '' : ' (PDA)'
+            console.log(`  ${i + 1}. ${taskArgs.actions[i].toUpperCase()}: ${taskArgs.addresses[i]}${pda}`)
         }
         console.log('─'.repeat(50))
 
@@ -96,10 +153,15 @@ task('lz:oft:solana:set-rate-limit-override', 'Manages rate limit override addre
                 .manageRateLimitOverride({
                     addresses: addressPublicKeys,
                     actions: actionEnums,
+                    expiresAt,
+                    direction,
+                    multiplier,
+                    scope,
                 })
                 .accounts({
                     admin: adminPubkey,
                     oftStore: new PublicKey(taskArgs.oftStore),
+                    approver: taskArgs.approver ? new PublicKey(taskArgs.approver) : null,
                 })
                 .instruction()
 
@@ -164,7 +226,15 @@ task('lz:oft:solana:set-rate-limit-override', 'Manages rate limit override addre
                 // Execute immediately
                 console.log('\n⚡ Executing rate limit override...')
 
-                const txId = await sendAndConfirmTransaction(connection, transaction, [keypair])
+                const signers = [keypair]
+                if (taskArgs.approver) {
+                    const approverKey = process.env.SOLANA_OVERRIDE_APPROVER_PRIVATE_KEY
+                    assert(!!approverKey, 'SOLANA_OVERRIDE_APPROVER_PRIVATE_KEY is not defined in the environment variables.')
+                    const approver = Keypair.fromSecretKey(bs58.decode(approverKey))
+                    assert(approver.publicKey.toBase58() === taskArgs.approver, 'The approver key does not match --approver')
+                    signers.push(approver)
+                }
+                const txId = await sendAndConfirmTransaction(connection, transaction, signers)
 
                 console.log('✅ Transaction successful!')
                 console.log(`Transaction ID: ${txId}`)
@@ -0,0 +1,95 @@
+import fs from 'fs'
+import path from 'path'
+
+import { AccountInfo, PublicKey } from '@solana/web3.js'
+import { task } from 'hardhat/config'
+
+import { types } from '@layerzerolabs/devtools-evm-hardhat'
+import { EndpointId } from '@layerzerolabs/lz-definitions'
+
+import { createSolanaConnectionFactory } from '../common/utils'
+
+interface Args {
+    eid: EndpointId
+    programId: string
+    oftStore: string
+    remoteEids: string[]
+    outDir: string
+}
+
+// Offsets of the immutable OFTStore fields, see programs/oft/src/state/oft.rs
+const TOKEN_MINT_OFFSET = 8 + 1 + 8 // discriminator + oft_type + ld2sd_rate
+const TOKEN_ESCROW_OFFSET = TOKEN_MINT_OFFSET + 32
+const ENDPOINT_PROGRAM_OFFSET = TOKEN_ESCROW_OFFSET + 32
+
+// Written in the same format as `solana account <pubkey> --output json` so either can be used as a
+// fixture by programs/oft/tests/common/fixtures.rs
+function toFixture(pubkey: PublicKey, account: AccountInfo<Buffer>) {
+    return {
+        pubkey: pubkey.toBase58(),
+        account: {
+            lamports: account.lamports,
+            data: [account.data.toString('base64'), 'base64'],
+            owner: account.owner.toBase58(),
+            executable: account.executable,
+            // pinned so snapshots of unchanged accounts are byte-for-byte identical
+            rentEpoch: 0,
+            space: account.data.length,
+        },
+    }
+}
+
+task('lz:oft:solana:snapshot-accounts', 'Snapshots the OFT accounts into fixtures for the Rust test harness')
+    .addParam('eid', 'Solana mainnet (30168) or testnet (40168)', undefined, types.eid)
+    .addParam('programId', 'The OFT Program id')
+    .addParam('oftStore', 'The OFTStore account')
+    .addOptionalParam('remoteEids', 'Comma-separated list of peer eids to snapshot', [], types.csv)
+    .addOptionalParam('outDir', 'Directory to write the fixtures to', 'programs/oft/tests/fixtures', types.string)
+    .setAction(async (taskArgs: Args, _) => {
+        const connectionFactory = createSolanaConnectionFactory()
+        const connection = await connectionFactory(taskArgs.eid)
+
+        const programId = new PublicKey(taskArgs.programId)
+        const oftStore = new PublicKey(taskArgs.oftStore)
+        const oftStoreInfo = await connection.getAccountInfo(oftStore)
+        if (!oftStoreInfo) {
+            throw new Error(`OFT Store ${oftStore.toBase58()} not found`)
+        }
+        const tokenMint = new PublicKey(oftStoreInfo.data.subarray(TOKEN_MINT_OFFSET, TOKEN_ESCROW_OFFSET))
+        const tokenEscrow = new PublicKey(oftStoreInfo.data.subarray(TOKEN_ESCROW_OFFSET, ENDPOINT_PROGRAM_OFFSET))
+        const endpointProgram = new PublicKey(
+            oftStoreInfo.data.subarray(ENDPOINT_PROGRAM_OFFSET, ENDPOINT_PROGRAM_OFFSET + 32)
+        )
+
+        const pubkeys: PublicKey[] = [
+            oftStore,
+            tokenMint,
+            tokenEscrow,
+            PublicKey.findProgramAddressSync([Buffer.from('LzReceiveTypes'), oftStore.toBuffer()], programId)[0],
+            PublicKey.findProgramAddressSync([Buffer.from('OApp'), oftStore.toBuffer()], endpointProgram)[0],
+            PublicKey.findProgramAddressSync([Buffer.from('Endpoint')], endpointProgram)[0],
+        ]
+        for (const remoteEid of taskArgs.remoteEids) {
+            const eidBytes = Buffer.alloc(4)
+            eidBytes.writeUInt32BE(Number(remoteEid))
+            pubkeys.push(
+                PublicKey.findProgramAddressSync([Buffer.from('Peer'), oftStore.toBuffer(), eidBytes], programId)[0]
+            )
+        }
+
+        // fetch everything at a single slot so the fixtures are consistent with each other
+        const { context, value: accounts } = await connection.getMultipleAccountsInfoAndContext(pubkeys)
+
+        fs.mkdirSync(taskArgs.outDir, { recursive: true })
+        pubkeys.forEach((pubkey, index) => {
+            const account = accounts[index]
+            if (!account) {
+                console.log(`⚠️  ${pubkey.toBase58()} not found, skipping`)
+                return
+            }
+            const file = path.join(taskArgs.outDir, `${pubkey.toBase58()}.json`)
+            fs.writeFileSync(file, JSON.stringify(toFixture(pubkey, account), null, 2) + '\n')
+            console.log(`✅ ${file}`)
+        })
+        console.log(`📸 Snapshot taken at slot ${context.slot}`)
+    })
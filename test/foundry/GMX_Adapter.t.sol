@@ -3,10 +3,13 @@ pragma solidity ^0.8.22;
 
 // OZ imports
 import { IERC20 } from "@openzeppelin/contracts/token/ERC20/IERC20.sol";
+import { Ownable } from "@openzeppelin/contracts/access/Ownable.sol";
 
 // OApp imports
 import { OptionsBuilder } from "@layerzerolabs/oapp-evm/contracts/oapp/libs/OptionsBuilder.sol";
 import { RateLimiter } from "@layerzerolabs/oapp-evm/contracts/oapp/utils/RateLimiter.sol";
+import { Origin } from "@layerzerolabs/oapp-evm/contracts/oapp/OApp.sol";
+import { ILayerZeroEndpointV2, MessagingParams } from "@layerzerolabs/lz-evm-protocol-v2/contracts/interfaces/ILayerZeroEndpointV2.sol";
 
 // OFT imports
 import { IMintableBurnable } from "@layerzerolabs/oft-evm/contracts/interfaces/IMintableBurnable.sol";
@@ -16,7 +19,7 @@ import { MessagingFee, MessagingReceipt } from "@layerzerolabs/oft-evm/contracts
 import { OFTMsgCodec } from "@layerzerolabs/oft-evm/contracts/libs/OFTMsgCodec.sol";
 
 // Contract imports
-import { RateLimitExemptAddress } from "../../contracts/interfaces/IOverridableInboundRateLimiter.sol";
+import { IOverridableInboundRateLimiter, RateLimitExemptAddress } from "../../contracts/interfaces/IOverridableInboundRateLimiter.sol";
 import { GMX_MintBurnAdapter } from "../../contracts/GMX_MintBurnAdapter.sol";
 import { GMX_LockboxAdapter } from "../../contracts/GMX_LockboxAdapter.sol";
 
@@ -24,7 +27,7 @@ import { GMX_LockboxAdapter } from "../../contracts/GMX_LockboxAdapter.sol";
 import { IGMXToken } from "../mocks/IGMXToken.sol";
 
 // Forge imports
-import { Test, console } from "forge-std/Test.sol";
+import { Test, Vm, console } from "forge-std/Test.sol";
 
 contract GMX_AdapterForkTest is Test {
     using OptionsBuilder for bytes;
@@ -254,4 +257,179 @@ contract GMX_AdapterForkTest is Test {
         assertEq(IERC20(GMX_AVALANCHE).totalSupply(), initialTotalSupply - tokensToSend);
         assertEq(IERC20(GMX_AVALANCHE).balanceOf(userA), INITIAL_BALANCE - tokensToSend);
     }
+
+    function test_send_override_guids_matches_solana_layout() public {
+        vm.selectFork(0); // Arbitrum fork
+
+        bytes32[] memory guids = new bytes32[](2);
+        guids[0] = keccak256("guid-0");
+        guids[1] = keccak256("guid-1");
+        uint32[] memory srcEids = new uint32[](2);
+        srcEids[0] = AVALANCHE_EID;
+        srcEids[1] = 0;
+
+        // sendTo (32) | amountSD (8) | composeFrom (32) | controlType (1) | [guid (32) | srcEid (4)]...
+        bytes memory expected = abi.encodePacked(
+            bytes32("gmx-oft:control-message"),
+            uint64(0),
+            address(arbitrumLockboxAdapter).addressToBytes32(),
+            uint8(3),
+            guids[0],
+            srcEids[0],
+            guids[1],
+            srcEids[1]
+        );
+        assertEq(expected.length, 73 + 2 * 36);
+
+        bytes memory options = OptionsBuilder.newOptions().addExecutorLzReceiveOption(200000, 0);
+        MessagingFee memory fee = ILayerZeroEndpointV2(ARBITRUM_ENDPOINT).quote(
+            MessagingParams({
+                dstEid: AVALANCHE_EID,
+                receiver: address(avalancheMintBurnAdapter).addressToBytes32(),
+                message: expected,
+                options: options,
+                payInLzToken: false
+            }),
+            address(arbitrumLockboxAdapter)
+        );
+
+        vm.expectEmit(address(arbitrumLockboxAdapter));
+        emit IOverridableInboundRateLimiter.OverrideGuidsSent(AVALANCHE_EID, guids, srcEids);
+        vm.recordLogs();
+        arbitrumLockboxAdapter.sendOverrideGuids{ value: fee.nativeFee }(AVALANCHE_EID, guids, srcEids, options);
+
+        // The endpoint's PacketSent carries the encoded packet, which ends with the message
+        Vm.Log[] memory logs = vm.getRecordedLogs();
+        bytes memory packet;
+        for (uint256 i; i < logs.length; ++i) {
+            if (logs[i].topics[0] == keccak256("PacketSent(bytes,bytes,address)")) {
+                (packet, , ) = abi.decode(logs[i].data, (bytes, bytes, address));
+            }
+        }
+        assertGt(packet.length, expected.length);
+        bytes memory tail = new bytes(expected.length);
+        for (uint256 i; i < expected.length; ++i) {
+            tail[i] = packet[packet.length - expected.length + i];
+        }
+        assertEq(tail, expected);
+    }
+
+    function test_pause_notice_is_reported_without_credit() public {
+        vm.selectFork(1); // Avalanche fork
+
+        bytes32 peer = address(arbitrumLockboxAdapter).addressToBytes32();
+        uint64 sentAt = 1_700_000_000;
+        // sendTo (32) | amountSD (8) | composeFrom (32) | controlType (1) | sentAt (8), from a paused Solana store
+        bytes memory notice = abi.encodePacked(bytes32("gmx-oft:control-message"), uint64(0), peer, uint8(4), sentAt);
+        uint256 totalSupply = IERC20(GMX_AVALANCHE).totalSupply();
+
+        vm.expectEmit(address(avalancheMintBurnAdapter));
+        emit IOverridableInboundRateLimiter.PauseNoticeReceived(ARBITRUM_EID, sentAt);
+        vm.prank(AVALANCHE_ENDPOINT);
+        avalancheMintBurnAdapter.lzReceive(
+            Origin({ srcEid: ARBITRUM_EID, sender: peer, nonce: 1 }),
+            keccak256("pause-notice"),
+            notice,
+            address(this),
+            ""
+        );
+
+        assertEq(IERC20(GMX_AVALANCHE).totalSupply(), totalSupply);
+    }
+
+    function test_delivery_ack_is_sent_once() public {
+        vm.selectFork(1); // Avalanche fork
+
+        bytes32 peer = address(arbitrumLockboxAdapter).addressToBytes32();
+        bytes32 guid = keccak256("acked-transfer");
+        uint64 amountSD = 1_000_000; // 1 GMX in shared decimals
+        bytes memory transfer = abi.encodePacked(userB.addressToBytes32(), amountSD);
+
+        vm.expectEmit(address(avalancheMintBurnAdapter));
+        emit IOverridableInboundRateLimiter.DeliveryAcksUpdated(ARBITRUM_EID, true);
+        avalancheMintBurnAdapter.setDeliveryAcks(ARBITRUM_EID, true);
+
+        vm.prank(AVALANCHE_ENDPOINT);
+        avalancheMintBurnAdapter.lzReceive(
+            Origin({ srcEid: ARBITRUM_EID, sender: peer, nonce: 1 }),
+            guid,
+            transfer,
+            address(this),
+            ""
+        );
+        (uint32 srcEid, uint64 pendingAmountSD) = avalancheMintBurnAdapter.pendingDeliveryAcks(guid);
+        assertEq(srcEid, ARBITRUM_EID);
+        assertEq(pendingAmountSD, amountSD);
+
+        // sendTo (32) | amountSD (8) | composeFrom (32) | controlType (1) | guid (32) | amountSD (8)
+        bytes memory ack = abi.encodePacked(
+            bytes32("gmx-oft:control-message"),
+            uint64(0),
+            address(avalancheMintBurnAdapter).addressToBytes32(),
+            uint8(5),
+            guid,
+            amountSD
+        );
+        bytes memory options = OptionsBuilder.newOptions().addExecutorLzReceiveOption(200000, 0);
+        MessagingFee memory fee = ILayerZeroEndpointV2(AVALANCHE_ENDPOINT).quote(
+            MessagingParams({
+                dstEid: ARBITRUM_EID,
+                receiver: peer,
+                message: ack,
+                options: options,
+                payInLzToken: false
+            }),
+            address(avalancheMintBurnAdapter)
+        );
+
+        vm.expectEmit(address(avalancheMintBurnAdapter));
+        emit IOverridableInboundRateLimiter.DeliveryAckSent(ARBITRUM_EID, guid, amountSD);
+        avalancheMintBurnAdapter.sendDeliveryAck{ value: fee.nativeFee }(guid, options);
+
+        // each delivery is acknowledged once
+        vm.expectRevert(abi.encodeWithSelector(IOverridableInboundRateLimiter.NoPendingDeliveryAck.selector, guid));
+        avalancheMintBurnAdapter.sendDeliveryAck{ value: fee.nativeFee }(guid, options);
+    }
+
+    function test_control_message_rejects_unknown_type() public {
+        vm.selectFork(1); // Avalanche fork
+
+        bytes32 peer = address(arbitrumLockboxAdapter).addressToBytes32();
+        bytes memory message = abi.encodePacked(bytes32("gmx-oft:control-message"), uint64(0), peer, uint8(0));
+
+        vm.prank(AVALANCHE_ENDPOINT);
+        vm.expectRevert(abi.encodeWithSelector(IOverridableInboundRateLimiter.InvalidControlMessage.selector, 0, 0));
+        avalancheMintBurnAdapter.lzReceive(
+            Origin({ srcEid: ARBITRUM_EID, sender: peer, nonce: 1 }),
+            keccak256("unknown-control-message"),
+            message,
+            address(this),
+            ""
+        );
+    }
+
+    function test_send_override_guids_only_owner() public {
+        vm.selectFork(1); // Avalanche fork
+
+        bytes32[] memory guids = new bytes32[](1);
+        guids[0] = keccak256("guid-0");
+        uint32[] memory srcEids = new uint32[](1);
+
+        vm.prank(userA);
+        vm.expectRevert(abi.encodeWithSelector(Ownable.OwnableUnauthorizedAccount.selector, userA));
+        avalancheMintBurnAdapter.sendOverrideGuids(ARBITRUM_EID, guids, srcEids, "");
+    }
+
+    function test_send_override_guids_rejects_malformed_input() public {
+        vm.selectFork(1); // Avalanche fork
+
+        bytes32[] memory guids = new bytes32[](2);
+        uint32[] memory srcEids = new uint32[](1);
+
+        vm.expectRevert(abi.encodeWithSelector(IOverridableInboundRateLimiter.InputLengthMismatch.selector, 2, 1));
+        avalancheMintBurnAdapter.sendOverrideGuids(ARBITRUM_EID, guids, srcEids, "");
+
+        vm.expectRevert(IOverridableInboundRateLimiter.NoOverrideGuids.selector);
+        avalancheMintBurnAdapter.sendOverrideGuids(ARBITRUM_EID, new bytes32[](0), new uint32[](0), "");
+    }
 }